@@ -0,0 +1,235 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The 'BASE' table: where a font's baselines are for each script, so
+// text in different scripts and fonts can be lined up on one of them,
+// and how far glyphs reach above and below for each language.
+// Coordinates are in design units, above the alphabetic baseline (or
+// to the right of the vertical one).  The device table adjustments of
+// format 3 coordinates are not applied, and the contour point of format
+// 2 ones isn't looked up.
+//
+// For fonts without a 'BASE' table, or without a baseline in it,
+// `font_baseline` works one out from the font's metrics.
+
+use ot_layout::DEFAULT_SCRIPT;
+use ot_parse::TableData;
+use super::{FontFace, FontMetrics};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Baseline {
+    // The alphabetic baseline, which glyphs are drawn from.
+    Roman,
+    // Devanagari, Bengali, Gurmukhi and Tibetan hang from this one.
+    Hanging,
+    // The bottom and top of the ideographic em box.
+    Ideographic,
+    IdeographicTop,
+    // The bottom and top of the ideographic character face, inside
+    // the em box.
+    IdeographicFaceBottom,
+    IdeographicFaceTop,
+    // The math axis, which operators are centered on.
+    Math,
+}
+
+impl Baseline {
+    pub fn tag(self) -> u32 {
+        match self {
+            Baseline::Roman => ot_tag!(b"romn"),
+            Baseline::Hanging => ot_tag!(b"hang"),
+            Baseline::Ideographic => ot_tag!(b"ideo"),
+            Baseline::IdeographicTop => ot_tag!(b"idtp"),
+            Baseline::IdeographicFaceBottom => ot_tag!(b"icfb"),
+            Baseline::IdeographicFaceTop => ot_tag!(b"icft"),
+            Baseline::Math => ot_tag!(b"math"),
+        }
+    }
+
+    pub fn from_tag(tag: u32) -> Option<Baseline> {
+        [Baseline::Roman, Baseline::Hanging, Baseline::Ideographic, Baseline::IdeographicTop,
+         Baseline::IdeographicFaceBottom, Baseline::IdeographicFaceTop, Baseline::Math]
+            .iter()
+            .cloned()
+            .find(|b| b.tag() == tag)
+    }
+
+    // The baseline text in an OpenType script usually sits on, for
+    // fonts that don't say.
+    pub fn for_script(script_tag: u32) -> Baseline {
+        match &tag_bytes(script_tag) {
+            b"deva" | b"dev2" | b"beng" | b"bng2" | b"guru" | b"gur2" | b"tibt" => Baseline::Hanging,
+            b"hani" | b"kana" | b"hang" | b"bopo" | b"yi  " => Baseline::Ideographic,
+            _ => Baseline::Roman,
+        }
+    }
+}
+
+fn tag_bytes(tag: u32) -> [u8; 4] {
+    [(tag >> 24) as u8, (tag >> 16) as u8, (tag >> 8) as u8, tag as u8]
+}
+
+// How far a script's glyphs (or those of a language or feature in it)
+// reach below and above the baseline.  Either may be missing.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct MinMax {
+    pub min: Option<i16>,
+    pub max: Option<i16>,
+}
+
+#[derive(Debug)]
+pub struct BaseTable {
+    data: Vec<u8>,
+}
+
+impl BaseTable {
+    pub fn new(data: Vec<u8>) -> Option<BaseTable> {
+        {
+            let t = TableData::new(&data);
+            if t.u16(0)? != 1 {
+                return None;
+            }
+            t.u16(6)?;
+        }
+        Some(BaseTable { data: data })
+    }
+
+    pub fn from_font_face(face: &FontFace) -> Option<BaseTable> {
+        face.get_font_table(ot_tag!(b"BASE")).and_then(BaseTable::new)
+    }
+
+    fn table(&self) -> TableData<'_> {
+        TableData::new(&self.data)
+    }
+
+    fn axis(&self, vertical: bool) -> Option<TableData<'_>> {
+        self.table().offset16(if vertical { 6 } else { 4 })
+    }
+
+    // The baselines the font has coordinates for, on the horizontal or
+    // vertical axis.
+    pub fn baseline_tags(&self, vertical: bool) -> Vec<u32> {
+        let list = match self.axis(vertical).and_then(|a| a.offset16(0)) {
+            Some(l) => l,
+            None => return vec![],
+        };
+        let count = list.u16(0).unwrap_or(0) as usize;
+        (0..count).filter_map(|i| list.tag(2 + i * 4)).collect()
+    }
+
+    pub fn script_tags(&self, vertical: bool) -> Vec<u32> {
+        let list = match self.axis(vertical).and_then(|a| a.offset16(2)) {
+            Some(l) => l,
+            None => return vec![],
+        };
+        let count = list.u16(0).unwrap_or(0) as usize;
+        (0..count).filter_map(|i| list.tag(2 + i * 6)).collect()
+    }
+
+    // The BaseScript table for a script, or the 'DFLT' one if the
+    // script isn't listed.
+    fn script(&self, vertical: bool, script_tag: u32) -> Option<TableData<'_>> {
+        let list = self.axis(vertical)?.offset16(2)?;
+        let count = list.u16(0)? as usize;
+        let mut default = None;
+        for i in 0..count {
+            let tag = list.tag(2 + i * 6)?;
+            if tag == script_tag {
+                return list.offset16(2 + i * 6 + 4);
+            }
+            if tag == DEFAULT_SCRIPT {
+                default = list.offset16(2 + i * 6 + 4);
+            }
+        }
+        default
+    }
+
+    // The baseline the font lines a script up on.
+    pub fn default_baseline(&self, vertical: bool, script_tag: u32) -> Option<u32> {
+        let values = self.script(vertical, script_tag)?.offset16(0)?;
+        let index = values.u16(0)? as usize;
+        self.baseline_tags(vertical).get(index).cloned()
+    }
+
+    // Where a baseline is for text in a script.
+    pub fn baseline(&self, vertical: bool, script_tag: u32, baseline_tag: u32) -> Option<i16> {
+        let index = self.baseline_tags(vertical).iter().position(|&t| t == baseline_tag)?;
+        let values = self.script(vertical, script_tag)?.offset16(0)?;
+        if index >= values.u16(2)? as usize {
+            return None;
+        }
+        values.offset16(4 + index * 2)?.i16(2)
+    }
+
+    // Every baseline the font has for a script, with its coordinate.
+    pub fn baselines(&self, vertical: bool, script_tag: u32) -> Vec<(u32, i16)> {
+        self.baseline_tags(vertical)
+            .into_iter()
+            .filter_map(|tag| self.baseline(vertical, script_tag, tag).map(|coordinate| (tag, coordinate)))
+            .collect()
+    }
+
+    // The extents of a script's glyphs for a language, or the script's
+    // default ones when the language isn't listed, and those for a
+    // feature in it if `feature_tag` is given and listed.
+    pub fn min_max(&self, vertical: bool, script_tag: u32, language_tag: u32, feature_tag: Option<u32>)
+                   -> Option<MinMax> {
+        let script = self.script(vertical, script_tag)?;
+        let count = script.u16(4)? as usize;
+        let mut min_max = None;
+        for i in 0..count {
+            if script.tag(6 + i * 6)? == language_tag {
+                min_max = script.offset16(6 + i * 6 + 4);
+                break;
+            }
+        }
+        let min_max = min_max.or_else(|| script.offset16(2))?;
+        let coordinate = |table: TableData, at: usize| table.offset16(at).and_then(|c| c.i16(2));
+
+        if let Some(feature_tag) = feature_tag {
+            let count = min_max.u16(4)? as usize;
+            for i in 0..count {
+                if min_max.tag(6 + i * 8)? == feature_tag {
+                    return Some(MinMax {
+                        min: coordinate(min_max, 6 + i * 8 + 4),
+                        max: coordinate(min_max, 6 + i * 8 + 6),
+                    });
+                }
+            }
+        }
+        Some(MinMax { min: coordinate(min_max, 0), max: coordinate(min_max, 2) })
+    }
+}
+
+// Where a baseline is in a font, from its 'BASE' table if it has the
+// baseline for the script, and otherwise worked out from its metrics:
+// the em box is the ascent and descent scaled to an em, the character
+// face is a twentieth of an em inside it, the hanging baseline is at
+// four fifths of the ascent and the math axis at half the x-height.
+pub fn font_baseline(base: Option<&BaseTable>, metrics: &FontMetrics, vertical: bool, script_tag: u32,
+                     baseline: Baseline) -> i32 {
+    if let Some(coordinate) = base.and_then(|b| b.baseline(vertical, script_tag, baseline.tag())) {
+        return coordinate as i32;
+    }
+    let em = metrics.designUnitsPerEm as i32;
+    let height = (metrics.ascent as i32 + metrics.descent as i32).max(1);
+    let em_bottom = -(metrics.descent as i32 * em / height);
+    match baseline {
+        Baseline::Roman => 0,
+        Baseline::Hanging => metrics.ascent as i32 * 4 / 5,
+        Baseline::Ideographic => em_bottom,
+        Baseline::IdeographicTop => em_bottom + em,
+        Baseline::IdeographicFaceBottom => em_bottom + em / 20,
+        Baseline::IdeographicFaceTop => em_bottom + em - em / 20,
+        Baseline::Math => metrics.xHeight as i32 / 2,
+    }
+}
+
+// The baseline a font lines a script up on: the one its 'BASE' table
+// gives, or the script's usual one.
+pub fn font_default_baseline(base: Option<&BaseTable>, vertical: bool, script_tag: u32) -> Baseline {
+    base.and_then(|b| b.default_baseline(vertical, script_tag))
+        .and_then(Baseline::from_tag)
+        .unwrap_or_else(|| Baseline::for_script(script_tag))
+}
@@ -0,0 +1,661 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The Unicode Bidirectional Algorithm (UAX #9,
+// https://www.unicode.org/reports/tr9/): resolves the embedding level
+// of each character of a text, and orders the runs of a line for
+// display.  Text positions are UTF-16 offsets, as in DirectWrite and
+// ShapedGlyphs::cluster_map.
+//
+// Each run's level can be passed as the bidiLevel of the glyph runs
+// shaped from it; glyphs of odd (right-to-left) levels are drawn from
+// right to left.
+
+use glyph_buffer::TextDirection;
+use unicode_data::{self, BidiClass};
+use unicode_data::BidiClass::*;
+
+// The deepest embedding level explicit formatting characters can reach.
+const MAX_DEPTH: u8 = 125;
+
+// Nesting limit for paired brackets (BD16).
+const MAX_BRACKET_DEPTH: usize = 63;
+
+// A range of text at one embedding level.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct BidiRun {
+    pub start: usize,
+    pub end: usize,
+    pub level: u8,
+}
+
+impl BidiRun {
+    pub fn direction(&self) -> TextDirection {
+        level_direction(self.level)
+    }
+}
+
+fn level_direction(level: u8) -> TextDirection {
+    if level & 1 == 1 {
+        TextDirection::RightToLeft
+    } else {
+        TextDirection::LeftToRight
+    }
+}
+
+#[derive(Debug)]
+struct Paragraph {
+    // The character index the paragraph ends at.
+    end: usize,
+    level: u8,
+}
+
+#[derive(Debug)]
+pub struct BidiAnalysis {
+    // The UTF-16 offset of each character, followed by the length of
+    // the text.
+    offsets: Vec<usize>,
+    classes: Vec<BidiClass>,
+    levels: Vec<u8>,
+    paragraphs: Vec<Paragraph>,
+}
+
+impl BidiAnalysis {
+    // Analyzes `text`, which is split into paragraphs after each
+    // paragraph separator.  Paragraphs take the given direction, or
+    // without one, the direction of their first strong character.
+    pub fn new(text: &str, direction: Option<TextDirection>) -> BidiAnalysis {
+        let mut offsets = vec![];
+        let mut chars = vec![];
+        let mut offset = 0;
+        for c in text.chars() {
+            offsets.push(offset);
+            chars.push(c as u32);
+            offset += c.len_utf16();
+        }
+        offsets.push(offset);
+
+        let classes: Vec<BidiClass> = chars.iter().map(|&c| unicode_data::bidi_class(c)).collect();
+        let mut levels = vec![0; chars.len()];
+        let mut paragraphs = vec![];
+        let mut start = 0;
+        while start < chars.len() {
+            let end = classes[start..].iter().position(|&c| c == B)
+                .map(|i| start + i + 1)
+                .unwrap_or(chars.len());
+            let level = match direction {
+                Some(TextDirection::LeftToRight) => 0,
+                Some(TextDirection::RightToLeft) => 1,
+                None => first_strong_level(&classes[start..end]).unwrap_or(0),
+            };
+            resolve_paragraph(&chars[start..end], &classes[start..end], level, &mut levels[start..end]);
+            paragraphs.push(Paragraph { end: end, level: level });
+            start = end;
+        }
+
+        BidiAnalysis {
+            offsets: offsets,
+            classes: classes,
+            levels: levels,
+            paragraphs: paragraphs,
+        }
+    }
+
+    // The level of the paragraph containing `position`; the first
+    // paragraph's for empty text.
+    pub fn paragraph_level(&self, position: usize) -> u8 {
+        let index = self.char_index(position);
+        self.paragraphs.iter()
+            .find(|p| index < p.end)
+            .or(self.paragraphs.last())
+            .map(|p| p.level)
+            .unwrap_or(0)
+    }
+
+    // The resolved level of each UTF-16 code unit of the text.
+    pub fn levels(&self) -> Vec<u8> {
+        let mut levels = Vec::with_capacity(self.len());
+        for (i, &level) in self.levels.iter().enumerate() {
+            for _ in self.offsets[i]..self.offsets[i + 1] {
+                levels.push(level);
+            }
+        }
+        levels
+    }
+
+    // Maximal runs of text at the same level, in logical order.  These
+    // are the runs to shape; lines are ordered with reorder_line.
+    pub fn level_runs(&self) -> Vec<BidiRun> {
+        self.runs(0, &self.levels)
+    }
+
+    // The runs of the line from `start` to `end`, in visual order from
+    // left to right.  Whitespace at the end of the line and before tabs
+    // and paragraph separators goes back to the paragraph's level
+    // (rule L1), so it stays at the paragraph's edge.
+    pub fn reorder_line(&self, start: usize, end: usize) -> Vec<BidiRun> {
+        let first = self.char_index(start);
+        let last = self.char_index(end);
+        if first >= last {
+            return vec![];
+        }
+
+        let mut levels = self.levels[first..last].to_vec();
+        let mut trailing = true;
+        for i in (first..last).rev() {
+            let paragraph_level = self.paragraphs.iter().find(|p| i < p.end).unwrap().level;
+            match self.classes[i] {
+                B | S => {
+                    levels[i - first] = paragraph_level;
+                    trailing = true;
+                }
+                WS | LRI | RLI | FSI | PDI if trailing => levels[i - first] = paragraph_level,
+                class if trailing && is_removed_by_x9(class) => levels[i - first] = paragraph_level,
+                _ => trailing = false,
+            }
+        }
+
+        // Reverse any sequence of runs at or above each odd level, from
+        // the highest level down (rule L2).
+        let mut runs = self.runs(first, &levels);
+        let highest = runs.iter().map(|r| r.level).max().unwrap_or(0);
+        let lowest_odd = runs.iter().map(|r| r.level | 1).min().unwrap_or(1);
+        let mut level = highest;
+        while level >= lowest_odd {
+            let mut i = 0;
+            while i < runs.len() {
+                if runs[i].level < level {
+                    i += 1;
+                    continue;
+                }
+                let start = i;
+                while i < runs.len() && runs[i].level >= level {
+                    i += 1;
+                }
+                runs[start..i].reverse();
+            }
+            level -= 1;
+        }
+        runs
+    }
+
+    // The length of the text in UTF-16 code units.
+    fn len(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+
+    fn char_index(&self, position: usize) -> usize {
+        match self.offsets.binary_search(&position) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        }
+    }
+
+    // Runs of the characters from `first` on with the given levels.
+    fn runs(&self, first: usize, levels: &[u8]) -> Vec<BidiRun> {
+        let mut runs: Vec<BidiRun> = vec![];
+        for (k, &level) in levels.iter().enumerate() {
+            let i = first + k;
+            if let Some(run) = runs.last_mut() {
+                if run.level == level {
+                    run.end = self.offsets[i + 1];
+                    continue;
+                }
+            }
+            runs.push(BidiRun {
+                start: self.offsets[i],
+                end: self.offsets[i + 1],
+                level: level,
+            });
+        }
+        runs
+    }
+}
+
+// The level given by the first strong character, skipping isolates
+// (rules P2 and P3).
+fn first_strong_level(classes: &[BidiClass]) -> Option<u8> {
+    let mut isolates = 0;
+    for &class in classes {
+        match class {
+            L if isolates == 0 => return Some(0),
+            R | AL if isolates == 0 => return Some(1),
+            LRI | RLI | FSI => isolates += 1,
+            PDI if isolates > 0 => isolates -= 1,
+            B => break,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_isolate_initiator(class: BidiClass) -> bool {
+    class == LRI || class == RLI || class == FSI
+}
+
+// Explicit embedding and override characters don't take part in
+// resolving levels (rule X9); they're given the level of the character
+// before them.
+fn is_removed_by_x9(class: BidiClass) -> bool {
+    match class {
+        RLE | LRE | RLO | LRO | PDF | BN => true,
+        _ => false,
+    }
+}
+
+// The least odd or even level above `level`.
+fn next_level(level: u8, rtl: bool) -> u8 {
+    if rtl {
+        (level + 1) | 1
+    } else {
+        (level + 2) & !1
+    }
+}
+
+// For each isolate initiator, the index of its matching PDI (BD9).
+fn matching_pdis(classes: &[BidiClass]) -> Vec<Option<usize>> {
+    let mut matching = vec![None; classes.len()];
+    let mut open = vec![];
+    for (i, &class) in classes.iter().enumerate() {
+        if is_isolate_initiator(class) {
+            open.push(i);
+        } else if class == PDI {
+            if let Some(initiator) = open.pop() {
+                matching[initiator] = Some(i);
+            }
+        }
+    }
+    matching
+}
+
+#[derive(Clone, Copy)]
+struct DirectionalStatus {
+    level: u8,
+    override_class: Option<BidiClass>,
+    isolate: bool,
+}
+
+fn resolve_paragraph(chars: &[u32], original: &[BidiClass], paragraph_level: u8, levels: &mut [u8]) {
+    let matching_pdi = matching_pdis(original);
+    let mut classes = original.to_vec();
+    let mut embedding_levels = vec![0; original.len()];
+    resolve_explicit_levels(original, &matching_pdi, paragraph_level, &mut classes, &mut embedding_levels);
+
+    levels.copy_from_slice(&embedding_levels);
+    for sequence in isolating_run_sequences(original, &matching_pdi, &embedding_levels) {
+        resolve_sequence(chars, original, &classes, &matching_pdi, paragraph_level, &embedding_levels,
+                         &sequence, levels);
+    }
+
+    let mut level = paragraph_level;
+    for (i, &class) in original.iter().enumerate() {
+        if is_removed_by_x9(class) {
+            levels[i] = level;
+        }
+        level = levels[i];
+    }
+}
+
+// Rules X1 to X8.
+fn resolve_explicit_levels(original: &[BidiClass], matching_pdi: &[Option<usize>], paragraph_level: u8,
+                           classes: &mut [BidiClass], levels: &mut [u8]) {
+    let mut stack = vec![DirectionalStatus {
+        level: paragraph_level,
+        override_class: None,
+        isolate: false,
+    }];
+    let mut overflow_isolates = 0;
+    let mut overflow_embeddings = 0;
+    let mut valid_isolates = 0;
+
+    for i in 0..original.len() {
+        let top = *stack.last().unwrap();
+        match original[i] {
+            RLE | LRE | RLO | LRO => {
+                levels[i] = top.level;
+                let class = original[i];
+                let level = next_level(top.level, class == RLE || class == RLO);
+                if level <= MAX_DEPTH && overflow_isolates == 0 && overflow_embeddings == 0 {
+                    stack.push(DirectionalStatus {
+                        level: level,
+                        override_class: match class {
+                            RLO => Some(R),
+                            LRO => Some(L),
+                            _ => None,
+                        },
+                        isolate: false,
+                    });
+                } else if overflow_isolates == 0 {
+                    overflow_embeddings += 1;
+                }
+            }
+            RLI | LRI | FSI => {
+                levels[i] = top.level;
+                if let Some(class) = top.override_class {
+                    classes[i] = class;
+                }
+                let rtl = match original[i] {
+                    RLI => true,
+                    LRI => false,
+                    _ => {
+                        let end = matching_pdi[i].unwrap_or(original.len());
+                        first_strong_level(&original[i + 1..end]) == Some(1)
+                    }
+                };
+                let level = next_level(top.level, rtl);
+                if level <= MAX_DEPTH && overflow_isolates == 0 && overflow_embeddings == 0 {
+                    valid_isolates += 1;
+                    stack.push(DirectionalStatus {
+                        level: level,
+                        override_class: None,
+                        isolate: true,
+                    });
+                } else {
+                    overflow_isolates += 1;
+                }
+            }
+            PDI => {
+                if overflow_isolates > 0 {
+                    overflow_isolates -= 1;
+                } else if valid_isolates > 0 {
+                    overflow_embeddings = 0;
+                    while !stack.last().unwrap().isolate {
+                        stack.pop();
+                    }
+                    stack.pop();
+                    valid_isolates -= 1;
+                }
+                let top = *stack.last().unwrap();
+                levels[i] = top.level;
+                if let Some(class) = top.override_class {
+                    classes[i] = class;
+                }
+            }
+            PDF => {
+                levels[i] = top.level;
+                if overflow_isolates > 0 {
+                    // Inside an isolate that overflowed.
+                } else if overflow_embeddings > 0 {
+                    overflow_embeddings -= 1;
+                } else if !top.isolate && stack.len() >= 2 {
+                    stack.pop();
+                }
+            }
+            B => levels[i] = paragraph_level,
+            BN => levels[i] = top.level,
+            _ => {
+                levels[i] = top.level;
+                if let Some(class) = top.override_class {
+                    classes[i] = class;
+                }
+            }
+        }
+    }
+}
+
+// Splits the paragraph into level runs (BD7), ignoring characters
+// removed by X9, and joins the runs either side of each isolate into
+// isolating run sequences (BD13).
+fn isolating_run_sequences(original: &[BidiClass], matching_pdi: &[Option<usize>], levels: &[u8])
+                           -> Vec<Vec<usize>> {
+    let mut runs: Vec<Vec<usize>> = vec![];
+    let mut last_level = None;
+    for i in 0..original.len() {
+        if is_removed_by_x9(original[i]) {
+            continue;
+        }
+        if last_level == Some(levels[i]) {
+            runs.last_mut().unwrap().push(i);
+        } else {
+            runs.push(vec![i]);
+        }
+        last_level = Some(levels[i]);
+    }
+
+    let mut run_starting_at = vec![None; original.len()];
+    for (r, run) in runs.iter().enumerate() {
+        run_starting_at[run[0]] = Some(r);
+    }
+    let mut is_matched_pdi = vec![false; original.len()];
+    for pdi in matching_pdi.iter().filter_map(|&m| m) {
+        is_matched_pdi[pdi] = true;
+    }
+
+    let mut sequences = vec![];
+    for run in &runs {
+        if original[run[0]] == PDI && is_matched_pdi[run[0]] {
+            // Continues the sequence before the isolate.
+            continue;
+        }
+        let mut sequence = run.clone();
+        loop {
+            let last = *sequence.last().unwrap();
+            let next = match matching_pdi[last] {
+                Some(pdi) if is_isolate_initiator(original[last]) => run_starting_at[pdi],
+                _ => None,
+            };
+            match next {
+                Some(r) => sequence.extend_from_slice(&runs[r]),
+                None => break,
+            }
+        }
+        sequences.push(sequence);
+    }
+    sequences
+}
+
+// L for even levels, R for odd ones.
+fn embedding_direction(level: u8) -> BidiClass {
+    if level & 1 == 1 { R } else { L }
+}
+
+// The strong direction a resolved type counts as for rules N0 to N2;
+// numbers count as R.
+fn strong_direction(class: BidiClass) -> Option<BidiClass> {
+    match class {
+        L => Some(L),
+        R | AL | EN | AN => Some(R),
+        _ => None,
+    }
+}
+
+fn is_neutral_or_isolate(class: BidiClass) -> bool {
+    match class {
+        B | S | WS | ON | LRI | RLI | FSI | PDI => true,
+        _ => false,
+    }
+}
+
+// Resolves weak types, neutrals and finally levels of one isolating
+// run sequence (rules W1 to I2), given the levels from the explicit
+// formatting characters.
+fn resolve_sequence(chars: &[u32], original: &[BidiClass], classes: &[BidiClass],
+                    matching_pdi: &[Option<usize>], paragraph_level: u8, embedding_levels: &[u8],
+                    sequence: &[usize], levels: &mut [u8]) {
+    let first = sequence[0];
+    let last = *sequence.last().unwrap();
+    let level = embedding_levels[first];
+
+    // The directions at the sequence's edges (X10).
+    let level_before = (0..first).rev()
+        .find(|&i| !is_removed_by_x9(original[i]))
+        .map(|i| embedding_levels[i])
+        .unwrap_or(paragraph_level);
+    let level_after = if is_isolate_initiator(original[last]) && matching_pdi[last].is_none() {
+        paragraph_level
+    } else {
+        (last + 1..original.len())
+            .find(|&i| !is_removed_by_x9(original[i]))
+            .map(|i| embedding_levels[i])
+            .unwrap_or(paragraph_level)
+    };
+    let sos = embedding_direction(::std::cmp::max(level, level_before));
+    let eos = embedding_direction(::std::cmp::max(level, level_after));
+
+    let mut types: Vec<BidiClass> = sequence.iter().map(|&i| classes[i]).collect();
+    let n = types.len();
+
+    // W1: non-spacing marks take the type of what they follow.
+    let mut previous = sos;
+    for t in &mut types {
+        if *t == NSM {
+            *t = match previous {
+                LRI | RLI | FSI | PDI => ON,
+                p => p,
+            };
+        }
+        previous = *t;
+    }
+
+    // W2: numbers after Arabic letters are Arabic numbers.  W3: Arabic
+    // letters are R from here on.
+    let mut last_strong = sos;
+    for t in &mut types {
+        match *t {
+            L | R | AL => last_strong = *t,
+            EN if last_strong == AL => *t = AN,
+            _ => {}
+        }
+    }
+    for t in &mut types {
+        if *t == AL {
+            *t = R;
+        }
+    }
+
+    // W4: single separators between numbers of the same kind.
+    for k in 1..n.saturating_sub(1) {
+        let (before, after) = (types[k - 1], types[k + 1]);
+        if types[k] == ES && before == EN && after == EN {
+            types[k] = EN;
+        } else if types[k] == CS && before == after && (before == EN || before == AN) {
+            types[k] = before;
+        }
+    }
+
+    // W5: terminators next to European numbers.
+    let mut k = 0;
+    while k < n {
+        if types[k] != ET {
+            k += 1;
+            continue;
+        }
+        let start = k;
+        while k < n && types[k] == ET {
+            k += 1;
+        }
+        if (start > 0 && types[start - 1] == EN) || (k < n && types[k] == EN) {
+            for t in &mut types[start..k] {
+                *t = EN;
+            }
+        }
+    }
+
+    // W6: other separators and terminators are neutral.  W7: European
+    // numbers in left-to-right context are L.
+    last_strong = sos;
+    for t in &mut types {
+        match *t {
+            ES | ET | CS => *t = ON,
+            L | R => last_strong = *t,
+            EN if last_strong == L => *t = L,
+            _ => {}
+        }
+    }
+
+    // N0: paired brackets take the direction of what they enclose,
+    // when that's the embedding direction or matches the context.
+    let e = embedding_direction(level);
+    for (open, close) in bracket_pairs(chars, &types, sequence) {
+        let mut found_opposite = false;
+        let mut direction = None;
+        for t in &types[open + 1..close] {
+            match strong_direction(*t) {
+                Some(d) if d == e => {
+                    direction = Some(e);
+                    break;
+                }
+                Some(_) => found_opposite = true,
+                None => {}
+            }
+        }
+        if direction.is_none() && found_opposite {
+            let context = types[..open].iter().rev()
+                .filter_map(|&t| strong_direction(t))
+                .next()
+                .unwrap_or(sos);
+            direction = Some(context);
+        }
+        if let Some(d) = direction {
+            for &bracket in &[open, close] {
+                types[bracket] = d;
+                // Marks on a bracket go with it.
+                let mut k = bracket + 1;
+                while k < n && original[sequence[k]] == NSM {
+                    types[k] = d;
+                    k += 1;
+                }
+            }
+        }
+    }
+
+    // N1 and N2: neutrals between two characters of the same direction
+    // take it, and otherwise the embedding direction.
+    let mut k = 0;
+    while k < n {
+        if !is_neutral_or_isolate(types[k]) {
+            k += 1;
+            continue;
+        }
+        let start = k;
+        while k < n && is_neutral_or_isolate(types[k]) {
+            k += 1;
+        }
+        let before = if start == 0 { Some(sos) } else { strong_direction(types[start - 1]) };
+        let after = if k == n { Some(eos) } else { strong_direction(types[k]) };
+        let direction = if before == after { before.unwrap_or(e) } else { e };
+        for t in &mut types[start..k] {
+            *t = direction;
+        }
+    }
+
+    // I1 and I2.
+    for (k, &i) in sequence.iter().enumerate() {
+        levels[i] = level + match (level & 1, types[k]) {
+            (0, R) => 1,
+            (0, AN) | (0, EN) => 2,
+            (1, L) | (1, EN) | (1, AN) => 1,
+            _ => 0,
+        };
+    }
+}
+
+// The paired brackets of a sequence (BD16), as indices into it, sorted
+// by the opening bracket.
+fn bracket_pairs(chars: &[u32], types: &[BidiClass], sequence: &[usize]) -> Vec<(usize, usize)> {
+    let mut open: Vec<(u32, usize)> = vec![];
+    let mut pairs = vec![];
+    for (k, &i) in sequence.iter().enumerate() {
+        if types[k] != ON {
+            continue;
+        }
+        match unicode_data::bidi_bracket(chars[i]) {
+            Some((bracket, true)) => {
+                if open.len() == MAX_BRACKET_DEPTH {
+                    break;
+                }
+                open.push((bracket, k));
+            }
+            Some((bracket, false)) => {
+                if let Some(j) = open.iter().rposition(|o| o.0 == bracket) {
+                    pairs.push((open[j].1, k));
+                    open.truncate(j);
+                }
+            }
+            None => {}
+        }
+    }
+    pairs.sort();
+    pairs
+}
@@ -34,6 +34,24 @@ impl BitmapRenderTarget {
         }
     }
 
+    // The transform applied to everything drawn, e.g. a rotation for
+    // vertical text (see TextLayout::vertical_transform).
+    pub fn set_current_transform(&self, transform: &winapi::DWRITE_MATRIX) {
+        unsafe {
+            let hr = (*self.native.get()).SetCurrentTransform(transform);
+            assert!(hr == 0);
+        }
+    }
+
+    pub fn get_current_transform(&self) -> winapi::DWRITE_MATRIX {
+        unsafe {
+            let mut transform: winapi::DWRITE_MATRIX = zeroed();
+            let hr = (*self.native.get()).GetCurrentTransform(&mut transform);
+            assert!(hr == 0);
+            transform
+        }
+    }
+
     pub fn get_memory_dc(&self) -> winapi::HDC {
         unsafe {
             (*self.native.get()).GetMemoryDC()
@@ -52,6 +70,52 @@ impl BitmapRenderTarget {
                           rendering_params: &RenderingParams,
                           color: &(f32, f32, f32))
         -> winapi::RECT
+    {
+        self.draw_glyph_run_at_level(baseline_origin_x, baseline_origin_y, measuring_mode, font_face,
+                                     em_size, glyph_indices, glyph_advances, glyph_offsets, 0,
+                                     rendering_params, color)
+    }
+
+    // Like draw_glyph_run, for a run at the given bidi level (see
+    // BidiAnalysis).  Runs at odd levels are drawn right to left from
+    // the baseline origin.
+    pub fn draw_glyph_run_at_level(&self,
+                                   baseline_origin_x: f32,
+                                   baseline_origin_y: f32,
+                                   measuring_mode: winapi::DWRITE_MEASURING_MODE,
+                                   font_face: &FontFace,
+                                   em_size: f32,
+                                   glyph_indices: &[u16],
+                                   glyph_advances: &[f32],
+                                   glyph_offsets: &[winapi::DWRITE_GLYPH_OFFSET],
+                                   bidi_level: u32,
+                                   rendering_params: &RenderingParams,
+                                   color: &(f32, f32, f32))
+        -> winapi::RECT
+    {
+        self.draw_glyph_run_sideways(baseline_origin_x, baseline_origin_y, measuring_mode, font_face,
+                                     em_size, glyph_indices, glyph_advances, glyph_offsets, bidi_level,
+                                     false, rendering_params, color)
+    }
+
+    // Like draw_glyph_run_at_level, with the glyphs turned 90 degrees
+    // left and advancing by their vertical metrics if `is_sideways`.
+    // Glyphs set upright in vertical text are drawn that way, under a
+    // transform turning the whole run 90 degrees right.
+    pub fn draw_glyph_run_sideways(&self,
+                                   baseline_origin_x: f32,
+                                   baseline_origin_y: f32,
+                                   measuring_mode: winapi::DWRITE_MEASURING_MODE,
+                                   font_face: &FontFace,
+                                   em_size: f32,
+                                   glyph_indices: &[u16],
+                                   glyph_advances: &[f32],
+                                   glyph_offsets: &[winapi::DWRITE_GLYPH_OFFSET],
+                                   bidi_level: u32,
+                                   is_sideways: bool,
+                                   rendering_params: &RenderingParams,
+                                   color: &(f32, f32, f32))
+        -> winapi::RECT
     {
         unsafe {
             assert!(glyph_indices.len() == glyph_advances.len());
@@ -68,8 +132,8 @@ impl BitmapRenderTarget {
             glyph_run.glyphIndices = glyph_indices.as_ptr();
             glyph_run.glyphAdvances = glyph_advances.as_ptr();
             glyph_run.glyphOffsets = glyph_offsets.as_ptr();
-            glyph_run.isSideways = 0;
-            glyph_run.bidiLevel = 0;
+            glyph_run.isSideways = is_sideways as winapi::BOOL;
+            glyph_run.bidiLevel = bidi_level;
 
             let mut rect: winapi::RECT = zeroed();
             let hr = (*self.native.get()).DrawGlyphRun(baseline_origin_x,
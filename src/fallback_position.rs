@@ -0,0 +1,256 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Positions combining marks for fonts without GPOS mark attachment,
+// so they don't all pile up at the origin.  Each mark is placed above
+// or below its base (or ligature component) according to its combining
+// class, using the glyphs' ink boxes, and marks of the same class are
+// stacked away from the base.  This follows HarfBuzz's fallback
+// positioning.
+
+use glyph_buffer::{GlyphBuffer, GlyphInfo, GlyphPosition, TextDirection};
+use unicode_data;
+use super::FontFace;
+
+// Canonical combining classes that say where a mark goes.
+const ATTACHED_BELOW_LEFT: u8 = 200;
+const ATTACHED_BELOW: u8 = 202;
+const ATTACHED_ABOVE: u8 = 214;
+const ATTACHED_ABOVE_RIGHT: u8 = 216;
+const BELOW_LEFT: u8 = 218;
+const BELOW: u8 = 220;
+const BELOW_RIGHT: u8 = 222;
+const ABOVE_LEFT: u8 = 228;
+const ABOVE: u8 = 230;
+const ABOVE_RIGHT: u8 = 232;
+const DOUBLE_BELOW: u8 = 233;
+const DOUBLE_ABOVE: u8 = 234;
+
+// A glyph's ink box in design units, relative to its origin: y_bearing
+// is the top and height is negative, so the bottom is at
+// y_bearing + height.
+#[derive(Clone, Copy)]
+struct Extents {
+    x_bearing: i32,
+    y_bearing: i32,
+    width: i32,
+    height: i32,
+}
+
+// The combining class to position a mark by.  Hebrew, Arabic, Thai,
+// Lao, Telugu and Tibetan have fixed-position classes that only say how
+// marks are ordered, so those are mapped to where the marks actually go.
+fn positioning_class(cp: u32) -> u8 {
+    let class = unicode_data::combining_class(cp);
+    if class >= 200 {
+        return class;
+    }
+
+    // Thai and Lao marks that have class 0 (or one not saying
+    // which side they go).
+    if cp & !0xFF == 0x0E00 {
+        match cp {
+            0x0E31 | 0x0E34 | 0x0E35 | 0x0E36 | 0x0E37 | 0x0E47 | 0x0E4C | 0x0E4D | 0x0E4E =>
+                return ABOVE_RIGHT,
+            0x0EB1 | 0x0EB4 | 0x0EB5 | 0x0EB6 | 0x0EB7 | 0x0EBB | 0x0ECC | 0x0ECD => return ABOVE,
+            0x0EBC => return BELOW,
+            0x0E3A => return BELOW_RIGHT,
+            _ => {}
+        }
+    }
+
+    match class {
+        // Hebrew points: sheva to qamats, qubuts and meteg go below,
+        // rafe, the shin and sin dots, holam and varika above.  Dagesh
+        // (21) is drawn inside the letter.
+        c if (10 <= c && c <= 18) || c == 20 || c == 22 => BELOW,
+        23 => ATTACHED_ABOVE,
+        24 => ABOVE_RIGHT,
+        25 | 19 => ABOVE_LEFT,
+        26 => ABOVE,
+
+        // Arabic harakat: kasratan and kasra go below, the rest above.
+        29 | 32 => BELOW,
+        27 | 28 | 30 | 31 | 33 | 34 | 35 | 36 => ABOVE,
+
+        // Thai, Lao, Telugu and Tibetan vowel signs and tone marks.
+        103 => BELOW_RIGHT,
+        107 => ABOVE_RIGHT,
+        118 | 129 | 132 => BELOW,
+        122 | 130 => ABOVE,
+
+        _ => class,
+    }
+}
+
+fn glyph_extents(metrics: &::winapi::DWRITE_GLYPH_METRICS) -> Extents {
+    let top = metrics.verticalOriginY - metrics.topSideBearing;
+    let bottom = metrics.verticalOriginY - (metrics.advanceHeight as i32 - metrics.bottomSideBearing);
+    Extents {
+        x_bearing: metrics.leftSideBearing,
+        y_bearing: top,
+        width: metrics.advanceWidth as i32 - metrics.leftSideBearing - metrics.rightSideBearing,
+        height: bottom - top,
+    }
+}
+
+pub fn position_marks(face: &FontFace, buffer: &mut GlyphBuffer) {
+    let classes: Vec<u8> = buffer.glyphs.iter().map(|g| positioning_class(g.codepoint)).collect();
+    let is_mark = |info: &GlyphInfo, class: u8| class != 0 || info.is_mark();
+    if !classes.iter().any(|&c| c != 0) {
+        return;
+    }
+
+    let metrics = face.get_design_glyph_metrics(&buffer.glyph_indices(), false);
+    let extents: Vec<Extents> = metrics.iter().map(glyph_extents).collect();
+    let y_gap = face.metrics().designUnitsPerEm as i32 / 16;
+
+    let len = buffer.len();
+    let mut base = 0;
+    while base < len {
+        let mut end = base + 1;
+        while end < len && is_mark(&buffer.glyphs[end], classes[end]) {
+            end += 1;
+        }
+        if end > base + 1 && !is_mark(&buffer.glyphs[base], classes[base]) {
+            position_around_base(buffer, &classes, &extents, y_gap, base, end);
+        }
+        base = end;
+    }
+}
+
+fn position_around_base(buffer: &mut GlyphBuffer, classes: &[u8], extents: &[Extents], y_gap: i32,
+                        base: usize, end: usize) {
+    let direction = buffer.direction;
+    let base_info = buffer.glyphs[base];
+    let base_pos = buffer.positions[base];
+
+    // Horizontally, marks are placed relative to the base's advance
+    // rather than its ink, which also works for bases without ink.
+    let base_extents = Extents {
+        x_bearing: base_pos.x_offset,
+        y_bearing: extents[base].y_bearing + base_pos.y_offset,
+        width: base_pos.x_advance,
+        height: extents[base].height,
+    };
+
+    let num_components = base_info.lig_num_components as i32;
+    let mut last_component = -1;
+    let mut last_class = 255;
+    let mut component_extents = base_extents;
+    let mut cluster_extents = base_extents;
+
+    // Where each glyph's origin is relative to the base's, as the pen
+    // moves over the glyphs that aren't repositioned.
+    let mut pen = match direction {
+        TextDirection::LeftToRight => base_pos.x_advance,
+        TextDirection::RightToLeft => 0,
+    };
+
+    for i in base + 1..end {
+        let class = classes[i];
+        if class == 0 {
+            match direction {
+                TextDirection::LeftToRight => pen += buffer.positions[i].x_advance,
+                TextDirection::RightToLeft => pen -= buffer.positions[i].x_advance,
+            }
+            continue;
+        }
+
+        // Marks on a ligature go on the component they belong to, if
+        // it's known, and otherwise on the last one.
+        if num_components > 1 {
+            let info = &buffer.glyphs[i];
+            let mut component = info.lig_component as i32 - 1;
+            if base_info.lig_id == 0 || info.lig_id != base_info.lig_id || component < 0 ||
+               component >= num_components {
+                component = num_components - 1;
+            }
+            if component != last_component {
+                last_component = component;
+                last_class = 255;
+                component_extents = base_extents;
+                let visual_component = match direction {
+                    TextDirection::LeftToRight => component,
+                    TextDirection::RightToLeft => num_components - 1 - component,
+                };
+                component_extents.x_bearing += visual_component * base_extents.width / num_components;
+                component_extents.width = base_extents.width / num_components;
+            }
+        }
+
+        // Marks of a new class start again from the base; marks of the
+        // same class stack on each other.
+        if class != last_class {
+            last_class = class;
+            cluster_extents = component_extents;
+        }
+
+        let pos = &mut buffer.positions[i];
+        position_mark(direction, &extents[i], pos, &mut cluster_extents, class, y_gap);
+        pos.x_advance = 0;
+        pos.y_advance = 0;
+        pos.x_offset -= pen;
+    }
+}
+
+fn position_mark(direction: TextDirection, mark: &Extents, pos: &mut GlyphPosition,
+                 base: &mut Extents, class: u8, y_gap: i32) {
+    pos.x_offset = match class {
+        DOUBLE_BELOW | DOUBLE_ABOVE => {
+            // Centered on the edge shared with the next character.
+            let edge = match direction {
+                TextDirection::LeftToRight => base.width,
+                TextDirection::RightToLeft => 0,
+            };
+            base.x_bearing + edge - mark.width / 2 - mark.x_bearing
+        }
+        ATTACHED_BELOW_LEFT | BELOW_LEFT | ABOVE_LEFT => base.x_bearing - mark.x_bearing,
+        ATTACHED_ABOVE_RIGHT | BELOW_RIGHT | ABOVE_RIGHT =>
+            base.x_bearing + base.width - mark.width - mark.x_bearing,
+        _ => base.x_bearing + (base.width - mark.width) / 2 - mark.x_bearing,
+    };
+    pos.y_offset = 0;
+
+    let is_attached = match class {
+        ATTACHED_BELOW_LEFT | ATTACHED_BELOW | ATTACHED_ABOVE | ATTACHED_ABOVE_RIGHT => true,
+        _ => false,
+    };
+
+    match class {
+        DOUBLE_BELOW | BELOW_LEFT | BELOW | BELOW_RIGHT | ATTACHED_BELOW_LEFT | ATTACHED_BELOW => {
+            if !is_attached {
+                // Leave a gap below the base.
+                base.height -= y_gap;
+            }
+            pos.y_offset = base.y_bearing + base.height - mark.y_bearing;
+            // Never move a below mark up; it's already clear of the
+            // base.
+            if (y_gap > 0) == (pos.y_offset > 0) {
+                base.height -= pos.y_offset;
+                pos.y_offset = 0;
+            }
+            base.height += mark.height;
+        }
+        DOUBLE_ABOVE | ABOVE_LEFT | ABOVE | ABOVE_RIGHT | ATTACHED_ABOVE | ATTACHED_ABOVE_RIGHT => {
+            if !is_attached {
+                base.y_bearing += y_gap;
+                base.height -= y_gap;
+            }
+            pos.y_offset = base.y_bearing - (mark.y_bearing + mark.height);
+            // A mark drawn higher than it needs to be would move
+            // down; only go half way, so it keeps some of the height
+            // the font gave it.
+            if (y_gap > 0) != (pos.y_offset > 0) {
+                let correction = -pos.y_offset / 2;
+                base.y_bearing += correction;
+                base.height -= correction;
+                pos.y_offset += correction;
+            }
+            base.y_bearing -= mark.height;
+            base.height += mark.height;
+        }
+        _ => {}
+    }
+}
@@ -74,6 +74,15 @@ impl Font {
         }
     }
 
+    pub fn has_character(&self, code_point: u32) -> bool {
+        unsafe {
+            let mut exists: winapi::BOOL = winapi::FALSE;
+            let hr = (*self.native.get()).HasCharacter(code_point, &mut exists);
+            assert!(hr == 0);
+            exists != winapi::FALSE
+        }
+    }
+
     pub fn create_font_face(&self) -> FontFace {
         // FIXME create_font_face should cache the FontFace and return it,
         // there's a 1:1 relationship
@@ -8,6 +8,10 @@ use std::cell::UnsafeCell;
 use std::mem::zeroed;
 
 use comptr::ComPtr;
+use font_features::{self, FontFeature};
+use glyph_metrics::GlyphMetricsTables;
+use line_metrics::FontLineMetrics;
+use math_table::MathTable;
 use super::{FontMetrics, FontFile, DefaultDWriteRenderParams};
 
 use winapi;
@@ -68,6 +72,19 @@ impl FontFace {
         }
     }
 
+    // The font's 'hhea', typo and win ascent, descent and line gap,
+    // which metrics() only gives one of.  None if the font has no
+    // 'hhea' table.
+    pub fn get_line_metrics(&self) -> Option<FontLineMetrics> {
+        FontLineMetrics::from_font_face(self)
+    }
+
+    // The font's 'MATH' table, for laying out math; None if it has
+    // none.
+    pub fn get_math_table(&self) -> Option<MathTable> {
+        MathTable::from_font_face(self)
+    }
+
     pub fn get_glyph_indices(&self, code_points: &[u32]) -> Vec<u16> {
         unsafe {
             let mut glyph_indices: Vec<u16> = vec![0; code_points.len()];
@@ -91,6 +108,14 @@ impl FontFace {
         }
     }
 
+    // Like get_design_glyph_metrics, worked out from the font's
+    // 'hmtx', 'vmtx' and 'VORG' tables instead of by DirectWrite (see
+    // GlyphMetricsTables).  None if the font has no horizontal metrics.
+    pub fn get_design_glyph_metrics_from_tables(&self, glyph_indices: &[u16])
+                                                -> Option<Vec<winapi::DWRITE_GLYPH_METRICS>> {
+        GlyphMetricsTables::from_font_face(self).map(|tables| tables.design_glyph_metrics(self, glyph_indices))
+    }
+
     pub fn get_gdi_compatible_glyph_metrics(&self, em_size: f32, pixels_per_dip: f32, transform: *const winapi::DWRITE_MATRIX,
                                             use_gdi_natural: bool, glyph_indices: &[u16], is_sideways: bool)
                                             -> Vec<winapi::DWRITE_GLYPH_METRICS>
@@ -135,6 +160,25 @@ impl FontFace {
         }
     }
 
+    // The OpenType script tags declared by the face's GSUB and GPOS
+    // tables.
+    pub fn layout_scripts(&self) -> Vec<u32> {
+        font_features::layout_scripts(self)
+    }
+
+    // The language system tags declared for a script, not including
+    // the script's default language system.
+    pub fn layout_languages(&self, script_tag: u32) -> Vec<u32> {
+        font_features::layout_languages(self, script_tag)
+    }
+
+    // The features available for a script and language; passing 'dflt'
+    // (or a language the script doesn't list) gives the features of the
+    // script's default language system.
+    pub fn layout_features(&self, script_tag: u32, language_tag: u32) -> Vec<FontFeature> {
+        font_features::layout_features(self, script_tag, language_tag)
+    }
+
     pub fn get_recommended_rendering_mode(&self,
                                           em_size: f32,
                                           pixels_per_dip: f32,
@@ -0,0 +1,174 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Picks a font for each grapheme cluster of a text: the requested one
+// if it has all the cluster's characters, and otherwise the first of
+// the fallback families that does, in the same weight, stretch and
+// style.  The fallback families are the ones Windows uses for scripts
+// and symbols that Segoe UI lacks; if none of them has a character,
+// every family in the collection is tried.
+
+use std::rc::Rc;
+
+use base_table::BaseTable;
+use segmentation;
+use shaping::Shaper;
+use super::{Font, FontCollection, FontDescriptor, FontFace};
+
+const FALLBACK_FAMILIES: &'static [&'static str] = &[
+    "Segoe UI",
+    "Segoe UI Symbol",
+    "Segoe UI Emoji",
+    "Segoe UI Historic",
+    "Microsoft YaHei",
+    "Yu Gothic",
+    "Malgun Gothic",
+    "Microsoft JhengHei",
+    "Nirmala UI",
+    "Leelawadee UI",
+    "Khmer UI",
+    "Lao UI",
+    "Myanmar Text",
+    "Ebrima",
+    "Gadugi",
+    "Javanese Text",
+    "Mongolian Baiti",
+    "Microsoft Himalaya",
+    "Microsoft Yi Baiti",
+    "Sylfaen",
+    "Cambria Math",
+];
+
+// A font used by a layout, with the tables to shape with it and to
+// line it up with other fonts.
+pub struct FallbackFont {
+    pub font: Font,
+    pub face: Rc<FontFace>,
+    pub shaper: Shaper,
+    pub base: Option<BaseTable>,
+}
+
+pub struct FontFallback<'a> {
+    collection: &'a FontCollection,
+    pub descriptor: FontDescriptor,
+    // The fonts picked so far; the requested font is first.
+    pub fonts: Vec<FallbackFont>,
+    // The fallback families looked at so far, with their font matching
+    // the descriptor and its index in `fonts` once it has been picked.
+    families: Vec<(String, Option<Font>, Option<usize>)>,
+}
+
+// Characters that are never drawn with a glyph of their own, so don't
+// need a font that has them.
+fn needs_glyph(c: char) -> bool {
+    let cp = c as u32;
+    !(c.is_control() || cp == 0x00AD || cp == 0x034F || cp == 0x061C ||
+      (0x200B <= cp && cp <= 0x200F) || (0x2028 <= cp && cp <= 0x202E) ||
+      (0x2060 <= cp && cp <= 0x206F) || cp == 0xFEFF || (0xFE00 <= cp && cp <= 0xFE0F) ||
+      (0xE0000 <= cp && cp <= 0xE0FFF))
+}
+
+impl<'a> FontFallback<'a> {
+    // Starts with the font matching `descriptor`, or the collection's
+    // closest match in its family, or failing that in the first
+    // fallback family the collection has.
+    pub fn new(collection: &'a FontCollection, descriptor: &FontDescriptor) -> FontFallback<'a> {
+        let family = collection.get_font_family_by_name(&descriptor.family_name)
+            .or_else(|| FALLBACK_FAMILIES.iter().filter_map(|name| collection.get_font_family_by_name(name)).next())
+            .unwrap_or_else(|| collection.get_font_family(0));
+        let font = family.get_first_matching_font(descriptor.weight, descriptor.stretch, descriptor.style);
+        let mut fallback = FontFallback {
+            collection: collection,
+            descriptor: descriptor.clone(),
+            fonts: vec![],
+            families: vec![(family.name(), None, Some(0))],
+        };
+        fallback.add_font(font);
+        fallback
+    }
+
+    fn add_font(&mut self, font: Font) -> usize {
+        let face = font.create_font_face();
+        let shaper = Shaper::new(&face);
+        let base = BaseTable::from_font_face(&face);
+        self.fonts.push(FallbackFont { font: font, face: Rc::new(face), shaper: shaper, base: base });
+        self.fonts.len() - 1
+    }
+
+    fn has_characters(font: &Font, chars: &[char]) -> bool {
+        chars.iter().all(|&c| !needs_glyph(c) || font.has_character(c as u32))
+    }
+
+    // Looks for the family in the collection the first time it's
+    // asked for.
+    fn family_index(&mut self, name: &str) -> usize {
+        if let Some(index) = self.families.iter().position(|f| f.0 == name) {
+            return index;
+        }
+        let font = self.collection.get_font_family_by_name(name).map(|family| {
+            family.get_first_matching_font(self.descriptor.weight, self.descriptor.stretch, self.descriptor.style)
+        });
+        self.families.push((name.to_owned(), font, None));
+        self.families.len() - 1
+    }
+
+    // The index in `fonts` of the font to draw a grapheme cluster with.
+    pub fn font_for_cluster(&mut self, chars: &[char]) -> usize {
+        // Fonts already in use come first, so runs stay together.
+        for index in 0..self.fonts.len() {
+            if FontFallback::has_characters(&self.fonts[index].font, chars) {
+                return index;
+            }
+        }
+        let mut names: Vec<String> = FALLBACK_FAMILIES.iter().map(|&name| name.to_owned()).collect();
+        names.extend(self.collection.families_iter().map(|family| family.name()));
+        for name in names {
+            let family = self.family_index(&name);
+            let has_characters = match self.families[family].1 {
+                Some(ref font) => self.families[family].2.is_none() && FontFallback::has_characters(font, chars),
+                None => false,
+            };
+            if has_characters {
+                let font = self.families[family].1.take().unwrap();
+                let index = self.add_font(font);
+                self.families[family].2 = Some(index);
+                return index;
+            }
+        }
+        // Nothing has the whole cluster; draw it with a font that has
+        // its first character, or with missing glyphs.
+        (0..self.fonts.len()).find(|&index| FontFallback::has_characters(&self.fonts[index].font, &chars[..1]))
+                             .unwrap_or(0)
+    }
+
+    // Splits `text` into runs of one font, as (start, end, font index)
+    // in UTF-16 code units.
+    pub fn font_runs(&mut self, text: &str) -> Vec<(usize, usize, usize)> {
+        let chars: Vec<(usize, char)> = {
+            let mut offset = 0;
+            text.chars().map(|c| { let o = offset; offset += c.len_utf16(); (o, c) }).collect()
+        };
+        let boundaries = segmentation::grapheme_boundaries(text);
+        let mut runs: Vec<(usize, usize, usize)> = vec![];
+        let mut i = 0;
+        for pair in boundaries.windows(2) {
+            let first = i;
+            while i < chars.len() && chars[i].0 < pair[1] {
+                i += 1;
+            }
+            let cluster: Vec<char> = chars[first..i].iter().map(|c| c.1).collect();
+            // Clusters of characters without glyphs go with the run
+            // they're in.
+            let font = match runs.last() {
+                Some(run) if !cluster.iter().any(|&c| needs_glyph(c)) => run.2,
+                _ => self.font_for_cluster(&cluster),
+            };
+            match runs.last_mut() {
+                Some(run) if run.2 == font => run.1 = pair[1],
+                _ => runs.push((pair[0], pair[1], font)),
+            }
+        }
+        runs
+    }
+}
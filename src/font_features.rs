@@ -0,0 +1,130 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Enumeration of the scripts, language systems and features that a
+// face's GSUB and GPOS tables declare, for feature pickers and the
+// like.  Tags use the same byte order as DWRITE_MAKE_OPENTYPE_TAG.
+
+use ot_parse::TableData;
+use ot_layout::LayoutTable;
+use name_table::NameTable;
+use super::FontFace;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct FontFeature {
+    pub tag: u32,
+    // For stylistic sets (ss01-ss20) and character variants
+    // (cv01-cv99), the names the font gives them in its 'name' table.
+    pub ui_name: Option<String>,
+    pub tooltip: Option<String>,
+    pub sample_text: Option<String>,
+    // Names of the individual values of a character variant feature.
+    pub parameter_names: Vec<String>,
+    // Characters a character variant feature applies to.
+    pub characters: Vec<u32>,
+}
+
+pub fn layout_tables(face: &FontFace) -> Vec<LayoutTable> {
+    let mut tables = vec![];
+    // 7 and 9 are the extension lookup types of GSUB and GPOS.
+    if let Some(gsub) = face.get_font_table(ot_tag!(b"GSUB")).and_then(|d| LayoutTable::new(d, 7)) {
+        tables.push(gsub);
+    }
+    if let Some(gpos) = face.get_font_table(ot_tag!(b"GPOS")).and_then(|d| LayoutTable::new(d, 9)) {
+        tables.push(gpos);
+    }
+    tables
+}
+
+fn push_unique(list: &mut Vec<u32>, tags: Vec<u32>) {
+    for tag in tags {
+        if !list.contains(&tag) {
+            list.push(tag);
+        }
+    }
+}
+
+pub fn layout_scripts(face: &FontFace) -> Vec<u32> {
+    let mut scripts = vec![];
+    for table in layout_tables(face) {
+        push_unique(&mut scripts, table.script_tags());
+    }
+    scripts
+}
+
+pub fn layout_languages(face: &FontFace, script_tag: u32) -> Vec<u32> {
+    let mut languages = vec![];
+    for table in layout_tables(face) {
+        push_unique(&mut languages, table.language_tags(script_tag));
+    }
+    languages
+}
+
+pub fn layout_features(face: &FontFace, script_tag: u32, language_tag: u32) -> Vec<FontFeature> {
+    let names = NameTable::from_font_face(face);
+    let mut features: Vec<FontFeature> = vec![];
+    for table in layout_tables(face) {
+        let (required, indices) = table.feature_indices(script_tag, language_tag);
+        for index in required.into_iter().chain(indices.into_iter()) {
+            let tag = match table.feature_tag(index) {
+                Some(t) => t,
+                None => continue,
+            };
+            if features.iter().any(|f| f.tag == tag) {
+                continue;
+            }
+            let mut feature = FontFeature {
+                tag: tag,
+                ui_name: None,
+                tooltip: None,
+                sample_text: None,
+                parameter_names: vec![],
+                characters: vec![],
+            };
+            if let (Some(params), Some(names)) = (table.feature(index).and_then(|f| f.offset16(0)), names.as_ref()) {
+                read_feature_params(&mut feature, params, names);
+            }
+            features.push(feature);
+        }
+    }
+    features
+}
+
+fn is_tag_with_number(tag: u32, prefix: &[u8; 2]) -> bool {
+    let bytes = [tag as u8, (tag >> 8) as u8, (tag >> 16) as u8, (tag >> 24) as u8];
+    bytes[0] == prefix[0] && bytes[1] == prefix[1] &&
+        (bytes[2] as char).is_digit(10) && (bytes[3] as char).is_digit(10)
+}
+
+fn read_feature_params(feature: &mut FontFeature, params: TableData, names: &NameTable) {
+    let name = |id: Option<u16>| match id {
+        Some(0) | None => None,
+        Some(id) => names.get_name(id),
+    };
+
+    if is_tag_with_number(feature.tag, b"ss") {
+        // FeatureParamsStylisticSet: version, uiNameID
+        if params.u16(0) == Some(0) {
+            feature.ui_name = name(params.u16(2));
+        }
+    } else if is_tag_with_number(feature.tag, b"cv") {
+        // FeatureParamsCharacterVariants
+        if params.u16(0) != Some(0) {
+            return;
+        }
+        feature.ui_name = name(params.u16(2));
+        feature.tooltip = name(params.u16(4));
+        feature.sample_text = name(params.u16(6));
+        let num_named = params.u16(8).unwrap_or(0);
+        if let Some(first) = params.u16(10) {
+            if first != 0 {
+                feature.parameter_names = (0..num_named)
+                    .filter_map(|i| names.get_name(first.wrapping_add(i)))
+                    .collect();
+            }
+        }
+        let char_count = params.u16(12).unwrap_or(0) as usize;
+        feature.characters = (0..char_count).filter_map(|i| params.u24(14 + i * 3)).collect();
+    }
+}
@@ -0,0 +1,160 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use ot_parse::TableData;
+use ot_layout::{class_value, coverage_index};
+use glyph_buffer::{GlyphBuffer, GlyphInfo, GlyphClass};
+use super::FontFace;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum CaretValue {
+    // An X (or Y, for vertical text) coordinate in design units.  The
+    // device table adjustments of format 3 carets are not applied.
+    Coordinate(i16),
+    // A contour point index on the ligature glyph's outline.
+    ContourPoint(u16),
+}
+
+#[derive(Debug)]
+pub struct GdefTable {
+    data: Vec<u8>,
+}
+
+impl GdefTable {
+    pub fn new(data: Vec<u8>) -> Option<GdefTable> {
+        {
+            let t = TableData::new(&data);
+            if t.u16(0)? != 1 {
+                return None;
+            }
+            t.u16(10)?;
+        }
+        Some(GdefTable { data: data })
+    }
+
+    pub fn from_font_face(face: &FontFace) -> Option<GdefTable> {
+        face.get_font_table(ot_tag!(b"GDEF")).and_then(GdefTable::new)
+    }
+
+    fn table(&self) -> TableData<'_> {
+        TableData::new(&self.data)
+    }
+
+    fn minor_version(&self) -> u16 {
+        self.table().u16(2).unwrap_or(0)
+    }
+
+    pub fn has_glyph_classes(&self) -> bool {
+        self.table().offset16(4).is_some()
+    }
+
+    pub fn glyph_class(&self, glyph: u16) -> GlyphClass {
+        GlyphClass::from_u16(class_value(self.table().offset16(4), glyph))
+    }
+
+    pub fn mark_attachment_class(&self, glyph: u16) -> u16 {
+        class_value(self.table().offset16(10), glyph)
+    }
+
+    fn mark_glyph_sets(&self) -> Option<TableData<'_>> {
+        if self.minor_version() < 2 {
+            return None;
+        }
+        let sets = self.table().offset16(12)?;
+        if sets.u16(0)? != 1 {
+            return None;
+        }
+        Some(sets)
+    }
+
+    pub fn mark_glyph_set_count(&self) -> u16 {
+        self.mark_glyph_sets().and_then(|s| s.u16(2)).unwrap_or(0)
+    }
+
+    // The coverage table for a mark glyph set, as referenced by lookups
+    // with the UseMarkFilteringSet flag.
+    pub fn mark_glyph_set(&self, set_index: u16) -> Option<TableData<'_>> {
+        let sets = self.mark_glyph_sets()?;
+        if set_index >= sets.u16(2)? {
+            return None;
+        }
+        sets.offset32(4 + set_index as usize * 4)
+    }
+
+    pub fn is_in_mark_glyph_set(&self, set_index: u16, glyph: u16) -> bool {
+        self.mark_glyph_set(set_index)
+            .and_then(|coverage| coverage_index(coverage, glyph))
+            .is_some()
+    }
+
+    // The caret positions inside a ligature glyph, in logical order
+    // (one fewer than the number of components).  Empty if the font
+    // doesn't define carets for the glyph.
+    pub fn ligature_carets(&self, glyph: u16) -> Vec<CaretValue> {
+        let mut carets = vec![];
+        let lig_caret_list = match self.table().offset16(8) {
+            Some(l) => l,
+            None => return carets,
+        };
+        let index = match lig_caret_list.offset16(0).and_then(|c| coverage_index(c, glyph)) {
+            Some(i) => i,
+            None => return carets,
+        };
+        if index >= lig_caret_list.u16(2).unwrap_or(0) {
+            return carets;
+        }
+        let lig_glyph = match lig_caret_list.offset16(4 + index as usize * 2) {
+            Some(l) => l,
+            None => return carets,
+        };
+        let count = lig_glyph.u16(0).unwrap_or(0) as usize;
+        for i in 0..count {
+            let caret = match lig_glyph.offset16(2 + i * 2) {
+                Some(c) => c,
+                None => continue,
+            };
+            let value = match (caret.u16(0), caret.u16(2)) {
+                (Some(1), Some(v)) | (Some(3), Some(v)) => CaretValue::Coordinate(v as i16),
+                (Some(2), Some(v)) => CaretValue::ContourPoint(v),
+                _ => continue,
+            };
+            carets.push(value);
+        }
+        carets
+    }
+
+    // Caret coordinates in design units for a ligature glyph.  Carets
+    // given as contour points can't be resolved without the outline,
+    // so they (and glyphs without caret data) fall back to dividing
+    // `advance` evenly between `num_components`.
+    pub fn ligature_caret_positions(&self, glyph: u16, num_components: usize, advance: i32) -> Vec<i32> {
+        let carets = self.ligature_carets(glyph);
+        let coordinates: Vec<i32> = carets.iter().filter_map(|c| match *c {
+            CaretValue::Coordinate(x) => Some(x as i32),
+            CaretValue::ContourPoint(_) => None,
+        }).collect();
+        if !coordinates.is_empty() && coordinates.len() == carets.len() {
+            return coordinates;
+        }
+        if num_components < 2 {
+            return vec![];
+        }
+        (1..num_components).map(|i| advance * i as i32 / num_components as i32).collect()
+    }
+
+    // Fills in the glyph class and mark attachment class of every glyph
+    // in the buffer.  Lookup flags are evaluated against these.
+    pub fn classify(&self, buffer: &mut GlyphBuffer) {
+        for info in buffer.glyphs.iter_mut() {
+            self.classify_glyph(info);
+        }
+    }
+
+    pub fn classify_glyph(&self, info: &mut GlyphInfo) {
+        if self.has_glyph_classes() {
+            info.glyph_class = self.glyph_class(info.glyph_index);
+        }
+        info.mark_attachment_class = self.mark_attachment_class(info.glyph_index) as u8;
+    }
+}
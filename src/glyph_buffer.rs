@@ -0,0 +1,278 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use super::FontFace;
+
+// mirrors the GDEF GlyphClassDef values
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum GlyphClass {
+    Unclassified = 0,
+    Base = 1,
+    Ligature = 2,
+    Mark = 3,
+    Component = 4,
+}
+
+impl GlyphClass {
+    pub fn from_u16(v: u16) -> GlyphClass {
+        match v {
+            1 => GlyphClass::Base,
+            2 => GlyphClass::Ligature,
+            3 => GlyphClass::Mark,
+            4 => GlyphClass::Component,
+            _ => GlyphClass::Unclassified,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct GlyphInfo {
+    pub glyph_index: u16,
+    // The character this glyph was originally mapped from; glyphs
+    // produced by substitutions inherit it from the glyph they replace.
+    pub codepoint: u32,
+    // Index of the first text position this glyph belongs to.
+    pub cluster: u32,
+    // Lookups only apply to glyphs whose mask intersects the mask
+    // they're applied with; shapers use this to enable features on
+    // parts of a run.
+    pub mask: u32,
+    pub glyph_class: GlyphClass,
+    pub mark_attachment_class: u8,
+    // Nonzero on a glyph produced by a ligature substitution, and on
+    // any marks that were skipped over while forming it.
+    pub lig_id: u8,
+    // For marks carrying a lig_id, the ligature component (1-based)
+    // they were attached to; 0 for the ligature glyph itself.
+    pub lig_component: u8,
+    // Number of components a ligature glyph was formed from.
+    pub lig_num_components: u8,
+    // For glyphs produced by a multiple substitution, their (1-based)
+    // position in the substituted sequence; 0 otherwise.
+    pub multiplied_component: u8,
+    // Scratch space for script-specific shapers, e.g. the joining form
+    // chosen by the Arabic shaper.
+    pub shaper_data: u8,
+    // The character's category for shapers that segment text into
+    // syllables.
+    pub shaper_category: u8,
+    // Syllable serial number (high 4 bits) and type (low 4 bits), as
+    // found by those shapers; 0 outside of them.
+    pub syllable: u8,
+    // Set on glyphs produced by any substitution...
+    pub substituted: bool,
+    // ... and on those produced by a ligature substitution.
+    pub ligated: bool,
+}
+
+impl GlyphInfo {
+    pub fn new(glyph_index: u16, codepoint: u32, cluster: u32) -> GlyphInfo {
+        GlyphInfo {
+            glyph_index: glyph_index,
+            codepoint: codepoint,
+            cluster: cluster,
+            mask: !0,
+            glyph_class: GlyphClass::Unclassified,
+            mark_attachment_class: 0,
+            lig_id: 0,
+            lig_component: 0,
+            lig_num_components: 1,
+            multiplied_component: 0,
+            shaper_data: 0,
+            shaper_category: 0,
+            syllable: 0,
+            substituted: false,
+            ligated: false,
+        }
+    }
+
+    pub fn is_mark(&self) -> bool {
+        self.glyph_class == GlyphClass::Mark
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+pub const ATTACH_TYPE_NONE: u8 = 0;
+pub const ATTACH_TYPE_MARK: u8 = 1;
+pub const ATTACH_TYPE_CURSIVE: u8 = 2;
+
+// Positions are in font design units.  Offsets are along the x axis
+// (positive to the right) and y axis (positive up), independent of the
+// run's direction; glyphs set upright in vertical text advance down
+// the y axis, so their y_advance is negative.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct GlyphPosition {
+    pub x_advance: i32,
+    pub y_advance: i32,
+    pub x_offset: i32,
+    pub y_offset: i32,
+    // Relative index of the glyph this one is attached to, and how.
+    // Attachment offsets are relative to that glyph until
+    // resolve_attachments is called.
+    pub attach_chain: i16,
+    pub attach_type: u8,
+}
+
+// A run of glyphs being shaped, in logical order.  Substitutions edit
+// `glyphs` in place, keeping clusters monotonic so that each glyph can
+// be mapped back to the text it came from.  `positions` is filled in
+// once substitution is done.
+#[derive(Debug, Clone)]
+pub struct GlyphBuffer {
+    pub glyphs: Vec<GlyphInfo>,
+    pub positions: Vec<GlyphPosition>,
+    pub direction: TextDirection,
+    // Whether the glyphs are set upright in a vertical line.
+    pub is_vertical: bool,
+    // While set, context matching doesn't cross from one syllable into
+    // another; used for features shapers apply per syllable.
+    pub restrict_to_syllable: bool,
+    next_lig_id: u8,
+}
+
+impl GlyphBuffer {
+    pub fn new() -> GlyphBuffer {
+        GlyphBuffer {
+            glyphs: vec![],
+            positions: vec![],
+            direction: TextDirection::LeftToRight,
+            is_vertical: false,
+            restrict_to_syllable: false,
+            next_lig_id: 1,
+        }
+    }
+
+    // Each glyph gets its index as its cluster.
+    pub fn from_glyph_indices(glyph_indices: &[u16]) -> GlyphBuffer {
+        let mut buffer = GlyphBuffer::new();
+        for (i, &g) in glyph_indices.iter().enumerate() {
+            buffer.glyphs.push(GlyphInfo::new(g, 0, i as u32));
+        }
+        buffer
+    }
+
+    // Maps the code points through the face's cmap; each glyph gets
+    // the index of its code point as its cluster.
+    pub fn from_code_points(face: &FontFace, code_points: &[u32]) -> GlyphBuffer {
+        let glyph_indices = face.get_glyph_indices(code_points);
+        let mut buffer = GlyphBuffer::new();
+        for (i, (&g, &c)) in glyph_indices.iter().zip(code_points.iter()).enumerate() {
+            buffer.glyphs.push(GlyphInfo::new(g, c, i as u32));
+        }
+        buffer
+    }
+
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    pub fn glyph_indices(&self) -> Vec<u16> {
+        self.glyphs.iter().map(|g| g.glyph_index).collect()
+    }
+
+    pub fn clusters(&self) -> Vec<u32> {
+        self.glyphs.iter().map(|g| g.cluster).collect()
+    }
+
+    pub fn reverse(&mut self) {
+        self.glyphs.reverse();
+        self.positions.reverse();
+    }
+
+    pub fn allocate_lig_id(&mut self) -> u8 {
+        let id = self.next_lig_id;
+        self.next_lig_id = self.next_lig_id.wrapping_add(1);
+        if self.next_lig_id == 0 {
+            self.next_lig_id = 1;
+        }
+        id
+    }
+
+    // Gives every glyph in start..end the smallest cluster value in
+    // that range.
+    pub fn merge_clusters(&mut self, start: usize, end: usize) {
+        if end <= start + 1 || end > self.glyphs.len() {
+            return;
+        }
+        let cluster = self.glyphs[start..end].iter().map(|g| g.cluster).min().unwrap();
+        for g in &mut self.glyphs[start..end] {
+            g.cluster = cluster;
+        }
+    }
+
+    // Starts positioning with the given advances (in design units).
+    pub fn init_positions(&mut self, x_advances: &[i32]) {
+        assert!(x_advances.len() == self.glyphs.len());
+        self.positions = x_advances.iter().map(|&a| GlyphPosition {
+            x_advance: a,
+            ..GlyphPosition::default()
+        }).collect();
+    }
+
+    // Turns attachment offsets, which GPOS computes relative to the
+    // glyph being attached to, into offsets from each glyph's own pen
+    // position.
+    pub fn resolve_attachments(&mut self) {
+        let len = self.positions.len();
+        let mut resolved = vec![false; len];
+        for i in 0..len {
+            self.resolve_attachment(i, &mut resolved, 0);
+        }
+    }
+
+    fn resolve_attachment(&mut self, i: usize, resolved: &mut Vec<bool>, depth: u32) {
+        if resolved[i] {
+            return;
+        }
+        resolved[i] = true;
+        let chain = self.positions[i].attach_chain;
+        if chain == 0 || depth > 32 {
+            return;
+        }
+        let j = i as isize + chain as isize;
+        if j < 0 || j as usize >= self.positions.len() {
+            self.positions[i].attach_chain = 0;
+            return;
+        }
+        let j = j as usize;
+        self.resolve_attachment(j, resolved, depth + 1);
+
+        // Account for the pen movement between the two glyphs, which is
+        // downwards in vertical runs.
+        let (lo, hi) = if j < i { (j, i) } else { (i, j) };
+        let advance = |p: &GlyphPosition| if self.is_vertical { p.y_advance } else { p.x_advance };
+        let between: i32 = match self.direction {
+            TextDirection::LeftToRight => self.positions[lo..hi].iter().map(&advance).sum(),
+            TextDirection::RightToLeft => self.positions[lo + 1..hi + 1].iter().map(&advance).sum(),
+        };
+        let pen_delta = match (self.direction, j < i) {
+            (TextDirection::LeftToRight, true) | (TextDirection::RightToLeft, false) => -between,
+            _ => between,
+        };
+
+        let parent = self.positions[j];
+        let is_vertical = self.is_vertical;
+        let pos = &mut self.positions[i];
+        if is_vertical {
+            pos.x_offset += parent.x_offset;
+            if pos.attach_type != ATTACH_TYPE_CURSIVE {
+                pos.y_offset += parent.y_offset + pen_delta;
+            }
+        } else {
+            pos.y_offset += parent.y_offset;
+            if pos.attach_type != ATTACH_TYPE_CURSIVE {
+                pos.x_offset += parent.x_offset + pen_delta;
+            }
+        }
+    }
+}
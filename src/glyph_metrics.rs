@@ -0,0 +1,191 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Design glyph metrics worked out from the font's own tables, in the
+// form FontFace::get_design_glyph_metrics gives them.  Horizontal
+// metrics come from 'hhea' and 'hmtx', and vertical ones from 'vhea'
+// and 'vmtx', with the vertical origin from 'VORG' (which CFF fonts
+// have) or else the top of the glyph plus its top side bearing.  Fonts
+// without vertical metrics get what DirectWrite gives them: glyphs
+// advance by the OS/2 typo ascender minus descender, with the origin at
+// the typo ascender.
+//
+// Glyph bounds are read from 'glyf'.  CFF outlines aren't parsed, so
+// for those fonts the bounds are taken from DirectWrite's metrics.
+
+use ot_parse::TableData;
+use winapi::DWRITE_GLYPH_METRICS;
+use super::FontFace;
+
+#[derive(Debug)]
+pub struct GlyphMetricsTables {
+    hmtx: Vec<u8>,
+    // How many glyphs have an advance of their own in 'hmtx' and
+    // 'vmtx'; the rest have the last one's.
+    h_metric_count: usize,
+    vmtx: Option<Vec<u8>>,
+    v_metric_count: usize,
+    vorg: Option<Vec<u8>>,
+    // 'loca', 'glyf', and whether 'loca' has 32 bit offsets.
+    outlines: Option<(Vec<u8>, Vec<u8>, bool)>,
+    // The OS/2 typo ascender and descender (or those of 'hhea'), for
+    // fonts without vertical metrics.
+    ascender: i32,
+    descender: i32,
+}
+
+// A glyph's bounds in design units, as (x min, y min, x max, y max).
+type Bounds = (i32, i32, i32, i32);
+
+impl GlyphMetricsTables {
+    pub fn from_font_face(face: &FontFace) -> Option<GlyphMetricsTables> {
+        let hhea = face.get_font_table(ot_tag!(b"hhea"))?;
+        let hmtx = face.get_font_table(ot_tag!(b"hmtx"))?;
+        let (h_metric_count, mut ascender, mut descender) = {
+            let t = TableData::new(&hhea);
+            (t.u16(34)? as usize, t.i16(4)? as i32, t.i16(6)? as i32)
+        };
+        if let Some(os2) = face.get_font_table(ot_tag!(b"OS/2")) {
+            let t = TableData::new(&os2);
+            if let (Some(typo_ascender), Some(typo_descender)) = (t.i16(68), t.i16(70)) {
+                ascender = typo_ascender as i32;
+                descender = typo_descender as i32;
+            }
+        }
+
+        let vhea = face.get_font_table(ot_tag!(b"vhea"));
+        let vmtx = face.get_font_table(ot_tag!(b"vmtx"));
+        let (vmtx, v_metric_count) = match (vhea, vmtx) {
+            (Some(vhea), Some(vmtx)) => match TableData::new(&vhea).u16(34) {
+                Some(count) if count > 0 => (Some(vmtx), count as usize),
+                _ => (None, 0),
+            },
+            _ => (None, 0),
+        };
+
+        let outlines = match (face.get_font_table(ot_tag!(b"head")),
+                              face.get_font_table(ot_tag!(b"loca")),
+                              face.get_font_table(ot_tag!(b"glyf"))) {
+            (Some(head), Some(loca), Some(glyf)) => {
+                TableData::new(&head).i16(50).map(|format| (loca, glyf, format == 1))
+            }
+            _ => None,
+        };
+
+        if h_metric_count == 0 {
+            return None;
+        }
+        Some(GlyphMetricsTables {
+            hmtx: hmtx,
+            h_metric_count: h_metric_count,
+            vmtx: vmtx,
+            v_metric_count: v_metric_count,
+            vorg: face.get_font_table(ot_tag!(b"VORG")),
+            outlines: outlines,
+            ascender: ascender,
+            descender: descender,
+        })
+    }
+
+    // A glyph's advance and side bearing from 'hmtx' or 'vmtx'.
+    fn long_metric(data: &[u8], count: usize, glyph: u16) -> (i32, i32) {
+        let t = TableData::new(data);
+        let glyph = glyph as usize;
+        let advance = t.u16(4 * glyph.min(count - 1)).unwrap_or(0) as i32;
+        let bearing = if glyph < count {
+            t.i16(4 * glyph + 2)
+        } else {
+            t.i16(4 * count + 2 * (glyph - count))
+        };
+        (advance, bearing.unwrap_or(0) as i32)
+    }
+
+    // The glyph's bounds from 'glyf': Some(None) for a glyph without an
+    // outline, and None if the font has no 'glyf' table.
+    fn outline_bounds(&self, glyph: u16) -> Option<Option<Bounds>> {
+        let (ref loca, ref glyf, long_offsets) = *self.outlines.as_ref()?;
+        let loca = TableData::new(loca);
+        let glyph = glyph as usize;
+        let (start, end) = if long_offsets {
+            (loca.u32(4 * glyph)? as usize, loca.u32(4 * glyph + 4)? as usize)
+        } else {
+            (loca.u16(2 * glyph)? as usize * 2, loca.u16(2 * glyph + 2)? as usize * 2)
+        };
+        if end <= start {
+            return Some(None);
+        }
+        let t = TableData::new(glyf);
+        Some(Some((t.i16(start + 2)? as i32, t.i16(start + 4)? as i32,
+                   t.i16(start + 6)? as i32, t.i16(start + 8)? as i32)))
+    }
+
+    // The vertical origin's y from 'VORG'.
+    fn vorg_origin(&self, glyph: u16) -> Option<i32> {
+        let t = TableData::new(self.vorg.as_ref()?);
+        let default = t.i16(4)? as i32;
+        let count = t.u16(6)? as usize;
+        let (mut lo, mut hi) = (0, count);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let id = t.u16(8 + 4 * mid)?;
+            if glyph < id {
+                hi = mid;
+            } else if glyph > id {
+                lo = mid + 1;
+            } else {
+                return Some(t.i16(8 + 4 * mid + 2)? as i32);
+            }
+        }
+        Some(default)
+    }
+
+    // The metrics of each glyph, in design units.
+    pub fn design_glyph_metrics(&self, face: &FontFace, glyph_indices: &[u16]) -> Vec<DWRITE_GLYPH_METRICS> {
+        // Bounds of CFF glyphs, from DirectWrite.
+        let reported = if self.outlines.is_none() {
+            face.get_design_glyph_metrics(glyph_indices, false)
+        } else {
+            vec![]
+        };
+
+        glyph_indices.iter().enumerate().map(|(i, &glyph)| {
+            let (advance_width, left_side_bearing) = GlyphMetricsTables::long_metric(&self.hmtx, self.h_metric_count,
+                                                                                    glyph);
+            let bounds = match self.outline_bounds(glyph) {
+                Some(bounds) => bounds,
+                None => {
+                    let m = &reported[i];
+                    let x_max = m.advanceWidth as i32 - m.rightSideBearing;
+                    let y_max = m.verticalOriginY - m.topSideBearing;
+                    let y_min = m.bottomSideBearing + m.verticalOriginY - m.advanceHeight as i32;
+                    if x_max > m.leftSideBearing || y_max > y_min {
+                        Some((m.leftSideBearing, y_min, x_max, y_max))
+                    } else {
+                        None
+                    }
+                }
+            };
+            let (x_min, y_min, x_max, y_max) = bounds.unwrap_or((left_side_bearing, 0, left_side_bearing, 0));
+
+            let (advance_height, vertical_origin_y) = match self.vmtx {
+                Some(ref vmtx) => {
+                    let (advance, top_side_bearing) = GlyphMetricsTables::long_metric(vmtx, self.v_metric_count,
+                                                                                     glyph);
+                    (advance, self.vorg_origin(glyph).unwrap_or(y_max + top_side_bearing))
+                }
+                None => (self.ascender - self.descender, self.vorg_origin(glyph).unwrap_or(self.ascender)),
+            };
+
+            DWRITE_GLYPH_METRICS {
+                leftSideBearing: x_min,
+                advanceWidth: advance_width as u32,
+                rightSideBearing: advance_width - x_max,
+                topSideBearing: vertical_origin_y - y_max,
+                advanceHeight: advance_height as u32,
+                bottomSideBearing: y_min - (vertical_origin_y - advance_height),
+                verticalOriginY: vertical_origin_y,
+            }
+        }).collect()
+    }
+}
@@ -0,0 +1,428 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use ot_parse::TableData;
+use ot_layout::*;
+use glyph_buffer::{GlyphBuffer, TextDirection, ATTACH_TYPE_NONE, ATTACH_TYPE_MARK, ATTACH_TYPE_CURSIVE};
+use gdef::GdefTable;
+use super::FontFace;
+
+const GPOS_SINGLE: u16 = 1;
+const GPOS_PAIR: u16 = 2;
+const GPOS_CURSIVE: u16 = 3;
+const GPOS_MARK_TO_BASE: u16 = 4;
+const GPOS_MARK_TO_LIGATURE: u16 = 5;
+const GPOS_MARK_TO_MARK: u16 = 6;
+const GPOS_CONTEXT: u16 = 7;
+const GPOS_CHAINED_CONTEXT: u16 = 8;
+const GPOS_EXTENSION: u16 = 9;
+
+#[derive(Debug)]
+pub struct GposTable {
+    table: LayoutTable,
+    gdef: Option<GdefTable>,
+}
+
+impl GposTable {
+    pub fn new(data: Vec<u8>) -> Option<GposTable> {
+        LayoutTable::new(data, GPOS_EXTENSION).map(|table| GposTable { table: table, gdef: None })
+    }
+
+    // Also picks up the face's GDEF table, if it has one.
+    pub fn from_font_face(face: &FontFace) -> Option<GposTable> {
+        let mut gpos = face.get_font_table(ot_tag!(b"GPOS")).and_then(GposTable::new)?;
+        gpos.gdef = GdefTable::from_font_face(face);
+        Some(gpos)
+    }
+
+    pub fn set_gdef_table(&mut self, gdef: Option<GdefTable>) {
+        self.gdef = gdef;
+    }
+
+    pub fn layout_table(&self) -> &LayoutTable {
+        &self.table
+    }
+
+    pub fn lookup_count(&self) -> u16 {
+        self.table.lookup_count()
+    }
+
+    pub fn lookups_for_feature(&self, script_tag: u32, language_tag: u32, feature_tag: u32) -> Vec<u16> {
+        self.table.lookups_for_feature(script_tag, language_tag, feature_tag)
+    }
+
+    // Applies a single lookup across the whole buffer, to glyphs whose
+    // mask intersects `mask`.  The buffer's positions must have been
+    // initialized; attachments are left relative until
+    // GlyphBuffer::resolve_attachments is called.
+    pub fn apply_lookup(&self, lookup_index: u16, buffer: &mut GlyphBuffer, mask: u32) {
+        let lookup = match self.table.lookup(lookup_index) {
+            Some(l) => l,
+            None => return,
+        };
+        assert!(buffer.positions.len() == buffer.glyphs.len());
+
+        let filter = lookup.filter(self.gdef.as_ref());
+        let mut index = 0;
+        while index < buffer.len() {
+            if buffer.glyphs[index].mask & mask != 0 && !filter.skips(&buffer.glyphs[index]) {
+                if let Some(next) = self.apply_at(&lookup, buffer, index, mask, MAX_NESTING_LEVEL) {
+                    index = next.max(index + 1);
+                    continue;
+                }
+            }
+            index += 1;
+        }
+    }
+
+    fn apply_at(&self, lookup: &Lookup, buffer: &mut GlyphBuffer, index: usize, mask: u32,
+                nesting_level_left: u32) -> Option<usize> {
+        let filter = lookup.filter(self.gdef.as_ref());
+        for i in 0..lookup.subtable_count() {
+            let subtable = match lookup.subtable(i) {
+                Some(s) => s,
+                None => continue,
+            };
+            let applied = match lookup.lookup_type {
+                GPOS_SINGLE => apply_single(subtable, buffer, index),
+                GPOS_PAIR => apply_pair(subtable, buffer, &filter, index),
+                GPOS_CURSIVE => apply_cursive(subtable, buffer, &filter, lookup.flags, index),
+                GPOS_MARK_TO_BASE => apply_mark_to_base(subtable, buffer, &filter, index),
+                GPOS_MARK_TO_LIGATURE => apply_mark_to_ligature(subtable, buffer, &filter, index),
+                GPOS_MARK_TO_MARK => apply_mark_to_mark(subtable, buffer, &filter, index),
+                GPOS_CONTEXT => {
+                    match_sequence_context(subtable, buffer, &filter, mask, index)
+                        .map(|m| self.apply_records(buffer, m, mask, nesting_level_left))
+                }
+                GPOS_CHAINED_CONTEXT => {
+                    match_chained_sequence_context(subtable, buffer, &filter, mask, index)
+                        .map(|m| self.apply_records(buffer, m, mask, nesting_level_left))
+                }
+                _ => None,
+            };
+            if applied.is_some() {
+                return applied;
+            }
+        }
+        None
+    }
+
+    fn apply_records(&self, buffer: &mut GlyphBuffer, m: ContextMatch, mask: u32, nesting_level_left: u32) -> usize {
+        if nesting_level_left == 0 {
+            return *m.positions.last().unwrap() + 1;
+        }
+        apply_context_records(buffer, m, |buffer, lookup_index, position| {
+            if let Some(lookup) = self.table.lookup(lookup_index) {
+                if !lookup.filter(self.gdef.as_ref()).skips(&buffer.glyphs[position]) {
+                    self.apply_at(&lookup, buffer, position, mask, nesting_level_left - 1);
+                }
+            }
+        })
+    }
+}
+
+fn value_record_size(format: u16) -> usize {
+    (format & 0xFF).count_ones() as usize * 2
+}
+
+// Adds a ValueRecord to the position of the glyph at `index`.  Device
+// table adjustments are not applied, since we position in design
+// units.  Only the advance along the run is adjusted; vertical advances
+// grow downwards, while the font's YAdvance grows upwards.
+fn apply_value(format: u16, data: TableData, offset: usize, buffer: &mut GlyphBuffer,
+               index: usize) -> Option<()> {
+    let is_vertical = buffer.is_vertical;
+    let pos = &mut buffer.positions[index];
+    let mut offset = offset;
+    if format & 0x1 != 0 {
+        pos.x_offset += data.i16(offset)? as i32;
+        offset += 2;
+    }
+    if format & 0x2 != 0 {
+        pos.y_offset += data.i16(offset)? as i32;
+        offset += 2;
+    }
+    if format & 0x4 != 0 {
+        if !is_vertical {
+            pos.x_advance += data.i16(offset)? as i32;
+        }
+        offset += 2;
+    }
+    if format & 0x8 != 0 && is_vertical {
+        pos.y_advance -= data.i16(offset)? as i32;
+    }
+    Some(())
+}
+
+fn anchor(data: Option<TableData>) -> Option<(i32, i32)> {
+    let data = data?;
+    match data.u16(0)? {
+        1 | 2 | 3 => Some((data.i16(2)? as i32, data.i16(4)? as i32)),
+        _ => None,
+    }
+}
+
+fn apply_single(subtable: TableData, buffer: &mut GlyphBuffer, index: usize) -> Option<usize> {
+    let coverage = coverage_index(subtable.offset16(2)?, buffer.glyphs[index].glyph_index)?;
+    let format = subtable.u16(4)?;
+    let offset = match subtable.u16(0)? {
+        1 => 6,
+        2 => {
+            if coverage >= subtable.u16(6)? {
+                return None;
+            }
+            8 + coverage as usize * value_record_size(format)
+        }
+        _ => return None,
+    };
+    apply_value(format, subtable, offset, buffer, index)?;
+    Some(index + 1)
+}
+
+fn apply_pair(subtable: TableData, buffer: &mut GlyphBuffer, filter: &LookupFilter, index: usize) -> Option<usize> {
+    let first = buffer.glyphs[index].glyph_index;
+    let coverage = coverage_index(subtable.offset16(2)?, first)?;
+    let second_index = next_unskipped(buffer, filter, index)?;
+    let second = buffer.glyphs[second_index].glyph_index;
+    let format1 = subtable.u16(4)?;
+    let format2 = subtable.u16(6)?;
+    let size1 = value_record_size(format1);
+    let size2 = value_record_size(format2);
+
+    let (data, offset) = match subtable.u16(0)? {
+        1 => {
+            if coverage >= subtable.u16(8)? {
+                return None;
+            }
+            let pair_set = subtable.offset16(10 + coverage as usize * 2)?;
+            let count = pair_set.u16(0)? as usize;
+            let record_size = 2 + size1 + size2;
+            let (mut lo, mut hi) = (0, count);
+            let mut found = None;
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let g = pair_set.u16(2 + mid * record_size)?;
+                if g < second {
+                    lo = mid + 1;
+                } else if g > second {
+                    hi = mid;
+                } else {
+                    found = Some(2 + mid * record_size + 2);
+                    break;
+                }
+            }
+            (pair_set, found?)
+        }
+        2 => {
+            let class1 = class_value(subtable.offset16(8), first) as usize;
+            let class2 = class_value(subtable.offset16(10), second) as usize;
+            let class1_count = subtable.u16(12)? as usize;
+            let class2_count = subtable.u16(14)? as usize;
+            if class1 >= class1_count || class2 >= class2_count {
+                return None;
+            }
+            (subtable, 16 + (class1 * class2_count + class2) * (size1 + size2))
+        }
+        _ => return None,
+    };
+
+    apply_value(format1, data, offset, buffer, index)?;
+    apply_value(format2, data, offset + size1, buffer, second_index)?;
+
+    // If the second glyph was adjusted it can't start another pair.
+    if format2 != 0 {
+        Some(second_index + 1)
+    } else {
+        Some(second_index)
+    }
+}
+
+fn apply_cursive(subtable: TableData, buffer: &mut GlyphBuffer, filter: &LookupFilter, flags: u16,
+                 index: usize) -> Option<usize> {
+    if subtable.u16(0)? != 1 {
+        return None;
+    }
+    let coverage = subtable.offset16(2)?;
+    let count = subtable.u16(4)?;
+    // EntryExitRecord: entryAnchorOffset, exitAnchorOffset
+    let entry_exit = |glyph: u16| -> Option<(Option<(i32, i32)>, Option<(i32, i32)>)> {
+        let i = coverage_index(coverage, glyph)?;
+        if i >= count {
+            return None;
+        }
+        let rec = 6 + i as usize * 4;
+        Some((anchor(subtable.offset16(rec)), anchor(subtable.offset16(rec + 2))))
+    };
+
+    // The current glyph's entry attaches to the previous glyph's exit.
+    let (entry, _) = entry_exit(buffer.glyphs[index].glyph_index)?;
+    let (entry_x, entry_y) = entry?;
+    let prev = prev_unskipped(buffer, filter, index)?;
+    let (_, exit) = entry_exit(buffer.glyphs[prev].glyph_index)?;
+    let (exit_x, exit_y) = exit?;
+
+    let (i, j) = (prev, index);
+    match buffer.direction {
+        TextDirection::LeftToRight => {
+            buffer.positions[i].x_advance = exit_x + buffer.positions[i].x_offset;
+            let d = entry_x + buffer.positions[j].x_offset;
+            buffer.positions[j].x_advance -= d;
+            buffer.positions[j].x_offset -= d;
+        }
+        TextDirection::RightToLeft => {
+            let d = exit_x + buffer.positions[i].x_offset;
+            buffer.positions[i].x_advance -= d;
+            buffer.positions[i].x_offset -= d;
+            buffer.positions[j].x_advance = entry_x + buffer.positions[j].x_offset;
+        }
+    }
+
+    // The child glyph is moved vertically to line up with its parent;
+    // with the RightToLeft flag the last glyph stays on the baseline.
+    let (child, parent, y_offset) = if flags & LOOKUP_FLAG_RIGHT_TO_LEFT != 0 {
+        (i, j, entry_y - exit_y)
+    } else {
+        (j, i, exit_y - entry_y)
+    };
+    if buffer.positions[parent].attach_chain == (child as isize - parent as isize) as i16 {
+        // Don't create a cycle.
+        buffer.positions[parent].attach_chain = 0;
+        buffer.positions[parent].attach_type = ATTACH_TYPE_NONE;
+    }
+    let pos = &mut buffer.positions[child];
+    pos.attach_type = ATTACH_TYPE_CURSIVE;
+    pos.attach_chain = (parent as isize - child as isize) as i16;
+    pos.y_offset = y_offset;
+    Some(index + 1)
+}
+
+// MarkArray: markCount, MarkRecords { markClass, markAnchorOffset }
+fn mark_record(mark_array: TableData, mark_index: u16) -> Option<(u16, (i32, i32))> {
+    if mark_index >= mark_array.u16(0)? {
+        return None;
+    }
+    let rec = 2 + mark_index as usize * 4;
+    let class = mark_array.u16(rec)?;
+    Some((class, anchor(mark_array.offset16(rec + 2))?))
+}
+
+fn attach_mark(buffer: &mut GlyphBuffer, mark: usize, base: usize, mark_anchor: (i32, i32),
+               base_anchor: (i32, i32)) -> Option<usize> {
+    let pos = &mut buffer.positions[mark];
+    pos.x_offset = base_anchor.0 - mark_anchor.0;
+    pos.y_offset = base_anchor.1 - mark_anchor.1;
+    pos.attach_type = ATTACH_TYPE_MARK;
+    pos.attach_chain = (base as isize - mark as isize) as i16;
+    Some(mark + 1)
+}
+
+// Finds the glyph a mark attaches to for mark-to-base and
+// mark-to-ligature: the closest preceding glyph that isn't a mark and
+// that the lookup doesn't skip.
+fn find_mark_base(buffer: &GlyphBuffer, filter: &LookupFilter, index: usize) -> Option<usize> {
+    let base_filter = LookupFilter {
+        flags: filter.flags | LOOKUP_FLAG_IGNORE_MARKS,
+        mark_glyph_set: None,
+    };
+    prev_unskipped(buffer, &base_filter, index)
+}
+
+fn apply_mark_to_base(subtable: TableData, buffer: &mut GlyphBuffer, filter: &LookupFilter,
+                      index: usize) -> Option<usize> {
+    if subtable.u16(0)? != 1 {
+        return None;
+    }
+    let mark_index = coverage_index(subtable.offset16(2)?, buffer.glyphs[index].glyph_index)?;
+    let base = find_mark_base(buffer, filter, index)?;
+    let base_index = coverage_index(subtable.offset16(4)?, buffer.glyphs[base].glyph_index)?;
+    let class_count = subtable.u16(6)? as usize;
+    let (class, mark_anchor) = mark_record(subtable.offset16(8)?, mark_index)?;
+    if class as usize >= class_count {
+        return None;
+    }
+
+    // BaseArray: baseCount, BaseRecords { baseAnchorOffsets[classCount] }
+    let base_array = subtable.offset16(10)?;
+    if base_index >= base_array.u16(0)? {
+        return None;
+    }
+    let base_anchor = anchor(base_array.offset16(2 + (base_index as usize * class_count + class as usize) * 2))?;
+    attach_mark(buffer, index, base, mark_anchor, base_anchor)
+}
+
+fn apply_mark_to_ligature(subtable: TableData, buffer: &mut GlyphBuffer, filter: &LookupFilter,
+                          index: usize) -> Option<usize> {
+    if subtable.u16(0)? != 1 {
+        return None;
+    }
+    let mark_index = coverage_index(subtable.offset16(2)?, buffer.glyphs[index].glyph_index)?;
+    let lig = find_mark_base(buffer, filter, index)?;
+    let lig_index = coverage_index(subtable.offset16(4)?, buffer.glyphs[lig].glyph_index)?;
+    let class_count = subtable.u16(6)? as usize;
+    let (class, mark_anchor) = mark_record(subtable.offset16(8)?, mark_index)?;
+    if class as usize >= class_count {
+        return None;
+    }
+
+    // LigatureArray: ligatureCount, ligatureAttachOffsets[]
+    // LigatureAttach: componentCount, ComponentRecords { ligatureAnchorOffsets[classCount] }
+    let lig_array = subtable.offset16(10)?;
+    if lig_index >= lig_array.u16(0)? {
+        return None;
+    }
+    let lig_attach = lig_array.offset16(2 + lig_index as usize * 2)?;
+    let component_count = lig_attach.u16(0)? as usize;
+    if component_count == 0 {
+        return None;
+    }
+
+    // Marks that were between the ligature's components when it was
+    // formed go on their own component; anything else goes on the last.
+    let (mark_info, lig_info) = (buffer.glyphs[index], buffer.glyphs[lig]);
+    let component = if lig_info.lig_id != 0 && mark_info.lig_id == lig_info.lig_id && mark_info.lig_component > 0 {
+        (mark_info.lig_component as usize).min(component_count) - 1
+    } else {
+        component_count - 1
+    };
+    let lig_anchor = anchor(lig_attach.offset16(2 + (component * class_count + class as usize) * 2))?;
+    attach_mark(buffer, index, lig, mark_anchor, lig_anchor)
+}
+
+fn apply_mark_to_mark(subtable: TableData, buffer: &mut GlyphBuffer, filter: &LookupFilter,
+                      index: usize) -> Option<usize> {
+    if subtable.u16(0)? != 1 {
+        return None;
+    }
+    let mark1_index = coverage_index(subtable.offset16(2)?, buffer.glyphs[index].glyph_index)?;
+    let prev = prev_unskipped(buffer, filter, index)?;
+    if !buffer.glyphs[prev].is_mark() {
+        return None;
+    }
+
+    // Only attach to a mark on the same ligature component.
+    let (info1, info2) = (buffer.glyphs[index], buffer.glyphs[prev]);
+    let same_component = if info1.lig_id == info2.lig_id {
+        info1.lig_id == 0 || info1.lig_component == info2.lig_component
+    } else {
+        (info1.lig_id > 0 && info1.lig_component == 0) || (info2.lig_id > 0 && info2.lig_component == 0)
+    };
+    if !same_component {
+        return None;
+    }
+
+    let mark2_index = coverage_index(subtable.offset16(4)?, info2.glyph_index)?;
+    let class_count = subtable.u16(6)? as usize;
+    let (class, mark_anchor) = mark_record(subtable.offset16(8)?, mark1_index)?;
+    if class as usize >= class_count {
+        return None;
+    }
+
+    // Mark2Array: mark2Count, Mark2Records { mark2AnchorOffsets[classCount] }
+    let mark2_array = subtable.offset16(10)?;
+    if mark2_index >= mark2_array.u16(0)? {
+        return None;
+    }
+    let mark2_anchor = anchor(mark2_array.offset16(2 + (mark2_index as usize * class_count + class as usize) * 2))?;
+    attach_mark(buffer, index, prev, mark_anchor, mark2_anchor)
+}
@@ -0,0 +1,399 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use ot_parse::TableData;
+use ot_layout::*;
+use glyph_buffer::{GlyphBuffer, GlyphInfo, GlyphClass};
+use gdef::GdefTable;
+use super::FontFace;
+
+const GSUB_SINGLE: u16 = 1;
+const GSUB_MULTIPLE: u16 = 2;
+const GSUB_ALTERNATE: u16 = 3;
+const GSUB_LIGATURE: u16 = 4;
+const GSUB_CONTEXT: u16 = 5;
+const GSUB_CHAINED_CONTEXT: u16 = 6;
+const GSUB_EXTENSION: u16 = 7;
+const GSUB_REVERSE_CHAINED_SINGLE: u16 = 8;
+
+#[derive(Clone, Copy)]
+struct ApplyState {
+    mask: u32,
+    // 1-based alternate to pick for alternate substitutions.
+    feature_value: u32,
+    nesting_level_left: u32,
+}
+
+#[derive(Debug)]
+pub struct GsubTable {
+    table: LayoutTable,
+    gdef: Option<GdefTable>,
+}
+
+impl GsubTable {
+    pub fn new(data: Vec<u8>) -> Option<GsubTable> {
+        LayoutTable::new(data, GSUB_EXTENSION).map(|table| GsubTable { table: table, gdef: None })
+    }
+
+    // Also picks up the face's GDEF table, if it has one.
+    pub fn from_font_face(face: &FontFace) -> Option<GsubTable> {
+        let mut gsub = face.get_font_table(ot_tag!(b"GSUB")).and_then(GsubTable::new)?;
+        gsub.gdef = GdefTable::from_font_face(face);
+        Some(gsub)
+    }
+
+    // GDEF supplies mark filtering sets for lookup flags, and the glyph
+    // classes of glyphs produced by substitutions.
+    pub fn set_gdef_table(&mut self, gdef: Option<GdefTable>) {
+        self.gdef = gdef;
+    }
+
+    pub fn gdef_table(&self) -> Option<&GdefTable> {
+        self.gdef.as_ref()
+    }
+
+    pub fn layout_table(&self) -> &LayoutTable {
+        &self.table
+    }
+
+    pub fn lookup_count(&self) -> u16 {
+        self.table.lookup_count()
+    }
+
+    pub fn lookups_for_feature(&self, script_tag: u32, language_tag: u32, feature_tag: u32) -> Vec<u16> {
+        self.table.lookups_for_feature(script_tag, language_tag, feature_tag)
+    }
+
+    // Applies a single lookup across the whole buffer, to glyphs whose
+    // mask intersects `mask`.
+    pub fn apply_lookup(&self, lookup_index: u16, buffer: &mut GlyphBuffer, mask: u32) {
+        self.apply_lookup_with_value(lookup_index, buffer, mask, 1)
+    }
+
+    // Like apply_lookup, but with a feature value: for alternate
+    // substitutions the value picks which alternate (1-based) is used,
+    // as with 'salt' or 'aalt' set to N.
+    pub fn apply_lookup_with_value(&self, lookup_index: u16, buffer: &mut GlyphBuffer, mask: u32,
+                                   feature_value: u32) {
+        let lookup = match self.table.lookup(lookup_index) {
+            Some(l) => l,
+            None => return,
+        };
+        let state = ApplyState {
+            mask: mask,
+            feature_value: feature_value,
+            nesting_level_left: MAX_NESTING_LEVEL,
+        };
+
+        if lookup.lookup_type == GSUB_REVERSE_CHAINED_SINGLE {
+            // These are applied from the end of the buffer backwards,
+            // and never change its length.
+            let filter = lookup.filter(self.gdef.as_ref());
+            let mut index = buffer.len();
+            while index > 0 {
+                index -= 1;
+                if buffer.glyphs[index].mask & mask != 0 && !filter.skips(&buffer.glyphs[index]) {
+                    self.apply_at(&lookup, buffer, index, state);
+                }
+            }
+            return;
+        }
+
+        let filter = lookup.filter(self.gdef.as_ref());
+        let mut index = 0;
+        while index < buffer.len() {
+            if buffer.glyphs[index].mask & mask != 0 && !filter.skips(&buffer.glyphs[index]) {
+                let len = buffer.len();
+                if let Some(next) = self.apply_at(&lookup, buffer, index, state) {
+                    // If glyphs were deleted, the glyph that moved into
+                    // their place hasn't been looked at yet.
+                    index = if buffer.len() < len { next } else { next.max(index + 1) };
+                    continue;
+                }
+            }
+            index += 1;
+        }
+    }
+
+    // Whether the lookup would substitute the glyph sequence `glyphs`
+    // as a whole, regardless of lookup flags; shapers use this to find
+    // out which forms a font has.  Contextual rules only count if they
+    // need no glyphs before or after the sequence.
+    pub fn would_apply(&self, lookup_index: u16, glyphs: &[u16]) -> bool {
+        let lookup = match self.table.lookup(lookup_index) {
+            Some(l) => l,
+            None => return false,
+        };
+        if glyphs.is_empty() {
+            return false;
+        }
+        let buffer = GlyphBuffer::from_glyph_indices(glyphs);
+        let filter = LookupFilter { flags: 0, mark_glyph_set: None };
+        (0..lookup.subtable_count()).filter_map(|i| lookup.subtable(i)).any(|subtable| {
+            match lookup.lookup_type {
+                GSUB_SINGLE | GSUB_MULTIPLE | GSUB_ALTERNATE | GSUB_REVERSE_CHAINED_SINGLE => {
+                    glyphs.len() == 1 &&
+                        subtable.offset16(2).and_then(|c| coverage_index(c, glyphs[0])).is_some()
+                }
+                GSUB_LIGATURE => would_apply_ligature(subtable, glyphs).is_some(),
+                GSUB_CONTEXT => {
+                    match_sequence_context(subtable, &buffer, &filter, !0, 0)
+                        .map_or(false, |m| m.positions.len() == glyphs.len())
+                }
+                GSUB_CHAINED_CONTEXT => {
+                    match_chained_sequence_context(subtable, &buffer, &filter, !0, 0)
+                        .map_or(false, |m| m.positions.len() == glyphs.len())
+                }
+                _ => false,
+            }
+        })
+    }
+
+    // Tries each subtable of the lookup at `index`, returning the index
+    // to continue from if one of them applied.
+    fn apply_at(&self, lookup: &Lookup, buffer: &mut GlyphBuffer, index: usize, state: ApplyState) -> Option<usize> {
+        let gdef = self.gdef.as_ref();
+        let filter = lookup.filter(gdef);
+        for i in 0..lookup.subtable_count() {
+            let subtable = match lookup.subtable(i) {
+                Some(s) => s,
+                None => continue,
+            };
+            let applied = match lookup.lookup_type {
+                GSUB_SINGLE => apply_single(subtable, gdef, buffer, index),
+                GSUB_MULTIPLE => apply_multiple(subtable, gdef, buffer, index),
+                GSUB_ALTERNATE => apply_alternate(subtable, gdef, buffer, index, state.feature_value),
+                GSUB_LIGATURE => apply_ligature(subtable, gdef, buffer, &filter, state.mask, index),
+                GSUB_CONTEXT => {
+                    match_sequence_context(subtable, buffer, &filter, state.mask, index)
+                        .map(|m| self.apply_records(buffer, m, state))
+                }
+                GSUB_CHAINED_CONTEXT => {
+                    match_chained_sequence_context(subtable, buffer, &filter, state.mask, index)
+                        .map(|m| self.apply_records(buffer, m, state))
+                }
+                GSUB_REVERSE_CHAINED_SINGLE => {
+                    apply_reverse_chained_single(subtable, gdef, buffer, &filter, index)
+                }
+                _ => None,
+            };
+            if applied.is_some() {
+                return applied;
+            }
+        }
+        None
+    }
+
+    fn apply_records(&self, buffer: &mut GlyphBuffer, m: ContextMatch, state: ApplyState) -> usize {
+        if state.nesting_level_left == 0 {
+            return *m.positions.last().unwrap() + 1;
+        }
+        let nested_state = ApplyState {
+            nesting_level_left: state.nesting_level_left - 1,
+            ..state
+        };
+        apply_context_records(buffer, m, |buffer, lookup_index, position| {
+            if let Some(lookup) = self.table.lookup(lookup_index) {
+                if !lookup.filter(self.gdef.as_ref()).skips(&buffer.glyphs[position]) {
+                    self.apply_at(&lookup, buffer, position, nested_state);
+                }
+            }
+        })
+    }
+}
+
+fn set_glyph(gdef: Option<&GdefTable>, info: &mut GlyphInfo, glyph: u16) {
+    info.glyph_index = glyph;
+    info.substituted = true;
+    if let Some(gdef) = gdef {
+        gdef.classify_glyph(info);
+    }
+}
+
+fn replace_glyph(gdef: Option<&GdefTable>, buffer: &mut GlyphBuffer, index: usize, glyph: u16) {
+    set_glyph(gdef, &mut buffer.glyphs[index], glyph);
+}
+
+fn apply_single(subtable: TableData, gdef: Option<&GdefTable>, buffer: &mut GlyphBuffer, index: usize) -> Option<usize> {
+    let glyph = buffer.glyphs[index].glyph_index;
+    let coverage = coverage_index(subtable.offset16(2)?, glyph)?;
+    let substitute = match subtable.u16(0)? {
+        1 => (glyph as i32 + subtable.i16(4)? as i32) as u16,
+        2 => {
+            if coverage >= subtable.u16(4)? {
+                return None;
+            }
+            subtable.u16(6 + coverage as usize * 2)?
+        }
+        _ => return None,
+    };
+    replace_glyph(gdef, buffer, index, substitute);
+    Some(index + 1)
+}
+
+fn apply_multiple(subtable: TableData, gdef: Option<&GdefTable>, buffer: &mut GlyphBuffer, index: usize) -> Option<usize> {
+    if subtable.u16(0)? != 1 {
+        return None;
+    }
+    let coverage = coverage_index(subtable.offset16(2)?, buffer.glyphs[index].glyph_index)?;
+    if coverage >= subtable.u16(4)? {
+        return None;
+    }
+    let sequence = subtable.offset16(6 + coverage as usize * 2)?;
+    let count = sequence.u16(0)? as usize;
+    let substitutes = sequence.u16_array(2, count)?;
+
+    // An empty sequence isn't allowed by the spec, but deleting the
+    // glyph is what everyone does with it.
+    let original = buffer.glyphs[index];
+    let replacement: Vec<GlyphInfo> = substitutes.iter().enumerate().map(|(i, &g)| {
+        let mut info = original;
+        set_glyph(gdef, &mut info, g);
+        info.lig_num_components = 1;
+        if count > 1 {
+            info.multiplied_component = (i + 1).min(255) as u8;
+        }
+        info
+    }).collect();
+    buffer.glyphs.splice(index..index + 1, replacement);
+    Some(index + count)
+}
+
+fn apply_alternate(subtable: TableData, gdef: Option<&GdefTable>, buffer: &mut GlyphBuffer, index: usize, feature_value: u32) -> Option<usize> {
+    if subtable.u16(0)? != 1 {
+        return None;
+    }
+    let coverage = coverage_index(subtable.offset16(2)?, buffer.glyphs[index].glyph_index)?;
+    if coverage >= subtable.u16(4)? {
+        return None;
+    }
+    let alternate_set = subtable.offset16(6 + coverage as usize * 2)?;
+    let count = alternate_set.u16(0)? as u32;
+    if feature_value == 0 || feature_value > count {
+        return None;
+    }
+    let substitute = alternate_set.u16(2 + (feature_value as usize - 1) * 2)?;
+    replace_glyph(gdef, buffer, index, substitute);
+    Some(index + 1)
+}
+
+fn apply_ligature(subtable: TableData, gdef: Option<&GdefTable>, buffer: &mut GlyphBuffer,
+                  filter: &LookupFilter, mask: u32, index: usize) -> Option<usize> {
+    if subtable.u16(0)? != 1 {
+        return None;
+    }
+    let coverage = coverage_index(subtable.offset16(2)?, buffer.glyphs[index].glyph_index)?;
+    if coverage >= subtable.u16(4)? {
+        return None;
+    }
+    let ligature_set = subtable.offset16(6 + coverage as usize * 2)?;
+    let ligature_count = ligature_set.u16(0)? as usize;
+    for i in 0..ligature_count {
+        let ligature = match ligature_set.offset16(2 + i * 2) {
+            Some(l) => l,
+            None => continue,
+        };
+        let ligature_glyph = ligature.u16(0)?;
+        let component_count = ligature.u16(2)? as usize;
+        if component_count == 0 {
+            continue;
+        }
+        let components = ligature.u16_array(4, component_count - 1)?;
+        if let Some(positions) = match_glyph_sequence(buffer, filter, mask, index, &components) {
+            return Some(form_ligature(gdef, buffer, &positions, ligature_glyph));
+        }
+    }
+    None
+}
+
+fn would_apply_ligature(subtable: TableData, glyphs: &[u16]) -> Option<()> {
+    if subtable.u16(0)? != 1 {
+        return None;
+    }
+    let coverage = coverage_index(subtable.offset16(2)?, glyphs[0])?;
+    if coverage >= subtable.u16(4)? {
+        return None;
+    }
+    let ligature_set = subtable.offset16(6 + coverage as usize * 2)?;
+    let ligature_count = ligature_set.u16(0)? as usize;
+    (0..ligature_count).filter_map(|i| ligature_set.offset16(2 + i * 2)).find(|ligature| {
+        ligature.u16(2) == Some(glyphs.len() as u16) &&
+            ligature.u16_array(4, glyphs.len() - 1).map_or(false, |c| c[..] == glyphs[1..])
+    }).map(|_| ())
+}
+
+// Replaces the glyphs at `positions` with a single ligature glyph.
+// Marks that were skipped over between components stay where they
+// are, but remember which component they belonged to.
+fn form_ligature(gdef: Option<&GdefTable>, buffer: &mut GlyphBuffer, positions: &[usize],
+                 ligature_glyph: u16) -> usize {
+    let first = positions[0];
+    let last = *positions.last().unwrap();
+    buffer.merge_clusters(first, last + 1);
+
+    // A "ligature" made only of marks (e.g. stacked Hebrew points) is
+    // still a mark, and shouldn't capture anything.
+    let all_marks = positions.iter().all(|&p| buffer.glyphs[p].is_mark());
+    let num_components: usize = positions.iter()
+        .map(|&p| buffer.glyphs[p].lig_num_components.max(1) as usize)
+        .sum();
+
+    if !all_marks && positions.len() > 1 {
+        let lig_id = buffer.allocate_lig_id();
+        let mut component = 0;
+        for i in first..last + 1 {
+            if positions.contains(&i) {
+                component += buffer.glyphs[i].lig_num_components.max(1) as usize;
+            } else if buffer.glyphs[i].is_mark() {
+                buffer.glyphs[i].lig_id = lig_id;
+                buffer.glyphs[i].lig_component = component as u8;
+            }
+        }
+        let info = &mut buffer.glyphs[first];
+        info.lig_id = lig_id;
+        info.lig_component = 0;
+        info.glyph_class = GlyphClass::Ligature;
+    }
+
+    {
+        let info = &mut buffer.glyphs[first];
+        set_glyph(gdef, info, ligature_glyph);
+        info.ligated = true;
+        info.lig_num_components = num_components.min(255) as u8;
+    }
+
+    for &p in positions[1..].iter().rev() {
+        buffer.glyphs.remove(p);
+    }
+
+    last - (positions.len() - 1) + 1
+}
+
+fn apply_reverse_chained_single(subtable: TableData, gdef: Option<&GdefTable>, buffer: &mut GlyphBuffer,
+                                filter: &LookupFilter, index: usize) -> Option<usize> {
+    if subtable.u16(0)? != 1 {
+        return None;
+    }
+    let coverage = coverage_index(subtable.offset16(2)?, buffer.glyphs[index].glyph_index)?;
+    let mut offset = 4;
+    let backtrack_count = subtable.u16(offset)? as usize;
+    let backtrack = subtable.u16_array(offset + 2, backtrack_count)?;
+    offset += 2 + backtrack_count * 2;
+    let lookahead_count = subtable.u16(offset)? as usize;
+    let lookahead = subtable.u16_array(offset + 2, lookahead_count)?;
+    offset += 2 + lookahead_count * 2;
+    let glyph_count = subtable.u16(offset)?;
+    if coverage >= glyph_count {
+        return None;
+    }
+
+    if !match_backtrack_coverages(buffer, filter, index, subtable, &backtrack) ||
+       !match_lookahead_coverages(buffer, filter, index, subtable, &lookahead) {
+        return None;
+    }
+
+    let substitute = subtable.u16(offset + 2 + coverage as usize * 2)?;
+    replace_glyph(gdef, buffer, index, substitute);
+    Some(index + 1)
+}
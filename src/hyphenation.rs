@@ -0,0 +1,158 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Hyphenates words with Liang's patterns, the way TeX does: every
+// pattern found in the word (with dots marking its ends) gives levels
+// to the places between its letters, each place takes the highest
+// level any pattern gives it, and the word can be hyphenated where
+// that level is odd.  Words in the exception list are hyphenated as
+// listed instead.
+//
+// The patterns for a few languages are embedded from
+// src/hyphenation_patterns, in the format of TeX's pattern files.
+// They're from the TeX hyphenation patterns project and keep their own
+// licenses, given in each file and in src/hyphenation_patterns/LICENSE.
+
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref EN_US: Hyphenator = Hyphenator::new(include_str!("hyphenation_patterns/en-us.tex"), 2, 3);
+    static ref FRENCH: Hyphenator = Hyphenator::new(include_str!("hyphenation_patterns/fr.tex"), 2, 2);
+    static ref ITALIAN: Hyphenator = Hyphenator::new(include_str!("hyphenation_patterns/it.tex"), 2, 2);
+}
+
+pub struct Hyphenator {
+    // The letters of each pattern, and the level before each of them
+    // and after the last.
+    patterns: HashMap<Vec<char>, Vec<u8>>,
+    // The longest pattern's length in letters.
+    max_length: usize,
+    // Where each exception word is hyphenated, as character indices.
+    exceptions: HashMap<Vec<char>, Vec<usize>>,
+    // The fewest characters left before and after a hyphen.
+    pub left_min: usize,
+    pub right_min: usize,
+}
+
+impl Hyphenator {
+    // Reads a TeX pattern file: patterns like "hy3ph" in \patterns{},
+    // and words hyphenated like "ta-ble" in \hyphenation{}.  Anything
+    // after a % is a comment.  A file without either group is read as
+    // a list of patterns.
+    pub fn new(patterns: &str, left_min: usize, right_min: usize) -> Hyphenator {
+        let mut hyphenator = Hyphenator {
+            patterns: HashMap::new(),
+            max_length: 0,
+            exceptions: HashMap::new(),
+            left_min: left_min,
+            right_min: right_min,
+        };
+        let has_groups = patterns.contains("\\patterns") || patterns.contains("\\hyphenation");
+        let mut in_patterns = !has_groups;
+        let mut in_exceptions = false;
+        for line in patterns.lines() {
+            let line = line.split('%').next().unwrap();
+            for token in line.split_whitespace() {
+                if token.starts_with("\\patterns") {
+                    in_patterns = true;
+                } else if token.starts_with("\\hyphenation") {
+                    in_exceptions = true;
+                } else if token.starts_with('}') {
+                    in_patterns = !has_groups;
+                    in_exceptions = false;
+                } else if in_patterns {
+                    hyphenator.add_pattern(token.split('}').next().unwrap());
+                } else if in_exceptions {
+                    hyphenator.add_exception(token.split('}').next().unwrap());
+                }
+                if has_groups && token.len() > 1 && token.ends_with('}') {
+                    in_patterns = false;
+                    in_exceptions = false;
+                }
+            }
+        }
+        hyphenator
+    }
+
+    // The built-in patterns for a language, by its tag like "en-US";
+    // only the language part is looked at.
+    pub fn for_language(tag: &str) -> Option<&'static Hyphenator> {
+        let language = tag.split(|c| c == '-' || c == '_').next().unwrap().to_lowercase();
+        match &*language {
+            "en" => Some(&*EN_US),
+            "fr" => Some(&*FRENCH),
+            "it" => Some(&*ITALIAN),
+            _ => None,
+        }
+    }
+
+    fn add_pattern(&mut self, pattern: &str) {
+        if pattern.is_empty() {
+            return;
+        }
+        let mut letters = vec![];
+        let mut levels = vec![0];
+        for c in pattern.chars() {
+            match c.to_digit(10) {
+                Some(level) => *levels.last_mut().unwrap() = level as u8,
+                None => {
+                    letters.push(c);
+                    levels.push(0);
+                }
+            }
+        }
+        self.max_length = self.max_length.max(letters.len());
+        self.patterns.insert(letters, levels);
+    }
+
+    fn add_exception(&mut self, word: &str) {
+        let mut letters = vec![];
+        let mut hyphens = vec![];
+        for c in word.chars() {
+            if c == '-' {
+                hyphens.push(letters.len());
+            } else {
+                letters.push(lowercase(c));
+            }
+        }
+        if !letters.is_empty() {
+            self.exceptions.insert(letters, hyphens);
+        }
+    }
+
+    // Where `word` can be hyphenated, as indices of the characters a
+    // hyphen can go before, in order.
+    pub fn hyphenate(&self, word: &[char]) -> Vec<usize> {
+        let len = word.len();
+        if len < self.left_min + self.right_min || len < 2 {
+            return vec![];
+        }
+        let letters: Vec<char> = word.iter().map(|&c| lowercase(c)).collect();
+        let allowed = |i: usize| i >= self.left_min.max(1) && i + self.right_min.max(1) <= len;
+        if let Some(hyphens) = self.exceptions.get(&letters) {
+            return hyphens.iter().cloned().filter(|&i| allowed(i)).collect();
+        }
+
+        let mut dotted = vec!['.'];
+        dotted.extend(letters);
+        dotted.push('.');
+        // levels[i] is the level before dotted[i].
+        let mut levels = vec![0; dotted.len() + 1];
+        for start in 0..dotted.len() {
+            for end in start + 1..(start + self.max_length).min(dotted.len()) + 1 {
+                if let Some(pattern) = self.patterns.get(&dotted[start..end]) {
+                    for (k, &level) in pattern.iter().enumerate() {
+                        levels[start + k] = levels[start + k].max(level);
+                    }
+                }
+            }
+        }
+        // Before word[i] is before dotted[i + 1].
+        (1..len).filter(|&i| allowed(i) && levels[i + 1] % 2 == 1).collect()
+    }
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
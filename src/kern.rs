@@ -0,0 +1,134 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The legacy 'kern' table, used for kerning fonts that don't have
+// GPOS kerning.  Only format 0 (ordered pair list) subtables are
+// supported, in both the Microsoft and the Apple table layouts.
+
+use ot_parse::TableData;
+use glyph_buffer::GlyphBuffer;
+use super::FontFace;
+
+#[derive(Debug)]
+pub struct KernTable {
+    data: Vec<u8>,
+    // (offset of the format 0 data, is override) for each horizontal
+    // subtable.
+    subtables: Vec<(usize, bool)>,
+}
+
+impl KernTable {
+    pub fn new(data: Vec<u8>) -> Option<KernTable> {
+        let mut subtables = vec![];
+        {
+            let t = TableData::new(&data);
+            if t.u16(0)? == 0 {
+                // Microsoft: version, nTables; subtables have a 6 byte
+                // header of version, length, coverage.
+                let count = t.u16(2)? as usize;
+                let mut offset = 4;
+                for _ in 0..count {
+                    let length = t.u16(offset + 2)? as usize;
+                    let coverage = t.u16(offset + 4)?;
+                    let format = coverage >> 8;
+                    let horizontal = coverage & 0x1 != 0;
+                    let minimum = coverage & 0x2 != 0;
+                    let cross_stream = coverage & 0x4 != 0;
+                    if format == 0 && horizontal && !minimum && !cross_stream {
+                        subtables.push((offset + 6, coverage & 0x8 != 0));
+                    }
+                    if length < 6 {
+                        break;
+                    }
+                    offset += length;
+                }
+            } else if t.u32(0)? == 0x00010000 {
+                // Apple: version, nTables (32 bit); subtables have an
+                // 8 byte header of length (32 bit), coverage, tupleIndex.
+                let count = t.u32(4)? as usize;
+                let mut offset = 8;
+                for _ in 0..count {
+                    let length = t.u32(offset)? as usize;
+                    let coverage = t.u16(offset + 4)?;
+                    let format = coverage & 0xFF;
+                    let vertical = coverage & 0x8000 != 0;
+                    let cross_stream = coverage & 0x4000 != 0;
+                    let variation = coverage & 0x2000 != 0;
+                    if format == 0 && !vertical && !cross_stream && !variation {
+                        subtables.push((offset + 8, false));
+                    }
+                    if length < 8 {
+                        break;
+                    }
+                    offset += length;
+                }
+            } else {
+                return None;
+            }
+        }
+        Some(KernTable {
+            data: data,
+            subtables: subtables,
+        })
+    }
+
+    pub fn from_font_face(face: &FontFace) -> Option<KernTable> {
+        face.get_font_table(ot_tag!(b"kern")).and_then(KernTable::new)
+    }
+
+    pub fn has_horizontal_kerning(&self) -> bool {
+        !self.subtables.is_empty()
+    }
+
+    // The kerning adjustment between two glyphs, in design units.
+    pub fn kerning(&self, left: u16, right: u16) -> i32 {
+        let t = TableData::new(&self.data);
+        let key = ((left as u32) << 16) | right as u32;
+        let mut value = 0;
+        for &(offset, is_override) in &self.subtables {
+            // nPairs, searchRange, entrySelector, rangeShift, then
+            // KernPair { left, right, value } sorted by (left, right).
+            let count = t.u16(offset).unwrap_or(0) as usize;
+            let (mut lo, mut hi) = (0, count);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let rec = offset + 8 + mid * 6;
+                let pair = match t.u32(rec) {
+                    Some(p) => p,
+                    None => break,
+                };
+                if pair < key {
+                    lo = mid + 1;
+                } else if pair > key {
+                    hi = mid;
+                } else {
+                    let v = t.i16(rec + 4).unwrap_or(0) as i32;
+                    value = if is_override { v } else { value + v };
+                    break;
+                }
+            }
+        }
+        value
+    }
+
+    // Kerns each pair of adjacent non-mark glyphs in the buffer whose
+    // masks intersect `mask`, by adjusting the first glyph's advance.
+    pub fn apply(&self, buffer: &mut GlyphBuffer, mask: u32) {
+        assert!(buffer.positions.len() == buffer.glyphs.len());
+        let mut prev: Option<usize> = None;
+        for i in 0..buffer.len() {
+            let info = buffer.glyphs[i];
+            if info.is_mark() {
+                continue;
+            }
+            if let Some(p) = prev {
+                if info.mask & mask != 0 && buffer.glyphs[p].mask & mask != 0 {
+                    let k = self.kerning(buffer.glyphs[p].glyph_index, info.glyph_index);
+                    buffer.positions[p].x_advance += k;
+                }
+            }
+            prev = Some(i);
+        }
+    }
+}
@@ -67,6 +67,7 @@ pub use winapi::{DWRITE_MEASURING_MODE};
 pub use winapi::{DWRITE_TEXTURE_TYPE};
 
 #[macro_use] mod com_helpers;
+#[macro_use] mod ot_parse; pub use ot_parse::make_opentype_tag;
 
 mod bitmap_render_target; pub use bitmap_render_target::BitmapRenderTarget;
 mod font; pub use font::Font;
@@ -78,6 +79,42 @@ mod gdi_interop; pub use gdi_interop::GdiInterop;
 mod rendering_params; pub use rendering_params::RenderingParams;
 mod glyph_run_analysis; pub use glyph_run_analysis::GlyphRunAnalysis;
 
+// OpenType layout, implemented on top of FontFace::get_font_table.
+mod ot_layout;
+mod glyph_buffer; pub use glyph_buffer::{GlyphBuffer, GlyphInfo, GlyphClass, GlyphPosition, TextDirection};
+mod gdef; pub use gdef::{GdefTable, CaretValue};
+mod gsub; pub use gsub::GsubTable;
+mod gpos; pub use gpos::GposTable;
+mod kern; pub use kern::KernTable;
+mod math_table; pub use math_table::{MathTable, MathConstant, MathKernCorner, GlyphVariant, GlyphPart,
+                                     GlyphAssembly, StretchedGlyph};
+mod base_table; pub use base_table::{BaseTable, Baseline, MinMax, font_baseline, font_default_baseline};
+mod fallback_position;
+mod name_table; pub use name_table::NameTable;
+mod glyph_metrics; pub use glyph_metrics::GlyphMetricsTables;
+mod line_metrics; pub use line_metrics::{FontLineMetrics, LineMetrics};
+mod font_features; pub use font_features::FontFeature;
+mod shaping; pub use shaping::{shape, Shaper, ShapedGlyphs, FeatureSetting};
+mod bidi; pub use bidi::{BidiAnalysis, BidiRun};
+mod text_analysis; pub use text_analysis::{analyze_scripts, opentype_script_tag, ScriptRun};
+mod line_break; pub use line_break::{line_breaks, LineBreak};
+mod segmentation; pub use segmentation::{grapheme_boundaries, word_boundaries, caret_stops, CaretStop};
+mod word_dictionary;
+mod hyphenation; pub use hyphenation::Hyphenator;
+mod font_fallback;
+mod text_layout; pub use text_layout::{TextLayout, TextAlignment, TextFormat, LayoutLine, LayoutGlyphRun, TextMetrics,
+                                     HitTestMetrics, Trimming, TrimmingGranularity, LineSpacing,
+                                     LineSpacingMethod, BreakCondition, InlineObject, LayoutInlineObject};
+mod shaper_arabic;
+mod shaper_syllabic;
+mod shaper_indic;
+mod shaper_hangul;
+mod shaper_khmer;
+mod shaper_myanmar;
+mod shaper_thai;
+mod shaper_use;
+mod unicode_data;
+
 // This is an internal implementation of FontFileLoader, for our utility
 // functions.  We don't wrap the DWriteFontFileLoader interface and
 // related things.
@@ -0,0 +1,232 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Finds where lines may or must be broken, following the Unicode line
+// breaking algorithm (UAX #14, https://www.unicode.org/reports/tr14/),
+// like DirectWrite's IDWriteTextAnalyzer::AnalyzeLineBreakpoints.
+//
+// Classes are resolved as DirectWrite does for text without a
+// language that says otherwise: ambiguous and unknown characters are
+// alphabetic, conditional Japanese starters (small kana) can't start a
+// line, and SA characters are alphabetic, or combining marks if they
+// are marks.  Text in SA scripts with a dictionary (Thai, Lao, Khmer
+// and Burmese) can also be broken between the words the dictionary
+// finds in it.
+
+use unicode_data::{self, LineBreakClass};
+use unicode_data::LineBreakClass::*;
+use word_dictionary;
+
+// A position (in UTF-16 code units) a line can end at.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct LineBreak {
+    pub position: usize,
+    // Whether the line has to end here, after a paragraph or line
+    // separator or at the end of the text.
+    pub mandatory: bool,
+}
+
+fn resolve_class(cp: u32) -> LineBreakClass {
+    match unicode_data::line_break_class(cp) {
+        AI | SG | XX => AL,
+        CJ => NS,
+        SA if unicode_data::is_complex_context_mark(cp) => CM,
+        SA => AL,
+        class => class,
+    }
+}
+
+// Opening and closing punctuation that's wide in East Asian text,
+// which rule LB30 leaves breakable next to letters and digits.
+fn is_east_asian_wide(cp: u32) -> bool {
+    cp == 0x2329 || cp == 0x232A || (0x3000 <= cp && cp <= 0x303F) ||
+    (0xFE10 <= cp && cp <= 0xFE1F) || (0xFE30 <= cp && cp <= 0xFE6F) ||
+    (0xFF00 <= cp && cp <= 0xFFEF)
+}
+
+fn is_hangul(class: LineBreakClass) -> bool {
+    match class {
+        JL | JV | JT | H2 | H3 => true,
+        _ => false,
+    }
+}
+
+// The character before a possible break, after combining marks have
+// been attached to it (LB9) and ignoring trailing spaces.
+#[derive(Clone, Copy)]
+struct Before {
+    cp: u32,
+    class: LineBreakClass,
+    // The class of the character before this one.
+    previous: Option<LineBreakClass>,
+}
+
+pub fn line_breaks(text: &str) -> Vec<LineBreak> {
+    let mut breaks = vec![];
+    // The last character that isn't a space, and whether spaces follow it.
+    let mut before: Option<Before> = None;
+    let mut after_spaces = false;
+    // The previous character as it is in the text.
+    let mut last_class = None;
+    // How many regional indicators end the text so far.
+    let mut regional_indicators = 0;
+    let mut word_breaks = word_dictionary::dictionary_breaks(text).into_iter().peekable();
+
+    let mut offset = 0;
+    for c in text.chars() {
+        let cp = c as u32;
+        let class = resolve_class(cp);
+        let decision = match last_class {
+            // LB2: never break at the start of the text.
+            None => None,
+            Some(last) => pair_break(before, after_spaces, last, class, regional_indicators, cp),
+        };
+        // Words found by the dictionary are only inside runs of SA
+        // characters, which never break on their own.
+        let decision = if word_breaks.peek() == Some(&offset) {
+            word_breaks.next();
+            Some(false)
+        } else {
+            decision
+        };
+        if let Some(mandatory) = decision {
+            breaks.push(LineBreak { position: offset, mandatory: mandatory });
+        }
+
+        // LB9: combining marks and ZWJ take the class of the character
+        // they follow, unless that's a space or line break; LB10: other
+        // ones are alphabetic.
+        let attaches = (class == CM || class == ZWJ) && match last_class {
+            Some(BK) | Some(CR) | Some(LF) | Some(NL) | Some(SP) | Some(ZW) | None => false,
+            _ => true,
+        };
+        if !attaches {
+            let class = if class == CM || class == ZWJ { AL } else { class };
+            if class == SP {
+                after_spaces = true;
+            } else {
+                regional_indicators = match class {
+                    RI if !after_spaces => regional_indicators + 1,
+                    RI => 1,
+                    _ => 0,
+                };
+                before = Some(Before {
+                    cp: cp,
+                    class: class,
+                    previous: if after_spaces { Some(SP) } else { before.map(|b| b.class) },
+                });
+                after_spaces = false;
+            }
+        }
+        last_class = Some(class);
+        offset += c.len_utf16();
+    }
+
+    // LB3: always break at the end.
+    if offset > 0 {
+        breaks.push(LineBreak { position: offset, mandatory: true });
+    }
+    breaks
+}
+
+// Whether the text can be broken between a character and the next
+// one of class `next`, and if so whether it must be: None for no break,
+// Some(true) for a mandatory one.
+fn pair_break(before: Option<Before>, after_spaces: bool, last: LineBreakClass,
+              next: LineBreakClass, regional_indicators: usize, next_cp: u32) -> Option<bool> {
+    // LB4, LB5: break after hard line breaks, but not inside CR LF.
+    match last {
+        BK | LF | NL => return Some(true),
+        CR => return if next == LF { None } else { Some(true) },
+        _ => {}
+    }
+    match next {
+        // LB6: don't break before hard line breaks.
+        BK | CR | LF | NL => return None,
+        // LB7: don't break before spaces or zero width space.
+        SP | ZW => return None,
+        _ => {}
+    }
+
+    // Only spaces so far.
+    let before = before.unwrap_or(Before { cp: 0x20, class: SP, previous: None });
+    // LB8: break after zero width space, even if spaces follow it.
+    if before.class == ZW {
+        return Some(false);
+    }
+    // LB8a: don't break after a zero width joiner.
+    if last == ZWJ {
+        return None;
+    }
+    // LB9: don't break before marks that attach to the character before.
+    if (next == CM || next == ZWJ) && !after_spaces {
+        return None;
+    }
+    let next = if next == CM || next == ZWJ { AL } else { next };
+
+    let a = before.class;
+    let b = next;
+    let no_break = match (a, b) {
+        // LB11: word joiners glue on both sides.
+        (_, WJ) | (WJ, _) if !after_spaces || b == WJ => true,
+        // LB12, LB12a: non-breaking glue.
+        (GL, _) if !after_spaces => true,
+        (_, GL) if !after_spaces && a != BA && a != HY => true,
+        // LB13: don't break before closing punctuation.
+        (_, CL) | (_, CP) | (_, EX) | (_, IS) | (_, SY) => true,
+        // LB14: don't break after opening punctuation, even after spaces.
+        (OP, _) => true,
+        // LB15: a quotation mark before opening punctuation.
+        (QU, OP) => true,
+        // LB16: closing punctuation before a nonstarter.
+        (CL, NS) | (CP, NS) => true,
+        // LB17: a run of em dashes.
+        (B2, B2) => true,
+        // LB18: otherwise break after spaces.
+        _ if after_spaces => false,
+        // LB19: quotation marks.
+        (_, QU) | (QU, _) => true,
+        // LB20: break around contingent breaks.
+        (_, CB) | (CB, _) => false,
+        // LB21: don't break before hyphens and small kana, or after
+        // characters that precede what they apply to.
+        (_, BA) | (_, HY) | (_, NS) | (BB, _) => true,
+        // LB21a: don't break after a hyphen following a Hebrew letter.
+        (HY, _) | (BA, _) if before.previous == Some(HL) => true,
+        // LB21b: a solidus before a Hebrew letter.
+        (SY, HL) => true,
+        // LB22: inseparable characters, like ellipses.
+        (_, IN) => true,
+        // LB23: letters and digits.
+        (AL, NU) | (HL, NU) | (NU, AL) | (NU, HL) => true,
+        // LB23a: currency prefixes and postfixes around ideographs.
+        (PR, ID) | (PR, EB) | (PR, EM) | (ID, PO) | (EB, PO) | (EM, PO) => true,
+        // LB24: currency prefixes and postfixes around letters.
+        (PR, AL) | (PR, HL) | (PO, AL) | (PO, HL) | (AL, PR) | (AL, PO) | (HL, PR) | (HL, PO) => true,
+        // LB25: numbers.
+        (CL, PO) | (CP, PO) | (CL, PR) | (CP, PR) | (NU, PO) | (NU, PR) | (PO, OP) | (PO, NU) |
+        (PR, OP) | (PR, NU) | (HY, NU) | (IS, NU) | (NU, NU) | (SY, NU) => true,
+        // LB26, LB27: Korean syllable blocks.
+        (JL, JL) | (JL, JV) | (JL, H2) | (JL, H3) | (JV, JV) | (JV, JT) | (H2, JV) | (H2, JT) |
+        (JT, JT) | (H3, JT) => true,
+        (_, PO) if is_hangul(a) => true,
+        (PR, _) if is_hangul(b) => true,
+        // LB28: between letters.
+        (AL, AL) | (AL, HL) | (HL, AL) | (HL, HL) => true,
+        // LB29: punctuation inside words, like "e.g.".
+        (IS, AL) | (IS, HL) => true,
+        // LB30: parentheses right next to letters and digits, unless
+        // they're East Asian.
+        (AL, OP) | (HL, OP) | (NU, OP) => !is_east_asian_wide(next_cp),
+        (CP, AL) | (CP, HL) | (CP, NU) => !is_east_asian_wide(before.cp),
+        // LB30a: pairs of regional indicators make flags.
+        (RI, RI) => regional_indicators % 2 == 1,
+        // LB30b: emoji modifiers.
+        (EB, EM) => true,
+        (_, EM) => unicode_data::is_unassigned_pictographic(before.cp),
+        // LB31: break everywhere else.
+        _ => false,
+    };
+    if no_break { None } else { Some(false) }
+}
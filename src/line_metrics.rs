@@ -0,0 +1,92 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The three sets of ascent, descent and line gap a font has, which
+// different platforms space lines with: 'hhea' (Mac, and browsers),
+// the OS/2 typo metrics (browsers, when the font's USE_TYPO_METRICS
+// flag is set) and the OS/2 win metrics (GDI).  DirectWrite's own
+// FontMetrics are the win metrics, or the typo metrics if the flag is
+// set.
+
+use ot_parse::TableData;
+use super::FontFace;
+
+// fsSelection's USE_TYPO_METRICS bit.
+const USE_TYPO_METRICS: u16 = 1 << 7;
+
+// Ascent, descent and line gap in design units, with the descent
+// positive below the baseline, as in FontMetrics.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct LineMetrics {
+    pub ascent: i32,
+    pub descent: i32,
+    pub line_gap: i32,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct FontLineMetrics {
+    pub design_units_per_em: u16,
+    pub hhea: LineMetrics,
+    // From the OS/2 table, if the font has one.
+    pub typo: Option<LineMetrics>,
+    // GDI has no line gap of its own; this is the external leading it
+    // reports, what's left of the 'hhea' line height below the win
+    // ascent and descent.
+    pub win: Option<LineMetrics>,
+    pub use_typo_metrics: bool,
+}
+
+impl FontLineMetrics {
+    pub fn from_font_face(face: &FontFace) -> Option<FontLineMetrics> {
+        let hhea = face.get_font_table(ot_tag!(b"hhea"))?;
+        let head = face.get_font_table(ot_tag!(b"head"))?;
+        let hhea = {
+            let t = TableData::new(&hhea);
+            LineMetrics {
+                ascent: t.i16(4)? as i32,
+                descent: -(t.i16(6)? as i32),
+                line_gap: t.i16(8)? as i32,
+            }
+        };
+        let mut metrics = FontLineMetrics {
+            design_units_per_em: TableData::new(&head).u16(18)?,
+            hhea: hhea,
+            typo: None,
+            win: None,
+            use_typo_metrics: false,
+        };
+
+        if let Some(os2) = face.get_font_table(ot_tag!(b"OS/2")) {
+            let t = TableData::new(&os2);
+            if let (Some(ascent), Some(descent), Some(line_gap)) = (t.i16(68), t.i16(70), t.i16(72)) {
+                metrics.typo = Some(LineMetrics {
+                    ascent: ascent as i32,
+                    descent: -(descent as i32),
+                    line_gap: line_gap as i32,
+                });
+            }
+            if let (Some(ascent), Some(descent)) = (t.u16(74), t.u16(76)) {
+                let (ascent, descent) = (ascent as i32, descent as i32);
+                let hhea_height = hhea.ascent + hhea.descent + hhea.line_gap;
+                metrics.win = Some(LineMetrics {
+                    ascent: ascent,
+                    descent: descent,
+                    line_gap: (hhea_height - ascent - descent).max(0),
+                });
+            }
+            metrics.use_typo_metrics = metrics.typo.is_some() &&
+                                       t.u16(62).map_or(false, |flags| flags & USE_TYPO_METRICS != 0);
+        }
+        Some(metrics)
+    }
+
+    // The metrics browsers use for `line-height: normal`: the typo
+    // metrics if the font asks for them, and 'hhea' otherwise.
+    pub fn css_normal(&self) -> LineMetrics {
+        match self.typo {
+            Some(typo) if self.use_typo_metrics => typo,
+            _ => self.hhea,
+        }
+    }
+}
@@ -0,0 +1,401 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The 'MATH' table, which math layout needs on top of the usual tables:
+// the font's constants for placing scripts, fractions, radicals and so
+// on, per-glyph italics corrections, accent attachment points and
+// kerning, and the bigger variants of operators and the parts to build
+// them from when no variant is big enough.  Values are in design units;
+// the device table adjustments of MathValueRecords are not applied.
+
+use std::cmp;
+
+use ot_layout::coverage_index;
+use ot_parse::TableData;
+use super::{FontFace, GlyphOffset};
+
+// The MathConstants, in the order of the table.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum MathConstant {
+    ScriptPercentScaleDown,
+    ScriptScriptPercentScaleDown,
+    DelimitedSubFormulaMinHeight,
+    DisplayOperatorMinHeight,
+    MathLeading,
+    AxisHeight,
+    AccentBaseHeight,
+    FlattenedAccentBaseHeight,
+    SubscriptShiftDown,
+    SubscriptTopMax,
+    SubscriptBaselineDropMin,
+    SuperscriptShiftUp,
+    SuperscriptShiftUpCramped,
+    SuperscriptBottomMin,
+    SuperscriptBaselineDropMax,
+    SubSuperscriptGapMin,
+    SuperscriptBottomMaxWithSubscript,
+    SpaceAfterScript,
+    UpperLimitGapMin,
+    UpperLimitBaselineRiseMin,
+    LowerLimitGapMin,
+    LowerLimitBaselineDropMin,
+    StackTopShiftUp,
+    StackTopDisplayStyleShiftUp,
+    StackBottomShiftDown,
+    StackBottomDisplayStyleShiftDown,
+    StackGapMin,
+    StackDisplayStyleGapMin,
+    StretchStackTopShiftUp,
+    StretchStackBottomShiftDown,
+    StretchStackGapAboveMin,
+    StretchStackGapBelowMin,
+    FractionNumeratorShiftUp,
+    FractionNumeratorDisplayStyleShiftUp,
+    FractionDenominatorShiftDown,
+    FractionDenominatorDisplayStyleShiftDown,
+    FractionNumeratorGapMin,
+    FractionNumDisplayStyleGapMin,
+    FractionRuleThickness,
+    FractionDenominatorGapMin,
+    FractionDenomDisplayStyleGapMin,
+    SkewedFractionHorizontalGap,
+    SkewedFractionVerticalGap,
+    OverbarVerticalGap,
+    OverbarRuleThickness,
+    OverbarExtraAscender,
+    UnderbarVerticalGap,
+    UnderbarRuleThickness,
+    UnderbarExtraDescender,
+    RadicalVerticalGap,
+    RadicalDisplayStyleVerticalGap,
+    RadicalRuleThickness,
+    RadicalExtraAscender,
+    RadicalKernBeforeDegree,
+    RadicalKernAfterDegree,
+    RadicalDegreeBottomRaisePercent,
+}
+
+// The corners of a glyph that math kerning is given for.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum MathKernCorner {
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
+// A bigger form of a glyph, and its size in the direction it grows.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct GlyphVariant {
+    pub glyph: u16,
+    pub advance: u16,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct GlyphPart {
+    pub glyph: u16,
+    // How much of each end can overlap the next part.
+    pub start_connector_length: u16,
+    pub end_connector_length: u16,
+    pub full_advance: u16,
+    // Extenders can be repeated, or left out, to reach a size.
+    pub is_extender: bool,
+}
+
+// The parts to build a glyph of any size from, bottom to top or left
+// to right.
+#[derive(PartialEq, Debug, Clone)]
+pub struct GlyphAssembly {
+    pub italics_correction: i16,
+    pub parts: Vec<GlyphPart>,
+}
+
+// A stretched glyph, ready to draw with
+// BitmapRenderTarget::draw_glyph_run at `em_size` with its baseline
+// origin where the glyph would be.  Vertical assemblies are stacked up
+// from the baseline with glyph offsets.  Sizes are in DIPs.
+#[derive(Debug, Clone)]
+pub struct StretchedGlyph {
+    pub glyph_indices: Vec<u16>,
+    pub glyph_advances: Vec<f32>,
+    pub glyph_offsets: Vec<GlyphOffset>,
+    pub em_size: f32,
+    // The size reached in the direction stretched.
+    pub size: f32,
+    pub italics_correction: f32,
+}
+
+// Extenders are repeated at most this many times, so a huge size can't
+// make a huge glyph run.
+const MAX_EXTENDER_REPEATS: usize = 1000;
+
+#[derive(Debug)]
+pub struct MathTable {
+    data: Vec<u8>,
+}
+
+impl MathTable {
+    pub fn new(data: Vec<u8>) -> Option<MathTable> {
+        {
+            let t = TableData::new(&data);
+            if t.u16(0)? != 1 {
+                return None;
+            }
+            t.u16(8)?;
+        }
+        Some(MathTable { data: data })
+    }
+
+    pub fn from_font_face(face: &FontFace) -> Option<MathTable> {
+        face.get_font_table(ot_tag!(b"MATH")).and_then(MathTable::new)
+    }
+
+    fn table(&self) -> TableData<'_> {
+        TableData::new(&self.data)
+    }
+
+    // The percentages are as they are; the other constants are in
+    // design units.
+    pub fn constant(&self, constant: MathConstant) -> i32 {
+        let constants = match self.table().offset16(4) {
+            Some(c) => c,
+            None => return 0,
+        };
+        let index = constant as usize;
+        let value = match constant {
+            MathConstant::DelimitedSubFormulaMinHeight | MathConstant::DisplayOperatorMinHeight => {
+                constants.u16(index * 2).map(|v| v as i32)
+            }
+            MathConstant::ScriptPercentScaleDown | MathConstant::ScriptScriptPercentScaleDown => {
+                constants.i16(index * 2).map(|v| v as i32)
+            }
+            // After the 51 MathValueRecords.
+            MathConstant::RadicalDegreeBottomRaisePercent => constants.i16(8 + 51 * 4).map(|v| v as i32),
+            _ => constants.i16(8 + (index - 4) * 4).map(|v| v as i32),
+        };
+        value.unwrap_or(0)
+    }
+
+    fn glyph_info(&self) -> Option<TableData<'_>> {
+        self.table().offset16(6)
+    }
+
+    // The MathValueRecord for a glyph in a table of a coverage offset,
+    // a count and the records.
+    fn glyph_value(table: Option<TableData>, glyph: u16) -> Option<i16> {
+        let table = table?;
+        let index = coverage_index(table.offset16(0)?, glyph)? as usize;
+        if index >= table.u16(2)? as usize {
+            return None;
+        }
+        table.i16(4 + index * 4)
+    }
+
+    // How far to move what follows a slanted glyph, like an italic f,
+    // when it isn't a subscript.
+    pub fn italics_correction(&self, glyph: u16) -> Option<i16> {
+        MathTable::glyph_value(self.glyph_info().and_then(|i| i.offset16(0)), glyph)
+    }
+
+    // Where accents over the glyph are centered; None for the middle of
+    // its advance.
+    pub fn top_accent_attachment(&self, glyph: u16) -> Option<i16> {
+        MathTable::glyph_value(self.glyph_info().and_then(|i| i.offset16(2)), glyph)
+    }
+
+    // Whether the glyph's ascent or descent can be bigger than its
+    // size suggests, like a big operator's.
+    pub fn is_extended_shape(&self, glyph: u16) -> bool {
+        self.glyph_info()
+            .and_then(|i| i.offset16(4))
+            .and_then(|coverage| coverage_index(coverage, glyph))
+            .is_some()
+    }
+
+    // The kerning at a corner of a glyph for a script whose nearest
+    // corner is `height` above the baseline.
+    pub fn kerning(&self, glyph: u16, corner: MathKernCorner, height: i32) -> i32 {
+        self.kern_value(glyph, corner, height).unwrap_or(0)
+    }
+
+    fn kern_value(&self, glyph: u16, corner: MathKernCorner, height: i32) -> Option<i32> {
+        let info = self.glyph_info()?.offset16(6)?;
+        let index = coverage_index(info.offset16(0)?, glyph)? as usize;
+        if index >= info.u16(2)? as usize {
+            return None;
+        }
+        let kern = info.offset16(4 + index * 8 + corner as usize * 2)?;
+        // The heights split the glyph into bands, with a kern for each.
+        let count = kern.u16(0)? as usize;
+        let mut band = count;
+        for i in 0..count {
+            if height < kern.i16(2 + i * 4)? as i32 {
+                band = i;
+                break;
+            }
+        }
+        kern.i16(2 + (count + band) * 4).map(|v| v as i32)
+    }
+
+    fn variants(&self) -> Option<TableData<'_>> {
+        self.table().offset16(8)
+    }
+
+    // How much parts of an assembly overlap at least.
+    pub fn min_connector_overlap(&self) -> u16 {
+        self.variants().and_then(|v| v.u16(0)).unwrap_or(0)
+    }
+
+    fn glyph_construction(&self, glyph: u16, vertical: bool) -> Option<TableData<'_>> {
+        let variants = self.variants()?;
+        let vertical_count = variants.u16(6)? as usize;
+        let (coverage, count, first) = if vertical {
+            (variants.offset16(2)?, vertical_count, 10)
+        } else {
+            (variants.offset16(4)?, variants.u16(8)? as usize, 10 + vertical_count * 2)
+        };
+        let index = coverage_index(coverage, glyph)? as usize;
+        if index >= count {
+            return None;
+        }
+        variants.offset16(first + index * 2)
+    }
+
+    // The glyph's forms from smallest to biggest, growing vertically or
+    // horizontally, starting with the glyph itself.
+    pub fn glyph_variants(&self, glyph: u16, vertical: bool) -> Vec<GlyphVariant> {
+        let construction = match self.glyph_construction(glyph, vertical) {
+            Some(c) => c,
+            None => return vec![],
+        };
+        let count = construction.u16(2).unwrap_or(0) as usize;
+        (0..count).filter_map(|i| {
+            Some(GlyphVariant { glyph: construction.u16(4 + i * 4)?, advance: construction.u16(6 + i * 4)? })
+        }).collect()
+    }
+
+    pub fn glyph_assembly(&self, glyph: u16, vertical: bool) -> Option<GlyphAssembly> {
+        let assembly = self.glyph_construction(glyph, vertical)?.offset16(0)?;
+        let count = assembly.u16(4)? as usize;
+        let mut parts = vec![];
+        for i in 0..count {
+            let part = 6 + i * 10;
+            parts.push(GlyphPart {
+                glyph: assembly.u16(part)?,
+                start_connector_length: assembly.u16(part + 2)?,
+                end_connector_length: assembly.u16(part + 4)?,
+                full_advance: assembly.u16(part + 6)?,
+                is_extender: assembly.u16(part + 8)? & 0x1 != 0,
+            });
+        }
+        Some(GlyphAssembly { italics_correction: assembly.i16(0)?, parts: parts })
+    }
+
+    // The parts of an assembly to reach `size`, with the overlap
+    // between each two, which is the same for all of them.  Extenders
+    // are all repeated as often as needed, and the overlap made as big
+    // as the connectors allow so the size isn't overshot.
+    fn assembly_parts(&self, assembly: &GlyphAssembly, size: i32) -> (Vec<GlyphPart>, i32) {
+        let min_overlap = self.min_connector_overlap() as i32;
+        let mut repeats = 0;
+        loop {
+            let parts: Vec<GlyphPart> = assembly.parts.iter().flat_map(|part| {
+                let count = if part.is_extender { repeats } else { 1 };
+                (0..count).map(move |_| *part)
+            }).collect();
+            let total: i32 = parts.iter().map(|p| p.full_advance as i32).sum();
+            let joins = parts.len().saturating_sub(1) as i32;
+            let grows = assembly.parts.iter().any(|p| p.is_extender && p.full_advance as i32 > min_overlap);
+            if total - joins * min_overlap >= size || !grows || repeats == MAX_EXTENDER_REPEATS {
+                if joins == 0 {
+                    return (parts, 0);
+                }
+                let max_overlap = parts.windows(2)
+                                       .map(|pair| cmp::min(pair[0].end_connector_length,
+                                                            pair[1].start_connector_length) as i32)
+                                       .min()
+                                       .unwrap()
+                                       .max(min_overlap);
+                let overlap = ((total - size) / joins).max(min_overlap).min(max_overlap);
+                return (parts, overlap);
+            }
+            repeats += 1;
+        }
+    }
+
+    // Grows a glyph to at least `size` DIPs at `em_size`, vertically or
+    // horizontally: the first variant that big, or else the glyph
+    // assembled from its parts, or else its biggest variant.
+    pub fn stretch_glyph(&self, face: &FontFace, glyph: u16, vertical: bool, size: f32, em_size: f32)
+                         -> StretchedGlyph {
+        let scale = em_size / face.metrics().designUnitsPerEm as f32;
+        let design_size = (size / scale).ceil() as i32;
+        let advance_width = |glyph: u16| face.get_design_glyph_metrics(&[glyph], false)[0].advanceWidth as f32 * scale;
+        let zero_offset = GlyphOffset { advanceOffset: 0., ascenderOffset: 0. };
+
+        let mut variants = self.glyph_variants(glyph, vertical);
+        if variants.is_empty() {
+            // A glyph without variants is as big as it is.
+            let m = face.get_design_glyph_metrics(&[glyph], false)[0];
+            let advance = if vertical {
+                m.advanceHeight as i32 - m.topSideBearing - m.bottomSideBearing
+            } else {
+                m.advanceWidth as i32
+            };
+            variants.push(GlyphVariant { glyph: glyph, advance: advance.max(0) as u16 });
+        }
+        let variant = variants.iter().find(|v| v.advance as i32 >= design_size);
+        let assembly = match variant {
+            Some(_) => None,
+            None => self.glyph_assembly(glyph, vertical),
+        };
+        let assembly = match assembly {
+            Some(ref assembly) if !assembly.parts.is_empty() => assembly.clone(),
+            _ => {
+                let variant = *variant.unwrap_or(variants.last().unwrap());
+                return StretchedGlyph {
+                    glyph_indices: vec![variant.glyph],
+                    glyph_advances: vec![advance_width(variant.glyph)],
+                    glyph_offsets: vec![zero_offset],
+                    em_size: em_size,
+                    size: variant.advance as f32 * scale,
+                    italics_correction: self.italics_correction(variant.glyph).unwrap_or(0) as f32 * scale,
+                };
+            }
+        };
+
+        let (parts, overlap) = self.assembly_parts(&assembly, design_size);
+        let mut stretched = StretchedGlyph {
+            glyph_indices: parts.iter().map(|p| p.glyph).collect(),
+            glyph_advances: vec![],
+            glyph_offsets: vec![],
+            em_size: em_size,
+            size: 0.,
+            italics_correction: assembly.italics_correction as f32 * scale,
+        };
+        let mut position = 0;
+        for (i, part) in parts.iter().enumerate() {
+            let step = if i + 1 < parts.len() { part.full_advance as i32 - overlap } else { part.full_advance as i32 };
+            if vertical {
+                // The parts are drawn in one place, each raised above
+                // the one before.
+                stretched.glyph_advances.push(0.);
+                stretched.glyph_offsets.push(GlyphOffset {
+                    advanceOffset: 0.,
+                    ascenderOffset: position as f32 * scale,
+                });
+            } else {
+                stretched.glyph_advances.push(step as f32 * scale);
+                stretched.glyph_offsets.push(zero_offset);
+            }
+            position += step;
+        }
+        if vertical {
+            let width = parts.iter().map(|p| advance_width(p.glyph)).fold(0., f32::max);
+            *stretched.glyph_advances.last_mut().unwrap() = width;
+        }
+        stretched.size = position as f32 * scale;
+        stretched
+    }
+}
@@ -0,0 +1,77 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use ot_parse::TableData;
+use super::FontFace;
+
+const PLATFORM_UNICODE: u16 = 0;
+const PLATFORM_MACINTOSH: u16 = 1;
+const PLATFORM_WINDOWS: u16 = 3;
+
+const WINDOWS_ENGLISH_US: u16 = 0x0409;
+
+#[derive(Debug)]
+pub struct NameTable {
+    data: Vec<u8>,
+}
+
+impl NameTable {
+    pub fn new(data: Vec<u8>) -> Option<NameTable> {
+        {
+            let t = TableData::new(&data);
+            let count = t.u16(2)? as usize;
+            if t.len() < 6 + count * 12 {
+                return None;
+            }
+        }
+        Some(NameTable { data: data })
+    }
+
+    pub fn from_font_face(face: &FontFace) -> Option<NameTable> {
+        face.get_font_table(ot_tag!(b"name")).and_then(NameTable::new)
+    }
+
+    // Looks up a name, preferring US English Windows names, then any
+    // Windows name, then Unicode platform names, then Mac Roman ones.
+    pub fn get_name(&self, name_id: u16) -> Option<String> {
+        let t = TableData::new(&self.data);
+        let count = t.u16(2)? as usize;
+        let storage = t.u16(4)? as usize;
+
+        let mut best: Option<(u32, usize)> = None;
+        for i in 0..count {
+            let rec = 6 + i * 12;
+            if t.u16(rec + 6)? != name_id {
+                continue;
+            }
+            let platform = t.u16(rec)?;
+            let encoding = t.u16(rec + 2)?;
+            let language = t.u16(rec + 4)?;
+            let rank = match (platform, encoding, language) {
+                (PLATFORM_WINDOWS, 1, WINDOWS_ENGLISH_US) | (PLATFORM_WINDOWS, 10, WINDOWS_ENGLISH_US) => 0,
+                (PLATFORM_WINDOWS, 1, _) | (PLATFORM_WINDOWS, 10, _) => 1,
+                (PLATFORM_UNICODE, _, _) => 2,
+                (PLATFORM_MACINTOSH, 0, 0) => 3,
+                _ => continue,
+            };
+            if best.map_or(true, |(r, _)| rank < r) {
+                best = Some((rank, rec));
+            }
+        }
+
+        let (rank, rec) = best?;
+        let length = t.u16(rec + 8)? as usize;
+        let offset = storage + t.u16(rec + 10)? as usize;
+        let bytes = t.bytes().get(offset..offset + length)?;
+        if rank == 3 {
+            // Only the ASCII subset of Mac Roman is decoded faithfully.
+            return Some(bytes.iter().map(|&b| if b < 0x80 { b as char } else { '\u{FFFD}' }).collect());
+        }
+        let units: Vec<u16> = bytes.chunks(2)
+            .filter(|c| c.len() == 2)
+            .map(|c| ((c[0] as u16) << 8) | c[1] as u16)
+            .collect();
+        Some(String::from_utf16_lossy(&units))
+    }
+}
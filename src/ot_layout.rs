@@ -0,0 +1,734 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Pieces of OpenType Layout that are shared between GSUB and GPOS:
+// the script/feature/lookup lists, coverage and class definition
+// tables, lookup flags, and (chained) sequence context matching.
+
+use ot_parse::TableData;
+use glyph_buffer::{GlyphBuffer, GlyphInfo, GlyphClass};
+use gdef::GdefTable;
+
+pub const LOOKUP_FLAG_RIGHT_TO_LEFT: u16 = 0x0001;
+pub const LOOKUP_FLAG_IGNORE_BASE_GLYPHS: u16 = 0x0002;
+pub const LOOKUP_FLAG_IGNORE_LIGATURES: u16 = 0x0004;
+pub const LOOKUP_FLAG_IGNORE_MARKS: u16 = 0x0008;
+pub const LOOKUP_FLAG_USE_MARK_FILTERING_SET: u16 = 0x0010;
+pub const LOOKUP_FLAG_MARK_ATTACHMENT_TYPE: u16 = 0xFF00;
+
+// 'DFLT' and 'dflt'
+pub const DEFAULT_SCRIPT: u32 = ot_tag!(b"DFLT");
+pub const DEFAULT_LANGUAGE: u32 = ot_tag!(b"dflt");
+
+// Deeply nested contextual lookups are legal but a malicious font
+// could recurse forever, so cap it like everyone else does.
+pub const MAX_NESTING_LEVEL: u32 = 6;
+
+pub fn coverage_index(coverage: TableData, glyph: u16) -> Option<u16> {
+    match coverage.u16(0)? {
+        1 => {
+            let count = coverage.u16(2)? as usize;
+            let (mut lo, mut hi) = (0, count);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let g = coverage.u16(4 + mid * 2)?;
+                if g < glyph {
+                    lo = mid + 1;
+                } else if g > glyph {
+                    hi = mid;
+                } else {
+                    return Some(mid as u16);
+                }
+            }
+            None
+        }
+        2 => {
+            let count = coverage.u16(2)? as usize;
+            let (mut lo, mut hi) = (0, count);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let rec = 4 + mid * 6;
+                let start = coverage.u16(rec)?;
+                let end = coverage.u16(rec + 2)?;
+                if end < glyph {
+                    lo = mid + 1;
+                } else if start > glyph {
+                    hi = mid;
+                } else {
+                    let start_index = coverage.u16(rec + 4)?;
+                    return Some(start_index.wrapping_add(glyph - start));
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+// Glyphs not mentioned in a class definition table are in class 0,
+// and a missing table puts everything in class 0.
+pub fn class_value(class_def: Option<TableData>, glyph: u16) -> u16 {
+    let class_def = match class_def {
+        Some(cd) => cd,
+        None => return 0,
+    };
+    let value = match class_def.u16(0) {
+        Some(1) => {
+            let start = class_def.u16(2).unwrap_or(0);
+            let count = class_def.u16(4).unwrap_or(0);
+            if glyph >= start && ((glyph - start) as usize) < count as usize {
+                class_def.u16(6 + (glyph - start) as usize * 2)
+            } else {
+                None
+            }
+        }
+        Some(2) => {
+            let count = class_def.u16(2).unwrap_or(0) as usize;
+            let (mut lo, mut hi) = (0, count);
+            let mut found = None;
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let rec = 4 + mid * 6;
+                let (start, end) = match (class_def.u16(rec), class_def.u16(rec + 2)) {
+                    (Some(s), Some(e)) => (s, e),
+                    _ => break,
+                };
+                if end < glyph {
+                    lo = mid + 1;
+                } else if start > glyph {
+                    hi = mid;
+                } else {
+                    found = class_def.u16(rec + 4);
+                    break;
+                }
+            }
+            found
+        }
+        _ => None,
+    };
+    value.unwrap_or(0)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Lookup<'a> {
+    pub lookup_type: u16,
+    pub flags: u16,
+    pub mark_filtering_set: Option<u16>,
+    data: TableData<'a>,
+    subtable_count: usize,
+    extension_type: u16,
+}
+
+impl<'a> Lookup<'a> {
+    fn parse(data: TableData<'a>, extension_type: u16) -> Option<Lookup<'a>> {
+        let mut lookup_type = data.u16(0)?;
+        let flags = data.u16(2)?;
+        let subtable_count = data.u16(4)? as usize;
+        let mark_filtering_set = if flags & LOOKUP_FLAG_USE_MARK_FILTERING_SET != 0 {
+            data.u16(6 + subtable_count * 2)
+        } else {
+            None
+        };
+
+        // An extension lookup has the type of the subtables it wraps;
+        // all of them are required to agree.
+        if lookup_type == extension_type && subtable_count > 0 {
+            let ext = data.offset16(6)?;
+            lookup_type = ext.u16(2)?;
+        }
+
+        Some(Lookup {
+            lookup_type: lookup_type,
+            flags: flags,
+            mark_filtering_set: mark_filtering_set,
+            data: data,
+            subtable_count: subtable_count,
+            extension_type: extension_type,
+        })
+    }
+
+    pub fn subtable_count(&self) -> usize {
+        self.subtable_count
+    }
+
+    pub fn subtable(&self, index: usize) -> Option<TableData<'a>> {
+        if index >= self.subtable_count {
+            return None;
+        }
+        let subtable = self.data.offset16(6 + index * 2)?;
+        if self.data.u16(0) == Some(self.extension_type) {
+            if subtable.u16(0)? != 1 {
+                return None;
+            }
+            return subtable.offset32(4);
+        }
+        Some(subtable)
+    }
+
+    // The mark filtering set, if any, comes from GDEF.
+    pub fn filter<'b>(&self, gdef: Option<&'b GdefTable>) -> LookupFilter<'b> {
+        let mark_glyph_set = match (self.mark_filtering_set, gdef) {
+            (Some(set), Some(gdef)) => gdef.mark_glyph_set(set),
+            _ => None,
+        };
+        LookupFilter {
+            flags: self.flags,
+            mark_glyph_set: mark_glyph_set,
+        }
+    }
+}
+
+// The common header of GSUB and GPOS.
+#[derive(Debug)]
+pub struct LayoutTable {
+    data: Vec<u8>,
+    extension_type: u16,
+}
+
+impl LayoutTable {
+    pub fn new(data: Vec<u8>, extension_type: u16) -> Option<LayoutTable> {
+        {
+            let t = TableData::new(&data);
+            if t.u16(0)? != 1 {
+                return None;
+            }
+            t.u16(8)?;
+        }
+        Some(LayoutTable {
+            data: data,
+            extension_type: extension_type,
+        })
+    }
+
+    pub fn data(&self) -> TableData<'_> {
+        TableData::new(&self.data)
+    }
+
+    fn script_list(&self) -> Option<TableData<'_>> {
+        self.data().offset16(4)
+    }
+
+    fn feature_list(&self) -> Option<TableData<'_>> {
+        self.data().offset16(6)
+    }
+
+    fn lookup_list(&self) -> Option<TableData<'_>> {
+        self.data().offset16(8)
+    }
+
+    pub fn script_tags(&self) -> Vec<u32> {
+        let list = match self.script_list() {
+            Some(l) => l,
+            None => return vec![],
+        };
+        let count = list.u16(0).unwrap_or(0) as usize;
+        (0..count).filter_map(|i| list.tag(2 + i * 6)).collect()
+    }
+
+    pub fn script(&self, script_tag: u32) -> Option<TableData<'_>> {
+        let list = self.script_list()?;
+        let count = list.u16(0)? as usize;
+        for i in 0..count {
+            if list.tag(2 + i * 6)? == script_tag {
+                return list.offset16(2 + i * 6 + 4);
+            }
+        }
+        None
+    }
+
+    pub fn language_tags(&self, script_tag: u32) -> Vec<u32> {
+        let script = match self.script(script_tag) {
+            Some(s) => s,
+            None => return vec![],
+        };
+        let count = script.u16(2).unwrap_or(0) as usize;
+        (0..count).filter_map(|i| script.tag(4 + i * 6)).collect()
+    }
+
+    // Finds the LangSys table for the given script and language,
+    // falling back to the script's default LangSys when the language
+    // isn't listed.
+    pub fn lang_sys(&self, script_tag: u32, language_tag: u32) -> Option<TableData<'_>> {
+        let script = self.script(script_tag)?;
+        if language_tag != DEFAULT_LANGUAGE {
+            let count = script.u16(2)? as usize;
+            for i in 0..count {
+                if script.tag(4 + i * 6)? == language_tag {
+                    return script.offset16(4 + i * 6 + 4);
+                }
+            }
+        }
+        script.offset16(0)
+    }
+
+    // Picks the script to use for shaping: the requested one if
+    // present, otherwise 'DFLT', otherwise 'latn', which is what
+    // most engines end up doing for fonts with incomplete script
+    // lists.
+    pub fn select_script(&self, script_tags: &[u32]) -> Option<u32> {
+        for &tag in script_tags.iter().chain([DEFAULT_SCRIPT, ot_tag!(b"dflt"), ot_tag!(b"latn")].iter()) {
+            if self.script(tag).is_some() {
+                return Some(tag);
+            }
+        }
+        None
+    }
+
+    // Returns (required feature index, other feature indices) for a
+    // language system.
+    pub fn feature_indices(&self, script_tag: u32, language_tag: u32) -> (Option<u16>, Vec<u16>) {
+        let lang_sys = match self.lang_sys(script_tag, language_tag) {
+            Some(l) => l,
+            None => return (None, vec![]),
+        };
+        let required = match lang_sys.u16(2) {
+            Some(0xFFFF) | None => None,
+            Some(index) => Some(index),
+        };
+        let count = lang_sys.u16(4).unwrap_or(0) as usize;
+        (required, lang_sys.u16_array(6, count).unwrap_or(vec![]))
+    }
+
+    pub fn feature_count(&self) -> u16 {
+        self.feature_list().and_then(|l| l.u16(0)).unwrap_or(0)
+    }
+
+    pub fn feature_tag(&self, feature_index: u16) -> Option<u32> {
+        self.feature_list()?.tag(2 + feature_index as usize * 6)
+    }
+
+    pub fn feature(&self, feature_index: u16) -> Option<TableData<'_>> {
+        let list = self.feature_list()?;
+        if feature_index >= list.u16(0)? {
+            return None;
+        }
+        list.offset16(2 + feature_index as usize * 6 + 4)
+    }
+
+    pub fn feature_lookups(&self, feature_index: u16) -> Vec<u16> {
+        let feature = match self.feature(feature_index) {
+            Some(f) => f,
+            None => return vec![],
+        };
+        let count = feature.u16(2).unwrap_or(0) as usize;
+        feature.u16_array(4, count).unwrap_or(vec![])
+    }
+
+    // All of the lookups that implement `feature_tag` for the given
+    // script and language, sorted and deduplicated.
+    pub fn lookups_for_feature(&self, script_tag: u32, language_tag: u32, feature_tag: u32) -> Vec<u16> {
+        let (required, indices) = self.feature_indices(script_tag, language_tag);
+        let mut lookups = vec![];
+        for index in required.into_iter().chain(indices.into_iter()) {
+            if self.feature_tag(index) == Some(feature_tag) {
+                lookups.extend(self.feature_lookups(index));
+            }
+        }
+        lookups.sort();
+        lookups.dedup();
+        lookups
+    }
+
+    pub fn lookup_count(&self) -> u16 {
+        self.lookup_list().and_then(|l| l.u16(0)).unwrap_or(0)
+    }
+
+    pub fn lookup(&self, lookup_index: u16) -> Option<Lookup<'_>> {
+        let list = self.lookup_list()?;
+        if lookup_index >= list.u16(0)? {
+            return None;
+        }
+        Lookup::parse(list.offset16(2 + lookup_index as usize * 2)?, self.extension_type)
+    }
+}
+
+// Decides which glyphs a lookup can "see", based on the lookup flags
+// and the glyph classes stored in the buffer.
+#[derive(Clone, Copy, Debug)]
+pub struct LookupFilter<'a> {
+    pub flags: u16,
+    // Coverage table of the lookup's mark filtering set.
+    pub mark_glyph_set: Option<TableData<'a>>,
+}
+
+impl<'a> LookupFilter<'a> {
+    pub fn skips(&self, info: &GlyphInfo) -> bool {
+        match info.glyph_class {
+            GlyphClass::Base => self.flags & LOOKUP_FLAG_IGNORE_BASE_GLYPHS != 0,
+            GlyphClass::Ligature => self.flags & LOOKUP_FLAG_IGNORE_LIGATURES != 0,
+            GlyphClass::Mark => {
+                if self.flags & LOOKUP_FLAG_IGNORE_MARKS != 0 {
+                    return true;
+                }
+                if self.flags & LOOKUP_FLAG_USE_MARK_FILTERING_SET != 0 {
+                    return match self.mark_glyph_set {
+                        Some(set) => coverage_index(set, info.glyph_index).is_none(),
+                        None => false,
+                    };
+                }
+                let attachment_type = (self.flags & LOOKUP_FLAG_MARK_ATTACHMENT_TYPE) >> 8;
+                attachment_type != 0 && attachment_type != info.mark_attachment_class as u16
+            }
+            _ => false,
+        }
+    }
+}
+
+// Whether context matching may go from the glyph at `from` to the one
+// at `to`.
+fn same_syllable(buffer: &GlyphBuffer, from: usize, to: usize) -> bool {
+    !buffer.restrict_to_syllable || from >= buffer.len() ||
+        buffer.glyphs[from].syllable == buffer.glyphs[to].syllable
+}
+
+// The index of the next glyph after `index` that the lookup doesn't
+// skip, if any.
+pub fn next_unskipped(buffer: &GlyphBuffer, filter: &LookupFilter, index: usize) -> Option<usize> {
+    let mut i = index + 1;
+    while i < buffer.len() && same_syllable(buffer, index, i) {
+        if !filter.skips(&buffer.glyphs[i]) {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+pub fn prev_unskipped(buffer: &GlyphBuffer, filter: &LookupFilter, index: usize) -> Option<usize> {
+    let mut i = index;
+    while i > 0 && same_syllable(buffer, index, i - 1) {
+        i -= 1;
+        if !filter.skips(&buffer.glyphs[i]) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+// How the values in a rule's sequence are to be compared with glyphs.
+#[derive(Clone, Copy)]
+enum SequenceMatch<'a> {
+    Glyph,
+    Class(Option<TableData<'a>>),
+    // The value is an offset to a coverage table relative to the
+    // given subtable.
+    Coverage(TableData<'a>),
+}
+
+impl<'a> SequenceMatch<'a> {
+    fn matches(&self, value: u16, glyph: u16) -> bool {
+        match *self {
+            SequenceMatch::Glyph => value == glyph,
+            SequenceMatch::Class(class_def) => class_value(class_def, glyph) == value,
+            SequenceMatch::Coverage(base) => {
+                base.sub(value as usize).and_then(|c| coverage_index(c, glyph)).is_some()
+            }
+        }
+    }
+}
+
+// The result of matching a (chained) sequence context rule.
+#[derive(Debug)]
+pub struct ContextMatch {
+    // Buffer positions of each input glyph, starting with the glyph
+    // the match started at.
+    pub positions: Vec<usize>,
+    // (sequence index, lookup index) pairs to apply.
+    pub records: Vec<(u16, u16)>,
+}
+
+// Matches `values` (the input sequence minus its first glyph) forward
+// from `start`, returning the positions of all input glyphs.
+fn match_input(buffer: &GlyphBuffer, filter: &LookupFilter, mask: u32, start: usize,
+               values: &[u16], how: SequenceMatch) -> Option<Vec<usize>> {
+    let mut positions = Vec::with_capacity(values.len() + 1);
+    positions.push(start);
+    let mut index = start;
+    for &value in values {
+        index = next_unskipped(buffer, filter, index)?;
+        let info = &buffer.glyphs[index];
+        if info.mask & mask == 0 || !how.matches(value, info.glyph_index) {
+            return None;
+        }
+        positions.push(index);
+    }
+    Some(positions)
+}
+
+fn match_backtrack(buffer: &GlyphBuffer, filter: &LookupFilter, start: usize,
+                   values: &[u16], how: SequenceMatch) -> bool {
+    let mut index = start;
+    for &value in values {
+        index = match prev_unskipped(buffer, filter, index) {
+            Some(i) => i,
+            None => return false,
+        };
+        if !how.matches(value, buffer.glyphs[index].glyph_index) {
+            return false;
+        }
+    }
+    true
+}
+
+fn match_lookahead(buffer: &GlyphBuffer, filter: &LookupFilter, end: usize,
+                   values: &[u16], how: SequenceMatch) -> bool {
+    let mut index = end;
+    for &value in values {
+        index = match next_unskipped(buffer, filter, index) {
+            Some(i) => i,
+            None => return false,
+        };
+        if !how.matches(value, buffer.glyphs[index].glyph_index) {
+            return false;
+        }
+    }
+    true
+}
+
+// Matches the glyphs before `index` against a list of coverage table
+// offsets relative to `base`, as used by reverse chaining and format 3
+// chained context subtables.
+pub fn match_backtrack_coverages(buffer: &GlyphBuffer, filter: &LookupFilter, index: usize,
+                                 base: TableData, offsets: &[u16]) -> bool {
+    match_backtrack(buffer, filter, index, offsets, SequenceMatch::Coverage(base))
+}
+
+pub fn match_lookahead_coverages(buffer: &GlyphBuffer, filter: &LookupFilter, index: usize,
+                                 base: TableData, offsets: &[u16]) -> bool {
+    match_lookahead(buffer, filter, index, offsets, SequenceMatch::Coverage(base))
+}
+
+// Matches a plain glyph sequence after `start`, as used by ligature
+// substitution.
+pub fn match_glyph_sequence(buffer: &GlyphBuffer, filter: &LookupFilter, mask: u32, start: usize,
+                            glyphs: &[u16]) -> Option<Vec<usize>> {
+    match_input(buffer, filter, mask, start, glyphs, SequenceMatch::Glyph)
+}
+
+fn lookup_records(data: TableData, offset: usize, count: usize) -> Option<Vec<(u16, u16)>> {
+    let raw = data.u16_array(offset, count * 2)?;
+    Some(raw.chunks(2).map(|r| (r[0], r[1])).collect())
+}
+
+// SequenceRule: glyphCount, seqLookupCount, inputSequence[glyphCount - 1],
+// seqLookupRecords[seqLookupCount]
+fn match_sequence_rule(rule: TableData, buffer: &GlyphBuffer, filter: &LookupFilter, mask: u32,
+                       index: usize, how: SequenceMatch) -> Option<ContextMatch> {
+    let glyph_count = rule.u16(0)? as usize;
+    let lookup_count = rule.u16(2)? as usize;
+    if glyph_count == 0 {
+        return None;
+    }
+    let input = rule.u16_array(4, glyph_count - 1)?;
+    let positions = match_input(buffer, filter, mask, index, &input, how)?;
+    let records = lookup_records(rule, 4 + (glyph_count - 1) * 2, lookup_count)?;
+    Some(ContextMatch { positions: positions, records: records })
+}
+
+// ChainedSequenceRule: backtrackGlyphCount, backtrackSequence[],
+// inputGlyphCount, inputSequence[inputGlyphCount - 1],
+// lookaheadGlyphCount, lookaheadSequence[], seqLookupCount,
+// seqLookupRecords[]
+fn match_chained_sequence_rule(rule: TableData, buffer: &GlyphBuffer, filter: &LookupFilter, mask: u32,
+                               index: usize, backtrack_how: SequenceMatch, input_how: SequenceMatch,
+                               lookahead_how: SequenceMatch) -> Option<ContextMatch> {
+    let mut offset = 0;
+    let backtrack_count = rule.u16(offset)? as usize;
+    let backtrack = rule.u16_array(offset + 2, backtrack_count)?;
+    offset += 2 + backtrack_count * 2;
+    let input_count = rule.u16(offset)? as usize;
+    if input_count == 0 {
+        return None;
+    }
+    let input = rule.u16_array(offset + 2, input_count - 1)?;
+    offset += 2 + (input_count - 1) * 2;
+    let lookahead_count = rule.u16(offset)? as usize;
+    let lookahead = rule.u16_array(offset + 2, lookahead_count)?;
+    offset += 2 + lookahead_count * 2;
+    let lookup_count = rule.u16(offset)? as usize;
+
+    let positions = match_input(buffer, filter, mask, index, &input, input_how)?;
+    if !match_backtrack(buffer, filter, index, &backtrack, backtrack_how) {
+        return None;
+    }
+    if !match_lookahead(buffer, filter, *positions.last().unwrap(), &lookahead, lookahead_how) {
+        return None;
+    }
+    let records = lookup_records(rule, offset + 2, lookup_count)?;
+    Some(ContextMatch { positions: positions, records: records })
+}
+
+// Tries each rule in a rule set in order, returning the first match.
+fn match_rule_set<F>(rule_set: TableData, mut f: F) -> Option<ContextMatch>
+    where F: FnMut(TableData) -> Option<ContextMatch>
+{
+    let count = rule_set.u16(0)? as usize;
+    for i in 0..count {
+        if let Some(rule) = rule_set.offset16(2 + i * 2) {
+            if let Some(m) = f(rule) {
+                return Some(m);
+            }
+        }
+    }
+    None
+}
+
+// Sequence Context subtables (GSUB type 5, GPOS type 7).
+pub fn match_sequence_context(subtable: TableData, buffer: &GlyphBuffer, filter: &LookupFilter,
+                              mask: u32, index: usize) -> Option<ContextMatch> {
+    let glyph = buffer.glyphs[index].glyph_index;
+    match subtable.u16(0)? {
+        1 => {
+            let coverage = coverage_index(subtable.offset16(2)?, glyph)?;
+            if coverage >= subtable.u16(4)? {
+                return None;
+            }
+            let rule_set = subtable.offset16(6 + coverage as usize * 2)?;
+            match_rule_set(rule_set, |rule| {
+                match_sequence_rule(rule, buffer, filter, mask, index, SequenceMatch::Glyph)
+            })
+        }
+        2 => {
+            coverage_index(subtable.offset16(2)?, glyph)?;
+            let class_def = subtable.offset16(4);
+            let class = class_value(class_def, glyph);
+            if class >= subtable.u16(6)? {
+                return None;
+            }
+            let rule_set = subtable.offset16(8 + class as usize * 2)?;
+            match_rule_set(rule_set, |rule| {
+                match_sequence_rule(rule, buffer, filter, mask, index, SequenceMatch::Class(class_def))
+            })
+        }
+        3 => {
+            let glyph_count = subtable.u16(2)? as usize;
+            let lookup_count = subtable.u16(4)? as usize;
+            if glyph_count == 0 {
+                return None;
+            }
+            coverage_index(subtable.offset16(6)?, glyph)?;
+            let coverages = subtable.u16_array(8, glyph_count - 1)?;
+            let positions = match_input(buffer, filter, mask, index, &coverages,
+                                        SequenceMatch::Coverage(subtable))?;
+            let records = lookup_records(subtable, 6 + glyph_count * 2, lookup_count)?;
+            Some(ContextMatch { positions: positions, records: records })
+        }
+        _ => None,
+    }
+}
+
+// Chained Sequence Context subtables (GSUB type 6, GPOS type 8).
+pub fn match_chained_sequence_context(subtable: TableData, buffer: &GlyphBuffer, filter: &LookupFilter,
+                                      mask: u32, index: usize) -> Option<ContextMatch> {
+    let glyph = buffer.glyphs[index].glyph_index;
+    match subtable.u16(0)? {
+        1 => {
+            let coverage = coverage_index(subtable.offset16(2)?, glyph)?;
+            if coverage >= subtable.u16(4)? {
+                return None;
+            }
+            let rule_set = subtable.offset16(6 + coverage as usize * 2)?;
+            match_rule_set(rule_set, |rule| {
+                match_chained_sequence_rule(rule, buffer, filter, mask, index,
+                                            SequenceMatch::Glyph, SequenceMatch::Glyph, SequenceMatch::Glyph)
+            })
+        }
+        2 => {
+            coverage_index(subtable.offset16(2)?, glyph)?;
+            let backtrack_def = subtable.offset16(4);
+            let input_def = subtable.offset16(6);
+            let lookahead_def = subtable.offset16(8);
+            let class = class_value(input_def, glyph);
+            if class >= subtable.u16(10)? {
+                return None;
+            }
+            let rule_set = subtable.offset16(12 + class as usize * 2)?;
+            match_rule_set(rule_set, |rule| {
+                match_chained_sequence_rule(rule, buffer, filter, mask, index,
+                                            SequenceMatch::Class(backtrack_def),
+                                            SequenceMatch::Class(input_def),
+                                            SequenceMatch::Class(lookahead_def))
+            })
+        }
+        3 => {
+            let mut offset = 2;
+            let backtrack_count = subtable.u16(offset)? as usize;
+            let backtrack = subtable.u16_array(offset + 2, backtrack_count)?;
+            offset += 2 + backtrack_count * 2;
+            let input_count = subtable.u16(offset)? as usize;
+            if input_count == 0 {
+                return None;
+            }
+            let input = subtable.u16_array(offset + 2, input_count)?;
+            offset += 2 + input_count * 2;
+            let lookahead_count = subtable.u16(offset)? as usize;
+            let lookahead = subtable.u16_array(offset + 2, lookahead_count)?;
+            offset += 2 + lookahead_count * 2;
+            let lookup_count = subtable.u16(offset)? as usize;
+
+            let how = SequenceMatch::Coverage(subtable);
+            if !how.matches(input[0], glyph) {
+                return None;
+            }
+            let positions = match_input(buffer, filter, mask, index, &input[1..], how)?;
+            if !match_backtrack(buffer, filter, index, &backtrack, how) {
+                return None;
+            }
+            if !match_lookahead(buffer, filter, *positions.last().unwrap(), &lookahead, how) {
+                return None;
+            }
+            let records = lookup_records(subtable, offset + 2, lookup_count)?;
+            Some(ContextMatch { positions: positions, records: records })
+        }
+        _ => None,
+    }
+}
+
+// Applies the nested lookups of a matched context rule, keeping the
+// matched positions in sync as nested substitutions grow or shrink the
+// buffer.  Returns the buffer index just past the matched input.
+pub fn apply_context_records<F>(buffer: &mut GlyphBuffer, m: ContextMatch, mut apply: F) -> usize
+    where F: FnMut(&mut GlyphBuffer, u16, usize)
+{
+    let mut positions = m.positions;
+    let mut end = *positions.last().unwrap() as isize + 1;
+    for (sequence_index, lookup_index) in m.records {
+        let sequence_index = sequence_index as usize;
+        if sequence_index >= positions.len() {
+            continue;
+        }
+        let position = positions[sequence_index];
+        if position >= buffer.len() {
+            continue;
+        }
+
+        let old_len = buffer.len() as isize;
+        apply(buffer, lookup_index, position);
+        let delta = buffer.len() as isize - old_len;
+        if delta == 0 {
+            continue;
+        }
+
+        end += delta;
+        let next = sequence_index + 1;
+        if delta > 0 {
+            // A multiple substitution inserted glyphs right after
+            // `position`; they become part of the matched sequence.
+            for p in positions[next..].iter_mut() {
+                *p = (*p as isize + delta) as usize;
+            }
+            for i in 0..delta as usize {
+                positions.insert(next + i, position + 1 + i);
+            }
+        } else {
+            // A ligature consumed the glyphs that followed `position`.
+            let removed = ((-delta) as usize).min(positions.len() - next);
+            positions.drain(next..next + removed);
+            for p in positions[next..].iter_mut() {
+                *p = (*p as isize + delta).max(position as isize + 1) as usize;
+            }
+        }
+    }
+    end.max(0) as usize
+}
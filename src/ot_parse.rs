@@ -0,0 +1,113 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Minimal bounds-checked readers for big-endian OpenType table data.
+// Everything here returns None instead of panicking on truncated or
+// malformed tables, since font data comes from untrusted files.
+
+// Tags are kept in the same byte order that DWRITE_MAKE_OPENTYPE_TAG
+// uses, so that a tag read out of a table can be compared directly
+// with what gets passed to FontFace::get_font_table.
+macro_rules! ot_tag {
+    ($s:expr) => {
+        ($s[0] as u32) | (($s[1] as u32) << 8) | (($s[2] as u32) << 16) | (($s[3] as u32) << 24)
+    }
+}
+
+pub fn make_opentype_tag(a: u8, b: u8, c: u8, d: u8) -> u32 {
+    ot_tag!([a, b, c, d])
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TableData<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> TableData<'a> {
+    pub fn new(data: &'a [u8]) -> TableData<'a> {
+        TableData { data: data }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn u8(&self, offset: usize) -> Option<u8> {
+        self.data.get(offset).cloned()
+    }
+
+    pub fn u16(&self, offset: usize) -> Option<u16> {
+        if offset + 2 > self.data.len() {
+            return None;
+        }
+        Some(((self.data[offset] as u16) << 8) | self.data[offset + 1] as u16)
+    }
+
+    pub fn i16(&self, offset: usize) -> Option<i16> {
+        self.u16(offset).map(|v| v as i16)
+    }
+
+    pub fn u24(&self, offset: usize) -> Option<u32> {
+        if offset + 3 > self.data.len() {
+            return None;
+        }
+        Some(((self.data[offset] as u32) << 16) |
+             ((self.data[offset + 1] as u32) << 8) |
+             self.data[offset + 2] as u32)
+    }
+
+    pub fn u32(&self, offset: usize) -> Option<u32> {
+        if offset + 4 > self.data.len() {
+            return None;
+        }
+        Some(((self.data[offset] as u32) << 24) |
+             ((self.data[offset + 1] as u32) << 16) |
+             ((self.data[offset + 2] as u32) << 8) |
+             self.data[offset + 3] as u32)
+    }
+
+    pub fn tag(&self, offset: usize) -> Option<u32> {
+        if offset + 4 > self.data.len() {
+            return None;
+        }
+        Some(ot_tag!(&self.data[offset..offset + 4]))
+    }
+
+    // Returns the table data starting at `offset`, or None if the
+    // offset points outside of this table.
+    pub fn sub(&self, offset: usize) -> Option<TableData<'a>> {
+        if offset > self.data.len() {
+            return None;
+        }
+        Some(TableData { data: &self.data[offset..] })
+    }
+
+    // Follows a 16-bit offset stored at `at`.  A zero offset is
+    // treated as a missing subtable.
+    pub fn offset16(&self, at: usize) -> Option<TableData<'a>> {
+        match self.u16(at) {
+            Some(0) | None => None,
+            Some(offset) => self.sub(offset as usize),
+        }
+    }
+
+    pub fn offset32(&self, at: usize) -> Option<TableData<'a>> {
+        match self.u32(at) {
+            Some(0) | None => None,
+            Some(offset) => self.sub(offset as usize),
+        }
+    }
+
+    // Reads `count` u16 values starting at `offset`.
+    pub fn u16_array(&self, offset: usize, count: usize) -> Option<Vec<u16>> {
+        if offset + count * 2 > self.data.len() {
+            return None;
+        }
+        Some((0..count).map(|i| self.u16(offset + i * 2).unwrap()).collect())
+    }
+}
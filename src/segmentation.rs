@@ -0,0 +1,280 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Grapheme cluster and word boundaries, following UAX #29
+// (https://www.unicode.org/reports/tr29/), and the caret stops they
+// give a shaped run.  Positions are in UTF-16 code units, like the
+// shaper's cluster map, and both lists include the start and end of
+// the text.
+
+use shaping::ShapedGlyphs;
+use unicode_data::{self, GraphemeBreak, WordBreak};
+use word_dictionary;
+
+// A text position the caret can be put at, and how far along the run
+// it is drawn: from the left edge for left-to-right runs and from the
+// right edge for right-to-left ones.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct CaretStop {
+    pub position: usize,
+    pub advance: f32,
+}
+
+fn utf16_offsets(chars: &[char]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(chars.len() + 1);
+    let mut offset = 0;
+    for c in chars {
+        offsets.push(offset);
+        offset += c.len_utf16();
+    }
+    offsets.push(offset);
+    offsets
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Conjunct {
+    None,
+    // After a consonant, and maybe marks.
+    Consonant,
+    // After a consonant and a virama, which join it to the next one.
+    Linker,
+}
+
+pub fn grapheme_boundaries(text: &str) -> Vec<usize> {
+    use unicode_data::GraphemeBreak::*;
+
+    let chars: Vec<char> = text.chars().collect();
+    let offsets = utf16_offsets(&chars);
+    let mut boundaries = vec![0];
+    let mut previous = Other;
+    let mut regional_indicators = 0;
+    // Whether the text so far ends with an extended pictographic
+    // character and extending characters...
+    let mut after_pictographic = false;
+    // ... followed by a ZWJ.
+    let mut after_pictographic_zwj = false;
+    let mut conjunct = Conjunct::None;
+
+    for (i, &c) in chars.iter().enumerate() {
+        let cp = c as u32;
+        let current = unicode_data::grapheme_break(cp);
+        if i > 0 {
+            let no_break = match (previous, current) {
+                // GB3, GB4, GB5: CR LF stays together, and controls are
+                // on their own.
+                (CR, LF) => true,
+                (CR, _) | (LF, _) | (Control, _) | (_, CR) | (_, LF) | (_, Control) => false,
+                // GB6, GB7, GB8: Hangul syllables.
+                (L, L) | (L, V) | (L, LV) | (L, LVT) | (LV, V) | (LV, T) | (V, V) | (V, T) |
+                (LVT, T) | (T, T) => true,
+                // GB9, GB9a, GB9b: marks and prepended characters.
+                (_, Extend) | (_, ZWJ) | (_, SpacingMark) | (Prepend, _) => true,
+                // GB9c: consonants joined by a virama.
+                (_, InCBConsonant) if conjunct == Conjunct::Linker => true,
+                // GB11: emoji ZWJ sequences.
+                (ZWJ, ExtendedPictographic) => after_pictographic_zwj,
+                // GB12, GB13: pairs of regional indicators make flags.
+                (RegionalIndicator, RegionalIndicator) => regional_indicators % 2 == 1,
+                // GB999: break everywhere else.
+                _ => false,
+            };
+            if !no_break {
+                boundaries.push(offsets[i]);
+            }
+        }
+
+        regional_indicators = if current == RegionalIndicator { regional_indicators + 1 } else { 0 };
+        after_pictographic_zwj = current == ZWJ && after_pictographic;
+        after_pictographic = current == ExtendedPictographic || (current == Extend && after_pictographic);
+        conjunct = if current == InCBConsonant {
+            Conjunct::Consonant
+        } else if conjunct != Conjunct::None && unicode_data::is_incb_linker(cp) {
+            Conjunct::Linker
+        } else if conjunct != Conjunct::None && unicode_data::is_incb_extend(cp) {
+            conjunct
+        } else {
+            Conjunct::None
+        };
+        previous = current;
+    }
+
+    if !chars.is_empty() {
+        boundaries.push(offsets[chars.len()]);
+    }
+    boundaries
+}
+
+// Characters that WB4 attaches to the character before them.
+fn is_ignored(class: WordBreak) -> bool {
+    class == WordBreak::Extend || class == WordBreak::Format || class == WordBreak::ZWJ
+}
+
+fn is_ah_letter(class: WordBreak) -> bool {
+    class == WordBreak::ALetter || class == WordBreak::HebrewLetter
+}
+
+fn is_mid_letter(class: WordBreak) -> bool {
+    class == WordBreak::MidLetter || class == WordBreak::MidNumLet || class == WordBreak::SingleQuote
+}
+
+fn is_mid_num(class: WordBreak) -> bool {
+    class == WordBreak::MidNum || class == WordBreak::MidNumLet || class == WordBreak::SingleQuote
+}
+
+// Where words start and end, for selecting a word by double-clicking
+// it.  Spaces and punctuation are "words" of their own.  Runs of Thai,
+// Lao, Khmer and Burmese are split into words with the dictionary the
+// line breaking uses.
+pub fn word_boundaries(text: &str) -> Vec<usize> {
+    use unicode_data::WordBreak::*;
+
+    let chars: Vec<char> = text.chars().collect();
+    let offsets = utf16_offsets(&chars);
+    let classes: Vec<WordBreak> = chars.iter().map(|&c| unicode_data::word_break(c as u32)).collect();
+    let dictionary_breaks = word_dictionary::dictionary_breaks(text);
+
+    // The character a WB4 sequence ending at `i` belongs to, if any.
+    let base_of = |i: usize| -> Option<usize> {
+        let mut j = i;
+        while j > 0 && is_ignored(classes[j]) {
+            j -= 1;
+        }
+        match classes[j] {
+            CR | LF | Newline => None,
+            class if is_ignored(class) => None,
+            _ => Some(j),
+        }
+    };
+    // The class of the next character after `i` that WB4 doesn't skip.
+    let next_class = |i: usize| -> Option<WordBreak> {
+        classes[i + 1..].iter().cloned().find(|&c| !is_ignored(c))
+    };
+
+    let mut boundaries = vec![0];
+    for i in 1..chars.len() {
+        let (last, current) = (classes[i - 1], classes[i]);
+        let no_break = match (last, current) {
+            // WB3, WB3a, WB3b: CR LF stays together, and line breaks
+            // are on their own.
+            (CR, LF) => true,
+            (CR, _) | (LF, _) | (Newline, _) | (_, CR) | (_, LF) | (_, Newline) => false,
+            // WB3c: emoji ZWJ sequences.
+            (ZWJ, _) if unicode_data::grapheme_break(chars[i] as u32) == GraphemeBreak::ExtendedPictographic => true,
+            // WB3d: runs of spaces.
+            (WSegSpace, WSegSpace) => true,
+            // WB4: marks and format characters go with the character
+            // before them.
+            (_, Extend) | (_, Format) | (_, ZWJ) => true,
+            _ => {
+                // The rest look at characters ignoring what WB4 skips.
+                let a_index = base_of(i - 1);
+                let a = a_index.map_or(last, |j| classes[j]);
+                let before_a = a_index.and_then(|j| if j > 0 { base_of(j - 1) } else { None })
+                                      .map(|j| classes[j]);
+                let b = current;
+                let after_b = next_class(i);
+
+                // Dictionary words inside Thai, Lao, Khmer and Burmese
+                // runs.
+                if word_dictionary::uses_dictionary(chars[i - 1] as u32) &&
+                   word_dictionary::uses_dictionary(chars[i] as u32) &&
+                   unicode_data::script(chars[i - 1] as u32) == unicode_data::script(chars[i] as u32) {
+                    dictionary_breaks.binary_search(&offsets[i]).is_err()
+                } else {
+                    match (a, b) {
+                        // WB5: between letters.
+                        (a, b) if is_ah_letter(a) && is_ah_letter(b) => true,
+                        // WB7a: a Hebrew letter and an apostrophe.
+                        (HebrewLetter, SingleQuote) => true,
+                        // WB6, WB7: letters around an apostrophe or dot.
+                        (a, b) if is_ah_letter(a) && is_mid_letter(b) => after_b.map_or(false, is_ah_letter),
+                        (a, b) if is_mid_letter(a) && is_ah_letter(b) => before_a.map_or(false, is_ah_letter),
+                        // WB7b, WB7c: Hebrew letters around a quotation mark.
+                        (HebrewLetter, DoubleQuote) => after_b == Some(HebrewLetter),
+                        (DoubleQuote, HebrewLetter) => before_a == Some(HebrewLetter),
+                        // WB8, WB9, WB10: letters and digits.
+                        (Numeric, Numeric) => true,
+                        (a, Numeric) if is_ah_letter(a) => true,
+                        (Numeric, b) if is_ah_letter(b) => true,
+                        // WB11, WB12: digits around separators, like "3.14".
+                        (a, Numeric) if is_mid_num(a) => before_a == Some(Numeric),
+                        (Numeric, b) if is_mid_num(b) => after_b == Some(Numeric),
+                        // WB13, WB13a, WB13b: katakana and connectors.
+                        (Katakana, Katakana) => true,
+                        (a, ExtendNumLet) if is_ah_letter(a) || a == Numeric || a == Katakana ||
+                                             a == ExtendNumLet => true,
+                        (ExtendNumLet, b) if is_ah_letter(b) || b == Numeric || b == Katakana => true,
+                        // WB15, WB16: pairs of regional indicators.
+                        (RegionalIndicator, RegionalIndicator) => {
+                            let mut count = 0;
+                            let mut j = a_index;
+                            while let Some(k) = j {
+                                if classes[k] != RegionalIndicator {
+                                    break;
+                                }
+                                count += 1;
+                                j = if k > 0 { base_of(k - 1) } else { None };
+                            }
+                            count % 2 == 1
+                        }
+                        // WB999: break everywhere else.
+                        _ => false,
+                    }
+                }
+            }
+        };
+        if !no_break {
+            boundaries.push(offsets[i]);
+        }
+    }
+
+    if !chars.is_empty() {
+        boundaries.push(offsets[chars.len()]);
+    }
+    boundaries
+}
+
+// The caret stops of a shaped run of `text`: every grapheme boundary,
+// in text order.  A caret inside a glyph cluster, like a ligature of
+// several graphemes, is put at the matching fraction of the cluster's
+// advance.
+pub fn caret_stops(text: &str, glyphs: &ShapedGlyphs) -> Vec<CaretStop> {
+    let graphemes = grapheme_boundaries(text);
+    let text_len = glyphs.cluster_map.len();
+    let mut stops = Vec::with_capacity(graphemes.len());
+
+    let mut advance = 0.;
+    let mut cluster_start = 0;
+    let mut next_grapheme = 0;
+    while cluster_start < text_len {
+        let first_glyph = glyphs.cluster_map[cluster_start] as usize;
+        let mut cluster_end = cluster_start + 1;
+        while cluster_end < text_len && glyphs.cluster_map[cluster_end] as usize == first_glyph {
+            cluster_end += 1;
+        }
+        let end_glyph = if cluster_end < text_len {
+            glyphs.cluster_map[cluster_end] as usize
+        } else {
+            glyphs.glyph_advances.len()
+        };
+        let width: f32 = glyphs.glyph_advances[first_glyph..end_glyph].iter().sum();
+
+        let first = next_grapheme;
+        while next_grapheme < graphemes.len() && graphemes[next_grapheme] < cluster_end {
+            next_grapheme += 1;
+        }
+        let count = next_grapheme - first;
+        for (n, &position) in graphemes[first..next_grapheme].iter().enumerate() {
+            stops.push(CaretStop {
+                position: position,
+                advance: advance + width * n as f32 / count as f32,
+            });
+        }
+        advance += width;
+        cluster_start = cluster_end;
+    }
+
+    stops.push(CaretStop { position: text_len, advance: advance });
+    stops
+}
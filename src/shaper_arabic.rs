@@ -0,0 +1,293 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Shaping for Arabic and Syriac; the joining logic is also used by the
+// Universal Shaping Engine for the other cursively joining scripts
+// (N'Ko, Mongolian, ...).  Each letter's joining form is picked by
+// running the Unicode joining types through the state machine from the
+// OpenType Arabic script development spec, and the matching
+// isol/fina/fin2/fin3/medi/med2/init feature is enabled on it.  Syriac
+// 'stch' stretching is done after positioning, by repeating the
+// stretched glyph across the word that follows it.
+
+use glyph_buffer::{GlyphBuffer, GlyphPosition, TextDirection};
+use shaping::{self, ComplexShaper, ShapePlan, ShapePlanBuilder, Shaper, ZeroWidthMarks};
+use unicode_data::{self, JoiningType};
+use super::FontFace;
+
+// Joining actions, stored in GlyphInfo::shaper_data.  1-7 index
+// JOINING_FEATURES.
+const NONE: u8 = 0;
+const ISOL: u8 = 1;
+const FINA: u8 = 2;
+const FIN2: u8 = 3;
+const FIN3: u8 = 4;
+const MEDI: u8 = 5;
+const MED2: u8 = 6;
+const INIT: u8 = 7;
+const STRETCHING_FIXED: u8 = 8;
+const STRETCHING_REPEATING: u8 = 9;
+
+const JOINING_FEATURES: [u32; 7] = [
+    ot_tag!(b"isol"),
+    ot_tag!(b"fina"),
+    ot_tag!(b"fin2"),
+    ot_tag!(b"fin3"),
+    ot_tag!(b"medi"),
+    ot_tag!(b"med2"),
+    ot_tag!(b"init"),
+];
+
+pub static ARABIC_SHAPER: ComplexShaper = ComplexShaper {
+    collect_features: collect_features,
+    override_features: shaping::override_no_features,
+    preprocess_text: shaping::preprocess_nothing,
+    decompose: shaping::decompose_nothing,
+    setup_masks: setup_masks,
+    postprocess_glyphs: apply_stch,
+    zero_width_marks: ZeroWidthMarks::ByGdefLate,
+    fallback_position: true,
+};
+
+// Scripts with Unicode joining type data.
+pub fn is_joining_script(script: u32) -> bool {
+    [ot_tag!(b"arab"), ot_tag!(b"syrc"), ot_tag!(b"nko "), ot_tag!(b"mong"),
+     ot_tag!(b"adlm"), ot_tag!(b"mand"), ot_tag!(b"mani"), ot_tag!(b"phag"),
+     ot_tag!(b"phlp"), ot_tag!(b"rohg"), ot_tag!(b"sogd"), ot_tag!(b"chrs"),
+     ot_tag!(b"ougr")].contains(&script)
+}
+
+fn collect_features(builder: &mut ShapePlanBuilder) {
+    // The joining forms are applied one after another, in the order the
+    // spec gives, with a pause between each; the pause before 'rlig' is
+    // needed by fonts whose ligatures expect the joining forms.  Only
+    // Arabic gets a pause between 'rlig' and 'calt', which is what
+    // Uniscribe appears to do.
+    builder.add_global_feature(ot_tag!(b"stch"));
+    builder.add_gsub_pause(Some(record_stch));
+
+    builder.add_global_feature(ot_tag!(b"ccmp"));
+    builder.add_global_feature(ot_tag!(b"locl"));
+    builder.add_gsub_pause(None);
+
+    for &tag in &JOINING_FEATURES {
+        builder.add_feature(tag);
+        builder.add_gsub_pause(None);
+    }
+
+    builder.add_global_feature(ot_tag!(b"rlig"));
+    if builder.script == ot_tag!(b"arab") {
+        builder.add_gsub_pause(None);
+    }
+    builder.add_global_feature(ot_tag!(b"calt"));
+    builder.add_gsub_pause(None);
+
+    builder.add_global_feature(ot_tag!(b"liga"));
+    builder.add_global_feature(ot_tag!(b"clig"));
+    builder.add_global_feature(ot_tag!(b"mset"));
+}
+
+// Columns of STATE_TABLE.
+fn joining_class(joining_type: JoiningType) -> Option<usize> {
+    match joining_type {
+        JoiningType::NonJoining => Some(0),
+        JoiningType::LeftJoining => Some(1),
+        JoiningType::RightJoining => Some(2),
+        JoiningType::DualJoining => Some(3),
+        JoiningType::Alaph => Some(4),
+        JoiningType::DalathRish => Some(5),
+        JoiningType::Transparent => None,
+    }
+}
+
+// For each state and joining class of the next character: the action
+// for the previous character, the action for this one, and the next
+// state.
+const STATE_TABLE: [[(u8, u8, usize); 6]; 7] = [
+    // State 0: prev was U, not willing to join.
+    [(NONE, NONE, 0), (NONE, ISOL, 2), (NONE, ISOL, 1), (NONE, ISOL, 2), (NONE, ISOL, 1), (NONE, ISOL, 6)],
+    // State 1: prev was R or ISOL/ALAPH, not willing to join.
+    [(NONE, NONE, 0), (NONE, ISOL, 2), (NONE, ISOL, 1), (NONE, ISOL, 2), (NONE, FIN2, 5), (NONE, ISOL, 6)],
+    // State 2: prev was D/L in ISOL form, willing to join.
+    [(NONE, NONE, 0), (NONE, ISOL, 2), (INIT, FINA, 1), (INIT, FINA, 3), (INIT, FINA, 4), (INIT, FINA, 6)],
+    // State 3: prev was D in FINA form, willing to join.
+    [(NONE, NONE, 0), (NONE, ISOL, 2), (MEDI, FINA, 1), (MEDI, FINA, 3), (MEDI, FINA, 4), (MEDI, FINA, 6)],
+    // State 4: prev was FINA ALAPH, not willing to join.
+    [(NONE, NONE, 0), (NONE, ISOL, 2), (MED2, ISOL, 1), (MED2, ISOL, 2), (MED2, FIN2, 5), (MED2, ISOL, 6)],
+    // State 5: prev was FIN2/FIN3 ALAPH, not willing to join.
+    [(NONE, NONE, 0), (NONE, ISOL, 2), (ISOL, ISOL, 1), (ISOL, ISOL, 2), (ISOL, FIN2, 5), (ISOL, ISOL, 6)],
+    // State 6: prev was DALATH/RISH, not willing to join.
+    [(NONE, NONE, 0), (NONE, ISOL, 2), (NONE, ISOL, 1), (NONE, ISOL, 2), (NONE, FIN3, 5), (NONE, ISOL, 6)],
+];
+
+// Records the joining action for every character in the buffer.
+fn arabic_joining(buffer: &mut GlyphBuffer) {
+    let mut prev: Option<usize> = None;
+    let mut state = 0;
+    for i in 0..buffer.len() {
+        let class = match joining_class(unicode_data::joining_type(buffer.glyphs[i].codepoint)) {
+            Some(c) => c,
+            None => {
+                buffer.glyphs[i].shaper_data = NONE;
+                continue;
+            }
+        };
+        let (prev_action, action, next_state) = STATE_TABLE[state][class];
+        if let Some(p) = prev {
+            if prev_action != NONE {
+                buffer.glyphs[p].shaper_data = prev_action;
+            }
+        }
+        buffer.glyphs[i].shaper_data = action;
+        prev = Some(i);
+        state = next_state;
+    }
+}
+
+// Mongolian free variation selectors take the form of the letter they
+// follow.
+fn mongolian_variation_selectors(buffer: &mut GlyphBuffer) {
+    for i in 1..buffer.len() {
+        let cp = buffer.glyphs[i].codepoint;
+        if (0x180B <= cp && cp <= 0x180D) || cp == 0x180F {
+            buffer.glyphs[i].shaper_data = buffer.glyphs[i - 1].shaper_data;
+        }
+    }
+}
+
+// Enables the joining form features the plan has on each letter.
+pub fn setup_masks(plan: &ShapePlan, buffer: &mut GlyphBuffer) {
+    arabic_joining(buffer);
+    if plan.script == ot_tag!(b"mong") {
+        mongolian_variation_selectors(buffer);
+    }
+
+    let mut masks = [0; 8];
+    for (i, &tag) in JOINING_FEATURES.iter().enumerate() {
+        masks[i + 1] = plan.mask_for(tag);
+    }
+    for info in &mut buffer.glyphs {
+        info.mask |= masks[info.shaper_data as usize];
+    }
+}
+
+// Run right after 'stch': every glyph it multiplied is marked for
+// stretching.  Odd pieces of the sequence are repeated to fill the
+// space, even ones are drawn once.
+fn record_stch(plan: &ShapePlan, _: &Shaper, _: &FontFace, buffer: &mut GlyphBuffer) {
+    if plan.mask_for(ot_tag!(b"stch")) == 0 {
+        return;
+    }
+    for info in &mut buffer.glyphs {
+        if info.multiplied_component != 0 {
+            info.shaper_data = if info.multiplied_component % 2 == 0 {
+                STRETCHING_REPEATING
+            } else {
+                STRETCHING_FIXED
+            };
+        }
+    }
+}
+
+fn is_stch(action: u8) -> bool {
+    action == STRETCHING_FIXED || action == STRETCHING_REPEATING
+}
+
+// Whether a character continues the word a stretched glyph spans.
+fn is_word_char(cp: u32) -> bool {
+    if cp == 0x200C || cp == 0x200D || unicode_data::joining_type(cp) == JoiningType::Transparent {
+        return true;
+    }
+    match ::std::char::from_u32(cp) {
+        Some(c) => c.is_alphanumeric() || (0xE000 <= cp && cp <= 0xF8FF),
+        None => false,
+    }
+}
+
+// Lays each run of stretched glyphs over the word that follows it,
+// repeating the repeating pieces as many times as needed to cover it.
+// The pieces take no space of their own.
+fn apply_stch(_: &ShapePlan, face: &FontFace, buffer: &mut GlyphBuffer) {
+    if !buffer.glyphs.iter().any(|g| is_stch(g.shaper_data)) {
+        return;
+    }
+    let rtl = buffer.direction == TextDirection::RightToLeft;
+    let widths: Vec<i32> = face.get_design_glyph_metrics(&buffer.glyph_indices(), false)
+        .iter()
+        .map(|m| m.advanceWidth as i32)
+        .collect();
+
+    let mut glyphs = Vec::with_capacity(buffer.len());
+    let mut positions: Vec<GlyphPosition> = Vec::with_capacity(buffer.len());
+    let mut i = 0;
+    while i < buffer.len() {
+        if !is_stch(buffer.glyphs[i].shaper_data) {
+            glyphs.push(buffer.glyphs[i]);
+            positions.push(buffer.positions[i]);
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let (mut w_fixed, mut w_repeating, mut n_repeating) = (0, 0, 0);
+        while i < buffer.len() && is_stch(buffer.glyphs[i].shaper_data) {
+            if buffer.glyphs[i].shaper_data == STRETCHING_FIXED {
+                w_fixed += widths[i];
+            } else {
+                w_repeating += widths[i];
+                n_repeating += 1;
+            }
+            i += 1;
+        }
+        let end = i;
+        let mut w_total = 0;
+        let mut context = end;
+        while context < buffer.len() && !is_stch(buffer.glyphs[context].shaper_data) &&
+              is_word_char(buffer.glyphs[context].codepoint) {
+            w_total += buffer.positions[context].x_advance;
+            context += 1;
+        }
+
+        // How many extra times to repeat each repeating piece, and how
+        // much to overlap them by to make them fit exactly.
+        let mut n_copies = 0;
+        let mut w_remaining = w_total - w_fixed;
+        if w_remaining > w_repeating && w_repeating > 0 {
+            n_copies = w_remaining / w_repeating - 1;
+        }
+        let mut overlap = 0;
+        let shortfall = w_remaining - w_repeating * (n_copies + 1);
+        if shortfall > 0 && n_repeating > 0 {
+            n_copies += 1;
+            let excess = (n_copies + 1) * w_repeating - w_remaining;
+            if excess > 0 {
+                overlap = excess / (n_copies * n_repeating);
+                w_remaining = 0;
+            }
+        }
+
+        // Distance covered so far, in the direction of the run.
+        let mut covered = w_remaining / 2;
+        for k in start..end {
+            let repeat = if buffer.glyphs[k].shaper_data == STRETCHING_REPEATING {
+                n_copies + 1
+            } else {
+                1
+            };
+            for n in 0..repeat {
+                if n > 0 {
+                    covered -= overlap;
+                }
+                let mut pos = buffer.positions[k];
+                pos.x_advance = 0;
+                pos.x_offset = if rtl { -(covered + widths[k]) } else { covered };
+                glyphs.push(buffer.glyphs[k]);
+                positions.push(pos);
+                covered += widths[k];
+            }
+        }
+    }
+    buffer.glyphs = glyphs;
+    buffer.positions = positions;
+}
@@ -0,0 +1,232 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Shaping for Hangul.  Sequences of conjoining jamo are composed into
+// precomposed syllables when the font has a glyph for the syllable;
+// otherwise the jamo are left for the font's 'ljmo', 'vjmo' and 'tjmo'
+// features to combine (and precomposed syllables the font lacks are
+// decomposed for them).  Tone marks are moved before the syllable
+// they follow, which is where they're drawn.
+
+use glyph_buffer::{GlyphBuffer, GlyphInfo};
+use shaping::{self, ComplexShaper, ShapePlan, ShapePlanBuilder, ZeroWidthMarks};
+use super::FontFace;
+
+pub static HANGUL_SHAPER: ComplexShaper = ComplexShaper {
+    collect_features: collect_features,
+    override_features: override_features,
+    preprocess_text: preprocess_text,
+    decompose: shaping::decompose_nothing,
+    setup_masks: setup_masks,
+    postprocess_glyphs: shaping::postprocess_nothing,
+    zero_width_marks: ZeroWidthMarks::None,
+    fallback_position: false,
+};
+
+// Which jamo feature a glyph gets, kept in shaper_data.
+const LJMO: u8 = 1;
+const VJMO: u8 = 2;
+const TJMO: u8 = 3;
+
+const JAMO_FEATURES: [u32; 3] = [ot_tag!(b"ljmo"), ot_tag!(b"vjmo"), ot_tag!(b"tjmo")];
+
+// The jamo precomposed syllables are made of.
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const N_COUNT: u32 = V_COUNT * T_COUNT;
+const S_COUNT: u32 = L_COUNT * N_COUNT;
+const S_BASE: u32 = 0xAC00;
+
+fn is_combining_l(cp: u32) -> bool {
+    L_BASE <= cp && cp < L_BASE + L_COUNT
+}
+
+fn is_combining_v(cp: u32) -> bool {
+    V_BASE <= cp && cp < V_BASE + V_COUNT
+}
+
+fn is_combining_t(cp: u32) -> bool {
+    T_BASE < cp && cp < T_BASE + T_COUNT
+}
+
+fn is_combined_s(cp: u32) -> bool {
+    S_BASE <= cp && cp < S_BASE + S_COUNT
+}
+
+// Leading consonants, vowels and trailing consonants, including the
+// old Hangul jamo that have no precomposed syllables.
+fn is_l(cp: u32) -> bool {
+    (0x1100 <= cp && cp <= 0x115F) || (0xA960 <= cp && cp <= 0xA97C)
+}
+
+fn is_v(cp: u32) -> bool {
+    (0x1160 <= cp && cp <= 0x11A7) || (0xD7B0 <= cp && cp <= 0xD7C6)
+}
+
+fn is_t(cp: u32) -> bool {
+    (0x11A8 <= cp && cp <= 0x11FF) || (0xD7CB <= cp && cp <= 0xD7FB)
+}
+
+fn is_tone_mark(cp: u32) -> bool {
+    cp == 0x302E || cp == 0x302F
+}
+
+fn collect_features(builder: &mut ShapePlanBuilder) {
+    for &tag in &JAMO_FEATURES {
+        builder.add_feature(tag);
+    }
+}
+
+fn override_features(builder: &mut ShapePlanBuilder) {
+    // Fonts use 'calt' for Hangul layouts that aren't what Uniscribe
+    // produces.
+    builder.disable_feature(ot_tag!(b"calt"));
+}
+
+fn setup_masks(plan: &ShapePlan, buffer: &mut GlyphBuffer) {
+    let masks = [0,
+                 plan.mask_for(JAMO_FEATURES[0]),
+                 plan.mask_for(JAMO_FEATURES[1]),
+                 plan.mask_for(JAMO_FEATURES[2])];
+    for info in &mut buffer.glyphs {
+        info.mask |= masks[info.shaper_data as usize];
+    }
+}
+
+fn preprocess_text(_: &ShapePlan, face: &FontFace, buffer: &mut GlyphBuffer) {
+    let has_glyph = |cp: u32| face.get_glyph_indices(&[cp])[0] != 0;
+    let is_zero_width = |cp: u32| {
+        let glyph = face.get_glyph_indices(&[cp])[0];
+        glyph != 0 && face.get_design_glyph_metrics(&[glyph], false)[0].advanceWidth == 0
+    };
+
+    let input = ::std::mem::replace(&mut buffer.glyphs, vec![]);
+    let mut out = GlyphBuffer::new();
+    // The syllable last written to `out`, if it's still the last thing
+    // there, for tone marks to move in front of.
+    let mut start = 0;
+    let mut end = 0;
+    let mut i = 0;
+    while i < input.len() {
+        let info = input[i];
+        let cp = info.codepoint;
+
+        if is_tone_mark(cp) {
+            if start < end && end == out.len() {
+                out.glyphs.push(info);
+                // A font with zero-width tone marks positions them
+                // itself, after the syllable.
+                if !is_zero_width(cp) {
+                    out.merge_clusters(start, end + 1);
+                    out.glyphs[start..end + 1].rotate_right(1);
+                }
+            } else if has_glyph(0x25CC) {
+                // No syllable to attach to.
+                let dotted_circle = GlyphInfo { codepoint: 0x25CC, ..info };
+                if is_zero_width(cp) {
+                    out.glyphs.push(dotted_circle);
+                    out.glyphs.push(info);
+                } else {
+                    out.glyphs.push(info);
+                    out.glyphs.push(dotted_circle);
+                }
+            } else {
+                out.glyphs.push(info);
+            }
+            i += 1;
+            start = out.len();
+            end = out.len();
+            continue;
+        }
+
+        start = out.len();
+
+        if is_l(cp) && i + 1 < input.len() && is_v(input[i + 1].codepoint) {
+            let l = cp;
+            let v = input[i + 1].codepoint;
+            let t = if i + 2 < input.len() && is_t(input[i + 2].codepoint) {
+                input[i + 2].codepoint
+            } else {
+                0
+            };
+            let count = if t != 0 { 3 } else { 2 };
+
+            // Compose L,V(,T) if the font has the syllable.
+            if is_combining_l(l) && is_combining_v(v) && (t == 0 || is_combining_t(t)) {
+                let t_index = if t != 0 { t - T_BASE } else { 0 };
+                let s = S_BASE + (l - L_BASE) * N_COUNT + (v - V_BASE) * T_COUNT + t_index;
+                if has_glyph(s) {
+                    out.glyphs.push(GlyphInfo { codepoint: s, ..info });
+                    i += count;
+                    end = start + 1;
+                    continue;
+                }
+            }
+
+            // Otherwise leave the jamo for the font's features.
+            for (j, &feature) in [LJMO, VJMO, TJMO][..count].iter().enumerate() {
+                out.glyphs.push(GlyphInfo { shaper_data: feature, ..input[i + j] });
+            }
+            i += count;
+            end = start + count;
+            out.merge_clusters(start, end);
+            continue;
+        } else if is_combined_s(cp) {
+            let has_s = has_glyph(cp);
+            let l_index = (cp - S_BASE) / N_COUNT;
+            let v_index = (cp - S_BASE) % N_COUNT / T_COUNT;
+            let t_index = (cp - S_BASE) % T_COUNT;
+            let next = if i + 1 < input.len() { input[i + 1].codepoint } else { 0 };
+
+            // An LV syllable followed by a trailing consonant may
+            // compose into an LVT syllable.
+            if t_index == 0 && is_combining_t(next) {
+                let s = cp + next - T_BASE;
+                if has_glyph(s) {
+                    out.glyphs.push(GlyphInfo { codepoint: s, ..info });
+                    i += 2;
+                    end = start + 1;
+                    continue;
+                }
+            }
+
+            // Decompose syllables the font lacks, and LV syllables
+            // followed by an old trailing consonant, so the jamo
+            // features can combine them.
+            if !has_s || (t_index == 0 && is_t(next)) {
+                let jamo = [L_BASE + l_index, V_BASE + v_index, T_BASE + t_index];
+                let count = if t_index != 0 { 3 } else { 2 };
+                if face.get_glyph_indices(&jamo[..count]).iter().all(|&g| g != 0) {
+                    for (j, &feature) in [LJMO, VJMO, TJMO][..count].iter().enumerate() {
+                        out.glyphs.push(GlyphInfo { codepoint: jamo[j], shaper_data: feature, ..info });
+                    }
+                    i += 1;
+                    end = start + count;
+                    if t_index == 0 && is_t(next) {
+                        out.glyphs.push(GlyphInfo { shaper_data: TJMO, ..input[i] });
+                        i += 1;
+                        end += 1;
+                    }
+                    out.merge_clusters(start, end);
+                    continue;
+                }
+            }
+
+            if has_s {
+                out.glyphs.push(info);
+                i += 1;
+                end = start + 1;
+                continue;
+            }
+        }
+
+        out.glyphs.push(info);
+        i += 1;
+    }
+    buffer.glyphs = out.glyphs;
+}
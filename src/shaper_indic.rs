@@ -0,0 +1,966 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Shaping for the Brahmic scripts of India covered by the OpenType
+// Indic script specs (Devanagari, Bengali, Gurmukhi, Gujarati, Oriya,
+// Tamil, Telugu, Kannada and Malayalam), following
+// https://docs.microsoft.com/en-us/typography/script-development/devanagari
+// and the Uniscribe behavior HarfBuzz has documented along the way.
+//
+// Text is split into syllables, each syllable's base consonant is
+// found and its characters are reordered (pre-base matras to the
+// front, reph-forming Ra out of the way), then the basic shaping
+// features are applied one at a time, each limited to the glyphs the
+// spec says it applies to.  After that, pre-base matras, reph and
+// pre-base-reordering consonants are moved to their final positions
+// based on what the font formed, and the presentation features run.
+
+use glyph_buffer::{GlyphBuffer, GlyphInfo};
+use shaper_syllabic::{self, SyllableGrammar};
+use shaping::{self, ComplexShaper, ShapePlan, ShapePlanBuilder, Shaper, ZeroWidthMarks};
+use unicode_data;
+use unicode_data::indic::category::*;
+use unicode_data::indic::position::*;
+use super::FontFace;
+
+pub static INDIC_SHAPER: ComplexShaper = ComplexShaper {
+    collect_features: collect_features,
+    override_features: override_features,
+    preprocess_text: shaping::preprocess_nothing,
+    decompose: unicode_data::decompose_split_vowel,
+    setup_masks: setup_masks,
+    postprocess_glyphs: shaping::postprocess_nothing,
+    zero_width_marks: ZeroWidthMarks::None,
+    fallback_position: false,
+};
+
+// Syllable types.
+const CONSONANT_SYLLABLE: u8 = 0;
+const VOWEL_SYLLABLE: u8 = 1;
+const STANDALONE_CLUSTER: u8 = 2;
+const SYMBOL_CLUSTER: u8 = 3;
+const BROKEN_CLUSTER: u8 = 4;
+const NON_INDIC_CLUSTER: u8 = 5;
+
+lazy_static! {
+    static ref GRAMMAR: SyllableGrammar = SyllableGrammar::new("
+        X = 0; C = 1; V = 2; N = 3; H = 4; ZWNJ = 5; ZWJ = 6; M = 7; SM = 8;
+        A = 9; VD = 9; PLACEHOLDER = 10; DOTTEDCIRCLE = 11; RS = 12; MPst = 13;
+        Repha = 14; Ra = 15; CM = 16; Symbol = 17; CS = 18;
+
+        c = (C | Ra);                   # is_consonant
+        n = ((ZWNJ?.RS)? (N.N?)?);      # is_consonant_modifier
+        z = ZWJ|ZWNJ;                   # is_joiner
+        reph = (Ra H | Repha);          # possible reph
+
+        cn = c.ZWJ?.n?;
+        symbol = Symbol.N?;
+        matra_group = z*.(M | SM? MPst).N?.H?;
+        syllable_tail = (z?.SM.SM?.ZWNJ?)? (A | VD)*;
+        halant_group = (z?.H.(ZWJ.N?)?);
+        final_halant_group = halant_group | H.ZWNJ;
+        medial_group = CM?;
+        halant_or_matra_group = (final_halant_group | matra_group*);
+
+        complex_syllable_tail = (halant_group.cn)* medial_group halant_or_matra_group syllable_tail;
+
+        consonant_syllable = (Repha|CS)? cn complex_syllable_tail;
+        vowel_syllable = reph? V.n? (ZWJ | complex_syllable_tail);
+        standalone_cluster = ((Repha|CS)? PLACEHOLDER | reph? DOTTEDCIRCLE).n? complex_syllable_tail;
+        symbol_cluster = symbol syllable_tail;
+        broken_cluster = reph? n? complex_syllable_tail;
+    ", &[
+        ("consonant_syllable", CONSONANT_SYLLABLE),
+        ("vowel_syllable", VOWEL_SYLLABLE),
+        ("standalone_cluster", STANDALONE_CLUSTER),
+        ("symbol_cluster", SYMBOL_CLUSTER),
+        ("broken_cluster", BROKEN_CLUSTER),
+        ("any", NON_INDIC_CLUSTER),
+    ]);
+}
+
+// The basic features, applied one after another between the two
+// reordering passes, and whether each applies to the whole syllable.
+const BASIC_FEATURES: [(u32, bool); 11] = [
+    (ot_tag!(b"nukt"), true),
+    (ot_tag!(b"akhn"), true),
+    (ot_tag!(b"rphf"), false),
+    (ot_tag!(b"rkrf"), true),
+    (ot_tag!(b"pref"), false),
+    (ot_tag!(b"blwf"), false),
+    (ot_tag!(b"abvf"), false),
+    (ot_tag!(b"half"), false),
+    (ot_tag!(b"pstf"), false),
+    (ot_tag!(b"vatu"), true),
+    (ot_tag!(b"cjct"), true),
+];
+
+// Applied together after final reordering.
+const OTHER_FEATURES: [(u32, bool); 6] = [
+    (ot_tag!(b"init"), false),
+    (ot_tag!(b"pres"), true),
+    (ot_tag!(b"abvs"), true),
+    (ot_tag!(b"blws"), true),
+    (ot_tag!(b"psts"), true),
+    (ot_tag!(b"haln"), true),
+];
+
+#[derive(PartialEq, Clone, Copy)]
+enum RephPosition {
+    AfterMain,
+    BeforeSub,
+    AfterSub,
+    BeforePost,
+    AfterPost,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum RephMode {
+    // Reph formed out of an initial Ra,H sequence...
+    Implicit,
+    // ... or Ra,H,ZWJ...
+    Explicit,
+    // ... or encoded as its own character, which needs reordering.
+    LogicalRepha,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum BlwfMode {
+    PreAndPost,
+    PostOnly,
+}
+
+struct IndicConfig {
+    script: u32,
+    // Whether the script has an old-spec tag (like 'deva' vs 'dev2').
+    has_old_spec: bool,
+    virama: u32,
+    reph_position: RephPosition,
+    reph_mode: RephMode,
+    blwf_mode: BlwfMode,
+}
+
+const DEFAULT_CONFIG: IndicConfig = IndicConfig {
+    script: 0,
+    has_old_spec: false,
+    virama: 0,
+    reph_position: RephPosition::BeforePost,
+    reph_mode: RephMode::Implicit,
+    blwf_mode: BlwfMode::PreAndPost,
+};
+
+const CONFIGS: [IndicConfig; 9] = [
+    IndicConfig {
+        script: ot_tag!(b"deva"),
+        has_old_spec: true,
+        virama: 0x094D,
+        reph_position: RephPosition::BeforePost,
+        reph_mode: RephMode::Implicit,
+        blwf_mode: BlwfMode::PreAndPost,
+    },
+    IndicConfig {
+        script: ot_tag!(b"beng"),
+        has_old_spec: true,
+        virama: 0x09CD,
+        reph_position: RephPosition::AfterSub,
+        reph_mode: RephMode::Implicit,
+        blwf_mode: BlwfMode::PreAndPost,
+    },
+    IndicConfig {
+        script: ot_tag!(b"guru"),
+        has_old_spec: true,
+        virama: 0x0A4D,
+        reph_position: RephPosition::BeforeSub,
+        reph_mode: RephMode::Implicit,
+        blwf_mode: BlwfMode::PreAndPost,
+    },
+    IndicConfig {
+        script: ot_tag!(b"gujr"),
+        has_old_spec: true,
+        virama: 0x0ACD,
+        reph_position: RephPosition::BeforePost,
+        reph_mode: RephMode::Implicit,
+        blwf_mode: BlwfMode::PreAndPost,
+    },
+    IndicConfig {
+        script: ot_tag!(b"orya"),
+        has_old_spec: true,
+        virama: 0x0B4D,
+        reph_position: RephPosition::AfterMain,
+        reph_mode: RephMode::Implicit,
+        blwf_mode: BlwfMode::PreAndPost,
+    },
+    IndicConfig {
+        script: ot_tag!(b"taml"),
+        has_old_spec: true,
+        virama: 0x0BCD,
+        reph_position: RephPosition::AfterPost,
+        reph_mode: RephMode::Implicit,
+        blwf_mode: BlwfMode::PreAndPost,
+    },
+    IndicConfig {
+        script: ot_tag!(b"telu"),
+        has_old_spec: true,
+        virama: 0x0C4D,
+        reph_position: RephPosition::AfterPost,
+        reph_mode: RephMode::Explicit,
+        blwf_mode: BlwfMode::PostOnly,
+    },
+    IndicConfig {
+        script: ot_tag!(b"knda"),
+        has_old_spec: true,
+        virama: 0x0CCD,
+        reph_position: RephPosition::AfterPost,
+        reph_mode: RephMode::Implicit,
+        blwf_mode: BlwfMode::PostOnly,
+    },
+    IndicConfig {
+        script: ot_tag!(b"mlym"),
+        has_old_spec: true,
+        virama: 0x0D4D,
+        reph_position: RephPosition::AfterMain,
+        reph_mode: RephMode::LogicalRepha,
+        blwf_mode: BlwfMode::PreAndPost,
+    },
+];
+
+// What the reordering passes need to know about the plan.
+struct IndicPlan<'a> {
+    plan: &'a ShapePlan,
+    shaper: &'a Shaper,
+    config: &'static IndicConfig,
+    // Old-spec fonts expect Uniscribe's original behavior, e.g. halants
+    // moved after the last consonant.
+    is_old_spec: bool,
+    rphf_mask: u32,
+    pref_mask: u32,
+    blwf_mask: u32,
+    abvf_mask: u32,
+    half_mask: u32,
+    pstf_mask: u32,
+    init_mask: u32,
+}
+
+impl<'a> IndicPlan<'a> {
+    fn new(plan: &'a ShapePlan, shaper: &'a Shaper) -> IndicPlan<'a> {
+        let config = CONFIGS.iter().find(|c| c.script == plan.script).unwrap_or(&DEFAULT_CONFIG);
+        IndicPlan {
+            plan: plan,
+            shaper: shaper,
+            config: config,
+            is_old_spec: config.has_old_spec && plan.gsub_script.map_or(true, |t| (t >> 24) as u8 != b'2'),
+            rphf_mask: plan.mask_for(ot_tag!(b"rphf")),
+            pref_mask: plan.mask_for(ot_tag!(b"pref")),
+            blwf_mask: plan.mask_for(ot_tag!(b"blwf")),
+            abvf_mask: plan.mask_for(ot_tag!(b"abvf")),
+            half_mask: plan.mask_for(ot_tag!(b"half")),
+            pstf_mask: plan.mask_for(ot_tag!(b"pstf")),
+            init_mask: plan.mask_for(ot_tag!(b"init")),
+        }
+    }
+
+    fn would_substitute(&self, tag: u32, glyphs: &[u16]) -> bool {
+        self.plan.would_substitute(self.shaper, tag, glyphs)
+    }
+}
+
+fn category(info: &GlyphInfo) -> u8 {
+    info.shaper_category
+}
+
+fn position(info: &GlyphInfo) -> u8 {
+    info.shaper_data
+}
+
+fn set_position(info: &mut GlyphInfo, position: u8) {
+    info.shaper_data = position;
+}
+
+// Whether a glyph (that didn't ligate) has one of the categories.
+fn is_one_of(info: &GlyphInfo, categories: &[u8]) -> bool {
+    !info.ligated && categories.contains(&info.shaper_category)
+}
+
+fn is_joiner(info: &GlyphInfo) -> bool {
+    is_one_of(info, &[ZWJ, ZWNJ])
+}
+
+// Vowels and placeholders are treated as consonants: vowels can't
+// occur in consonant syllables, so the consonant syllable logic also
+// works for vowel syllables.
+fn is_consonant(info: &GlyphInfo) -> bool {
+    is_one_of(info, &[C, CS, RA, CM, V, PLACEHOLDER, DOTTED_CIRCLE])
+}
+
+fn is_halant(info: &GlyphInfo) -> bool {
+    is_one_of(info, &[H])
+}
+
+fn ligated_and_didnt_multiply(info: &GlyphInfo) -> bool {
+    info.ligated && info.multiplied_component == 0
+}
+
+fn collect_features(builder: &mut ShapePlanBuilder) {
+    builder.add_gsub_pause(Some(setup_syllables));
+
+    builder.add_syllable_feature(ot_tag!(b"locl"), true);
+    // The specs don't call for 'ccmp', but fonts that use it expect
+    // it to come first.
+    builder.add_syllable_feature(ot_tag!(b"ccmp"), true);
+    builder.add_gsub_pause(Some(initial_reordering));
+
+    for &(tag, global) in &BASIC_FEATURES {
+        builder.add_syllable_feature(tag, global);
+        builder.add_gsub_pause(None);
+    }
+    builder.add_gsub_pause(Some(final_reordering));
+
+    for &(tag, global) in &OTHER_FEATURES {
+        builder.add_syllable_feature(tag, global);
+    }
+}
+
+fn override_features(builder: &mut ShapePlanBuilder) {
+    builder.disable_feature(ot_tag!(b"liga"));
+    builder.add_gsub_pause(Some(shaper_syllabic::clear_syllables));
+}
+
+fn setup_masks(_: &ShapePlan, buffer: &mut GlyphBuffer) {
+    for info in &mut buffer.glyphs {
+        let (category, position) = unicode_data::indic_properties(info.codepoint);
+        info.shaper_category = category;
+        info.shaper_data = position;
+    }
+}
+
+fn setup_syllables(_: &ShapePlan, _: &Shaper, _: &FontFace, buffer: &mut GlyphBuffer) {
+    shaper_syllabic::set_syllables(buffer, &GRAMMAR);
+}
+
+fn initial_reordering(plan: &ShapePlan, shaper: &Shaper, face: &FontFace, buffer: &mut GlyphBuffer) {
+    let indic_plan = IndicPlan::new(plan, shaper);
+
+    update_consonant_positions(&indic_plan, face, buffer);
+    shaper_syllabic::insert_dotted_circles(shaper, face, buffer, BROKEN_CLUSTER, DOTTED_CIRCLE,
+                                           Some(REPHA), END);
+
+    for (start, end) in shaper_syllabic::syllables(buffer) {
+        match shaper_syllabic::syllable_type(&buffer.glyphs[start]) {
+            // Dotted circles were inserted into broken clusters, so
+            // they can be handled like standalone ones, which are
+            // handled like consonant syllables.
+            CONSONANT_SYLLABLE | VOWEL_SYLLABLE | STANDALONE_CLUSTER | BROKEN_CLUSTER => {
+                initial_reordering_consonant_syllable(&indic_plan, start, end, buffer);
+            }
+            _ => {}
+        }
+    }
+}
+
+// Consonants whose below-base, post-base or pre-base-reordering forms
+// the font has get those positions.
+fn update_consonant_positions(indic_plan: &IndicPlan, face: &FontFace, buffer: &mut GlyphBuffer) {
+    if indic_plan.config.virama == 0 {
+        return;
+    }
+    let virama = face.get_glyph_indices(&[indic_plan.config.virama])[0];
+    if virama == 0 {
+        return;
+    }
+    for info in &mut buffer.glyphs {
+        if position(info) == BASE_C {
+            let consonant = info.glyph_index;
+            set_position(info, consonant_position_from_face(indic_plan, consonant, virama));
+        }
+    }
+}
+
+fn consonant_position_from_face(indic_plan: &IndicPlan, consonant: u16, virama: u16) -> u8 {
+    // Old-spec fonts expect Consonant,Virama and new-spec ones
+    // Virama,Consonant, but some fonts copied their old-spec lookups
+    // into their new-spec tables, and Uniscribe goes along with that,
+    // so both orders are tried.
+    let would_substitute = |tag| {
+        indic_plan.would_substitute(tag, &[virama, consonant]) ||
+            indic_plan.would_substitute(tag, &[consonant, virama])
+    };
+    if would_substitute(ot_tag!(b"blwf")) || would_substitute(ot_tag!(b"vatu")) {
+        BELOW_C
+    } else if would_substitute(ot_tag!(b"pstf")) || would_substitute(ot_tag!(b"pref")) {
+        POST_C
+    } else {
+        BASE_C
+    }
+}
+
+fn initial_reordering_consonant_syllable(indic_plan: &IndicPlan, start: usize, end: usize,
+                                         buffer: &mut GlyphBuffer) {
+    let script = indic_plan.plan.script;
+
+    // For compatibility with legacy usage in Kannada, Ra,H,ZWJ must
+    // behave like Ra,ZWJ,H.
+    if script == ot_tag!(b"knda") && start + 3 <= end &&
+       is_one_of(&buffer.glyphs[start], &[RA]) &&
+       is_one_of(&buffer.glyphs[start + 1], &[H]) &&
+       is_one_of(&buffer.glyphs[start + 2], &[ZWJ]) {
+        buffer.merge_clusters(start + 1, start + 3);
+        buffer.glyphs.swap(start + 1, start + 2);
+    }
+
+    // 1. Find the base consonant: starting from the end of the
+    // syllable, move backwards until a consonant is found that doesn't
+    // have a below-base or post-base form (post-base forms have to
+    // follow below-base forms), or that isn't a pre-base-reordering
+    // Ra, or arrive at the first consonant.  If the syllable starts
+    // with Ra,H (in a script that has reph) and has more than one
+    // consonant, the Ra is excluded from the candidates.
+    let mut base = end;
+    let mut has_reph = false;
+    {
+        let mut limit = start;
+        let reph_mode = indic_plan.config.reph_mode;
+        if indic_plan.rphf_mask != 0 && start + 3 <= end &&
+           ((reph_mode == RephMode::Implicit && !is_joiner(&buffer.glyphs[start + 2])) ||
+            (reph_mode == RephMode::Explicit && category(&buffer.glyphs[start + 2]) == ZWJ)) {
+            let glyphs = [buffer.glyphs[start].glyph_index,
+                          buffer.glyphs[start + 1].glyph_index,
+                          buffer.glyphs[start + 2].glyph_index];
+            if indic_plan.would_substitute(ot_tag!(b"rphf"), &glyphs[..2]) ||
+               (reph_mode == RephMode::Explicit && indic_plan.would_substitute(ot_tag!(b"rphf"), &glyphs)) {
+                limit += 2;
+                while limit < end && is_joiner(&buffer.glyphs[limit]) {
+                    limit += 1;
+                }
+                base = start;
+                has_reph = true;
+            }
+        } else if reph_mode == RephMode::LogicalRepha && category(&buffer.glyphs[start]) == REPHA {
+            limit += 1;
+            while limit < end && is_joiner(&buffer.glyphs[limit]) {
+                limit += 1;
+            }
+            base = start;
+            has_reph = true;
+        }
+
+        let mut i = end;
+        let mut seen_below = false;
+        loop {
+            i -= 1;
+            let info = buffer.glyphs[i];
+            if is_consonant(&info) {
+                if position(&info) != BELOW_C && (position(&info) != POST_C || seen_below) {
+                    base = i;
+                    break;
+                }
+                if position(&info) == BELOW_C {
+                    seen_below = true;
+                }
+                // Pre-base-reordering Ra's are marked POST_C, so were
+                // skipped above.
+                base = i;
+            } else if start < i && category(&info) == ZWJ && category(&buffer.glyphs[i - 1]) == H {
+                // A ZWJ after a halant stops the search and asks for an
+                // explicit half form.  A ZWJ before a halant asks for a
+                // subjoined form instead, so the search goes on.
+                break;
+            }
+            if i <= limit {
+                break;
+            }
+        }
+
+        // Only Ra,H with no other consonant: the Ra becomes the base and
+        // no reph is formed.
+        if has_reph && base == start && limit - base <= 2 {
+            has_reph = false;
+        }
+    }
+
+    // 2. Reorder matras and other marks to their positions relative to
+    // the consonants.  Split matras have already been decomposed.
+    for info in &mut buffer.glyphs[start..base] {
+        let p = position(info).min(PRE_C);
+        set_position(info, p);
+    }
+    if base < end {
+        set_position(&mut buffer.glyphs[base], BASE_C);
+    }
+    if has_reph {
+        set_position(&mut buffer.glyphs[start], RA_TO_BECOME_REPH);
+    }
+
+    // For old-spec fonts, move the first post-base halant after the
+    // last consonant.  Uniscribe doesn't do this in Kannada if there's
+    // already a halant after the last consonant.
+    if indic_plan.is_old_spec {
+        let disallow_double_halants = script == ot_tag!(b"knda");
+        for i in base + 1..end {
+            if category(&buffer.glyphs[i]) == H {
+                let mut j = end - 1;
+                while j > i {
+                    if is_consonant(&buffer.glyphs[j]) ||
+                       (disallow_double_halants && category(&buffer.glyphs[j]) == H) {
+                        break;
+                    }
+                    j -= 1;
+                }
+                if category(&buffer.glyphs[j]) != H && j > i {
+                    buffer.glyphs[i..j + 1].rotate_left(1);
+                }
+                break;
+            }
+        }
+    }
+
+    // Attach miscellaneous marks to the previous character so they
+    // move with it.
+    {
+        let mut last_position = START;
+        for i in start..end {
+            let c = category(&buffer.glyphs[i]);
+            if [ZWJ, ZWNJ, N, RS, CM, H].contains(&c) {
+                set_position(&mut buffer.glyphs[i], last_position);
+                if c == H && position(&buffer.glyphs[i]) == PRE_M {
+                    // Uniscribe doesn't move the halant with a left
+                    // matra.
+                    for j in (start + 1..i + 1).rev() {
+                        if position(&buffer.glyphs[j - 1]) != PRE_M {
+                            let p = position(&buffer.glyphs[j - 1]);
+                            set_position(&mut buffer.glyphs[i], p);
+                            break;
+                        }
+                    }
+                }
+            } else if position(&buffer.glyphs[i]) != SMVD {
+                if c == M_PST && i > start && category(&buffer.glyphs[i - 1]) == SM {
+                    let p = position(&buffer.glyphs[i]);
+                    set_position(&mut buffer.glyphs[i - 1], p);
+                }
+                last_position = position(&buffer.glyphs[i]);
+            }
+        }
+    }
+
+    // Post-base consonants own everything before them since the last
+    // consonant or matra.
+    {
+        let mut last = base;
+        for i in base + 1..end {
+            if is_consonant(&buffer.glyphs[i]) {
+                let p = position(&buffer.glyphs[i]);
+                for info in &mut buffer.glyphs[last + 1..i] {
+                    if position(info) < SMVD {
+                        set_position(info, p);
+                    }
+                }
+                last = i;
+            } else if [M, M_PST].contains(&category(&buffer.glyphs[i])) {
+                last = i;
+            }
+        }
+    }
+
+    {
+        // The syllable field keeps track of where each glyph came from
+        // while sorting.
+        let syllable = buffer.glyphs[start].syllable;
+        for i in start..end {
+            buffer.glyphs[i].syllable = (i - start).min(255) as u8;
+        }
+        buffer.glyphs[start..end].sort_by_key(|g| g.shaper_data);
+
+        // Find the base again, and flip sequences of left matras.
+        let mut first_left_matra = end;
+        let mut last_left_matra = end;
+        base = end;
+        for i in start..end {
+            let p = position(&buffer.glyphs[i]);
+            if p == BASE_C {
+                base = i;
+                break;
+            } else if p == PRE_M {
+                if first_left_matra == end {
+                    first_left_matra = i;
+                }
+                last_left_matra = i;
+            }
+        }
+        if first_left_matra < last_left_matra {
+            buffer.glyphs[first_left_matra..last_left_matra + 1].reverse();
+            // ... but put back the nuktas etc. that go with each.
+            let mut i = first_left_matra;
+            for j in first_left_matra..last_left_matra + 1 {
+                if [M, M_PST].contains(&category(&buffer.glyphs[j])) {
+                    buffer.glyphs[i..j + 1].reverse();
+                    i = j + 1;
+                }
+            }
+        }
+
+        // Post-base glyphs may have been shuffled around, so merge the
+        // clusters of everything that moved past each other after the
+        // base (or everything after it, for old-spec fonts, where
+        // halants were moved).  Pre-base clusters are dealt with in
+        // final reordering.
+        if indic_plan.is_old_spec || end - start > 127 {
+            buffer.merge_clusters(base, end);
+        } else {
+            for i in base..end {
+                if buffer.glyphs[i].syllable != 255 {
+                    let mut min = i;
+                    let mut max = i;
+                    let mut j = start + buffer.glyphs[i].syllable as usize;
+                    while j != i {
+                        min = min.min(j);
+                        max = max.max(j);
+                        let next = start + buffer.glyphs[j].syllable as usize;
+                        buffer.glyphs[j].syllable = 255;
+                        j = next;
+                    }
+                    buffer.merge_clusters(base.max(min), max + 1);
+                }
+            }
+        }
+
+        for info in &mut buffer.glyphs[start..end] {
+            info.syllable = syllable;
+        }
+    }
+
+    // Set up masks.
+    for info in &mut buffer.glyphs[start..end] {
+        if position(info) != RA_TO_BECOME_REPH {
+            break;
+        }
+        info.mask |= indic_plan.rphf_mask;
+    }
+    let mut pre_base_mask = indic_plan.half_mask;
+    if !indic_plan.is_old_spec && indic_plan.config.blwf_mode == BlwfMode::PreAndPost {
+        pre_base_mask |= indic_plan.blwf_mask;
+    }
+    for info in &mut buffer.glyphs[start..base] {
+        info.mask |= pre_base_mask;
+    }
+    let post_base_mask = indic_plan.blwf_mask | indic_plan.abvf_mask | indic_plan.pstf_mask;
+    for info in &mut buffer.glyphs[(base + 1).min(end)..end] {
+        info.mask |= post_base_mask;
+    }
+
+    if indic_plan.is_old_spec && script == ot_tag!(b"deva") {
+        // Old-spec eyelash Ra: 'blwf' also applies to Ra,H before the
+        // base, unless it's followed by ZWJ, which asks for the eyelash
+        // form.
+        for i in start..base.saturating_sub(1) {
+            if category(&buffer.glyphs[i]) == RA && category(&buffer.glyphs[i + 1]) == H &&
+               (i + 2 == base || category(&buffer.glyphs[i + 2]) != ZWJ) {
+                buffer.glyphs[i].mask |= indic_plan.blwf_mask;
+                buffer.glyphs[i + 1].mask |= indic_plan.blwf_mask;
+            }
+        }
+    }
+
+    // Mark a halant,Ra sequence the font has a pre-base-reordering
+    // form for.
+    if indic_plan.pref_mask != 0 && base + 2 < end {
+        for i in base + 1..end - 1 {
+            let glyphs = [buffer.glyphs[i].glyph_index, buffer.glyphs[i + 1].glyph_index];
+            if indic_plan.would_substitute(ot_tag!(b"pref"), &glyphs) {
+                buffer.glyphs[i].mask |= indic_plan.pref_mask;
+                buffer.glyphs[i + 1].mask |= indic_plan.pref_mask;
+                break;
+            }
+        }
+    }
+
+    // A ZWNJ disables half forms before it (ZWJ and ZWNJ disable
+    // 'cjct' simply by being in the way).
+    for i in start + 1..end {
+        if is_joiner(&buffer.glyphs[i]) && category(&buffer.glyphs[i]) == ZWNJ {
+            let mut j = i;
+            loop {
+                j -= 1;
+                buffer.glyphs[j].mask &= !indic_plan.half_mask;
+                if j <= start || is_consonant(&buffer.glyphs[j]) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn final_reordering(plan: &ShapePlan, shaper: &Shaper, face: &FontFace, buffer: &mut GlyphBuffer) {
+    let indic_plan = IndicPlan::new(plan, shaper);
+
+    // Ligations and multiple substitutions may have lost track of
+    // which glyphs are halants; recover the common case of a virama
+    // glyph that came out of a decomposition.
+    if indic_plan.config.virama != 0 {
+        let virama = face.get_glyph_indices(&[indic_plan.config.virama])[0];
+        for info in &mut buffer.glyphs {
+            if virama != 0 && info.glyph_index == virama && info.ligated && info.multiplied_component != 0 {
+                info.shaper_category = H;
+                info.ligated = false;
+                info.multiplied_component = 0;
+            }
+        }
+    }
+
+    for (start, end) in shaper_syllabic::syllables(buffer) {
+        final_reordering_syllable(&indic_plan, start, end, buffer);
+    }
+}
+
+// Where a pre-base matra or pre-base-reordering consonant goes: after
+// the last standalone halant before `from`, unless it's followed by
+// ZWJ.  Malayalam and Tamil have no half forms, so what 'half' formed
+// there are chillus and explicit viramas, which things go after.
+fn pre_base_target(indic_plan: &IndicPlan, buffer: &GlyphBuffer, start: usize, mut new_position: usize) -> usize {
+    let script = indic_plan.plan.script;
+    if script == ot_tag!(b"mlym") || script == ot_tag!(b"taml") {
+        return new_position;
+    }
+    loop {
+        while new_position > start && !is_one_of(&buffer.glyphs[new_position], &[M, M_PST, H]) {
+            new_position -= 1;
+        }
+        // Proceed only if the halant doesn't belong to the matra
+        // itself.
+        if is_halant(&buffer.glyphs[new_position]) && position(&buffer.glyphs[new_position]) != PRE_M {
+            if new_position + 1 < buffer.len() && category(&buffer.glyphs[new_position + 1]) == ZWJ &&
+               new_position > start {
+                new_position -= 1;
+                continue;
+            }
+            return new_position;
+        }
+        return start;
+    }
+}
+
+fn final_reordering_syllable(indic_plan: &IndicPlan, start: usize, end: usize, buffer: &mut GlyphBuffer) {
+    let script = indic_plan.plan.script;
+
+    // Find the base again: the substitutions may have changed things.
+    let mut try_pref = indic_plan.pref_mask != 0;
+    let mut base = start;
+    while base < end {
+        if position(&buffer.glyphs[base]) >= BASE_C {
+            if try_pref && base + 1 < end {
+                for i in base + 1..end {
+                    if buffer.glyphs[i].mask & indic_plan.pref_mask != 0 {
+                        if !(buffer.glyphs[i].substituted && ligated_and_didnt_multiply(&buffer.glyphs[i])) {
+                            // A 'pref' candidate that didn't form
+                            // anything: the base is around here.
+                            base = i;
+                            while base < end && is_halant(&buffer.glyphs[base]) {
+                                base += 1;
+                            }
+                            if base < end {
+                                set_position(&mut buffer.glyphs[base], BASE_C);
+                            }
+                            try_pref = false;
+                        }
+                        break;
+                    }
+                }
+            }
+
+            // In Malayalam, skip over unformed below- (but not post-)
+            // forms.
+            if script == ot_tag!(b"mlym") {
+                let mut i = base + 1;
+                while i < end {
+                    while i < end && is_joiner(&buffer.glyphs[i]) {
+                        i += 1;
+                    }
+                    if i == end || !is_halant(&buffer.glyphs[i]) {
+                        break;
+                    }
+                    i += 1;
+                    while i < end && is_joiner(&buffer.glyphs[i]) {
+                        i += 1;
+                    }
+                    if i < end && is_consonant(&buffer.glyphs[i]) && position(&buffer.glyphs[i]) == BELOW_C {
+                        base = i;
+                        set_position(&mut buffer.glyphs[base], BASE_C);
+                    }
+                    i += 1;
+                }
+            }
+
+            if start < base && base < end && position(&buffer.glyphs[base]) > BASE_C {
+                base -= 1;
+            }
+            break;
+        }
+        base += 1;
+    }
+    if base == end && start < base && is_one_of(&buffer.glyphs[base - 1], &[ZWJ]) {
+        base -= 1;
+    }
+    if base < end {
+        while start < base && is_one_of(&buffer.glyphs[base], &[N, H]) {
+            base -= 1;
+        }
+    }
+
+    // Reorder pre-base matras: a matra that was moved before the base
+    // can now move closer to it, after the last standalone halant (so
+    // after any half forms that didn't form).  Testing shows Uniscribe
+    // doesn't move it past a halant followed by ZWJ.
+    if start + 1 < end && start < base {
+        let new_position = if base == end { base - 2 } else { base - 1 };
+        let mut new_position = pre_base_target(indic_plan, buffer, start, new_position);
+
+        if start < new_position && position(&buffer.glyphs[new_position]) != PRE_M {
+            for i in (start + 1..new_position + 1).rev() {
+                if position(&buffer.glyphs[i - 1]) == PRE_M {
+                    let old_position = i - 1;
+                    if old_position < base && base <= new_position {
+                        base -= 1;
+                    }
+                    buffer.glyphs[old_position..new_position + 1].rotate_left(1);
+                    // Merging after the move is intentional.
+                    buffer.merge_clusters(new_position, end.min(base + 1));
+                    new_position -= 1;
+                }
+            }
+        } else {
+            for i in start..base {
+                if position(&buffer.glyphs[i]) == PRE_M {
+                    buffer.merge_clusters(i, end.min(base + 1));
+                    break;
+                }
+            }
+        }
+    }
+
+    // Reorder reph.  A reph made of Ra,H(,ZWJ) only moves if it ligated
+    // into a reph glyph; an encoded repha only moves if it didn't
+    // ligate (if it did, the font is probably dealing with it).
+    if start + 1 < end && position(&buffer.glyphs[start]) == RA_TO_BECOME_REPH &&
+       ((category(&buffer.glyphs[start]) == REPHA) ^ ligated_and_didnt_multiply(&buffer.glyphs[start])) {
+        let new_reph_position = reph_target(indic_plan, buffer, start, end, base);
+        buffer.merge_clusters(start, new_reph_position + 1);
+        buffer.glyphs[start..new_reph_position + 1].rotate_left(1);
+        if start < base && base <= new_reph_position {
+            base -= 1;
+        }
+    }
+
+    // Reorder a pre-base-reordering consonant, if 'pref' formed one,
+    // the same way as a pre-base matra, or else to just before the
+    // base.
+    if try_pref && base + 1 < end {
+        for i in base + 1..end {
+            if buffer.glyphs[i].mask & indic_plan.pref_mask != 0 {
+                if ligated_and_didnt_multiply(&buffer.glyphs[i]) {
+                    let mut new_position = base;
+                    if script != ot_tag!(b"mlym") && script != ot_tag!(b"taml") {
+                        while new_position > start &&
+                              !is_one_of(&buffer.glyphs[new_position - 1], &[M, M_PST, H]) {
+                            new_position -= 1;
+                        }
+                    }
+                    if new_position > start && is_halant(&buffer.glyphs[new_position - 1]) &&
+                       new_position < end && is_joiner(&buffer.glyphs[new_position]) {
+                        new_position += 1;
+                    }
+                    buffer.merge_clusters(new_position, i + 1);
+                    buffer.glyphs[new_position..i + 1].rotate_right(1);
+                }
+                break;
+            }
+        }
+    }
+
+    // Apply 'init' to a left matra at the start of a word.
+    if position(&buffer.glyphs[start]) == PRE_M {
+        if start == 0 || !is_word_char(buffer.glyphs[start - 1].codepoint) {
+            buffer.glyphs[start].mask |= indic_plan.init_mask;
+        }
+    }
+}
+
+fn reph_target(indic_plan: &IndicPlan, buffer: &GlyphBuffer, start: usize, end: usize, base: usize) -> usize {
+    let reph_position = indic_plan.config.reph_position;
+
+    // After the first explicit halant between the first post-reph
+    // consonant and the last main consonant (and a joiner following
+    // it), if there is one.
+    let after_first_halant = || {
+        let mut new_position = start + 1;
+        while new_position < base && !is_halant(&buffer.glyphs[new_position]) {
+            new_position += 1;
+        }
+        if new_position < base && is_halant(&buffer.glyphs[new_position]) {
+            if new_position + 1 < base && is_joiner(&buffer.glyphs[new_position + 1]) {
+                new_position += 1;
+            }
+            return Some(new_position);
+        }
+        None
+    };
+
+    if reph_position != RephPosition::AfterPost {
+        if let Some(p) = after_first_halant() {
+            return p;
+        }
+
+        // After the main consonant and whatever ligated with it.
+        if reph_position == RephPosition::AfterMain {
+            let mut new_position = base;
+            while new_position + 1 < end && position(&buffer.glyphs[new_position + 1]) <= AFTER_MAIN {
+                new_position += 1;
+            }
+            if new_position < end {
+                return new_position;
+            }
+        }
+
+        // Before the first post-base consonant or matra.
+        if reph_position == RephPosition::AfterSub {
+            let mut new_position = base;
+            while new_position + 1 < end &&
+                  ![POST_C, AFTER_POST, SMVD].contains(&position(&buffer.glyphs[new_position + 1])) {
+                new_position += 1;
+            }
+            if new_position < end {
+                return new_position;
+            }
+        }
+    }
+
+    if let Some(p) = after_first_halant() {
+        return p;
+    }
+
+    // Otherwise at the end of the syllable, before any syllable
+    // modifiers and vedic signs, and before a final halant that
+    // follows a matra.
+    let mut new_position = end - 1;
+    while new_position > start && position(&buffer.glyphs[new_position]) == SMVD {
+        new_position -= 1;
+    }
+    if is_halant(&buffer.glyphs[new_position]) {
+        for i in base + 1..new_position {
+            if [M, M_PST].contains(&category(&buffer.glyphs[i])) {
+                new_position -= 1;
+            }
+        }
+    }
+    new_position
+}
+
+// Whether a character is part of a word, for 'init'.
+fn is_word_char(cp: u32) -> bool {
+    if cp == 0x200C || cp == 0x200D {
+        return true;
+    }
+    match ::std::char::from_u32(cp) {
+        Some(c) => c.is_alphanumeric() || unicode_data::indic_properties(cp).0 != X,
+        None => false,
+    }
+}
@@ -0,0 +1,159 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Shaping for Khmer, following
+// https://docs.microsoft.com/en-us/typography/script-development/khmer
+//
+// Much simpler than the Indic shaper: there's no reph and the base is
+// always the first consonant, so reordering only moves pre-base vowels
+// and the Coeng,Ro sequence (which forms a pre-base subscript) to the
+// front of the syllable.
+
+use glyph_buffer::GlyphBuffer;
+use shaper_syllabic::{self, SyllableGrammar};
+use shaping::{self, ComplexShaper, ShapePlan, ShapePlanBuilder, Shaper, ZeroWidthMarks};
+use unicode_data;
+use unicode_data::indic::category::*;
+use unicode_data::indic::position::END;
+use super::FontFace;
+
+pub static KHMER_SHAPER: ComplexShaper = ComplexShaper {
+    collect_features: collect_features,
+    override_features: override_features,
+    preprocess_text: shaping::preprocess_nothing,
+    decompose: unicode_data::decompose_split_vowel,
+    setup_masks: setup_masks,
+    postprocess_glyphs: shaping::postprocess_nothing,
+    zero_width_marks: ZeroWidthMarks::None,
+    fallback_position: false,
+};
+
+// Syllable types.
+const CONSONANT_SYLLABLE: u8 = 0;
+const BROKEN_CLUSTER: u8 = 1;
+const NON_KHMER_CLUSTER: u8 = 2;
+
+lazy_static! {
+    // The grammar was experimentally extracted from what Uniscribe
+    // allows.
+    static ref GRAMMAR: SyllableGrammar = SyllableGrammar::new("
+        C = 1; V = 2; H = 4; ZWNJ = 5; ZWJ = 6; PLACEHOLDER = 10;
+        DOTTEDCIRCLE = 11; Ra = 15; VAbv = 20; VBlw = 21; VPre = 22;
+        VPst = 23; Robatic = 25; Xgroup = 26; Ygroup = 27;
+
+        c = (C | Ra | V);
+        cn = c.((ZWJ|ZWNJ)?.Robatic)?;
+        joiner = (ZWJ | ZWNJ);
+        xgroup = (joiner*.Xgroup)*;
+        ygroup = Ygroup*;
+
+        matra_group = VPre? xgroup VBlw? xgroup (joiner?.VAbv)? xgroup VPst?;
+        syllable_tail = xgroup matra_group xgroup (H.c)? ygroup;
+
+        broken_cluster = Robatic? (H.cn)* (H | syllable_tail);
+        consonant_syllable = (cn|PLACEHOLDER|DOTTEDCIRCLE) broken_cluster;
+    ", &[
+        ("consonant_syllable", CONSONANT_SYLLABLE),
+        ("broken_cluster", BROKEN_CLUSTER),
+        ("any", NON_KHMER_CLUSTER),
+    ]);
+}
+
+// Applied per syllable, to the glyphs reordering picks.
+const MASKED_FEATURES: [u32; 5] = [
+    ot_tag!(b"pref"),
+    ot_tag!(b"blwf"),
+    ot_tag!(b"abvf"),
+    ot_tag!(b"pstf"),
+    ot_tag!(b"cfar"),
+];
+
+const OTHER_FEATURES: [u32; 4] = [
+    ot_tag!(b"pres"),
+    ot_tag!(b"abvs"),
+    ot_tag!(b"blws"),
+    ot_tag!(b"psts"),
+];
+
+fn collect_features(builder: &mut ShapePlanBuilder) {
+    builder.add_gsub_pause(Some(setup_syllables));
+    builder.add_gsub_pause(Some(reorder));
+
+    builder.add_syllable_feature(ot_tag!(b"locl"), true);
+    builder.add_syllable_feature(ot_tag!(b"ccmp"), true);
+    for &tag in &MASKED_FEATURES {
+        builder.add_syllable_feature(tag, false);
+    }
+    builder.add_gsub_pause(Some(shaper_syllabic::clear_syllables));
+
+    for &tag in &OTHER_FEATURES {
+        builder.add_global_feature(tag);
+    }
+}
+
+fn override_features(builder: &mut ShapePlanBuilder) {
+    // Uniscribe applies 'clig' but not 'liga' to Khmer.
+    builder.add_global_feature(ot_tag!(b"clig"));
+    builder.disable_feature(ot_tag!(b"liga"));
+}
+
+fn setup_masks(_: &ShapePlan, buffer: &mut GlyphBuffer) {
+    for info in &mut buffer.glyphs {
+        info.shaper_category = unicode_data::indic_properties(info.codepoint).0;
+    }
+}
+
+fn setup_syllables(_: &ShapePlan, _: &Shaper, _: &FontFace, buffer: &mut GlyphBuffer) {
+    shaper_syllabic::set_syllables(buffer, &GRAMMAR);
+}
+
+fn reorder(plan: &ShapePlan, shaper: &Shaper, face: &FontFace, buffer: &mut GlyphBuffer) {
+    shaper_syllabic::insert_dotted_circles(shaper, face, buffer, BROKEN_CLUSTER, DOTTED_CIRCLE,
+                                           Some(REPHA), END);
+
+    for (start, end) in shaper_syllabic::syllables(buffer) {
+        match shaper_syllabic::syllable_type(&buffer.glyphs[start]) {
+            CONSONANT_SYLLABLE | BROKEN_CLUSTER => reorder_consonant_syllable(plan, start, end, buffer),
+            _ => {}
+        }
+    }
+}
+
+fn reorder_consonant_syllable(plan: &ShapePlan, start: usize, end: usize, buffer: &mut GlyphBuffer) {
+    // Everything after the base may form below-, above- or post-base
+    // forms.
+    let post_base_mask = plan.mask_for(ot_tag!(b"blwf")) |
+                         plan.mask_for(ot_tag!(b"abvf")) |
+                         plan.mask_for(ot_tag!(b"pstf"));
+    for info in &mut buffer.glyphs[start + 1..end] {
+        info.mask |= post_base_mask;
+    }
+
+    let pref_mask = plan.mask_for(ot_tag!(b"pref"));
+    let cfar_mask = plan.mask_for(ot_tag!(b"cfar"));
+    let mut num_coengs = 0;
+    for i in start + 1..end {
+        let category = buffer.glyphs[i].shaper_category;
+        if category == H && num_coengs <= 2 && i + 1 < end {
+            num_coengs += 1;
+            if buffer.glyphs[i + 1].shaper_category == RA {
+                // Coeng,Ro forms a pre-base subscript, which moves to
+                // the front of the syllable, and the glyphs after it
+                // may have conjoining forms that go with it.
+                buffer.glyphs[i].mask |= pref_mask;
+                buffer.glyphs[i + 1].mask |= pref_mask;
+                buffer.merge_clusters(start, i + 2);
+                buffer.glyphs[start..i + 2].rotate_right(2);
+                for info in &mut buffer.glyphs[i + 2..end] {
+                    info.mask |= cfar_mask;
+                }
+                num_coengs = 2;
+            }
+        } else if category == V_PRE {
+            // Left matras move to the front as well.
+            buffer.merge_clusters(start, i + 1);
+            buffer.glyphs[start..i + 1].rotate_right(1);
+        }
+    }
+}
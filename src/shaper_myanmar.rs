@@ -0,0 +1,191 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Shaping for Myanmar fonts made for the 'mym2' spec,
+// https://docs.microsoft.com/en-us/typography/script-development/myanmar
+//
+// Like Indic shaping, but the base is simply the first consonant and
+// reordering happens once, before the basic features: kinzi (Ra,Asat,
+// Virama) moves after the base, and medial Ra and pre-base vowels move
+// before it.
+
+use glyph_buffer::{GlyphBuffer, GlyphInfo};
+use shaper_syllabic::{self, SyllableGrammar};
+use shaping::{self, ComplexShaper, ShapePlan, ShapePlanBuilder, Shaper, ZeroWidthMarks};
+use unicode_data;
+use unicode_data::indic::category::*;
+use unicode_data::indic::position::*;
+use super::FontFace;
+
+pub static MYANMAR_SHAPER: ComplexShaper = ComplexShaper {
+    collect_features: collect_features,
+    override_features: shaping::override_no_features,
+    preprocess_text: shaping::preprocess_nothing,
+    decompose: shaping::decompose_nothing,
+    setup_masks: setup_masks,
+    postprocess_glyphs: shaping::postprocess_nothing,
+    zero_width_marks: ZeroWidthMarks::ByGdefEarly,
+    fallback_position: false,
+};
+
+// Syllable types.
+const CONSONANT_SYLLABLE: u8 = 0;
+const PUNCTUATION_CLUSTER: u8 = 1;
+const BROKEN_CLUSTER: u8 = 2;
+const NON_MYANMAR_CLUSTER: u8 = 3;
+
+lazy_static! {
+    static ref GRAMMAR: SyllableGrammar = SyllableGrammar::new("
+        C = 1; IV = 2; DB = 3; H = 4; ZWNJ = 5; ZWJ = 6; SM = 8; A = 9;
+        GB = 10; DOTTEDCIRCLE = 11; Ra = 15; CS = 18; VAbv = 20; VBlw = 21;
+        VPre = 22; VPst = 23; As = 32; MH = 35; MR = 36; MW = 37; MY = 38;
+        PT = 39; VS = 40; ML = 41;
+
+        j = ZWJ|ZWNJ;                   # joiners
+        k = (Ra As H);                  # kinzi
+        c = C|Ra;
+
+        medial_group = MY? As? MR? ((MW MH? ML? | MH ML? | ML) As?)?;
+        main_vowel_group = (VPre.VS?)* VAbv* VBlw* A* (DB As?)?;
+        post_vowel_group = VPst MH? ML? As* VAbv* A* (DB As?)?;
+        pwo_tone_group = PT A* DB? As?;
+
+        complex_syllable_tail = As* medial_group main_vowel_group post_vowel_group* pwo_tone_group* SM* j?;
+        syllable_tail = (H (c|IV).VS?)* (H | complex_syllable_tail);
+
+        consonant_syllable = (k|CS)? (c|IV|GB|DOTTEDCIRCLE).VS? syllable_tail;
+        broken_cluster = k? VS? syllable_tail;
+    ", &[
+        ("consonant_syllable", CONSONANT_SYLLABLE),
+        ("j", PUNCTUATION_CLUSTER),
+        ("broken_cluster", BROKEN_CLUSTER),
+        ("any", NON_MYANMAR_CLUSTER),
+    ]);
+}
+
+// Applied one at a time after reordering...
+const BASIC_FEATURES: [u32; 4] = [
+    ot_tag!(b"rphf"),
+    ot_tag!(b"pref"),
+    ot_tag!(b"blwf"),
+    ot_tag!(b"pstf"),
+];
+
+// ... and these together once syllables no longer matter.
+const OTHER_FEATURES: [u32; 4] = [
+    ot_tag!(b"pres"),
+    ot_tag!(b"abvs"),
+    ot_tag!(b"blws"),
+    ot_tag!(b"psts"),
+];
+
+fn collect_features(builder: &mut ShapePlanBuilder) {
+    builder.add_gsub_pause(Some(setup_syllables));
+
+    builder.add_syllable_feature(ot_tag!(b"locl"), true);
+    builder.add_syllable_feature(ot_tag!(b"ccmp"), true);
+    builder.add_gsub_pause(Some(reorder));
+
+    for &tag in &BASIC_FEATURES {
+        builder.add_syllable_feature(tag, true);
+        builder.add_gsub_pause(None);
+    }
+    builder.add_gsub_pause(Some(shaper_syllabic::clear_syllables));
+
+    for &tag in &OTHER_FEATURES {
+        builder.add_global_feature(tag);
+    }
+}
+
+fn setup_masks(_: &ShapePlan, buffer: &mut GlyphBuffer) {
+    for info in &mut buffer.glyphs {
+        info.shaper_category = unicode_data::indic_properties(info.codepoint).0;
+    }
+}
+
+fn setup_syllables(_: &ShapePlan, _: &Shaper, _: &FontFace, buffer: &mut GlyphBuffer) {
+    shaper_syllabic::set_syllables(buffer, &GRAMMAR);
+}
+
+fn reorder(_: &ShapePlan, shaper: &Shaper, face: &FontFace, buffer: &mut GlyphBuffer) {
+    shaper_syllabic::insert_dotted_circles(shaper, face, buffer, BROKEN_CLUSTER, DOTTED_CIRCLE, None, END);
+
+    for (start, end) in shaper_syllabic::syllables(buffer) {
+        match shaper_syllabic::syllable_type(&buffer.glyphs[start]) {
+            CONSONANT_SYLLABLE | BROKEN_CLUSTER => reorder_consonant_syllable(start, end, buffer),
+            _ => {}
+        }
+    }
+}
+
+fn is_consonant(info: &GlyphInfo) -> bool {
+    !info.ligated && [C, CS, RA, V, PLACEHOLDER, DOTTED_CIRCLE].contains(&info.shaper_category)
+}
+
+fn reorder_consonant_syllable(start: usize, end: usize, buffer: &mut GlyphBuffer) {
+    let category = |buffer: &GlyphBuffer, i: usize| buffer.glyphs[i].shaper_category;
+
+    // The base is the first consonant, not counting a leading kinzi.
+    let has_kinzi = start + 3 <= end && category(buffer, start) == RA &&
+                    category(buffer, start + 1) == AS && category(buffer, start + 2) == H;
+    let limit = if has_kinzi { start + 3 } else { start };
+    let base = (limit..end).find(|&i| is_consonant(&buffer.glyphs[i]))
+                           .unwrap_or(start);
+
+    // Assign positions, using shaper_data.
+    let mut i = start;
+    while i < limit {
+        buffer.glyphs[i].shaper_data = AFTER_MAIN;
+        i += 1;
+    }
+    while i < base {
+        buffer.glyphs[i].shaper_data = PRE_C;
+        i += 1;
+    }
+    if i < end {
+        buffer.glyphs[i].shaper_data = BASE_C;
+        i += 1;
+    }
+    let mut position = AFTER_MAIN;
+    while i < end {
+        let c = category(buffer, i);
+        let p = if c == MR {
+            PRE_C
+        } else if c == V_PRE {
+            PRE_M
+        } else if c == VS {
+            buffer.glyphs[i - 1].shaper_data
+        } else if position == AFTER_MAIN && c == V_BLW {
+            position = BELOW_C;
+            position
+        } else if position == BELOW_C && c == A {
+            BEFORE_SUB
+        } else if position == BELOW_C && c != V_BLW {
+            position = AFTER_SUB;
+            position
+        } else {
+            position
+        };
+        buffer.glyphs[i].shaper_data = p;
+        i += 1;
+    }
+
+    shaper_syllabic::sort_merging_clusters(buffer, start, end, |info| info.shaper_data);
+
+    // Flip sequences of left matras back into logical order, keeping
+    // variation selectors after the matras they follow.
+    let left_matras: Vec<usize> = (start..end).filter(|&i| buffer.glyphs[i].shaper_data == PRE_M).collect();
+    if let (Some(&first), Some(&last)) = (left_matras.first(), left_matras.last()) {
+        if first < last {
+            buffer.glyphs[first..last + 1].reverse();
+            let mut i = first;
+            for j in first..last + 1 {
+                if category(buffer, j) == V_PRE {
+                    buffer.glyphs[i..j + 1].reverse();
+                    i = j + 1;
+                }
+            }
+        }
+    }
+}
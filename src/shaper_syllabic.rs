@@ -0,0 +1,373 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Pieces shared by the shapers that work a syllable at a time (Indic,
+// Khmer, Myanmar and the Universal Shaping Engine): splitting a run
+// into syllables by matching the characters' categories against the
+// script's syllable grammar, and fixing up broken syllables.
+//
+// Grammars are written in the notation of the Ragel machines the
+// script specs' reference implementations use, so they can be
+// compared with those directly: `name = expression;` defines either a
+// category (when the expression is a number) or a pattern, patterns
+// are built from names, `any`, `|` for alternatives, juxtaposition or
+// `.` for sequences, and the `?`, `*` and `+` suffixes, and `#` starts
+// a comment.
+
+use std::collections::HashMap;
+
+use glyph_buffer::{GlyphBuffer, GlyphInfo};
+use shaping::{ShapePlan, Shaper};
+use super::FontFace;
+
+#[derive(Clone, Debug)]
+enum Pattern {
+    Category(u8),
+    Any,
+    Sequence(Vec<Pattern>),
+    Alternatives(Vec<Pattern>),
+    Optional(Box<Pattern>),
+    Repeat(Box<Pattern>),
+}
+
+impl Pattern {
+    // All the positions a match starting at one of `starts` can end
+    // at, sorted.
+    fn ends(&self, categories: &[u8], starts: &[usize]) -> Vec<usize> {
+        let mut ends = match *self {
+            Pattern::Category(c) => {
+                starts.iter().filter(|&&s| s < categories.len() && categories[s] == c).map(|&s| s + 1).collect()
+            }
+            Pattern::Any => starts.iter().filter(|&&s| s < categories.len()).map(|&s| s + 1).collect(),
+            Pattern::Sequence(ref parts) => {
+                let mut ends = starts.to_vec();
+                for part in parts {
+                    if ends.is_empty() {
+                        break;
+                    }
+                    ends = part.ends(categories, &ends);
+                }
+                ends
+            }
+            Pattern::Alternatives(ref alternatives) => {
+                alternatives.iter().flat_map(|a| a.ends(categories, starts)).collect()
+            }
+            Pattern::Optional(ref p) => {
+                let mut ends = p.ends(categories, starts);
+                ends.extend_from_slice(starts);
+                ends
+            }
+            Pattern::Repeat(ref p) => {
+                let mut ends = starts.to_vec();
+                let mut frontier = starts.to_vec();
+                while !frontier.is_empty() {
+                    frontier = p.ends(categories, &frontier);
+                    frontier.retain(|e| !ends.contains(e));
+                    frontier.sort();
+                    frontier.dedup();
+                    ends.extend_from_slice(&frontier);
+                }
+                ends
+            }
+        };
+        ends.sort();
+        ends.dedup();
+        ends
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<&'a str>,
+    position: usize,
+    definitions: &'a HashMap<&'a str, Pattern>,
+}
+
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap();
+        let mut rest = line;
+        while let Some(start) = rest.find(|c: char| !c.is_whitespace()) {
+            rest = &rest[start..];
+            let len = if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+                rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len())
+            } else {
+                1
+            };
+            tokens.push(&rest[..len]);
+            rest = &rest[len..];
+        }
+    }
+    tokens
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).cloned()
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    fn alternatives(&mut self) -> Pattern {
+        let mut alternatives = vec![self.sequence()];
+        while self.peek() == Some("|") {
+            self.next();
+            alternatives.push(self.sequence());
+        }
+        if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Pattern::Alternatives(alternatives)
+        }
+    }
+
+    fn sequence(&mut self) -> Pattern {
+        let mut parts = vec![self.repetition()];
+        loop {
+            match self.peek() {
+                Some(".") => {
+                    self.next();
+                }
+                Some("(") => {}
+                Some(t) if t.starts_with(|c: char| c.is_alphanumeric() || c == '_') => {}
+                _ => break,
+            }
+            parts.push(self.repetition());
+        }
+        if parts.len() == 1 {
+            parts.pop().unwrap()
+        } else {
+            Pattern::Sequence(parts)
+        }
+    }
+
+    fn repetition(&mut self) -> Pattern {
+        let mut pattern = self.primary();
+        loop {
+            pattern = match self.peek() {
+                Some("?") => Pattern::Optional(Box::new(pattern)),
+                Some("*") => Pattern::Repeat(Box::new(pattern)),
+                Some("+") => Pattern::Sequence(vec![pattern.clone(), Pattern::Repeat(Box::new(pattern))]),
+                _ => return pattern,
+            };
+            self.next();
+        }
+    }
+
+    fn primary(&mut self) -> Pattern {
+        match self.next() {
+            Some("(") => {
+                let pattern = self.alternatives();
+                assert!(self.next() == Some(")"), "unbalanced parentheses in syllable grammar");
+                pattern
+            }
+            Some("any") => Pattern::Any,
+            Some(t) => {
+                if let Ok(category) = t.parse::<u8>() {
+                    return Pattern::Category(category);
+                }
+                match self.definitions.get(t) {
+                    Some(p) => p.clone(),
+                    None => panic!("undefined name {} in syllable grammar", t),
+                }
+            }
+            None => panic!("unexpected end of syllable grammar"),
+        }
+    }
+}
+
+fn parse_pattern<'a>(tokens: Vec<&'a str>, definitions: &'a HashMap<&'a str, Pattern>) -> Pattern {
+    let mut parser = Parser {
+        tokens: tokens,
+        position: 0,
+        definitions: definitions,
+    };
+    let pattern = parser.alternatives();
+    assert!(parser.peek().is_none(), "trailing text in syllable grammar");
+    pattern
+}
+
+pub struct SyllableGrammar {
+    // Syllable types and their patterns, in order of preference.
+    syllables: Vec<(u8, Pattern)>,
+}
+
+impl SyllableGrammar {
+    // `definitions` is a list of `name = expression;` definitions, and
+    // `syllables` the patterns for each type of syllable, written in
+    // terms of them.  When several match, the longest match wins, and
+    // of equally long ones the first listed.
+    pub fn new(definitions: &str, syllables: &[(&str, u8)]) -> SyllableGrammar {
+        let mut patterns = HashMap::new();
+        for definition in definitions.split(';') {
+            let tokens = tokenize(definition);
+            if tokens.is_empty() {
+                continue;
+            }
+            assert!(tokens.len() > 2 && tokens[1] == "=", "bad definition in syllable grammar");
+            let pattern = parse_pattern(tokens[2..].to_vec(), &patterns);
+            patterns.insert(tokens[0], pattern);
+        }
+        SyllableGrammar {
+            syllables: syllables.iter()
+                .map(|&(text, syllable_type)| (syllable_type, parse_pattern(tokenize(text), &patterns)))
+                .collect(),
+        }
+    }
+
+    // Splits a sequence of categories into (start, end, syllable type)
+    // spans.  Anything no pattern matches becomes a syllable of its own
+    // of the last listed type.
+    pub fn find_syllables(&self, categories: &[u8]) -> Vec<(usize, usize, u8)> {
+        let mut syllables = vec![];
+        let mut start = 0;
+        while start < categories.len() {
+            let mut best = (start + 1, self.syllables.last().map_or(0, |s| s.0));
+            let mut best_len = 0;
+            for &(syllable_type, ref pattern) in &self.syllables {
+                if let Some(&end) = pattern.ends(categories, &[start]).last() {
+                    if end - start > best_len {
+                        best = (end, syllable_type);
+                        best_len = end - start;
+                    }
+                }
+            }
+            syllables.push((start, best.0, best.1));
+            start = best.0;
+        }
+        syllables
+    }
+}
+
+// Runs the grammar over the glyphs' shaper categories and records the
+// syllables found in GlyphInfo::syllable, numbering them 1-15 in turn
+// so that neighbouring syllables always differ.
+pub fn set_syllables(buffer: &mut GlyphBuffer, grammar: &SyllableGrammar) {
+    let categories: Vec<u8> = buffer.glyphs.iter().map(|g| g.shaper_category).collect();
+    let syllables = grammar.find_syllables(&categories);
+    mark_syllables(buffer, &syllables);
+}
+
+pub fn mark_syllables(buffer: &mut GlyphBuffer, syllables: &[(usize, usize, u8)]) {
+    let mut serial = 1;
+    for &(start, end, syllable_type) in syllables {
+        for info in &mut buffer.glyphs[start..end] {
+            info.syllable = (serial << 4) | syllable_type;
+        }
+        serial = if serial == 15 { 1 } else { serial + 1 };
+    }
+}
+
+// The end of the syllable starting at `start`.
+pub fn next_syllable(buffer: &GlyphBuffer, start: usize) -> usize {
+    if start >= buffer.len() {
+        return start;
+    }
+    let syllable = buffer.glyphs[start].syllable;
+    let mut end = start + 1;
+    while end < buffer.len() && buffer.glyphs[end].syllable == syllable {
+        end += 1;
+    }
+    end
+}
+
+// The (start, end) of every syllable in the buffer.
+pub fn syllables(buffer: &GlyphBuffer) -> Vec<(usize, usize)> {
+    let mut syllables = vec![];
+    let mut start = 0;
+    while start < buffer.len() {
+        let end = next_syllable(buffer, start);
+        syllables.push((start, end));
+        start = end;
+    }
+    syllables
+}
+
+pub fn syllable_type(info: &GlyphInfo) -> u8 {
+    info.syllable & 0x0F
+}
+
+// Gives each broken syllable (one that doesn't start with a base, e.g.
+// a stray vowel sign) a dotted circle to attach to, after any leading
+// repha.  The dotted circle gets `category`, and `position` as its
+// shaper_data.  Does nothing if the font has no dotted circle glyph.
+pub fn insert_dotted_circles(shaper: &Shaper, face: &FontFace, buffer: &mut GlyphBuffer,
+                             broken_syllable_type: u8, category: u8,
+                             repha_category: Option<u8>, position: u8) {
+    if !buffer.glyphs.iter().any(|g| syllable_type(g) == broken_syllable_type) {
+        return;
+    }
+    let glyph = face.get_glyph_indices(&[0x25CC])[0];
+    if glyph == 0 {
+        return;
+    }
+
+    let mut glyphs = Vec::with_capacity(buffer.len() + 1);
+    let mut last_syllable = 0;
+    let mut i = 0;
+    while i < buffer.len() {
+        let current = buffer.glyphs[i];
+        if current.syllable == last_syllable || syllable_type(&current) != broken_syllable_type {
+            glyphs.push(current);
+            i += 1;
+            continue;
+        }
+        last_syllable = current.syllable;
+        while i < buffer.len() && buffer.glyphs[i].syllable == last_syllable &&
+              Some(buffer.glyphs[i].shaper_category) == repha_category {
+            glyphs.push(buffer.glyphs[i]);
+            i += 1;
+        }
+
+        let mut dotted_circle = GlyphInfo::new(glyph, 0x25CC, current.cluster);
+        dotted_circle.mask = current.mask;
+        dotted_circle.syllable = current.syllable;
+        dotted_circle.shaper_category = category;
+        dotted_circle.shaper_data = position;
+        if let Some(ref gdef) = shaper.gdef {
+            gdef.classify_glyph(&mut dotted_circle);
+        }
+        glyphs.push(dotted_circle);
+    }
+    buffer.glyphs = glyphs;
+}
+
+// Stable insertion sort of start..end by `key`, merging the clusters of
+// everything a glyph moves past.
+pub fn sort_merging_clusters<F>(buffer: &mut GlyphBuffer, start: usize, end: usize, key: F)
+    where F: Fn(&GlyphInfo) -> u8
+{
+    for i in start + 1..end {
+        let k = key(&buffer.glyphs[i]);
+        let mut j = i;
+        while j > start && key(&buffer.glyphs[j - 1]) > k {
+            j -= 1;
+        }
+        if j == i {
+            continue;
+        }
+        buffer.merge_clusters(j, i + 1);
+        buffer.glyphs[j..i + 1].rotate_right(1);
+    }
+}
+
+// Pause that forgets the syllables once the per-syllable features are
+// done with them.
+pub fn clear_syllables(_: &ShapePlan, _: &Shaper, _: &FontFace, buffer: &mut GlyphBuffer) {
+    for info in &mut buffer.glyphs {
+        info.syllable = 0;
+    }
+}
+
+// Pause that forgets which glyphs were substituted so far, for shapers
+// that want to know what a particular feature did.
+pub fn clear_substitution_flags(_: &ShapePlan, _: &Shaper, _: &FontFace, buffer: &mut GlyphBuffer) {
+    for info in &mut buffer.glyphs {
+        info.substituted = false;
+    }
+}
@@ -0,0 +1,288 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Shaping for Thai and Lao.  SARA AM is split into NIKHAHIT and SARA
+// AA, with the NIKHAHIT moved before any above-base marks it follows,
+// so fonts don't need to handle it themselves.
+//
+// Old Thai fonts without OpenType layout for Thai instead have
+// shifted variants of the marks at the Windows (or Mac) private use
+// code points; for those, marks are swapped for the variant that
+// avoids colliding with tall consonants and other marks, the way
+// Uniscribe does it.
+
+use glyph_buffer::{GlyphBuffer, GlyphInfo};
+use shaping::{self, ComplexShaper, ShapePlan, ZeroWidthMarks};
+use super::FontFace;
+
+pub static THAI_SHAPER: ComplexShaper = ComplexShaper {
+    collect_features: shaping::collect_no_features,
+    override_features: shaping::override_no_features,
+    preprocess_text: preprocess_text,
+    decompose: shaping::decompose_nothing,
+    setup_masks: shaping::setup_no_masks,
+    postprocess_glyphs: shaping::postprocess_nothing,
+    zero_width_marks: ZeroWidthMarks::ByGdefLate,
+    fallback_position: true,
+};
+
+// SARA AM is U+0E33 in Thai and U+0EB3 in Lao; the other characters
+// involved are at the same offsets in both blocks.
+fn is_sara_am(cp: u32) -> bool {
+    cp & !0x80 == 0x0E33
+}
+
+fn is_above_base_mark(cp: u32) -> bool {
+    let cp = cp & !0x80;
+    cp == 0x0E31 || (0x0E34 <= cp && cp <= 0x0E37) || cp == 0x0E3B || (0x0E47 <= cp && cp <= 0x0E4E)
+}
+
+fn preprocess_text(plan: &ShapePlan, face: &FontFace, buffer: &mut GlyphBuffer) {
+    if buffer.glyphs.iter().any(|g| is_sara_am(g.codepoint)) {
+        decompose_sara_am(buffer);
+    }
+
+    if plan.script == ot_tag!(b"thai") && plan.gsub_script != Some(plan.script) &&
+       plan.gpos_script != Some(plan.script) {
+        shift_marks(face, buffer);
+    }
+}
+
+fn decompose_sara_am(buffer: &mut GlyphBuffer) {
+    let input = ::std::mem::replace(&mut buffer.glyphs, vec![]);
+    let mut out = GlyphBuffer::new();
+    for info in input {
+        let cp = info.codepoint;
+        if !is_sara_am(cp) {
+            out.glyphs.push(info);
+            continue;
+        }
+
+        let nikhahit = GlyphInfo { codepoint: cp - 0x0E33 + 0x0E4D, ..info };
+        let sara_aa = GlyphInfo { codepoint: cp - 1, ..info };
+        out.glyphs.push(nikhahit);
+        out.glyphs.push(sara_aa);
+
+        // Move the NIKHAHIT before the above-base marks in front of it,
+        // so it ends up on top of them.
+        let end = out.len();
+        let mut start = end - 2;
+        while start > 0 && is_above_base_mark(out.glyphs[start - 1].codepoint) {
+            start -= 1;
+        }
+        if start + 2 < end {
+            out.merge_clusters(start, end);
+            out.glyphs[start..end - 1].rotate_right(1);
+        } else if start > 0 {
+            out.merge_clusters(start - 1, end);
+        }
+    }
+    buffer.glyphs = out.glyphs;
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Consonant {
+    // Normal, ascender, removable descender and strict descender
+    // consonants.
+    Normal,
+    Ascender,
+    RemovableDescender,
+    Descender,
+    NotConsonant,
+}
+
+fn consonant_type(cp: u32) -> Consonant {
+    match cp {
+        0x0E1B | 0x0E1D | 0x0E1F => Consonant::Ascender,
+        0x0E0D | 0x0E10 => Consonant::RemovableDescender,
+        0x0E0E | 0x0E0F => Consonant::Descender,
+        _ if 0x0E01 <= cp && cp <= 0x0E2E => Consonant::Normal,
+        _ => Consonant::NotConsonant,
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Mark {
+    AboveVowel,
+    BelowVowel,
+    Tone,
+    NotMark,
+}
+
+fn mark_type(cp: u32) -> Mark {
+    if cp == 0x0E31 || (0x0E34 <= cp && cp <= 0x0E37) || cp == 0x0E47 || cp == 0x0E4D || cp == 0x0E4E {
+        Mark::AboveVowel
+    } else if 0x0E38 <= cp && cp <= 0x0E3A {
+        Mark::BelowVowel
+    } else if 0x0E48 <= cp && cp <= 0x0E4C {
+        Mark::Tone
+    } else {
+        Mark::NotMark
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Action {
+    None,
+    // Shift the mark down...
+    ShiftDown,
+    // ... left...
+    ShiftLeft,
+    // ... or down and left.
+    ShiftDownLeft,
+    // Use the consonant's form without its descender.
+    RemoveDescender,
+}
+
+// (character, Windows PUA code point, Mac PUA code point) for each
+// action.
+const SHIFT_DOWN: [(u32, u32, u32); 8] = [
+    (0x0E48, 0xF70A, 0xF88B),
+    (0x0E49, 0xF70B, 0xF88E),
+    (0x0E4A, 0xF70C, 0xF891),
+    (0x0E4B, 0xF70D, 0xF894),
+    (0x0E4C, 0xF70E, 0xF897),
+    (0x0E38, 0xF718, 0xF89B),
+    (0x0E39, 0xF719, 0xF89C),
+    (0x0E3A, 0xF71A, 0xF89D),
+];
+
+const SHIFT_DOWN_LEFT: [(u32, u32, u32); 5] = [
+    (0x0E48, 0xF705, 0xF88C),
+    (0x0E49, 0xF706, 0xF88F),
+    (0x0E4A, 0xF707, 0xF892),
+    (0x0E4B, 0xF708, 0xF895),
+    (0x0E4C, 0xF709, 0xF898),
+];
+
+const SHIFT_LEFT: [(u32, u32, u32); 12] = [
+    (0x0E48, 0xF713, 0xF88A),
+    (0x0E49, 0xF714, 0xF88D),
+    (0x0E4A, 0xF715, 0xF890),
+    (0x0E4B, 0xF716, 0xF893),
+    (0x0E4C, 0xF717, 0xF896),
+    (0x0E31, 0xF710, 0xF884),
+    (0x0E34, 0xF701, 0xF885),
+    (0x0E35, 0xF702, 0xF886),
+    (0x0E36, 0xF703, 0xF887),
+    (0x0E37, 0xF704, 0xF888),
+    (0x0E47, 0xF712, 0xF889),
+    (0x0E4D, 0xF711, 0xF899),
+];
+
+const REMOVE_DESCENDER: [(u32, u32, u32); 2] = [
+    (0x0E0D, 0xF70F, 0xF89A),
+    (0x0E10, 0xF700, 0xF89E),
+];
+
+fn shifted_code_point(face: &FontFace, cp: u32, action: Action) -> u32 {
+    let mappings: &[(u32, u32, u32)] = match action {
+        Action::None => return cp,
+        Action::ShiftDown => &SHIFT_DOWN,
+        Action::ShiftLeft => &SHIFT_LEFT,
+        Action::ShiftDownLeft => &SHIFT_DOWN_LEFT,
+        Action::RemoveDescender => &REMOVE_DESCENDER,
+    };
+    match mappings.iter().find(|m| m.0 == cp) {
+        Some(&(_, windows, mac)) => {
+            let glyphs = face.get_glyph_indices(&[windows, mac]);
+            if glyphs[0] != 0 {
+                windows
+            } else if glyphs[1] != 0 {
+                mac
+            } else {
+                cp
+            }
+        }
+        None => cp,
+    }
+}
+
+// The state machines Uniscribe uses for the marks above and below a
+// consonant.  The start state depends on the consonant, and each mark
+// moves to a new state, possibly shifting the mark.
+#[derive(Clone, Copy)]
+enum AboveState {
+    // Normal consonant, no mark yet.
+    T0,
+    // Ascender consonant, no mark yet.
+    T1,
+    // Ascender consonant with a shifted mark.
+    T2,
+    // Nothing more to do.
+    T3,
+}
+
+fn above_start_state(consonant: Consonant) -> AboveState {
+    match consonant {
+        Consonant::Ascender => AboveState::T1,
+        Consonant::NotConsonant => AboveState::T3,
+        _ => AboveState::T0,
+    }
+}
+
+fn above_transition(state: AboveState, mark: Mark) -> (Action, AboveState) {
+    match (state, mark) {
+        (AboveState::T0, Mark::AboveVowel) => (Action::None, AboveState::T3),
+        (AboveState::T0, Mark::Tone) => (Action::ShiftDown, AboveState::T3),
+        (AboveState::T1, Mark::AboveVowel) => (Action::ShiftLeft, AboveState::T2),
+        (AboveState::T1, Mark::Tone) => (Action::ShiftDownLeft, AboveState::T2),
+        (AboveState::T2, Mark::Tone) => (Action::ShiftLeft, AboveState::T3),
+        (AboveState::T2, Mark::AboveVowel) => (Action::None, AboveState::T3),
+        (state, _) => (Action::None, state),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum BelowState {
+    // No descender.
+    B0,
+    // Removable descender.
+    B1,
+    // Strict descender, or nothing more to do.
+    B2,
+}
+
+fn below_start_state(consonant: Consonant) -> BelowState {
+    match consonant {
+        Consonant::RemovableDescender => BelowState::B1,
+        Consonant::Descender | Consonant::NotConsonant => BelowState::B2,
+        _ => BelowState::B0,
+    }
+}
+
+fn below_transition(state: BelowState, mark: Mark) -> (Action, BelowState) {
+    match (state, mark) {
+        (BelowState::B0, Mark::BelowVowel) => (Action::None, BelowState::B2),
+        (BelowState::B1, Mark::BelowVowel) => (Action::RemoveDescender, BelowState::B2),
+        (BelowState::B2, Mark::BelowVowel) => (Action::ShiftDown, BelowState::B2),
+        (state, _) => (Action::None, state),
+    }
+}
+
+fn shift_marks(face: &FontFace, buffer: &mut GlyphBuffer) {
+    let mut above_state = above_start_state(Consonant::NotConsonant);
+    let mut below_state = below_start_state(Consonant::NotConsonant);
+    let mut base = 0;
+    for i in 0..buffer.len() {
+        let mark = mark_type(buffer.glyphs[i].codepoint);
+        if mark == Mark::NotMark {
+            let consonant = consonant_type(buffer.glyphs[i].codepoint);
+            above_state = above_start_state(consonant);
+            below_state = below_start_state(consonant);
+            base = i;
+            continue;
+        }
+
+        let (above_action, next_above) = above_transition(above_state, mark);
+        let (below_action, next_below) = below_transition(below_state, mark);
+        above_state = next_above;
+        below_state = next_below;
+
+        let action = if above_action != Action::None { above_action } else { below_action };
+        let target = if action == Action::RemoveDescender { base } else { i };
+        let cp = buffer.glyphs[target].codepoint;
+        buffer.glyphs[target].codepoint = shifted_code_point(face, cp, action);
+    }
+}
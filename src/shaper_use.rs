@@ -0,0 +1,369 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The Universal Shaping Engine, for the many complex scripts that
+// don't have a shaper of their own (and Indic fonts made for it),
+// following
+// https://docs.microsoft.com/en-us/typography/script-development/use
+//
+// Characters get a USE category, which the cluster grammar below is
+// written in terms of.  Reordering is limited to moving a reph after
+// the base and pre-base vowels and vowel modifiers before it, and the
+// joining forms are applied per cluster rather than per letter.
+
+use glyph_buffer::GlyphBuffer;
+use shaper_arabic;
+use shaper_syllabic::{self, SyllableGrammar};
+use shaping::{self, ComplexShaper, ShapePlan, ShapePlanBuilder, Shaper, ZeroWidthMarks};
+use unicode_data;
+use unicode_data::use_category::category::*;
+use super::FontFace;
+
+pub static USE_SHAPER: ComplexShaper = ComplexShaper {
+    collect_features: collect_features,
+    override_features: shaping::override_no_features,
+    preprocess_text: shaping::preprocess_nothing,
+    decompose: unicode_data::decompose_split_vowel,
+    setup_masks: setup_masks,
+    postprocess_glyphs: shaping::postprocess_nothing,
+    zero_width_marks: ZeroWidthMarks::ByGdefEarly,
+    fallback_position: false,
+};
+
+// Scripts the Universal Shaping Engine handles.
+pub fn is_use_script(script: u32) -> bool {
+    [ot_tag!(b"tibt"), ot_tag!(b"mong"), ot_tag!(b"sinh"), ot_tag!(b"buhd"), ot_tag!(b"hano"),
+     ot_tag!(b"tglg"), ot_tag!(b"tagb"), ot_tag!(b"limb"), ot_tag!(b"tale"), ot_tag!(b"bugi"),
+     ot_tag!(b"khar"), ot_tag!(b"sylo"), ot_tag!(b"tfng"), ot_tag!(b"bali"), ot_tag!(b"nko "),
+     ot_tag!(b"phag"), ot_tag!(b"cham"), ot_tag!(b"kali"), ot_tag!(b"lepc"), ot_tag!(b"rjng"),
+     ot_tag!(b"saur"), ot_tag!(b"sund"), ot_tag!(b"egyp"), ot_tag!(b"java"), ot_tag!(b"kthi"),
+     ot_tag!(b"mtei"), ot_tag!(b"lana"), ot_tag!(b"tavt"), ot_tag!(b"batk"), ot_tag!(b"brah"),
+     ot_tag!(b"mand"), ot_tag!(b"cakm"), ot_tag!(b"plrd"), ot_tag!(b"shrd"), ot_tag!(b"takr"),
+     ot_tag!(b"dupl"), ot_tag!(b"gran"), ot_tag!(b"khoj"), ot_tag!(b"sind"), ot_tag!(b"mahj"),
+     ot_tag!(b"mani"), ot_tag!(b"modi"), ot_tag!(b"hmng"), ot_tag!(b"phlp"), ot_tag!(b"sidd"),
+     ot_tag!(b"tirh"), ot_tag!(b"ahom"), ot_tag!(b"mult"), ot_tag!(b"adlm"), ot_tag!(b"bhks"),
+     ot_tag!(b"marc"), ot_tag!(b"newa"), ot_tag!(b"gonm"), ot_tag!(b"soyo"), ot_tag!(b"zanb"),
+     ot_tag!(b"dogr"), ot_tag!(b"gong"), ot_tag!(b"rohg"), ot_tag!(b"maka"), ot_tag!(b"medf"),
+     ot_tag!(b"sogo"), ot_tag!(b"sogd"), ot_tag!(b"elym"), ot_tag!(b"nand"), ot_tag!(b"hmnp"),
+     ot_tag!(b"wcho"), ot_tag!(b"chrs"), ot_tag!(b"diak"), ot_tag!(b"kits"), ot_tag!(b"yezi"),
+     ot_tag!(b"cpmn"), ot_tag!(b"ougr"), ot_tag!(b"tnsa"), ot_tag!(b"toto"), ot_tag!(b"vith"),
+     ot_tag!(b"kawi"), ot_tag!(b"nagm"), ot_tag!(b"gara"), ot_tag!(b"gukh"), ot_tag!(b"krai"),
+     ot_tag!(b"onao"), ot_tag!(b"sunu"), ot_tag!(b"todr"), ot_tag!(b"tutg")].contains(&script)
+}
+
+// Syllable (cluster) types.
+const VIRAMA_TERMINATED_CLUSTER: u8 = 0;
+const SAKOT_TERMINATED_CLUSTER: u8 = 1;
+const STANDARD_CLUSTER: u8 = 2;
+const NUMBER_JOINER_TERMINATED_CLUSTER: u8 = 3;
+const NUMERAL_CLUSTER: u8 = 4;
+const SYMBOL_CLUSTER: u8 = 5;
+const HIEROGLYPH_CLUSTER: u8 = 6;
+const BROKEN_CLUSTER: u8 = 7;
+const NON_CLUSTER: u8 = 8;
+
+lazy_static! {
+    static ref GRAMMAR: SyllableGrammar = SyllableGrammar::new("
+        O = 0; B = 1; N = 4; GB = 5; CGJ = 6; SUB = 11; H = 12; HN = 13;
+        ZWNJ = 14; WJ = 16; R = 18; CS = 43; IS = 44; Sk = 48; G = 49; J = 50;
+        SB = 51; SE = 52; HVM = 53; HM = 54; HR = 55;
+        FAbv = 24; FBlw = 25; FPst = 26; MAbv = 27; MBlw = 28; MPst = 29;
+        MPre = 30; CMAbv = 31; CMBlw = 32; VAbv = 33; VBlw = 34; VPst = 35;
+        VPre = 22; VMAbv = 37; VMBlw = 38; VMPst = 39; VMPre = 23;
+        SMAbv = 41; SMBlw = 42; FMAbv = 45; FMBlw = 46; FMPst = 47;
+
+        h = H | HVM | IS | Sk;
+
+        consonant_modifiers = CMAbv* CMBlw* ((h B | SUB) CMAbv* CMBlw*)*;
+        medial_consonants = MPre? MAbv? MBlw? MPst?;
+        dependent_vowels = VPre* VAbv* VBlw* VPst* | H;
+        vowel_modifiers = HVM? VMPre* VMAbv* VMBlw* VMPst*;
+        final_consonants = FAbv* FBlw* FPst*;
+        final_modifiers = FMAbv* FMBlw* | FMPst?;
+
+        complex_syllable_start = (R | CS)? (B | GB);
+        complex_syllable_middle = consonant_modifiers medial_consonants dependent_vowels
+                                  vowel_modifiers (Sk B)*;
+        complex_syllable_tail = complex_syllable_middle final_consonants final_modifiers;
+        number_joiner_terminated_cluster_tail = (HN N)* HN;
+        numeral_cluster_tail = (HN N)+;
+        symbol_cluster_tail = SMAbv+ SMBlw* | SMBlw+;
+
+        virama_terminated_cluster_tail = consonant_modifiers IS;
+        virama_terminated_cluster = complex_syllable_start virama_terminated_cluster_tail;
+        sakot_terminated_cluster_tail = complex_syllable_middle Sk;
+        sakot_terminated_cluster = complex_syllable_start sakot_terminated_cluster_tail;
+        standard_cluster = complex_syllable_start complex_syllable_tail;
+        tail = complex_syllable_tail | sakot_terminated_cluster_tail | symbol_cluster_tail |
+               virama_terminated_cluster_tail;
+        broken_cluster = R? (tail | number_joiner_terminated_cluster_tail | numeral_cluster_tail);
+
+        number_joiner_terminated_cluster = N number_joiner_terminated_cluster_tail;
+        numeral_cluster = N numeral_cluster_tail?;
+        symbol_cluster = (O | GB | SB) tail?;
+        hieroglyph_cluster = SB* G HR? HM? SE* (J SB* (G HR? HM? SE*)?)*;
+    ", &[
+        ("virama_terminated_cluster ZWNJ?", VIRAMA_TERMINATED_CLUSTER),
+        ("sakot_terminated_cluster ZWNJ?", SAKOT_TERMINATED_CLUSTER),
+        ("standard_cluster ZWNJ?", STANDARD_CLUSTER),
+        ("number_joiner_terminated_cluster ZWNJ?", NUMBER_JOINER_TERMINATED_CLUSTER),
+        ("numeral_cluster ZWNJ?", NUMERAL_CLUSTER),
+        ("symbol_cluster ZWNJ?", SYMBOL_CLUSTER),
+        ("hieroglyph_cluster ZWNJ?", HIEROGLYPH_CLUSTER),
+        ("broken_cluster ZWNJ?", BROKEN_CLUSTER),
+        ("any", NON_CLUSTER),
+    ]);
+}
+
+const BASIC_FEATURES: [u32; 7] = [
+    ot_tag!(b"rkrf"),
+    ot_tag!(b"abvf"),
+    ot_tag!(b"blwf"),
+    ot_tag!(b"half"),
+    ot_tag!(b"pstf"),
+    ot_tag!(b"vatu"),
+    ot_tag!(b"cjct"),
+];
+
+// In the order of the forms a cluster can take: isolated, initial,
+// medial, final.
+const TOPOGRAPHICAL_FEATURES: [u32; 4] = [
+    ot_tag!(b"isol"),
+    ot_tag!(b"init"),
+    ot_tag!(b"medi"),
+    ot_tag!(b"fina"),
+];
+const ISOLATED: usize = 0;
+const INITIAL: usize = 1;
+const MEDIAL: usize = 2;
+const TERMINAL: usize = 3;
+
+const OTHER_FEATURES: [u32; 5] = [
+    ot_tag!(b"abvs"),
+    ot_tag!(b"blws"),
+    ot_tag!(b"haln"),
+    ot_tag!(b"pres"),
+    ot_tag!(b"psts"),
+];
+
+// Categories that follow the base.
+const POST_BASE_CATEGORIES: [u8; 18] = [
+    F_ABV, F_BLW, F_PST, FM_ABV, FM_BLW, FM_PST, M_ABV, M_BLW, M_PST, M_PRE,
+    V_ABV, V_BLW, V_PST, V_PRE, VM_ABV, VM_BLW, VM_PST, VM_PRE,
+];
+
+fn collect_features(builder: &mut ShapePlanBuilder) {
+    builder.add_gsub_pause(Some(setup_syllables));
+
+    // Default glyph pre-processing group.
+    builder.add_syllable_feature(ot_tag!(b"locl"), true);
+    builder.add_syllable_feature(ot_tag!(b"ccmp"), true);
+    builder.add_syllable_feature(ot_tag!(b"nukt"), true);
+    builder.add_syllable_feature(ot_tag!(b"akhn"), true);
+
+    // Reordering group: which glyphs 'rphf' and 'pref' substituted
+    // changes their categories for reordering.
+    builder.add_gsub_pause(Some(shaper_syllabic::clear_substitution_flags));
+    builder.add_syllable_feature(ot_tag!(b"rphf"), false);
+    builder.add_gsub_pause(Some(record_rphf));
+    builder.add_gsub_pause(Some(shaper_syllabic::clear_substitution_flags));
+    builder.add_syllable_feature(ot_tag!(b"pref"), true);
+    builder.add_gsub_pause(Some(record_pref));
+
+    // Orthographic unit shaping group.
+    for &tag in &BASIC_FEATURES {
+        builder.add_syllable_feature(tag, true);
+    }
+    builder.add_gsub_pause(Some(reorder));
+    builder.add_gsub_pause(Some(shaper_syllabic::clear_syllables));
+
+    for &tag in &TOPOGRAPHICAL_FEATURES {
+        builder.add_feature(tag);
+    }
+    builder.add_gsub_pause(None);
+
+    // Standard typographic presentation.
+    for &tag in &OTHER_FEATURES {
+        builder.add_global_feature(tag);
+    }
+}
+
+fn setup_masks(plan: &ShapePlan, buffer: &mut GlyphBuffer) {
+    // Scripts with joining type data join letter by letter, like
+    // Arabic.
+    if shaper_arabic::is_joining_script(plan.script) {
+        shaper_arabic::setup_masks(plan, buffer);
+    }
+    for info in &mut buffer.glyphs {
+        info.shaper_category = unicode_data::use_category(info.codepoint);
+    }
+}
+
+fn setup_syllables(plan: &ShapePlan, _: &Shaper, _: &FontFace, buffer: &mut GlyphBuffer) {
+    // The grammar doesn't see CGJs, or ZWNJs followed by a mark; they
+    // go with the cluster they're in.
+    let included: Vec<usize> = (0..buffer.len()).filter(|&i| is_included(buffer, i)).collect();
+    let categories: Vec<u8> = included.iter().map(|&i| buffer.glyphs[i].shaper_category).collect();
+    let mut syllables = GRAMMAR.find_syllables(&categories);
+    for (n, syllable) in syllables.iter_mut().enumerate() {
+        syllable.0 = if n == 0 { 0 } else { included[syllable.0] };
+        syllable.1 = if syllable.1 == included.len() { buffer.len() } else { included[syllable.1] };
+    }
+    if syllables.is_empty() && !buffer.is_empty() {
+        syllables.push((0, buffer.len(), NON_CLUSTER));
+    }
+    shaper_syllabic::mark_syllables(buffer, &syllables);
+
+    setup_rphf_mask(plan, buffer);
+    setup_topographical_masks(plan, buffer);
+}
+
+fn is_included(buffer: &GlyphBuffer, i: usize) -> bool {
+    match buffer.glyphs[i].shaper_category {
+        CGJ => false,
+        ZWNJ => {
+            match buffer.glyphs[i + 1..].iter().find(|g| g.shaper_category != CGJ) {
+                Some(next) => !next.is_mark(),
+                None => true,
+            }
+        }
+        _ => true,
+    }
+}
+
+// 'rphf' applies to a leading repha, or else to the first three glyphs
+// of a cluster, which may form a reph.
+fn setup_rphf_mask(plan: &ShapePlan, buffer: &mut GlyphBuffer) {
+    let mask = plan.mask_for(ot_tag!(b"rphf"));
+    if mask == 0 {
+        return;
+    }
+    for (start, end) in shaper_syllabic::syllables(buffer) {
+        let limit = if buffer.glyphs[start].shaper_category == R { 1 } else { (end - start).min(3) };
+        for info in &mut buffer.glyphs[start..start + limit] {
+            info.mask |= mask;
+        }
+    }
+}
+
+// Clusters join to their neighbours, and get the form features for
+// their position in the run of joined clusters.  Scripts that join
+// letter by letter have already had theirs set up.
+fn setup_topographical_masks(plan: &ShapePlan, buffer: &mut GlyphBuffer) {
+    if shaper_arabic::is_joining_script(plan.script) {
+        return;
+    }
+    let mut masks = [0; 4];
+    for (i, &tag) in TOPOGRAPHICAL_FEATURES.iter().enumerate() {
+        masks[i] = plan.mask_for(tag);
+        if masks[i] == shaping::GLOBAL_MASK {
+            masks[i] = 0;
+        }
+    }
+    let all_masks = masks.iter().fold(0, |all, m| all | m);
+    if all_masks == 0 {
+        return;
+    }
+
+    let mut last_start = 0;
+    let mut last_form = None;
+    for (start, end) in shaper_syllabic::syllables(buffer) {
+        let syllable_type = shaper_syllabic::syllable_type(&buffer.glyphs[start]);
+        if syllable_type == HIEROGLYPH_CLUSTER || syllable_type == NON_CLUSTER {
+            last_form = None;
+        } else {
+            let join = last_form == Some(TERMINAL) || last_form == Some(ISOLATED);
+            if join {
+                // The previous cluster now has a following neighbour.
+                let form = if last_form == Some(TERMINAL) { MEDIAL } else { INITIAL };
+                for info in &mut buffer.glyphs[last_start..start] {
+                    info.mask = (info.mask & !all_masks) | masks[form];
+                }
+            }
+            let form = if join { TERMINAL } else { ISOLATED };
+            last_form = Some(form);
+            for info in &mut buffer.glyphs[start..end] {
+                info.mask = (info.mask & !all_masks) | masks[form];
+            }
+        }
+        last_start = start;
+    }
+}
+
+// A glyph 'rphf' substituted is a repha from now on.
+fn record_rphf(plan: &ShapePlan, _: &Shaper, _: &FontFace, buffer: &mut GlyphBuffer) {
+    let mask = plan.mask_for(ot_tag!(b"rphf"));
+    if mask == 0 {
+        return;
+    }
+    for (start, end) in shaper_syllabic::syllables(buffer) {
+        for info in &mut buffer.glyphs[start..end] {
+            if info.mask & mask == 0 {
+                break;
+            }
+            if info.substituted {
+                info.shaper_category = R;
+                break;
+            }
+        }
+    }
+}
+
+// A glyph 'pref' substituted is reordered like a pre-base vowel.
+fn record_pref(_: &ShapePlan, _: &Shaper, _: &FontFace, buffer: &mut GlyphBuffer) {
+    for (start, end) in shaper_syllabic::syllables(buffer) {
+        if let Some(info) = buffer.glyphs[start..end].iter_mut().find(|g| g.substituted) {
+            info.shaper_category = V_PRE;
+        }
+    }
+}
+
+fn is_halant(category: u8, ligated: bool) -> bool {
+    [H, HVM, IS].contains(&category) && !ligated
+}
+
+fn reorder(_: &ShapePlan, shaper: &Shaper, face: &FontFace, buffer: &mut GlyphBuffer) {
+    shaper_syllabic::insert_dotted_circles(shaper, face, buffer, BROKEN_CLUSTER, B, Some(R), 0);
+
+    for (start, end) in shaper_syllabic::syllables(buffer) {
+        match shaper_syllabic::syllable_type(&buffer.glyphs[start]) {
+            VIRAMA_TERMINATED_CLUSTER | SAKOT_TERMINATED_CLUSTER | STANDARD_CLUSTER | BROKEN_CLUSTER => {
+                reorder_cluster(start, end, buffer);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn reorder_cluster(start: usize, end: usize, buffer: &mut GlyphBuffer) {
+    // Move a leading repha to just before the first post-base glyph, or
+    // to the end of the cluster.
+    if buffer.glyphs[start].shaper_category == R && end - start > 1 {
+        for i in start + 1..end {
+            let info = buffer.glyphs[i];
+            let is_post_base = POST_BASE_CATEGORIES.contains(&info.shaper_category) ||
+                               is_halant(info.shaper_category, info.ligated);
+            if is_post_base || i == end - 1 {
+                let i = if is_post_base { i - 1 } else { i };
+                buffer.merge_clusters(start, i + 1);
+                buffer.glyphs[start..i + 1].rotate_left(1);
+                break;
+            }
+        }
+    }
+
+    // Move pre-base vowels and vowel modifiers to just after the last
+    // halant before them, or to the start of the cluster.
+    let mut j = start;
+    for i in start..end {
+        let info = buffer.glyphs[i];
+        if is_halant(info.shaper_category, info.ligated) {
+            j = i + 1;
+        } else if (info.shaper_category == V_PRE || info.shaper_category == VM_PRE) &&
+                  info.lig_component == 0 && j < i {
+            buffer.merge_clusters(j, i + 1);
+            buffer.glyphs[j..i + 1].rotate_right(1);
+        }
+    }
+}
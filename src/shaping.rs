@@ -0,0 +1,805 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// A text shaper built on our GSUB/GPOS implementation: maps text to
+// glyphs, applies the OpenType features appropriate for the script, and
+// produces glyph indices, advances and offsets ready to be handed to
+// BitmapRenderTarget::draw_glyph_run or GlyphRunAnalysis::create.
+//
+// Text positions (clusters) are UTF-16 code unit offsets, like the
+// rest of DirectWrite.
+
+use glyph_buffer::{GlyphBuffer, GlyphInfo, TextDirection};
+use gsub::GsubTable;
+use gpos::GposTable;
+use gdef::GdefTable;
+use fallback_position;
+use kern::KernTable;
+use ot_layout::DEFAULT_SCRIPT;
+use shaper_arabic;
+use shaper_hangul;
+use shaper_indic;
+use shaper_khmer;
+use shaper_myanmar;
+use shaper_thai;
+use shaper_use;
+use unicode_data;
+use super::{FontFace, GlyphOffset};
+
+// Bit 0 of every glyph's mask is set; features that apply to the whole
+// run use it.
+pub const GLOBAL_MASK: u32 = 0x1;
+
+// mirrors DWRITE_FONT_FEATURE: a feature tag (as made by
+// make_opentype_tag) and its value.  A value of 0 turns off a feature
+// that would otherwise be on by default.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct FeatureSetting {
+    pub tag: u32,
+    pub value: u32,
+}
+
+impl FeatureSetting {
+    pub fn new(tag: u32, value: u32) -> FeatureSetting {
+        FeatureSetting {
+            tag: tag,
+            value: value,
+        }
+    }
+}
+
+// The output of shaping a run of text, in logical order.  For
+// right-to-left runs, draw with an odd bidi level and the glyphs will
+// be laid out from right to left starting at the baseline origin.
+#[derive(Debug, Clone)]
+pub struct ShapedGlyphs {
+    pub glyph_indices: Vec<u16>,
+    pub glyph_advances: Vec<f32>,
+    pub glyph_offsets: Vec<GlyphOffset>,
+    // For each UTF-16 code unit of the text, the index of the first
+    // glyph of the cluster it belongs to, like the cluster map
+    // returned by IDWriteTextAnalyzer::GetGlyphs.
+    pub cluster_map: Vec<u16>,
+    // For each glyph, the text position its cluster starts at.
+    pub glyph_clusters: Vec<u32>,
+    pub is_right_to_left: bool,
+    pub em_size: f32,
+}
+
+impl ShapedGlyphs {
+    pub fn bidi_level(&self) -> u32 {
+        if self.is_right_to_left { 1 } else { 0 }
+    }
+
+    pub fn total_advance(&self) -> f32 {
+        self.glyph_advances.iter().fold(0., |a, &b| a + b)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ZeroWidthMarks {
+    None,
+    // Zero the advance of GDEF marks before GPOS is applied...
+    ByGdefEarly,
+    // ... or after it.
+    ByGdefLate,
+}
+
+// Called between GSUB stages, e.g. for reordering that needs to see
+// the results of earlier features.
+pub type PauseFunc = fn(&ShapePlan, &Shaper, &FontFace, &mut GlyphBuffer);
+
+// Script-specific behavior layered on top of the generic shaping
+// pipeline.
+pub struct ComplexShaper {
+    // Adds the script's features (and pauses) to the plan.
+    pub collect_features: fn(&mut ShapePlanBuilder),
+    // Adjusts the plan once the features common to all scripts have
+    // been added, e.g. to turn off ones the script's spec doesn't want.
+    pub override_features: fn(&mut ShapePlanBuilder),
+    // Rewrites the text before it's mapped to glyphs, e.g. to compose
+    // or decompose characters depending on what the font supports.
+    pub preprocess_text: fn(&ShapePlan, &FontFace, &mut GlyphBuffer),
+    // Splits characters whose parts are shaped separately, such as
+    // Indic two-part vowel signs, before they're mapped to glyphs.
+    pub decompose: fn(u32) -> Option<(u32, u32)>,
+    // Sets per-glyph feature masks before GSUB runs.
+    pub setup_masks: fn(&ShapePlan, &mut GlyphBuffer),
+    // Adjusts the positioned glyphs (still in design units).
+    pub postprocess_glyphs: fn(&ShapePlan, &FontFace, &mut GlyphBuffer),
+    pub zero_width_marks: ZeroWidthMarks,
+    // Whether marks may be positioned from their combining classes
+    // when the font has no GPOS mark positioning; scripts whose marks
+    // are placed by reordering and GSUB forms leave them alone.
+    pub fallback_position: bool,
+}
+
+pub fn collect_no_features(_: &mut ShapePlanBuilder) {}
+pub fn override_no_features(_: &mut ShapePlanBuilder) {}
+pub fn preprocess_nothing(_: &ShapePlan, _: &FontFace, _: &mut GlyphBuffer) {}
+pub fn decompose_nothing(_: u32) -> Option<(u32, u32)> { None }
+pub fn setup_no_masks(_: &ShapePlan, _: &mut GlyphBuffer) {}
+pub fn postprocess_nothing(_: &ShapePlan, _: &FontFace, _: &mut GlyphBuffer) {}
+
+pub static DEFAULT_SHAPER: ComplexShaper = ComplexShaper {
+    collect_features: collect_no_features,
+    override_features: override_no_features,
+    preprocess_text: preprocess_nothing,
+    decompose: decompose_nothing,
+    setup_masks: setup_no_masks,
+    postprocess_glyphs: postprocess_nothing,
+    zero_width_marks: ZeroWidthMarks::ByGdefLate,
+    fallback_position: true,
+};
+
+// (original tag, tag for fonts made for the revised Indic shaping
+// spec, tag for fonts made for the Universal Shaping Engine).
+const INDIC_SCRIPT_TAGS: [(u32, u32, u32); 10] = [
+    (ot_tag!(b"deva"), ot_tag!(b"dev2"), ot_tag!(b"dev3")),
+    (ot_tag!(b"beng"), ot_tag!(b"bng2"), ot_tag!(b"bng3")),
+    (ot_tag!(b"guru"), ot_tag!(b"gur2"), ot_tag!(b"gur3")),
+    (ot_tag!(b"gujr"), ot_tag!(b"gjr2"), ot_tag!(b"gjr3")),
+    (ot_tag!(b"orya"), ot_tag!(b"ory2"), ot_tag!(b"ory3")),
+    (ot_tag!(b"taml"), ot_tag!(b"tml2"), ot_tag!(b"tml3")),
+    (ot_tag!(b"telu"), ot_tag!(b"tel2"), ot_tag!(b"tel3")),
+    (ot_tag!(b"knda"), ot_tag!(b"knd2"), ot_tag!(b"knd3")),
+    (ot_tag!(b"mlym"), ot_tag!(b"mlm2"), ot_tag!(b"mlm3")),
+    (ot_tag!(b"mymr"), ot_tag!(b"mym2"), 0),
+];
+
+// Accepts any of a script's tags, returning the original one.
+fn base_script_tag(script: u32) -> u32 {
+    INDIC_SCRIPT_TAGS.iter()
+        .find(|t| script == t.1 || (script == t.2 && t.2 != 0))
+        .map_or(script, |t| t.0)
+}
+
+// The script tags to look for in a font, best first.
+pub fn script_tag_candidates(script: u32) -> Vec<u32> {
+    match INDIC_SCRIPT_TAGS.iter().find(|t| t.0 == script) {
+        Some(&(old, new, 0)) => vec![new, old],
+        Some(&(old, new, universal)) => vec![universal, new, old],
+        None => vec![script],
+    }
+}
+
+// The last character of a tag, e.g. '2' for 'dev2'.
+fn tag_version(tag: u32) -> u8 {
+    (tag >> 24) as u8
+}
+
+// Which shaper to use depends on the script the font's GSUB table was
+// found to support: a font that only has lookups for 'DFLT' (or that
+// we fell back to 'latn' for) wasn't made with the script's shaping
+// model in mind, and gets the default shaper.
+fn complex_shaper_for_script(script: u32, gsub_script: Option<u32>) -> &'static ComplexShaper {
+    let made_for_script = match gsub_script {
+        Some(tag) => tag != DEFAULT_SCRIPT && tag != ot_tag!(b"dflt") && tag != ot_tag!(b"latn"),
+        None => true,
+    };
+    if script == ot_tag!(b"arab") || (script == ot_tag!(b"syrc") && gsub_script != Some(DEFAULT_SCRIPT)) {
+        return &shaper_arabic::ARABIC_SHAPER;
+    }
+    if script == ot_tag!(b"khmr") {
+        return &shaper_khmer::KHMER_SHAPER;
+    }
+    if script == ot_tag!(b"hang") {
+        return &shaper_hangul::HANGUL_SHAPER;
+    }
+    if script == ot_tag!(b"thai") || script == ot_tag!(b"lao ") {
+        return &shaper_thai::THAI_SHAPER;
+    }
+    if !made_for_script {
+        return &DEFAULT_SHAPER;
+    }
+    if script == ot_tag!(b"mymr") {
+        // 'mymr' fonts predate the Myanmar shaping spec.
+        if gsub_script == Some(ot_tag!(b"mymr")) {
+            return &DEFAULT_SHAPER;
+        }
+        return &shaper_myanmar::MYANMAR_SHAPER;
+    }
+    if INDIC_SCRIPT_TAGS.iter().any(|t| t.0 == script) {
+        if gsub_script.map_or(false, |tag| tag_version(tag) == b'3') {
+            return &shaper_use::USE_SHAPER;
+        }
+        return &shaper_indic::INDIC_SHAPER;
+    }
+    if shaper_use::is_use_script(script) {
+        return &shaper_use::USE_SHAPER;
+    }
+    &DEFAULT_SHAPER
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FeatureRequest {
+    tag: u32,
+    value: u32,
+    global: bool,
+    per_syllable: bool,
+    stage: usize,
+}
+
+pub struct ShapePlanBuilder {
+    pub script: u32,
+    pub language: u32,
+    pub direction: TextDirection,
+    // The script tag picked from the font's GSUB table, if it has one.
+    pub gsub_script: Option<u32>,
+    features: Vec<FeatureRequest>,
+    pauses: Vec<Option<PauseFunc>>,
+}
+
+impl ShapePlanBuilder {
+    fn new(script: u32, language: u32, direction: TextDirection, gsub_script: Option<u32>) -> ShapePlanBuilder {
+        ShapePlanBuilder {
+            script: script,
+            language: language,
+            direction: direction,
+            gsub_script: gsub_script,
+            features: vec![],
+            pauses: vec![],
+        }
+    }
+
+    // A feature that applies to every glyph in the run.
+    pub fn add_global_feature(&mut self, tag: u32) {
+        self.add_feature_with_value(tag, 1, true);
+    }
+
+    // A feature that only applies to glyphs whose masks the shaper
+    // sets up with ShapePlan::mask_for.
+    pub fn add_feature(&mut self, tag: u32) {
+        self.add_feature_with_value(tag, 1, false);
+    }
+
+    pub fn add_feature_with_value(&mut self, tag: u32, value: u32, global: bool) {
+        self.add_feature_request(tag, value, global, false);
+    }
+
+    // A feature whose lookups can't match across syllables, for
+    // shapers that set GlyphInfo::syllable.
+    pub fn add_syllable_feature(&mut self, tag: u32, global: bool) {
+        self.add_feature_request(tag, 1, global, true);
+    }
+
+    // Turns off a feature, unless the user asks for it.
+    pub fn disable_feature(&mut self, tag: u32) {
+        self.add_feature_with_value(tag, 0, true);
+    }
+
+    fn add_feature_request(&mut self, tag: u32, value: u32, global: bool, per_syllable: bool) {
+        let stage = self.pauses.len();
+        if let Some(f) = self.features.iter_mut().find(|f| f.tag == tag) {
+            f.value = value;
+            f.global = f.global && global;
+            f.per_syllable = f.per_syllable || per_syllable;
+            return;
+        }
+        self.features.push(FeatureRequest {
+            tag: tag,
+            value: value,
+            global: global,
+            per_syllable: per_syllable,
+            stage: stage,
+        });
+    }
+
+    // Ends the current GSUB stage.  Lookups of features added after
+    // this are applied after all lookups of features added before it,
+    // and `pause` (if any) runs in between.
+    pub fn add_gsub_pause(&mut self, pause: Option<PauseFunc>) {
+        self.pauses.push(pause);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LookupEntry {
+    index: u16,
+    mask: u32,
+    value: u32,
+    per_syllable: bool,
+}
+
+struct GsubStage {
+    lookups: Vec<LookupEntry>,
+    pause: Option<PauseFunc>,
+}
+
+pub struct ShapePlan {
+    pub script: u32,
+    pub gsub_script: Option<u32>,
+    // The script tag picked from the font's GPOS table, if it has one.
+    pub gpos_script: Option<u32>,
+    pub shaper: &'static ComplexShaper,
+    masks: Vec<(u32, u32)>,
+    // The GSUB lookups of each enabled feature, for would_substitute.
+    gsub_feature_lookups: Vec<(u32, Vec<u16>)>,
+    gsub_stages: Vec<GsubStage>,
+    gpos_lookups: Vec<LookupEntry>,
+    apply_fallback_kern: bool,
+    // Set when the font has no GPOS mark attachment for the script.
+    apply_fallback_position: bool,
+}
+
+impl ShapePlan {
+    pub fn new(shaper: &Shaper, script: u32, language: u32, direction: TextDirection, is_vertical: bool,
+               user_features: &[FeatureSetting]) -> ShapePlan {
+        let script = base_script_tag(script);
+        let candidates = script_tag_candidates(script);
+        let gsub_script = shaper.gsub.as_ref().and_then(|t| t.layout_table().select_script(&candidates));
+        let complex = complex_shaper_for_script(script, gsub_script);
+        let mut builder = ShapePlanBuilder::new(script, language, direction, gsub_script);
+
+        // The builder's direction and language pick the direction
+        // features here and the LangSys in compile().
+        match builder.direction {
+            TextDirection::LeftToRight => {
+                builder.add_global_feature(ot_tag!(b"ltra"));
+                builder.add_global_feature(ot_tag!(b"ltrm"));
+            }
+            TextDirection::RightToLeft => {
+                builder.add_global_feature(ot_tag!(b"rtla"));
+                // Only for characters that couldn't be mirrored by
+                // picking their mirror image's glyph.
+                builder.add_feature(ot_tag!(b"rtlm"));
+            }
+        }
+        builder.add_global_feature(ot_tag!(b"rvrn"));
+        builder.add_gsub_pause(None);
+
+        (complex.collect_features)(&mut builder);
+
+        for tag in &[b"abvm", b"blwm", b"ccmp", b"locl", b"mark", b"mkmk", b"rlig",
+                     b"calt", b"clig", b"liga", b"rclt"] {
+            builder.add_global_feature(ot_tag!(tag));
+        }
+        if is_vertical {
+            // Fonts with 'vrt2' mean it to be used instead of 'vert'.
+            let has_vrt2 = match (shaper.gsub.as_ref(), gsub_script) {
+                (Some(gsub), Some(script)) => {
+                    !gsub.lookups_for_feature(script, builder.language, ot_tag!(b"vrt2")).is_empty()
+                }
+                _ => false,
+            };
+            builder.add_global_feature(if has_vrt2 { ot_tag!(b"vrt2") } else { ot_tag!(b"vert") });
+            builder.add_global_feature(ot_tag!(b"vkrn"));
+        } else {
+            for tag in &[b"curs", b"dist", b"kern"] {
+                builder.add_global_feature(ot_tag!(tag));
+            }
+        }
+        (complex.override_features)(&mut builder);
+        for f in user_features {
+            builder.add_feature_with_value(f.tag, f.value, true);
+        }
+
+        ShapePlan::compile(shaper, builder, complex)
+    }
+
+    fn compile(shaper: &Shaper, builder: ShapePlanBuilder, complex: &'static ComplexShaper) -> ShapePlan {
+        let gsub_script = builder.gsub_script;
+        let gpos_script = shaper.gpos.as_ref()
+            .and_then(|t| t.layout_table().select_script(&script_tag_candidates(builder.script)));
+
+        let mut gsub_stages: Vec<GsubStage> = builder.pauses.iter().map(|&p| GsubStage {
+            lookups: vec![],
+            pause: p,
+        }).collect();
+        gsub_stages.push(GsubStage { lookups: vec![], pause: None });
+        let mut gpos_lookups = vec![];
+        let mut gsub_feature_lookups = vec![];
+        let mut masks = vec![];
+        let mut next_bit = 1;
+        let mut has_gpos_kern = false;
+        let mut has_gpos_mark = false;
+        let mut kern_mask = 0;
+
+        for f in &builder.features {
+            if f.value == 0 {
+                continue;
+            }
+            let mask = if f.global {
+                GLOBAL_MASK
+            } else if next_bit < 32 {
+                next_bit += 1;
+                1 << (next_bit - 1)
+            } else {
+                continue;
+            };
+            masks.push((f.tag, mask));
+            if f.tag == ot_tag!(b"kern") {
+                kern_mask = mask;
+            }
+
+            if let (Some(gsub), Some(script)) = (shaper.gsub.as_ref(), gsub_script) {
+                let lookups = gsub.lookups_for_feature(script, builder.language, f.tag);
+                for &index in &lookups {
+                    gsub_stages[f.stage].lookups.push(LookupEntry {
+                        index: index,
+                        mask: mask,
+                        value: f.value,
+                        per_syllable: f.per_syllable,
+                    });
+                }
+                gsub_feature_lookups.push((f.tag, lookups));
+            }
+            if let (Some(gpos), Some(script)) = (shaper.gpos.as_ref(), gpos_script) {
+                let lookups = gpos.lookups_for_feature(script, builder.language, f.tag);
+                if f.tag == ot_tag!(b"kern") && !lookups.is_empty() {
+                    has_gpos_kern = true;
+                }
+                if (f.tag == ot_tag!(b"mark") || f.tag == ot_tag!(b"mkmk")) && !lookups.is_empty() {
+                    has_gpos_mark = true;
+                }
+                for index in lookups {
+                    gpos_lookups.push(LookupEntry { index: index, mask: mask, value: f.value, per_syllable: false });
+                }
+            }
+        }
+
+        // Within a stage, lookups are applied in lookup list order no
+        // matter which feature they came from.
+        for stage in &mut gsub_stages {
+            stage.lookups.sort_by_key(|l| l.index);
+        }
+        gpos_lookups.sort_by_key(|l| l.index);
+
+        ShapePlan {
+            script: builder.script,
+            gsub_script: gsub_script,
+            gpos_script: gpos_script,
+            shaper: complex,
+            masks: masks,
+            gsub_feature_lookups: gsub_feature_lookups,
+            gsub_stages: gsub_stages,
+            gpos_lookups: gpos_lookups,
+            apply_fallback_kern: kern_mask != 0 && !has_gpos_kern,
+            apply_fallback_position: complex.fallback_position && !has_gpos_mark,
+        }
+    }
+
+    // The mask bit a (non-global) feature was given; 0 if the feature
+    // isn't part of the plan.
+    pub fn mask_for(&self, tag: u32) -> u32 {
+        self.masks.iter().find(|m| m.0 == tag).map(|m| m.1).unwrap_or(0)
+    }
+
+    // Whether one of the feature's lookups would substitute `glyphs`
+    // as a whole (see GsubTable::would_apply).  Shapers use this to
+    // find out which forms the font has, e.g. whether a consonant has
+    // a below-base form.
+    pub fn would_substitute(&self, shaper: &Shaper, tag: u32, glyphs: &[u16]) -> bool {
+        let gsub = match shaper.gsub {
+            Some(ref gsub) => gsub,
+            None => return false,
+        };
+        self.gsub_feature_lookups.iter()
+            .filter(|f| f.0 == tag)
+            .any(|f| f.1.iter().any(|&index| gsub.would_apply(index, glyphs)))
+    }
+}
+
+// The font tables shaping needs, loaded once per face.
+#[derive(Debug)]
+pub struct Shaper {
+    pub gsub: Option<GsubTable>,
+    pub gpos: Option<GposTable>,
+    pub gdef: Option<GdefTable>,
+    pub kern: Option<KernTable>,
+}
+
+impl Shaper {
+    pub fn new(face: &FontFace) -> Shaper {
+        Shaper {
+            gsub: GsubTable::from_font_face(face),
+            gpos: GposTable::from_font_face(face),
+            gdef: GdefTable::from_font_face(face),
+            kern: KernTable::from_font_face(face),
+        }
+    }
+
+    // Shapes `text` with a face the tables were loaded from.
+    pub fn shape(&self,
+                 face: &FontFace,
+                 text: &str,
+                 em_size: f32,
+                 script: u32,
+                 language: u32,
+                 direction: TextDirection,
+                 features: &[FeatureSetting])
+        -> ShapedGlyphs
+    {
+        let mut buffer = buffer_from_text(text);
+        buffer.direction = direction;
+        self.shape_buffer(face, &mut buffer, script, language, features);
+
+        let text_len = text.encode_utf16().count();
+        shaped_glyphs_from_buffer(face, &buffer, text_len, em_size)
+    }
+
+    // Shapes `text` to be set upright in a vertical line, top to
+    // bottom: with the font's vertical forms ('vrt2', or 'vert' if it
+    // has none), the advances from its vertical metrics, and its 'vkrn'
+    // and mark positioning.  The glyphs are drawn sideways (see
+    // BitmapRenderTarget::draw_glyph_run_sideways).
+    pub fn shape_vertical(&self,
+                          face: &FontFace,
+                          text: &str,
+                          em_size: f32,
+                          script: u32,
+                          language: u32,
+                          features: &[FeatureSetting])
+        -> ShapedGlyphs
+    {
+        let mut buffer = buffer_from_text(text);
+        buffer.is_vertical = true;
+        self.shape_buffer(face, &mut buffer, script, language, features);
+
+        let text_len = text.encode_utf16().count();
+        shaped_glyphs_from_buffer(face, &buffer, text_len, em_size)
+    }
+
+    // Whether the font has a vertical form of `glyph`, which UAX #50
+    // characters with a Tr orientation are set upright with.
+    pub fn has_vertical_form(&self, glyph: u16, script: u32, language: u32) -> bool {
+        let gsub = match self.gsub {
+            Some(ref gsub) => gsub,
+            None => return false,
+        };
+        let script = match gsub.layout_table().select_script(&script_tag_candidates(base_script_tag(script))) {
+            Some(script) => script,
+            None => return false,
+        };
+        [ot_tag!(b"vrt2"), ot_tag!(b"vert")].iter().any(|&tag| {
+            gsub.lookups_for_feature(script, language, tag).iter().any(|&index| gsub.would_apply(index, &[glyph]))
+        })
+    }
+
+    // Runs the whole pipeline on a buffer of code points (as made by
+    // buffer_from_text), leaving glyphs and positions in design units.
+    pub fn shape_buffer(&self,
+                        face: &FontFace,
+                        buffer: &mut GlyphBuffer,
+                        script: u32,
+                        language: u32,
+                        features: &[FeatureSetting]) {
+        let plan = ShapePlan::new(self, script, language, buffer.direction, buffer.is_vertical, features);
+
+        (plan.shaper.preprocess_text)(&plan, face, buffer);
+        decompose_characters(face, buffer, plan.shaper.decompose);
+        let code_points: Vec<u32> = buffer.glyphs.iter().map(|g| g.codepoint).collect();
+        let glyph_indices = face.get_glyph_indices(&code_points);
+        for (info, &g) in buffer.glyphs.iter_mut().zip(glyph_indices.iter()) {
+            info.glyph_index = g;
+            info.mask = GLOBAL_MASK;
+        }
+        if buffer.direction == TextDirection::RightToLeft {
+            mirror_characters(&plan, face, buffer);
+        }
+        if let Some(ref gdef) = self.gdef {
+            gdef.classify(buffer);
+        }
+
+        (plan.shaper.setup_masks)(&plan, buffer);
+
+        if let Some(ref gsub) = self.gsub {
+            for stage in &plan.gsub_stages {
+                for l in &stage.lookups {
+                    buffer.restrict_to_syllable = l.per_syllable;
+                    gsub.apply_lookup_with_value(l.index, buffer, l.mask, l.value);
+                }
+                buffer.restrict_to_syllable = false;
+                if let Some(pause) = stage.pause {
+                    pause(&plan, self, face, buffer);
+                }
+            }
+        } else {
+            for stage in &plan.gsub_stages {
+                if let Some(pause) = stage.pause {
+                    pause(&plan, self, face, buffer);
+                }
+            }
+        }
+
+        self.position(&plan, face, buffer);
+        (plan.shaper.postprocess_glyphs)(&plan, face, buffer);
+    }
+
+    fn position(&self, plan: &ShapePlan, face: &FontFace, buffer: &mut GlyphBuffer) {
+        let glyph_indices = buffer.glyph_indices();
+        let metrics = face.get_design_glyph_metrics(&glyph_indices, buffer.is_vertical);
+        // Vertical runs are drawn from each glyph's vertical origin, but
+        // GPOS anchors are relative to the horizontal one, so until GPOS
+        // is done the offsets hold the horizontal origin's position
+        // relative to the vertical one.
+        let vertical_origins: Vec<(i32, i32)> = if buffer.is_vertical {
+            metrics.iter().map(|m| (m.advanceWidth as i32 / 2, m.verticalOriginY)).collect()
+        } else {
+            vec![]
+        };
+        if buffer.is_vertical {
+            buffer.init_positions(&vec![0; metrics.len()]);
+            for ((pos, m), origin) in buffer.positions.iter_mut().zip(metrics.iter()).zip(vertical_origins.iter()) {
+                pos.y_advance = -(m.advanceHeight as i32);
+                pos.x_offset = -origin.0;
+                pos.y_offset = -origin.1;
+            }
+        } else {
+            let advances: Vec<i32> = metrics.iter().map(|m| m.advanceWidth as i32).collect();
+            buffer.init_positions(&advances);
+        }
+
+        if plan.shaper.zero_width_marks == ZeroWidthMarks::ByGdefEarly {
+            zero_mark_advances(buffer);
+        }
+
+        if let Some(ref gpos) = self.gpos {
+            for l in &plan.gpos_lookups {
+                gpos.apply_lookup(l.index, buffer, l.mask);
+            }
+        }
+        if plan.apply_fallback_kern {
+            if let Some(ref kern) = self.kern {
+                kern.apply(buffer, plan.mask_for(ot_tag!(b"kern")));
+            }
+        }
+
+        if plan.shaper.zero_width_marks == ZeroWidthMarks::ByGdefLate {
+            zero_mark_advances(buffer);
+        }
+        // The fallback mark positions are worked out along a horizontal
+        // baseline, so vertical runs only get the font's own.
+        if plan.apply_fallback_position && !buffer.is_vertical {
+            fallback_position::position_marks(face, buffer);
+        }
+
+        buffer.resolve_attachments();
+        for (pos, origin) in buffer.positions.iter_mut().zip(vertical_origins.iter()) {
+            pos.x_offset += origin.0;
+            pos.y_offset += origin.1;
+        }
+    }
+}
+
+// Splits the characters the shaper wants decomposed, if the font has
+// glyphs for all of their parts.  The parts stay in the character's
+// cluster.
+fn decompose_characters(face: &FontFace, buffer: &mut GlyphBuffer, decompose: fn(u32) -> Option<(u32, u32)>) {
+    if !buffer.glyphs.iter().any(|g| decompose(g.codepoint).is_some()) {
+        return;
+    }
+    let mut glyphs = Vec::with_capacity(buffer.len() + 1);
+    for info in &buffer.glyphs {
+        let mut parts = vec![];
+        let mut cp = info.codepoint;
+        while let Some((first, last)) = decompose(cp) {
+            parts.insert(0, last);
+            cp = first;
+        }
+        parts.insert(0, cp);
+        if parts.len() > 1 && face.get_glyph_indices(&parts).iter().all(|&g| g != 0) {
+            for &part in &parts {
+                glyphs.push(GlyphInfo { codepoint: part, ..*info });
+            }
+        } else {
+            glyphs.push(*info);
+        }
+    }
+    buffer.glyphs = glyphs;
+}
+
+// Maps characters with a Bidi_Mirroring_Glyph, like brackets, to the
+// glyph of their mirror image in right-to-left runs.  The rest are
+// left to the font's 'rtlm' feature.
+fn mirror_characters(plan: &ShapePlan, face: &FontFace, buffer: &mut GlyphBuffer) {
+    let rtlm_mask = plan.mask_for(ot_tag!(b"rtlm"));
+    for info in &mut buffer.glyphs {
+        if let Some(mirror) = unicode_data::bidi_mirror(info.codepoint) {
+            let glyph = face.get_glyph_indices(&[mirror])[0];
+            if glyph != 0 {
+                info.codepoint = mirror;
+                info.glyph_index = glyph;
+                continue;
+            }
+        }
+        info.mask |= rtlm_mask;
+    }
+}
+
+fn zero_mark_advances(buffer: &mut GlyphBuffer) {
+    for (info, pos) in buffer.glyphs.iter().zip(buffer.positions.iter_mut()) {
+        if info.is_mark() {
+            pos.x_advance = 0;
+            pos.y_advance = 0;
+        }
+    }
+}
+
+// Makes a buffer with one entry per character of `text`, with
+// clusters set to UTF-16 offsets.  Glyph indices are filled in by
+// shaping.
+pub fn buffer_from_text(text: &str) -> GlyphBuffer {
+    let mut buffer = GlyphBuffer::new();
+    let mut offset = 0;
+    for c in text.chars() {
+        buffer.glyphs.push(GlyphInfo::new(0, c as u32, offset));
+        offset += c.len_utf16() as u32;
+    }
+    buffer
+}
+
+// Scales a shaped buffer to `em_size` and converts it into the form
+// DWRITE_GLYPH_RUN wants.
+pub fn shaped_glyphs_from_buffer(face: &FontFace, buffer: &GlyphBuffer, text_len: usize, em_size: f32) -> ShapedGlyphs {
+    let scale = em_size / face.metrics().designUnitsPerEm as f32;
+    let is_rtl = buffer.direction == TextDirection::RightToLeft;
+
+    let glyph_advances = buffer.positions.iter()
+        .map(|p| if buffer.is_vertical { -p.y_advance } else { p.x_advance } as f32 * scale)
+        .collect();
+    // DWRITE_GLYPH_OFFSET's advanceOffset is along the direction of
+    // the run, not the x axis.  Vertical runs are turned 90 degrees
+    // right, so their ascender side is the glyphs' right.
+    let glyph_offsets = buffer.positions.iter().map(|p| if buffer.is_vertical {
+        GlyphOffset {
+            advanceOffset: -p.y_offset as f32 * scale,
+            ascenderOffset: p.x_offset as f32 * scale,
+        }
+    } else {
+        GlyphOffset {
+            advanceOffset: if is_rtl { -p.x_offset as f32 } else { p.x_offset as f32 } * scale,
+            ascenderOffset: p.y_offset as f32 * scale,
+        }
+    }).collect();
+
+    ShapedGlyphs {
+        glyph_indices: buffer.glyph_indices(),
+        glyph_advances: glyph_advances,
+        glyph_offsets: glyph_offsets,
+        cluster_map: build_cluster_map(buffer, text_len),
+        glyph_clusters: buffer.clusters(),
+        is_right_to_left: is_rtl,
+        em_size: em_size,
+    }
+}
+
+// Text positions between two cluster starts belong to the earlier
+// cluster.  Clusters are expected to be non-decreasing in the buffer.
+fn build_cluster_map(buffer: &GlyphBuffer, text_len: usize) -> Vec<u16> {
+    let mut cluster_map = vec![0u16; text_len];
+    let mut i = 0;
+    while i < buffer.len() {
+        let cluster = buffer.glyphs[i].cluster as usize;
+        let mut j = i + 1;
+        while j < buffer.len() && buffer.glyphs[j].cluster as usize == cluster {
+            j += 1;
+        }
+        let end = if j < buffer.len() {
+            buffer.glyphs[j].cluster as usize
+        } else {
+            text_len
+        };
+        for position in cluster.min(text_len)..end.min(text_len) {
+            cluster_map[position] = i as u16;
+        }
+        i = j;
+    }
+    cluster_map
+}
+
+// Shapes a run of text in a single font, script and direction.
+// `script` and `language` are OpenType tags as made by
+// make_opentype_tag, e.g. 'latn' and 'dflt'.
+pub fn shape(text: &str,
+             face: &FontFace,
+             em_size: f32,
+             script: u32,
+             language: u32,
+             direction: TextDirection,
+             features: &[FeatureSetting])
+    -> ShapedGlyphs
+{
+    Shaper::new(face).shape(face, text, em_size, script, language, direction, features)
+}
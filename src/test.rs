@@ -120,3 +120,1595 @@ fn test_glyph_image() {
     let bytes = rt.get_opaque_values_as_mask();
     println!("bytes length: {}", bytes.len());
 }
+
+#[test]
+fn test_gsub_single_and_ligature() {
+    // A GSUB with no scripts or features and two lookups:
+    // 0: single substitution (format 1), glyph 10 -> 15
+    // 1: ligature substitution, 20 21 22 -> 99
+    let gsub_data: Vec<u8> = vec![
+        0, 1, 0, 0, 0, 10, 0, 10, 0, 12,
+        // ScriptList/FeatureList (empty)
+        0, 0,
+        // LookupList
+        0, 2, 0, 6, 0, 26,
+        // Lookup 0
+        0, 1, 0, 0, 0, 1, 0, 8,
+        0, 1, 0, 6, 0, 5,
+        0, 1, 0, 1, 0, 10,
+        // Lookup 1
+        0, 4, 0, 0, 0, 1, 0, 8,
+        0, 1, 0, 8, 0, 1, 0, 14,
+        0, 1, 0, 1, 0, 20,
+        0, 1, 0, 4,
+        0, 99, 0, 3, 0, 21, 0, 22,
+    ];
+    let gsub = GsubTable::new(gsub_data).unwrap();
+    assert_eq!(gsub.lookup_count(), 2);
+
+    let mut buffer = GlyphBuffer::from_glyph_indices(&[10, 20, 21, 22, 5]);
+    gsub.apply_lookup(0, &mut buffer, !0);
+    assert_eq!(buffer.glyph_indices(), vec![15, 20, 21, 22, 5]);
+    gsub.apply_lookup(1, &mut buffer, !0);
+    assert_eq!(buffer.glyph_indices(), vec![15, 99, 5]);
+    assert_eq!(buffer.clusters(), vec![0, 1, 4]);
+    assert_eq!(buffer.glyphs[1].glyph_class, GlyphClass::Ligature);
+}
+
+#[test]
+fn test_gsub_multiple_deletion() {
+    // A GSUB with one multiple substitution lookup: glyph 5 is deleted
+    // (an empty sequence) and glyph 6 becomes 7 8.
+    let gsub_data: Vec<u8> = vec![
+        0, 1, 0, 0, 0, 10, 0, 10, 0, 12,
+        // ScriptList/FeatureList (empty)
+        0, 0,
+        // LookupList
+        0, 1, 0, 4,
+        // Lookup 0
+        0, 2, 0, 0, 0, 1, 0, 8,
+        0, 1, 0, 18, 0, 2, 0, 10, 0, 12,
+        // Sequences
+        0, 0,
+        0, 2, 0, 7, 0, 8,
+        // Coverage
+        0, 1, 0, 2, 0, 5, 0, 6,
+    ];
+    let gsub = GsubTable::new(gsub_data).unwrap();
+
+    // The glyph after a deleted one is still substituted.
+    let mut buffer = GlyphBuffer::from_glyph_indices(&[5, 6, 5, 5, 9]);
+    gsub.apply_lookup(0, &mut buffer, !0);
+    assert_eq!(buffer.glyph_indices(), vec![7, 8, 9]);
+    assert_eq!(buffer.clusters(), vec![1, 1, 4]);
+}
+
+#[test]
+fn test_gpos_mark_to_base_lookup_flags() {
+    // A GPOS with one mark-to-base lookup: mark 20 (anchor at 50,0)
+    // attaches to glyph 10 (anchor at 300,0) or 11 (at 500,0).
+    let gpos_with_flags = |flags: u8| {
+        let gpos_data: Vec<u8> = vec![
+            0, 1, 0, 0, 0, 10, 0, 10, 0, 12,
+            // ScriptList/FeatureList (empty)
+            0, 0,
+            // LookupList
+            0, 1, 0, 4,
+            // Lookup 0
+            0, 4, 0, flags, 0, 1, 0, 8,
+            0, 1, 0, 12, 0, 18, 0, 1, 0, 26, 0, 38,
+            // Mark coverage, base coverage
+            0, 1, 0, 1, 0, 20,
+            0, 1, 0, 2, 0, 10, 0, 11,
+            // MarkArray
+            0, 1, 0, 0, 0, 6,
+            0, 1, 0, 50, 0, 0,
+            // BaseArray
+            0, 2, 0, 6, 0, 12,
+            0, 1, 1, 44, 0, 0,
+            0, 1, 1, 244, 0, 0,
+        ];
+        GposTable::new(gpos_data).unwrap()
+    };
+    let buffer = || {
+        let mut buffer = GlyphBuffer::from_glyph_indices(&[10, 11, 20]);
+        buffer.glyphs[0].glyph_class = GlyphClass::Base;
+        buffer.glyphs[1].glyph_class = GlyphClass::Ligature;
+        buffer.glyphs[2].glyph_class = GlyphClass::Mark;
+        buffer.init_positions(&[600, 700, 0]);
+        buffer
+    };
+
+    let mut plain = buffer();
+    gpos_with_flags(0).apply_lookup(0, &mut plain, !0);
+    assert_eq!(plain.positions[2].attach_chain, -1);
+    assert_eq!(plain.positions[2].x_offset, 450);
+
+    // With IgnoreLigatures the mark goes past the ligature to the base.
+    let mut ignoring = buffer();
+    gpos_with_flags(4).apply_lookup(0, &mut ignoring, !0);
+    assert_eq!(ignoring.positions[2].attach_chain, -2);
+    assert_eq!(ignoring.positions[2].x_offset, 250);
+}
+
+#[test]
+fn test_gdef_classes_and_carets() {
+    // Glyph 10 is a ligature with two carets (a coordinate and a
+    // contour point), glyphs 20 and 21 are marks in attachment
+    // classes 1 and 2.
+    let gdef_data: Vec<u8> = vec![
+        0, 1, 0, 0, 0, 12, 0, 0, 0, 38, 0, 28,
+        // GlyphClassDef
+        0, 2, 0, 2, 0, 10, 0, 10, 0, 2, 0, 20, 0, 21, 0, 3,
+        // MarkAttachClassDef
+        0, 1, 0, 20, 0, 2, 0, 1, 0, 2,
+        // LigCaretList
+        0, 6, 0, 1, 0, 12,
+        0, 1, 0, 1, 0, 10,
+        0, 2, 0, 6, 0, 10,
+        0, 1, 1, 244,
+        0, 2, 0, 7,
+    ];
+    let gdef = GdefTable::new(gdef_data).unwrap();
+    assert_eq!(gdef.glyph_class(10), GlyphClass::Ligature);
+    assert_eq!(gdef.glyph_class(21), GlyphClass::Mark);
+    assert_eq!(gdef.glyph_class(5), GlyphClass::Unclassified);
+    assert_eq!(gdef.mark_attachment_class(21), 2);
+    assert_eq!(gdef.ligature_carets(10), vec![CaretValue::Coordinate(500), CaretValue::ContourPoint(7)]);
+    // The contour point can't be resolved, so carets are spread evenly.
+    assert_eq!(gdef.ligature_caret_positions(10, 3, 1200), vec![400, 800]);
+    assert!(gdef.ligature_carets(11).is_empty());
+}
+
+#[test]
+fn test_layout_scripts_and_features() {
+    let system_fc = FontCollection::system();
+    let arial_family = system_fc.get_font_family_by_name("Arial").unwrap();
+    let arial_font = arial_family.get_first_matching_font(FontWeight::Regular,
+                                                          FontStretch::Normal,
+                                                          FontStyle::Normal);
+    let face = arial_font.create_font_face();
+
+    let latn = make_opentype_tag(b'l', b'a', b't', b'n');
+    let dflt = make_opentype_tag(b'd', b'f', b'l', b't');
+    assert!(face.layout_scripts().contains(&latn));
+
+    let features = face.layout_features(latn, dflt);
+    assert!(features.iter().any(|f| f.tag == make_opentype_tag(b'k', b'e', b'r', b'n')));
+}
+
+#[test]
+fn test_name_table_lookup() {
+    // Name 256 as both a Windows US English name and a Mac Roman name.
+    let name_data: Vec<u8> = vec![
+        0, 0, 0, 2, 0, 30,
+        0, 1, 0, 0, 0, 0, 1, 0, 0, 4, 0, 8,
+        0, 3, 0, 1, 4, 9, 1, 0, 0, 8, 0, 0,
+        0, b'S', 0, b'w', 0, b's', 0, b'h',
+        b'M', b'a', b'c', b'!',
+    ];
+    let names = NameTable::new(name_data).unwrap();
+    assert_eq!(names.get_name(256), Some("Swsh".to_owned()));
+    assert_eq!(names.get_name(257), None);
+}
+
+#[test]
+fn test_kern_table_pairs() {
+    // A Microsoft kern table with one format 0 subtable kerning
+    // (36, 57) by -80 and (57, 36) by -60.
+    let kern_data: Vec<u8> = vec![
+        0, 0, 0, 1,
+        0, 0, 0, 26, 0, 1,
+        0, 2, 0, 12, 0, 1, 0, 0,
+        0, 36, 0, 57, 255, 176,
+        0, 57, 0, 36, 255, 196,
+    ];
+    let kern = KernTable::new(kern_data).unwrap();
+    assert!(kern.has_horizontal_kerning());
+    assert_eq!(kern.kerning(36, 57), -80);
+    assert_eq!(kern.kerning(57, 36), -60);
+    assert_eq!(kern.kerning(36, 36), 0);
+}
+
+#[test]
+fn test_shape_latin_run() {
+    let system_fc = FontCollection::system();
+    let arial_family = system_fc.get_font_family_by_name("Arial").unwrap();
+    let arial_font = arial_family.get_first_matching_font(FontWeight::Regular,
+                                                          FontStretch::Normal,
+                                                          FontStyle::Normal);
+    let face = arial_font.create_font_face();
+
+    let latn = make_opentype_tag(b'l', b'a', b't', b'n');
+    let dflt = make_opentype_tag(b'd', b'f', b'l', b't');
+    let shaped = shape("AV", &face, 16.0, latn, dflt, TextDirection::LeftToRight, &[]);
+    assert_eq!(shaped.glyph_indices, face.get_glyph_indices(&['A' as u32, 'V' as u32]));
+    assert_eq!(shaped.cluster_map, vec![0, 1]);
+    assert_eq!(shaped.bidi_level(), 0);
+
+    // Arial kerns "AV", so 'A' ends up narrower than its nominal advance.
+    let metrics = face.get_design_glyph_metrics(&shaped.glyph_indices[..1], false);
+    let nominal = metrics[0].advanceWidth as f32 * (16.0 / face.metrics().designUnitsPerEm as f32);
+    assert!(shaped.glyph_advances[0] < nominal);
+
+    let unkerned = shape("AV", &face, 16.0, latn, dflt, TextDirection::LeftToRight,
+                         &[FeatureSetting::new(make_opentype_tag(b'k', b'e', b'r', b'n'), 0)]);
+    assert_eq!(unkerned.glyph_advances[0], nominal);
+}
+
+// Input glyphs and the glyph they're replaced with.
+type Substitution = (Vec<u16>, u16);
+
+// A GSUB table for shaping tests, with every feature on the default
+// language of each script.  Each feature has one lookup: single
+// substitutions, or ligatures if the inputs have more than one glyph
+// (one ligature per first glyph).
+fn fixture_gsub(scripts: &[u32], features: &[(u32, Vec<Substitution>)]) -> GsubTable {
+    fn push(data: &mut Vec<u8>, value: usize) {
+        data.push((value >> 8) as u8);
+        data.push(value as u8);
+    }
+    // Tags as made by make_opentype_tag, first character lowest.
+    fn push_tag(data: &mut Vec<u8>, tag: u32) {
+        data.extend(&[tag as u8, (tag >> 8) as u8, (tag >> 16) as u8, (tag >> 24) as u8]);
+    }
+    fn coverage(data: &mut Vec<u8>, glyphs: &[u16]) {
+        push(data, 1);
+        push(data, glyphs.len());
+        for &g in glyphs {
+            push(data, g as usize);
+        }
+    }
+
+    let mut script_list = vec![];
+    push(&mut script_list, scripts.len());
+    let script_size = 4 + 6 + 2 * features.len();
+    for (i, &tag) in scripts.iter().enumerate() {
+        push_tag(&mut script_list, tag);
+        push(&mut script_list, 2 + 6 * scripts.len() + i * script_size);
+    }
+    for _ in scripts {
+        push(&mut script_list, 4);
+        push(&mut script_list, 0);
+        push(&mut script_list, 0);
+        push(&mut script_list, 0xFFFF);
+        push(&mut script_list, features.len());
+        for i in 0..features.len() {
+            push(&mut script_list, i);
+        }
+    }
+
+    let mut feature_list = vec![];
+    push(&mut feature_list, features.len());
+    for (i, &(tag, _)) in features.iter().enumerate() {
+        push_tag(&mut feature_list, tag);
+        push(&mut feature_list, 2 + 6 * features.len() + i * 6);
+    }
+    for i in 0..features.len() {
+        push(&mut feature_list, 0);
+        push(&mut feature_list, 1);
+        push(&mut feature_list, i);
+    }
+
+    let mut lookups = vec![];
+    for &(_, ref substitutions) in features {
+        let mut substitutions = substitutions.clone();
+        substitutions.sort();
+        let first_glyphs: Vec<u16> = substitutions.iter().map(|s| s.0[0]).collect();
+        let mut lookup = vec![];
+        if substitutions.iter().all(|s| s.0.len() == 1) {
+            push(&mut lookup, 1);
+            push(&mut lookup, 0);
+            push(&mut lookup, 1);
+            push(&mut lookup, 8);
+            push(&mut lookup, 2);
+            push(&mut lookup, 6 + 2 * substitutions.len());
+            push(&mut lookup, substitutions.len());
+            for s in &substitutions {
+                push(&mut lookup, s.1 as usize);
+            }
+        } else {
+            push(&mut lookup, 4);
+            push(&mut lookup, 0);
+            push(&mut lookup, 1);
+            push(&mut lookup, 8);
+            push(&mut lookup, 1);
+            let sets_start = 6 + 2 * substitutions.len();
+            let set_size = |s: &Substitution| 8 + 2 * (s.0.len() - 1);
+            push(&mut lookup, sets_start + substitutions.iter().map(&set_size).sum::<usize>());
+            push(&mut lookup, substitutions.len());
+            let mut offset = sets_start;
+            for s in &substitutions {
+                push(&mut lookup, offset);
+                offset += set_size(s);
+            }
+            for s in &substitutions {
+                push(&mut lookup, 1);
+                push(&mut lookup, 4);
+                push(&mut lookup, s.1 as usize);
+                push(&mut lookup, s.0.len());
+                for &g in &s.0[1..] {
+                    push(&mut lookup, g as usize);
+                }
+            }
+        }
+        coverage(&mut lookup, &first_glyphs);
+        lookups.push(lookup);
+    }
+    let mut lookup_list = vec![];
+    push(&mut lookup_list, lookups.len());
+    let mut offset = 2 + 2 * lookups.len();
+    for lookup in &lookups {
+        push(&mut lookup_list, offset);
+        offset += lookup.len();
+    }
+    for lookup in lookups {
+        lookup_list.extend(lookup);
+    }
+
+    let mut data = vec![0, 1, 0, 0];
+    push(&mut data, 10);
+    push(&mut data, 10 + script_list.len());
+    push(&mut data, 10 + script_list.len() + feature_list.len());
+    data.extend(script_list);
+    data.extend(feature_list);
+    data.extend(lookup_list);
+    GsubTable::new(data).unwrap()
+}
+
+// A font for shaping tests that maps each character to a glyph, with
+// 1000 units to the em, every glyph 500 units wide and no outlines.
+// The layout tables are left to the Shaper.
+fn fixture_face(characters: &[(char, u16)], glyph_count: u16) -> FontFace {
+    fn push(data: &mut Vec<u8>, value: usize) {
+        data.push((value >> 8) as u8);
+        data.push(value as u8);
+    }
+    fn push32(data: &mut Vec<u8>, value: u32) {
+        push(data, (value >> 16) as usize);
+        push(data, value as usize & 0xFFFF);
+    }
+    // (searchRange, entrySelector, rangeShift) for binary searching
+    // `count` records of `size` bytes.
+    fn search_params(count: usize, size: usize) -> [usize; 3] {
+        let mut log = 0;
+        while 2 << log <= count {
+            log += 1;
+        }
+        [size << log, log, count * size - (size << log)]
+    }
+
+    // A format 4 subtable with a segment per character.
+    let mut code_points: Vec<(usize, u16)> = characters.iter().map(|&(c, g)| (c as usize, g)).collect();
+    code_points.sort();
+    assert!(code_points.iter().all(|&(c, _)| c < 0xFFFF));
+    let seg_count = code_points.len() + 1;
+    let mut cmap = vec![];
+    for &v in &[0, 1, 3, 1, 0, 12, 4, 16 + 8 * seg_count, 0, 2 * seg_count] {
+        push(&mut cmap, v);
+    }
+    for &v in &search_params(seg_count, 2) {
+        push(&mut cmap, v);
+    }
+    for &(c, _) in &code_points {
+        push(&mut cmap, c);
+    }
+    push(&mut cmap, 0xFFFF);
+    push(&mut cmap, 0);
+    for &(c, _) in &code_points {
+        push(&mut cmap, c);
+    }
+    push(&mut cmap, 0xFFFF);
+    for &(c, g) in &code_points {
+        push(&mut cmap, (g as usize).wrapping_sub(c) & 0xFFFF);
+    }
+    push(&mut cmap, 1);
+    for _ in 0..seg_count {
+        push(&mut cmap, 0);
+    }
+
+    let mut head = vec![0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x5F, 0x0F, 0x3C, 0xF5, 0, 3];
+    push(&mut head, 1000);
+    head.extend(&[0; 24]);
+    for &v in &[0, 8, 2, 0, 0] {
+        push(&mut head, v);
+    }
+    let mut hhea = vec![0, 1, 0, 0];
+    for &v in &[800, 0xFF38, 0, 500, 0, 0, 500, 1, 0, 0, 0, 0, 0, 0, 0, glyph_count as usize] {
+        push(&mut hhea, v);
+    }
+    let mut maxp = vec![0, 1, 0, 0];
+    push(&mut maxp, glyph_count as usize);
+    for &v in &[0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0] {
+        push(&mut maxp, v);
+    }
+    let mut os2 = vec![];
+    for &v in &[4, 500, 400, 5, 0, 650, 600, 0, 75, 650, 600, 0, 350, 50, 300, 0] {
+        push(&mut os2, v);
+    }
+    os2.extend(&[0; 26]);
+    os2.extend(b"NONE");
+    for &v in &[0x40, 0x20, 0xFFFF, 800, 0xFF38, 0, 800, 200, 0, 0, 0, 0, 500, 700, 0, 0x20, 2] {
+        push(&mut os2, v);
+    }
+    let mut hmtx = vec![];
+    for _ in 0..glyph_count {
+        push(&mut hmtx, 500);
+        push(&mut hmtx, 0);
+    }
+    let loca = vec![0; 2 * (glyph_count as usize + 1)];
+    let mut name = vec![];
+    for &v in &[0, 2, 30, 3, 1, 0x409, 1, 14, 0, 3, 1, 0x409, 2, 14, 14] {
+        push(&mut name, v);
+    }
+    for c in "FixtureRegular".encode_utf16() {
+        push(&mut name, c as usize);
+    }
+    let mut post = vec![0, 3, 0, 0, 0, 0, 0, 0];
+    push(&mut post, 0xFF9C);
+    push(&mut post, 50);
+    post.extend(&[0; 20]);
+
+    let mut tables: Vec<(&[u8; 4], Vec<u8>)> = vec![
+        (b"OS/2", os2), (b"cmap", cmap), (b"glyf", vec![]), (b"head", head), (b"hhea", hhea),
+        (b"hmtx", hmtx), (b"loca", loca), (b"maxp", maxp), (b"name", name), (b"post", post),
+    ];
+    let mut data = vec![0, 1, 0, 0];
+    push(&mut data, tables.len());
+    for &v in &search_params(tables.len(), 16) {
+        push(&mut data, v);
+    }
+    let mut offset = 12 + 16 * tables.len();
+    for &mut (tag, ref mut table) in &mut tables {
+        let length = table.len();
+        table.resize((length + 3) & !3, 0);
+        let checksum = table.chunks(4).fold(0u32, |sum, word| {
+            sum.wrapping_add(((word[0] as u32) << 24) | ((word[1] as u32) << 16) | ((word[2] as u32) << 8) |
+                             word[3] as u32)
+        });
+        data.extend(tag);
+        push32(&mut data, checksum);
+        push32(&mut data, offset as u32);
+        push32(&mut data, length as u32);
+        offset += table.len();
+    }
+    for (_, table) in tables {
+        data.extend(table);
+    }
+    FontFile::new_from_data(&data).unwrap().create_face(0, DWRITE_FONT_SIMULATIONS_NONE)
+}
+
+#[test]
+fn test_shape_vertical_positioning() {
+    let face = fixture_face(&[('A', 1), ('B', 2), ('\u{301}', 3)], 4);
+    let tag = |t: &[u8; 4]| make_opentype_tag(t[0], t[1], t[2], t[3]);
+    // Glyph 3 is a mark.
+    let gdef_data: Vec<u8> = vec![
+        0, 1, 0, 0, 0, 12, 0, 0, 0, 0, 0, 0,
+        0, 1, 0, 3, 0, 1, 0, 3,
+    ];
+    // A GPOS with two features for 'latn': 'mark' attaches glyph 3
+    // (anchor at 250,600) to glyphs 1 and 2 (at 250,800), and 'vkrn'
+    // takes 100 units off the advance of glyph 1 before glyph 2.
+    let gpos_data: Vec<u8> = vec![
+        0, 1, 0, 0, 0, 10, 0, 32, 0, 58,
+        // ScriptList
+        0, 1, b'l', b'a', b't', b'n', 0, 8,
+        0, 4, 0, 0,
+        0, 0, 0xFF, 0xFF, 0, 2, 0, 0, 0, 1,
+        // FeatureList
+        0, 2, b'm', b'a', b'r', b'k', 0, 14, b'v', b'k', b'r', b'n', 0, 20,
+        0, 0, 0, 1, 0, 0,
+        0, 0, 0, 1, 0, 1,
+        // LookupList
+        0, 2, 0, 6, 0, 64,
+        // Lookup 0: mark-to-base
+        0, 4, 0, 0, 0, 1, 0, 8,
+        0, 1, 0, 12, 0, 18, 0, 1, 0, 26, 0, 38,
+        0, 1, 0, 1, 0, 3,
+        0, 1, 0, 2, 0, 1, 0, 2,
+        0, 1, 0, 0, 0, 6,
+        0, 1, 0, 250, 2, 88,
+        0, 2, 0, 6, 0, 6,
+        0, 1, 0, 250, 3, 32,
+        // Lookup 1: pair adjustment of the YAdvance
+        0, 2, 0, 0, 0, 1, 0, 8,
+        0, 1, 0, 18, 0, 8, 0, 0, 0, 1, 0, 12,
+        0, 1, 0, 2, 0xFF, 0x9C,
+        0, 1, 0, 1, 0, 1,
+    ];
+    let shaper = Shaper {
+        gsub: None,
+        gpos: Some(GposTable::new(gpos_data).unwrap()),
+        gdef: Some(GdefTable::new(gdef_data).unwrap()),
+        kern: None,
+    };
+
+    // At 1000 units to the em, an em_size of 1000 keeps design units.
+    let shaped = shaper.shape_vertical(&face, "AB\u{301}", 1000.0, tag(b"latn"), tag(b"dflt"), &[]);
+    assert_eq!(shaped.glyph_indices, vec![1, 2, 3]);
+    let advance = face.get_design_glyph_metrics(&[1], true)[0].advanceHeight as f32;
+    assert_eq!(shaped.glyph_advances, vec![advance - 100.0, advance, 0.0]);
+    // The mark goes back up the advance of the glyph it's on, and 200
+    // units higher.  Offsets along a vertical run point down it.
+    assert_eq!(shaped.glyph_offsets[0].advanceOffset, 0.0);
+    assert_eq!(shaped.glyph_offsets[2].advanceOffset, -(advance + 200.0));
+    assert_eq!(shaped.glyph_offsets[2].ascenderOffset, 0.0);
+}
+
+#[test]
+fn test_shape_arabic_joining_forms() {
+    let system_fc = FontCollection::system();
+    let face = |family: &str| {
+        system_fc.get_font_family_by_name(family)
+                 .unwrap()
+                 .get_first_matching_font(FontWeight::Regular, FontStretch::Normal, FontStyle::Normal)
+                 .create_font_face()
+    };
+    // The fonts only provide the nominal glyphs and advances; the forms
+    // come from the fixture's lookups.
+    let arial = face("Arial");
+    let ebrima = face("Ebrima");
+    let glyph = |face: &FontFace, c: char| face.get_glyph_indices(&[c as u32])[0];
+    let (beh, lam, alef, fatha) = (glyph(&arial, '\u{628}'), glyph(&arial, '\u{644}'), glyph(&arial, '\u{627}'),
+                                   glyph(&arial, '\u{64E}'));
+    let ba = glyph(&ebrima, '\u{7D3}');
+    let tag = |t: &[u8; 4]| make_opentype_tag(t[0], t[1], t[2], t[3]);
+    let forms = |glyphs: &[(u16, [u16; 4])], form: usize| -> Vec<Substitution> {
+        glyphs.iter().filter(|g| g.1[form] != 0).map(|g| (vec![g.0], g.1[form])).collect()
+    };
+    // isol, fina, medi and init forms of each letter.
+    let letters = [(beh, [5, 6, 7, 8]), (lam, [0, 0, 10, 9]), (alef, [12, 11, 0, 0]), (ba, [15, 16, 17, 18])];
+    let gsub = fixture_gsub(&[tag(b"arab"), tag(b"nko ")], &[
+        (tag(b"isol"), forms(&letters, 0)),
+        (tag(b"fina"), forms(&letters, 1)),
+        (tag(b"medi"), forms(&letters, 2)),
+        (tag(b"init"), forms(&letters, 3)),
+        (tag(b"rlig"), vec![(vec![9, 11], 13), (vec![10, 11], 14)]),
+    ]);
+    let shaper = Shaper { gsub: Some(gsub), gpos: None, gdef: None, kern: None };
+    let dflt = tag(b"dflt");
+    let shape = |face: &FontFace, text: &str, script: u32| {
+        shaper.shape(face, text, 16.0, script, dflt, TextDirection::RightToLeft, &[])
+    };
+
+    let shaped = shape(&arial, "\u{628}\u{628}\u{628}", tag(b"arab"));
+    assert_eq!(shaped.glyph_indices, vec![8, 7, 6]);
+    assert_eq!(shaped.bidi_level(), 1);
+    assert_eq!(shape(&arial, "\u{628}", tag(b"arab")).glyph_indices, vec![5]);
+    // A space breaks the joining; a mark doesn't.
+    let space = glyph(&arial, ' ');
+    assert_eq!(shape(&arial, "\u{628} \u{628}", tag(b"arab")).glyph_indices, vec![5, space, 5]);
+    assert_eq!(shape(&arial, "\u{628}\u{64E}\u{628}", tag(b"arab")).glyph_indices, vec![8, fatha, 6]);
+    // LAM ALEF ligatures are made from the joined forms.
+    assert_eq!(shape(&arial, "\u{644}\u{627}", tag(b"arab")).glyph_indices, vec![13]);
+    let shaped = shape(&arial, "\u{628}\u{644}\u{627}", tag(b"arab"));
+    assert_eq!(shaped.glyph_indices, vec![8, 14]);
+    assert_eq!(shaped.cluster_map, vec![0, 1, 1]);
+    // N'Ko joins the same way.
+    assert_eq!(shape(&ebrima, "\u{7D3}\u{7D3}\u{7D3}", tag(b"nko ")).glyph_indices, vec![18, 17, 16]);
+    assert_eq!(shape(&ebrima, "\u{7D3} \u{7D3}\u{7D3}", tag(b"nko ")).glyph_indices[2..].to_vec(), vec![18, 16]);
+}
+
+#[test]
+fn test_shape_devanagari_pre_base_matra() {
+    let system_fc = FontCollection::system();
+    let nirmala_family = system_fc.get_font_family_by_name("Nirmala UI").unwrap();
+    let nirmala_font = nirmala_family.get_first_matching_font(FontWeight::Regular,
+                                                              FontStretch::Normal,
+                                                              FontStyle::Normal);
+    let face = nirmala_font.create_font_face();
+
+    let deva = make_opentype_tag(b'd', b'e', b'v', b'a');
+    let dflt = make_opentype_tag(b'd', b'f', b'l', b't');
+    // The I matra is drawn before the consonant it follows, and both
+    // end up in one cluster.
+    let shaped = shape("\u{915}\u{93F}", &face, 16.0, deva, dflt, TextDirection::LeftToRight, &[]);
+    assert_eq!(shaped.glyph_indices.len(), 2);
+    assert_eq!(shaped.glyph_indices[1], face.get_glyph_indices(&[0x915])[0]);
+    assert_eq!(shaped.cluster_map, vec![0, 0]);
+}
+
+#[test]
+fn test_shape_indic_reph_and_half_forms() {
+    // KA, KHA, RA, VIRAMA and the I matra, with Ra,Virama forming reph
+    // (glyph 10) and Ka,Virama a half form (glyph 11).
+    let face = fixture_face(&[('\u{915}', 1), ('\u{916}', 2), ('\u{930}', 3), ('\u{94D}', 4), ('\u{93F}', 5)],
+                            12);
+    let tag = |t: &[u8; 4]| make_opentype_tag(t[0], t[1], t[2], t[3]);
+    let gsub = fixture_gsub(&[tag(b"dev2")], &[
+        (tag(b"rphf"), vec![(vec![3, 4], 10)]),
+        (tag(b"half"), vec![(vec![1, 4], 11)]),
+    ]);
+    let shaper = Shaper { gsub: Some(gsub), gpos: None, gdef: None, kern: None };
+    let shape = |text: &str, features: &[FeatureSetting]| {
+        shaper.shape(&face, text, 16.0, tag(b"deva"), tag(b"dflt"), TextDirection::LeftToRight, features)
+    };
+
+    // The reph goes to the end of the syllable, after the base and the
+    // matra drawn before it.
+    let shaped = shape("\u{930}\u{94D}\u{915}", &[]);
+    assert_eq!(shaped.glyph_indices, vec![1, 10]);
+    assert_eq!(shaped.cluster_map, vec![0, 0, 0]);
+    assert_eq!(shape("\u{930}\u{94D}\u{915}\u{93F}", &[]).glyph_indices, vec![5, 1, 10]);
+
+    // The matra goes before the half form, but after a virama that
+    // didn't form one.
+    let shaped = shape("\u{915}\u{94D}\u{916}\u{93F}", &[]);
+    assert_eq!(shaped.glyph_indices, vec![5, 11, 2]);
+    assert_eq!(shaped.cluster_map, vec![0, 0, 0, 0]);
+    let no_half = [FeatureSetting::new(tag(b"half"), 0)];
+    assert_eq!(shape("\u{915}\u{94D}\u{916}\u{93F}", &no_half).glyph_indices, vec![1, 4, 5, 2]);
+}
+
+#[test]
+fn test_shape_khmer_and_myanmar_pre_base_reordering() {
+    let tag = |t: &[u8; 4]| make_opentype_tag(t[0], t[1], t[2], t[3]);
+
+    // Khmer KA, COENG, RO and the E vowel, with Coeng,Ro forming a
+    // pre-base subscript (glyph 10).
+    let face = fixture_face(&[('\u{1780}', 1), ('\u{17D2}', 2), ('\u{179A}', 3), ('\u{17C1}', 4)], 11);
+    let gsub = fixture_gsub(&[tag(b"khmr")], &[(tag(b"pref"), vec![(vec![2, 3], 10)])]);
+    let shaper = Shaper { gsub: Some(gsub), gpos: None, gdef: None, kern: None };
+    // Coeng,Ro goes before the base, and the vowel before that.
+    let shaped = shaper.shape(&face, "\u{1780}\u{17D2}\u{179A}\u{17C1}", 16.0, tag(b"khmr"), tag(b"dflt"),
+                              TextDirection::LeftToRight, &[]);
+    assert_eq!(shaped.glyph_indices, vec![4, 10, 1]);
+    assert_eq!(shaped.cluster_map, vec![0, 0, 0, 0]);
+
+    // Myanmar KA, MEDIAL RA and the E vowel, with the medial Ra's
+    // pre-base form (glyph 10).
+    let face = fixture_face(&[('\u{1000}', 1), ('\u{103C}', 2), ('\u{1031}', 3)], 11);
+    let gsub = fixture_gsub(&[tag(b"mym2")], &[(tag(b"pref"), vec![(vec![2], 10)])]);
+    let shaper = Shaper { gsub: Some(gsub), gpos: None, gdef: None, kern: None };
+    let shaped = shaper.shape(&face, "\u{1000}\u{103C}\u{1031}", 16.0, tag(b"mymr"), tag(b"dflt"),
+                              TextDirection::LeftToRight, &[]);
+    assert_eq!(shaped.glyph_indices, vec![3, 10, 1]);
+    assert_eq!(shaped.cluster_map, vec![0, 0, 0]);
+}
+
+#[test]
+fn test_shape_use_cluster_reordering() {
+    // Devanagari shaped by the Universal Shaping Engine, as fonts with
+    // 'dev3' ask for: Ra,Virama forms a reph (glyph 10).
+    let face = fixture_face(&[('\u{915}', 1), ('\u{930}', 3), ('\u{94D}', 4), ('\u{93F}', 5)], 11);
+    let tag = |t: &[u8; 4]| make_opentype_tag(t[0], t[1], t[2], t[3]);
+    let gsub = fixture_gsub(&[tag(b"dev3")], &[(tag(b"rphf"), vec![(vec![3, 4], 10)])]);
+    let shaper = Shaper { gsub: Some(gsub), gpos: None, gdef: None, kern: None };
+
+    // The reph moves after the base, and the matra before it.
+    let shaped = shaper.shape(&face, "\u{930}\u{94D}\u{915}\u{93F}", 16.0, tag(b"deva"), tag(b"dflt"),
+                              TextDirection::LeftToRight, &[]);
+    assert_eq!(shaped.glyph_indices, vec![5, 1, 10]);
+    assert_eq!(shaped.cluster_map, vec![0, 0, 0, 0]);
+}
+
+#[test]
+fn test_shape_hangul_and_thai() {
+    let system_fc = FontCollection::system();
+    let malgun_family = system_fc.get_font_family_by_name("Malgun Gothic").unwrap();
+    let malgun_font = malgun_family.get_first_matching_font(FontWeight::Regular,
+                                                            FontStretch::Normal,
+                                                            FontStyle::Normal);
+    let face = malgun_font.create_font_face();
+
+    let hang = make_opentype_tag(b'h', b'a', b'n', b'g');
+    let dflt = make_opentype_tag(b'd', b'f', b'l', b't');
+    // Conjoining jamo compose into the precomposed syllable the font has.
+    let shaped = shape("\u{1100}\u{1161}", &face, 16.0, hang, dflt, TextDirection::LeftToRight, &[]);
+    assert_eq!(shaped.glyph_indices, face.get_glyph_indices(&[0xAC00]));
+    assert_eq!(shaped.cluster_map, vec![0, 0]);
+
+    let tahoma_family = system_fc.get_font_family_by_name("Tahoma").unwrap();
+    let tahoma_font = tahoma_family.get_first_matching_font(FontWeight::Regular,
+                                                            FontStretch::Normal,
+                                                            FontStyle::Normal);
+    let face = tahoma_font.create_font_face();
+
+    let thai = make_opentype_tag(b't', b'h', b'a', b'i');
+    // SARA AM is drawn as NIKHAHIT and SARA AA, in the consonant's
+    // cluster.
+    let shaped = shape("\u{E01}\u{E33}", &face, 16.0, thai, dflt, TextDirection::LeftToRight, &[]);
+    assert_eq!(shaped.glyph_indices.len(), 3);
+    assert_eq!(shaped.glyph_indices[2], face.get_glyph_indices(&[0xE32])[0]);
+    assert_eq!(shaped.cluster_map, vec![0, 0]);
+}
+
+#[test]
+fn test_shape_jamo_and_sara_am() {
+    let tag = |t: &[u8; 4]| make_opentype_tag(t[0], t[1], t[2], t[3]);
+
+    // The jamo KIYEOK, A and final KIYEOK and the syllable GA, but not
+    // GAG, with the jamo features giving glyphs 10 to 12.
+    let face = fixture_face(&[('\u{1100}', 1), ('\u{1161}', 2), ('\u{11A8}', 3), ('\u{AC00}', 4)], 13);
+    let gsub = fixture_gsub(&[tag(b"hang")], &[
+        (tag(b"ljmo"), vec![(vec![1], 10)]),
+        (tag(b"vjmo"), vec![(vec![2], 11)]),
+        (tag(b"tjmo"), vec![(vec![3], 12)]),
+    ]);
+    let shaper = Shaper { gsub: Some(gsub), gpos: None, gdef: None, kern: None };
+    let shape = |text: &str| {
+        shaper.shape(&face, text, 16.0, tag(b"hang"), tag(b"dflt"), TextDirection::LeftToRight, &[])
+    };
+    // Jamo compose into a syllable the font has...
+    let shaped = shape("\u{1100}\u{1161}");
+    assert_eq!(shaped.glyph_indices, vec![4]);
+    assert_eq!(shaped.cluster_map, vec![0, 0]);
+    // ... and are left to the jamo features otherwise, as are the jamo
+    // of a syllable the font lacks.
+    let shaped = shape("\u{1100}\u{1161}\u{11A8}");
+    assert_eq!(shaped.glyph_indices, vec![10, 11, 12]);
+    assert_eq!(shaped.cluster_map, vec![0, 0, 0]);
+    let shaped = shape("\u{AC01}");
+    assert_eq!(shaped.glyph_indices, vec![10, 11, 12]);
+    assert_eq!(shaped.cluster_map, vec![0]);
+
+    // KO KAI, SARA AM, NIKHAHIT, SARA AA and MAI EK, in a font with
+    // OpenType layout for Thai.
+    let face = fixture_face(&[('\u{E01}', 1), ('\u{E33}', 2), ('\u{E4D}', 3), ('\u{E32}', 4), ('\u{E48}', 5)],
+                            6);
+    let gsub = fixture_gsub(&[tag(b"thai")], &[]);
+    let shaper = Shaper { gsub: Some(gsub), gpos: None, gdef: None, kern: None };
+    let shape = |text: &str| {
+        shaper.shape(&face, text, 16.0, tag(b"thai"), tag(b"dflt"), TextDirection::LeftToRight, &[])
+    };
+    // SARA AM is drawn as NIKHAHIT and SARA AA, in the consonant's
+    // cluster...
+    let shaped = shape("\u{E01}\u{E33}");
+    assert_eq!(shaped.glyph_indices, vec![1, 3, 4]);
+    assert_eq!(shaped.cluster_map, vec![0, 0]);
+    // ... with the NIKHAHIT moved before a tone mark, into its cluster.
+    let shaped = shape("\u{E01}\u{E48}\u{E33}");
+    assert_eq!(shaped.glyph_indices, vec![1, 3, 5, 4]);
+    assert_eq!(shaped.cluster_map, vec![0, 1, 1]);
+}
+
+#[test]
+fn test_shape_fallback_mark_positioning() {
+    let system_fc = FontCollection::system();
+    let arial_family = system_fc.get_font_family_by_name("Arial").unwrap();
+    let arial_font = arial_family.get_first_matching_font(FontWeight::Regular,
+                                                          FontStretch::Normal,
+                                                          FontStyle::Normal);
+    let face = arial_font.create_font_face();
+
+    let latn = make_opentype_tag(b'l', b'a', b't', b'n');
+    let dflt = make_opentype_tag(b'd', b'f', b'l', b't');
+    // With the font's mark attachment turned off, the acute goes above
+    // the 'x' and the dot below it, both within its advance.
+    let features = [FeatureSetting::new(make_opentype_tag(b'm', b'a', b'r', b'k'), 0),
+                    FeatureSetting::new(make_opentype_tag(b'm', b'k', b'm', b'k'), 0)];
+    let shaped = shape("x\u{301}\u{323}", &face, 16.0, latn, dflt, TextDirection::LeftToRight, &features);
+    assert_eq!(shaped.glyph_indices.len(), 3);
+    assert_eq!(shaped.glyph_advances[1], 0.);
+    assert_eq!(shaped.glyph_advances[2], 0.);
+    assert!(shaped.glyph_offsets[1].ascenderOffset > 0.);
+    assert!(shaped.glyph_offsets[2].ascenderOffset <= 0.);
+    for offset in &shaped.glyph_offsets[1..] {
+        assert!(offset.advanceOffset < 0. && offset.advanceOffset > -shaped.glyph_advances[0]);
+    }
+}
+
+#[test]
+fn test_bidi_levels_and_line_order() {
+    // "abc ABC 123 D" with Hebrew capitals, in a left-to-right paragraph.
+    let text = "abc \u{5D0}\u{5D1}\u{5D2} 123 \u{5D3}";
+    let bidi = BidiAnalysis::new(text, None);
+    assert_eq!(bidi.paragraph_level(0), 0);
+    assert_eq!(bidi.levels(), vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 1, 1]);
+    assert_eq!(bidi.level_runs().len(), 4);
+
+    // The right-to-left part is drawn in reverse, with the number
+    // still reading left to right inside it.
+    let runs = bidi.reorder_line(0, 13);
+    let starts: Vec<usize> = runs.iter().map(|r| r.start).collect();
+    assert_eq!(starts, vec![0, 11, 8, 4]);
+    assert_eq!(runs[1].direction(), TextDirection::RightToLeft);
+
+    // A paragraph starting with a Hebrew letter is right-to-left, and
+    // brackets are mirrored when shaped in it.
+    let bidi = BidiAnalysis::new("\u{5D0} (a)", None);
+    assert_eq!(bidi.paragraph_level(0), 1);
+    assert_eq!(bidi.levels(), vec![1, 1, 1, 2, 1]);
+
+    let system_fc = FontCollection::system();
+    let arial_family = system_fc.get_font_family_by_name("Arial").unwrap();
+    let arial_font = arial_family.get_first_matching_font(FontWeight::Regular,
+                                                          FontStretch::Normal,
+                                                          FontStyle::Normal);
+    let face = arial_font.create_font_face();
+    let hebr = make_opentype_tag(b'h', b'e', b'b', b'r');
+    let dflt = make_opentype_tag(b'd', b'f', b'l', b't');
+    let shaped = shape("(", &face, 16.0, hebr, dflt, TextDirection::RightToLeft, &[]);
+    assert_eq!(shaped.glyph_indices, face.get_glyph_indices(&[')' as u32]));
+}
+
+#[test]
+fn test_analyze_scripts() {
+    // Punctuation and spaces stay with the script before them, and the
+    // closing bracket goes with the opening one.
+    let runs = analyze_scripts("Hello, \u{43C}\u{438}\u{440} (\u{5E9}\u{5DC}\u{5D5}\u{5DD}) 123");
+    let scripts: Vec<(usize, usize, u32)> = runs.iter().map(|r| (r.start, r.end, r.script)).collect();
+    assert_eq!(scripts, vec![(0, 7, make_opentype_tag(b'L', b'a', b't', b'n')),
+                             (7, 12, make_opentype_tag(b'C', b'y', b'r', b'l')),
+                             (12, 16, make_opentype_tag(b'H', b'e', b'b', b'r')),
+                             (16, 21, make_opentype_tag(b'C', b'y', b'r', b'l'))]);
+    assert_eq!(runs[1].opentype_script(), make_opentype_tag(b'c', b'y', b'r', b'l'));
+
+    // Combining marks and leading digits join the script's run, and
+    // Indic scripts list their newer tags first.
+    let runs = analyze_scripts("1 \u{915}\u{93F}");
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].opentype_script_tags(),
+               vec![make_opentype_tag(b'd', b'e', b'v', b'3'),
+                    make_opentype_tag(b'd', b'e', b'v', b'2'),
+                    make_opentype_tag(b'd', b'e', b'v', b'a')]);
+
+    // Hiragana and katakana share a run and OpenType tag.
+    let runs = analyze_scripts("\u{3072}\u{30E9}");
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].opentype_script(), make_opentype_tag(b'k', b'a', b'n', b'a'));
+}
+
+#[test]
+fn test_line_breaks() {
+    let positions = |text: &str| -> Vec<(usize, bool)> {
+        line_breaks(text).iter().map(|b| (b.position, b.mandatory)).collect()
+    };
+
+    // After spaces and hyphens, but not before punctuation; a line
+    // separator forces a break, and so does the end of the text.
+    assert_eq!(positions("Hello, world-wide (web)!\r\nNext"),
+               vec![(7, false), (13, false), (18, false), (26, true), (30, true)]);
+
+    // Ideographs break anywhere, but not before small kana or closing
+    // punctuation, and not after opening punctuation.
+    assert_eq!(positions("\u{4E00}\u{3063}\u{4E8C}\u{300C}\u{4E09}\u{3002}"),
+               vec![(2, false), (3, false), (6, true)]);
+
+    // Numbers stay with their currency signs, and emoji with their
+    // skin tone modifiers.
+    assert_eq!(positions("$12.50 \u{261D}\u{1F3FB} ok"), vec![(7, false), (11, false), (13, true)]);
+}
+
+#[test]
+fn test_line_breaks_dictionary() {
+    let positions = |text: &str| -> Vec<usize> { line_breaks(text).iter().map(|b| b.position).collect() };
+
+    // Thai has no spaces between words, so the breaks come from the
+    // dictionary: "ฉัน|รัก|ภาษา|ไทย".
+    assert_eq!(positions("\u{E09}\u{E31}\u{E19}\u{E23}\u{E31}\u{E01}\u{E20}\u{E32}\u{E29}\u{E32}\u{E44}\u{E17}\u{E22}"),
+               vec![3, 6, 10, 13]);
+
+    // Letters that aren't in a word stay with the word before them,
+    // or after them at the start: "ฉันกข|ครับ", "กขครับ".
+    assert_eq!(positions("\u{E09}\u{E31}\u{E19}\u{E01}\u{E02}\u{E04}\u{E23}\u{E31}\u{E1A}"), vec![5, 9]);
+    assert_eq!(positions("\u{E01}\u{E02}\u{E04}\u{E23}\u{E31}\u{E1A}"), vec![6]);
+    // And a leading vowel stays with the consonant after it:
+    // "ฉันเค|รับ", not "ฉันเ|ครับ".
+    assert_eq!(positions("\u{E09}\u{E31}\u{E19}\u{E40}\u{E04}\u{E23}\u{E31}\u{E1A}"), vec![5, 8]);
+
+    // Khmer: "ខ្ញុំ|ស្រលាញ់|ភាសាខ្មែរ".
+    assert_eq!(positions("\u{1781}\u{17D2}\u{1789}\u{17BB}\u{17C6}\u{179F}\u{17D2}\u{179A}\u{179B}\u{17B6}\u{1789}\u{17CB}\u{1797}\u{17B6}\u{179F}\u{17B6}\u{1781}\u{17D2}\u{1798}\u{17C2}\u{179A}"),
+               vec![5, 12, 21]);
+}
+
+#[test]
+fn test_line_breaks_dictionary_paragraphs() {
+    // The text between each break and the next.
+    let words = |text: &str| -> String {
+        let utf16: Vec<u16> = text.encode_utf16().collect();
+        let mut start = 0;
+        let mut words = vec![];
+        for b in line_breaks(text) {
+            words.push(String::from_utf16(&utf16[start..b.position]).unwrap());
+            start = b.position;
+        }
+        words.join("|")
+    };
+
+    assert_eq!(words("ประเทศไทยมีประชากรประมาณเจ็ดสิบล้านคน \
+                      กรุงเทพมหานครเป็นเมืองหลวงและเป็นเมืองที่ใหญ่ที่สุดของประเทศ \
+                      ภาษาไทยเขียนโดยไม่เว้นวรรคระหว่างคำ \
+                      จึงต้องใช้พจนานุกรมเพื่อหาตำแหน่งที่ตัดบรรทัดได้"),
+               "ประเทศไทย|มี|ประชากร|ประมาณ|เจ็ด|สิบ|ล้าน|คน \
+                |กรุงเทพมหานคร|เป็น|เมือง|หลวง|และ|เป็น|เมือง|ที่|ใหญ่|ที่สุด|ของ|ประเทศ \
+                |ภาษา|ไทย|เขียน|โดย|ไม่|เว้น|วรรค|ระหว่าง|คำ \
+                |จึง|ต้อง|ใช้|พจนานุกรม|เพื่อ|หา|ตำแหน่ง|ที่|ตัด|บรรทัด|ได้");
+    assert_eq!(words("ພາສາລາວເປັນພາສາທາງການຂອງປະເທດລາວ"), "ພາສາ|ລາວ|ເປັນ|ພາສາ|ທາງການ|ຂອງ|ປະເທດ|ລາວ");
+    assert_eq!(words("ភាសាខ្មែរជាភាសាផ្លូវការរបស់ប្រទេសកម្ពុជា"), "ភាសាខ្មែរ|ជា|ភាសាផ្លូវការ|របស់|ប្រទេស|កម្ពុជា");
+    assert_eq!(words("မြန်မာဘာသာစကားသည်မြန်မာ"), "မြန်မာ|ဘာသာစကား|သည်|မြန်မာ");
+}
+
+#[test]
+fn test_grapheme_and_word_boundaries() {
+    // An emoji ZWJ sequence, a Hangul syllable in jamo, a Devanagari
+    // conjunct with its vowel sign, and a flag are one grapheme each.
+    let text = "\u{1F469}\u{200D}\u{1F4BB}\u{1100}\u{1161}\u{11A8}\u{915}\u{94D}\u{937}\u{93F}\u{1F1EF}\u{1F1F5}";
+    assert_eq!(grapheme_boundaries(text), vec![0, 5, 8, 12, 16]);
+
+    // Apostrophes and decimal points stay inside words and numbers.
+    assert_eq!(word_boundaries("can't stop 3.14!"), vec![0, 5, 6, 10, 11, 15, 16]);
+
+    // Thai words come from the dictionary: "ฉัน|กิน|ข้าว".
+    assert_eq!(word_boundaries("\u{E09}\u{E31}\u{E19}\u{E01}\u{E34}\u{E19}\u{E02}\u{E49}\u{E32}\u{E27}"),
+               vec![0, 3, 6, 10]);
+
+    // Caret stops are at grapheme boundaries; inside a ligature they
+    // divide its advance.
+    let glyphs = ShapedGlyphs {
+        glyph_indices: vec![1, 2, 3],
+        glyph_advances: vec![12., 5., 0.],
+        glyph_offsets: vec![GlyphOffset { advanceOffset: 0., ascenderOffset: 0. }; 3],
+        cluster_map: vec![0, 0, 1, 1],
+        glyph_clusters: vec![0, 2, 2],
+        is_right_to_left: false,
+        em_size: 16.,
+    };
+    let stops: Vec<(usize, f32)> = caret_stops("fie\u{301}", &glyphs).iter().map(|s| (s.position, s.advance)).collect();
+    assert_eq!(stops, vec![(0, 0.), (1, 6.), (2, 12.), (4, 17.)]);
+}
+
+#[test]
+fn test_text_layout_lines() {
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Arial".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+    let text = "The quick brown fox jumps over the lazy dog.\nSecond paragraph";
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, 100.0);
+
+    // Lines cover the text, fit in the width without their trailing
+    // spaces, and are stacked one under the other.
+    let lines = layout.lines().to_vec();
+    assert!(lines.len() > 2);
+    assert_eq!(lines[0].start, 0);
+    assert_eq!(lines[lines.len() - 1].end, text.len());
+    let mut top = 0.;
+    for pair in lines.windows(2) {
+        assert_eq!(pair[0].end, pair[1].start);
+    }
+    for line in &lines {
+        assert!(line.width <= 100.0);
+        assert_eq!(line.top, top);
+        assert!(line.top < line.baseline && line.baseline < line.top + line.height);
+        top += line.height;
+    }
+    assert!(lines[0].trailing_whitespace_length == 1);
+    let paragraph_end = lines.iter().find(|l| l.newline_length == 1).unwrap();
+    assert_eq!(paragraph_end.end, 45);
+
+    let metrics = layout.metrics();
+    assert_eq!(metrics.line_count, lines.len());
+    assert_eq!(metrics.height, top);
+
+    // Runs are drawn from where their line starts.
+    for line in layout.lines() {
+        if let Some(run) = line.glyph_runs.first() {
+            assert_eq!(run.baseline_origin_x, 0.);
+            assert_eq!(run.baseline_origin_y, line.baseline);
+        }
+    }
+
+    layout.set_text_alignment(TextAlignment::Trailing);
+    for line in layout.lines() {
+        assert!((line.left + line.width - 100.0).abs() < 0.01);
+    }
+
+    // Justified lines fill the width, but not those ending a paragraph.
+    layout.set_text_alignment(TextAlignment::Justified);
+    let justified = layout.lines();
+    assert!((justified[0].width - 100.0).abs() < 0.01);
+    assert_eq!(justified[justified.len() - 1].width, lines[lines.len() - 1].width);
+
+    // Hebrew is shaped right to left and put to the right of the Latin
+    // text before it.
+    let layout = TextLayout::new("abc \u{5E9}\u{5DC}\u{5D5}\u{5DD}", &system_fc, &descriptor, 16.0, f32::INFINITY);
+    assert_eq!(layout.metrics().line_count, 1);
+    let runs = &layout.lines()[0].glyph_runs;
+    assert_eq!(runs[0].text_start, 0);
+    let hebrew = runs.iter().find(|r| r.text_start == 4).unwrap();
+    assert_eq!(hebrew.bidi_level, 1);
+    assert!((hebrew.baseline_origin_x - layout.metrics().width).abs() < 0.01);
+}
+
+#[test]
+fn test_text_layout_spans() {
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Arial".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+    let text = "plain bold big red";
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, f32::INFINITY);
+    let plain = layout.lines()[0].clone();
+    let plain_width = layout.metrics().width;
+
+    layout.set_font_weight(FontWeight::Bold, 6, 10);
+    layout.set_font_size(32.0, 11, 14);
+    layout.set_color((1.0, 0.0, 0.0), 15, 18);
+    assert_eq!(layout.format(7).font.weight, FontWeight::Bold);
+    assert_eq!(layout.format(12).font_size, 32.0);
+    assert_eq!(layout.format(12).font.weight, FontWeight::Regular);
+
+    // Runs are split at each change, and the line is as tall as its
+    // biggest text.
+    let line = layout.lines()[0].clone();
+    let run_at = |position: usize| line.glyph_runs.iter().find(|r| r.text_start == position).unwrap();
+    assert_eq!(run_at(11).em_size, 32.0);
+    assert_eq!(run_at(11).text_end(), 14);
+    assert_eq!(run_at(15).color, Some((1.0, 0.0, 0.0)));
+    assert_eq!(run_at(0).color, None);
+    assert!(line.height > plain.height * 1.5);
+    assert!(line.baseline > plain.baseline);
+
+    // Letter spacing adds space after every cluster.
+    let width = layout.metrics().width;
+    layout.set_letter_spacing(2.0, 0, 5);
+    assert!((layout.metrics().width - width - 10.0).abs() < 0.01);
+    assert!(width > plain_width);
+
+    // Text can still wrap across spans.
+    layout.set_max_width(plain_width / 2.0);
+    assert!(layout.metrics().line_count > 1);
+}
+
+#[test]
+fn test_text_layout_hit_testing() {
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Arial".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+    // "abc " then Hebrew, which is shown right to left.
+    let text = "abc \u{5E9}\u{5DC}\u{5D5}\u{5DD}\nx";
+    let layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, f32::INFINITY);
+
+    let (x, y, metrics) = layout.hit_test_text_position(0, false);
+    assert_eq!((x, y), (0.0, 0.0));
+    assert_eq!((metrics.text_position, metrics.length), (0, 1));
+
+    // Every character hits itself in the middle, and the caret goes
+    // after it on its trailing side: the left for Hebrew.
+    for position in 0..8 {
+        let (leading, y, metrics) = layout.hit_test_text_position(position, false);
+        let (trailing, _, _) = layout.hit_test_text_position(position, true);
+        assert_eq!(layout.hit_test_point(metrics.left + metrics.width / 4.0, y + 1.0).0, position);
+        if position >= 4 {
+            assert_eq!(metrics.bidi_level, 1);
+            assert!(leading > trailing);
+        } else {
+            assert!(leading < trailing);
+        }
+    }
+    let (_, y, x_metrics) = layout.hit_test_text_position(9, false);
+    assert_eq!(y, layout.lines()[1].top);
+    assert_eq!(layout.hit_test_point(1.0, y + 1.0), (9, false, true));
+
+    // Points outside the text hit the nearest character.
+    assert_eq!(layout.hit_test_point(-10.0, 1.0), (0, false, false));
+    assert_eq!(layout.hit_test_point(1000.0, y + 1.0), (9, true, false));
+    assert_eq!(layout.hit_test_point(x_metrics.left + 1.0, 1000.0).0, 9);
+    // A NaN coordinate hits something rather than panicking.
+    assert_eq!(layout.hit_test_point(f32::NAN, y + 1.0).0, 9);
+
+    // A selection across the direction change takes two boxes, and one
+    // more on the next line.
+    let rects = layout.hit_test_text_range(2, 8);
+    assert_eq!(rects.len(), 3);
+    assert_eq!((rects[0].text_position, rects[0].length), (2, 2));
+    assert_eq!((rects[1].text_position, rects[1].length, rects[1].bidi_level), (4, 4, 1));
+    assert!((rects[0].left + rects[0].width - rects[1].left).abs() < 0.01);
+    assert_eq!(rects[2].top, y);
+}
+
+#[test]
+fn test_text_layout_trimming() {
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Arial".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+    let character = Trimming { granularity: TrimmingGranularity::Character, delimiter: None, delimiter_count: 0 };
+
+    // A label that fits isn't trimmed.
+    let mut layout = TextLayout::new("Short", &system_fc, &descriptor, 16.0, 200.0);
+    layout.set_word_wrapping(false);
+    layout.set_trimming(character);
+    assert!(!layout.is_trimmed());
+
+    // One that doesn't ends in an ellipsis, which shows no text.
+    let text = "The quick brown fox jumps over the lazy dog";
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, 100.0);
+    layout.set_word_wrapping(false);
+    layout.set_trimming(character);
+    assert!(layout.is_trimmed());
+    assert_eq!(layout.metrics().line_count, 1);
+    assert!(layout.metrics().width <= 100.0);
+    let runs = layout.lines()[0].glyph_runs.clone();
+    let ellipsis = runs.last().unwrap();
+    assert!(ellipsis.cluster_map.is_empty());
+    assert_eq!(ellipsis.glyph_indices.len(), 1);
+    let kept_by_characters = runs[0].text_end();
+
+    // Word trimming keeps whole words, so no more than that.
+    layout.set_trimming(Trimming { granularity: TrimmingGranularity::Word, delimiter: None, delimiter_count: 0 });
+    let kept_by_words = layout.lines()[0].glyph_runs[0].text_end();
+    assert!(kept_by_words <= kept_by_characters);
+    assert_eq!(text.as_bytes()[kept_by_words], b' ');
+
+    // Paths keep their last segment.
+    let path = "C:\\Users\\someone\\Documents\\report.txt";
+    let mut layout = TextLayout::new(path, &system_fc, &descriptor, 16.0, 150.0);
+    layout.set_word_wrapping(false);
+    layout.set_trimming(Trimming { granularity: TrimmingGranularity::Character, delimiter: Some('\\'), delimiter_count: 1 });
+    assert!(layout.is_trimmed());
+    let runs = &layout.lines()[0].glyph_runs;
+    assert_eq!(runs[runs.len() - 1].text_start, 26);
+    assert_eq!(runs[runs.len() - 1].text_end(), path.len());
+
+    // Lines that don't fit in the height are left out, and the last
+    // one shown gets an ellipsis.
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, 100.0);
+    let line_height = layout.lines()[0].height;
+    layout.set_max_height(line_height * 2.5);
+    layout.set_trimming(character);
+    assert_eq!(layout.metrics().line_count, 2);
+    assert!(layout.is_trimmed());
+    assert!(layout.lines()[1].glyph_runs.last().unwrap().cluster_map.is_empty());
+}
+
+#[test]
+fn test_text_layout_justification() {
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Arial".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+    let width = |text: &str| TextLayout::new(text, &system_fc, &descriptor, 16.0, f32::INFINITY).metrics().width;
+
+    // Arabic is stretched with kashidas: "سلام عليكم" wrapped after its
+    // first word, which has no spaces to stretch.
+    let first = "\u{633}\u{644}\u{627}\u{645}";
+    let text = "\u{633}\u{644}\u{627}\u{645} \u{639}\u{644}\u{64A}\u{643}\u{645}";
+    let max_width = (width(first) + width(text)) / 2.0;
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, max_width);
+    layout.set_reading_direction(TextDirection::RightToLeft);
+    let glyph_count = layout.lines()[0].glyph_runs[0].glyph_indices.len();
+    layout.set_text_alignment(TextAlignment::Justified);
+    assert_eq!(layout.metrics().line_count, 2);
+    let line = &layout.lines()[0];
+    let run = &line.glyph_runs[0];
+    let tatweel = run.font_face.get_glyph_indices(&[0x640])[0];
+    let kashidas = run.glyph_indices.iter().filter(|&&g| g == tatweel).count();
+    assert!(kashidas > 0);
+    assert_eq!(run.glyph_indices.len(), glyph_count + kashidas);
+    assert!(line.width <= max_width && line.width > width(first));
+    // The kashidas belong to the letter before them.
+    assert_eq!(run.cluster_map[0], 0);
+    assert!(run.cluster_map[1] > 1);
+
+    // CJK text gets space after each character but the last.
+    let text = "\u{4E00}\u{4E8C}\u{4E09}\u{56DB}\u{4E94}\u{516D}\u{4E03}\u{516B}\u{4E5D}\u{5341}";
+    let max_width = width(text) * 0.75;
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, max_width);
+    layout.set_text_alignment(TextAlignment::Justified);
+    let line = &layout.lines()[0];
+    assert!((line.width - max_width).abs() < 0.01);
+    let total = line.glyph_runs.iter().fold(0.0, |total, run| total + run.width());
+    assert!((total - max_width).abs() < 0.01);
+}
+
+#[test]
+fn test_text_layout_hyphenation() {
+    let english = Hyphenator::for_language("en-US").unwrap();
+    let hyphenate = |word: &str| english.hyphenate(&word.chars().collect::<Vec<char>>());
+    assert_eq!(hyphenate("hyphenation"), vec![2, 6]);
+    assert_eq!(hyphenate("Extensive"), vec![2, 5]);
+    // Three letters at least after the hyphen, and exceptions.
+    assert_eq!(hyphenate("computer"), vec![3]);
+    assert_eq!(hyphenate("table"), vec![2]);
+    assert!(Hyphenator::for_language("xx").is_none());
+
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Arial".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+    let width = |text: &str| TextLayout::new(text, &system_fc, &descriptor, 16.0, f32::INFINITY).metrics().width;
+
+    // "representative" doesn't fit after "of", but "of represen-" does.
+    let text = "of representative words";
+    let max_width = width("of represen-") + 1.0;
+    assert!(width("of representative") > max_width);
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, max_width);
+    assert_eq!(layout.lines()[0].end, 3);
+    layout.set_hyphenator(Some(english));
+    let line = &layout.lines()[0];
+    assert_eq!(line.end, 11);
+    assert!(line.width <= max_width);
+    let hyphen = line.glyph_runs.last().unwrap();
+    assert!(hyphen.cluster_map.is_empty());
+    let face = &hyphen.font_face;
+    let expected = match face.get_glyph_indices(&[0x2010])[0] {
+        0 => face.get_glyph_indices(&[0x2D])[0],
+        glyph => glyph,
+    };
+    assert_eq!(hyphen.glyph_indices, vec![expected]);
+    assert_eq!(layout.lines()[1].start, 11);
+    assert!(layout.lines()[1].glyph_runs.iter().all(|r| !r.cluster_map.is_empty()));
+
+    // The hyphen takes the color of the word it breaks.
+    assert_eq!(hyphen.color, None);
+    layout.set_color((1.0, 0.0, 0.0), 3, 17);
+    assert_eq!(layout.lines()[0].glyph_runs.last().unwrap().color, Some((1.0, 0.0, 0.0)));
+
+    layout.set_hyphenator(None);
+    assert_eq!(layout.lines()[0].end, 3);
+}
+
+#[test]
+fn test_text_layout_vertical() {
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Yu Gothic".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+    // "日本語、abc": the ideographs and the comma are upright, the comma
+    // with its vertical form, and the Latin letters turned sideways.
+    let text = "\u{65E5}\u{672C}\u{8A9E}\u{3001}abc";
+    let horizontal = TextLayout::new(text, &system_fc, &descriptor, 16.0, f32::INFINITY);
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, f32::INFINITY);
+    layout.set_vertical(true);
+    assert!(layout.is_vertical());
+    let line = &layout.lines()[0];
+    assert_eq!(line.glyph_runs.len(), 2);
+    let (upright, latin) = (&line.glyph_runs[0], &line.glyph_runs[1]);
+    assert!(upright.is_sideways && !latin.is_sideways);
+    assert_eq!(upright.cluster_map.len(), 4);
+    // Full-width glyphs advance by an em down the line.
+    assert!(upright.glyph_advances.iter().all(|&a| (a - 16.0).abs() < 0.01));
+    let comma = horizontal.lines()[0].glyph_runs[0].glyph_indices[3];
+    assert!(upright.glyph_indices[3] != comma);
+    assert_eq!(latin.glyph_indices, horizontal.lines()[0].glyph_runs[1].glyph_indices);
+    // Both are centered on the line.
+    assert!((line.baseline - line.height / 2.0).abs() < 0.01);
+    assert!(latin.baseline_origin_y > line.baseline);
+
+    let transform = layout.vertical_transform();
+    assert_eq!((transform.m11, transform.m12, transform.m21, transform.m22), (0.0, 1.0, -1.0, 0.0));
+    assert_eq!(transform.dx, layout.metrics().height);
+}
+
+#[test]
+fn test_design_glyph_metrics_from_tables() {
+    let system_fc = FontCollection::system();
+    let face = |name: &str| {
+        let family = system_fc.get_font_family_by_name(name).unwrap();
+        family.get_first_matching_font(FontWeight::Regular, FontStretch::Normal, FontStyle::Normal).create_font_face()
+    };
+    let fields = |m: &winapi::DWRITE_GLYPH_METRICS| (m.leftSideBearing, m.advanceWidth, m.rightSideBearing,
+                                                     m.topSideBearing, m.advanceHeight, m.bottomSideBearing,
+                                                     m.verticalOriginY);
+
+    // Arial has no vertical metrics, so only the horizontal ones are
+    // the font's own.
+    let arial = face("Arial");
+    let glyphs = arial.get_glyph_indices(&['A' as u32, 'g' as u32, ' ' as u32]);
+    let ours = arial.get_design_glyph_metrics_from_tables(&glyphs).unwrap();
+    for (ours, theirs) in ours.iter().zip(arial.get_design_glyph_metrics(&glyphs, false).iter()) {
+        assert_eq!(fields(ours).0, fields(theirs).0);
+        assert_eq!(fields(ours).1, fields(theirs).1);
+        assert_eq!(fields(ours).2, fields(theirs).2);
+    }
+    assert!(ours.iter().all(|m| m.advanceHeight == ours[0].advanceHeight));
+
+    // Yu Gothic has 'vhea' and 'vmtx'.
+    let gothic = face("Yu Gothic");
+    let glyphs = gothic.get_glyph_indices(&[0x65E5, 0x3001, 'A' as u32]);
+    let ours = gothic.get_design_glyph_metrics_from_tables(&glyphs).unwrap();
+    for (ours, theirs) in ours.iter().zip(gothic.get_design_glyph_metrics(&glyphs, true).iter()) {
+        assert_eq!(fields(ours), fields(theirs));
+    }
+}
+
+#[test]
+fn test_text_layout_line_spacing() {
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Arial".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+
+    // Arial doesn't set USE_TYPO_METRICS, so DirectWrite uses its win
+    // metrics, and browsers its 'hhea' ones.
+    let face = system_fc.get_font_from_descriptor(&descriptor).unwrap().create_font_face();
+    let metrics = face.metrics();
+    let line_metrics = face.get_line_metrics().unwrap();
+    assert_eq!(line_metrics.design_units_per_em, metrics.designUnitsPerEm);
+    assert!(!line_metrics.use_typo_metrics);
+    assert!(line_metrics.typo.is_some());
+    assert_eq!(line_metrics.win, Some(LineMetrics {
+        ascent: metrics.ascent as i32,
+        descent: metrics.descent as i32,
+        line_gap: metrics.lineGap as i32,
+    }));
+    assert_eq!(line_metrics.css_normal(), line_metrics.hhea);
+
+    let text = "The quick brown fox jumps over the lazy dog.";
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, 100.0);
+    let default_lines = layout.lines().to_vec();
+    assert!(default_lines.len() > 1);
+
+    layout.set_line_spacing(LineSpacing { method: LineSpacingMethod::Uniform, height: 20., baseline: 15. });
+    for (i, line) in layout.lines().iter().enumerate() {
+        assert_eq!(line.top, 20. * i as f32);
+        assert_eq!(line.height, 20.);
+        assert_eq!(line.baseline, line.top + 15.);
+        assert!(line.glyph_runs.iter().all(|r| r.baseline_origin_y == line.baseline));
+    }
+    assert_eq!(layout.metrics().height, 20. * default_lines.len() as f32);
+
+    layout.set_line_spacing(LineSpacing { method: LineSpacingMethod::Proportional, height: 2., baseline: 1. });
+    for (line, default_line) in layout.lines().iter().zip(default_lines.iter()) {
+        assert!((line.height - 2. * default_line.height).abs() < 0.01);
+        assert!((line.baseline - line.top - (default_line.baseline - default_line.top)).abs() < 0.01);
+    }
+
+    // Half the 'hhea' line gap goes above the text.
+    layout.set_line_spacing(LineSpacing { method: LineSpacingMethod::CssNormal, height: 0., baseline: 0. });
+    let hhea = line_metrics.hhea;
+    let scale = 16. / metrics.designUnitsPerEm as f32;
+    let line = &layout.lines()[0];
+    assert!((line.height - (hhea.ascent + hhea.descent + hhea.line_gap) as f32 * scale).abs() < 0.01);
+    assert!((line.baseline - (hhea.ascent as f32 + hhea.line_gap as f32 / 2.) * scale).abs() < 0.01);
+    // Breaking doesn't change.
+    assert_eq!(layout.lines().len(), default_lines.len());
+}
+
+#[test]
+fn test_base_table_baselines() {
+    // Hanging, ideographic and roman baselines for the default script
+    // and for Devanagari, whose default baseline is the hanging one,
+    // with extents for Devanagari, for Hindi and for Hindi's 'abvm'.
+    let base_data: Vec<u8> = vec![
+        0, 1, 0, 0, 0, 8, 0, 0,
+        0, 4, 0, 18,
+        0, 3, b'h', b'a', b'n', b'g', b'i', b'd', b'e', b'o', b'r', b'o', b'm', b'n',
+        0, 2, b'D', b'F', b'L', b'T', 0, 14, b'd', b'e', b'v', b'a', 0, 42,
+        0, 6, 0, 0, 0, 0,
+        0, 2, 0, 3, 0, 10, 0, 14, 0, 18,
+        0, 1, 5, 220, 0, 1, 255, 136, 0, 1, 0, 0,
+        0, 12, 0, 34, 0, 1, b'H', b'I', b'N', b' ', 0, 48,
+        0, 0, 0, 3, 0, 10, 0, 14, 0, 18,
+        0, 1, 5, 120, 0, 1, 255, 106, 0, 1, 0, 0,
+        0, 6, 0, 10, 0, 0, 0, 1, 254, 12, 0, 1, 7, 8,
+        0, 14, 0, 0, 0, 1, b'a', b'b', b'v', b'm', 0, 0, 0, 18,
+        0, 1, 253, 168, 0, 1, 7, 208,
+    ];
+    let base = BaseTable::new(base_data).unwrap();
+    let tag = |t: &[u8; 4]| make_opentype_tag(t[0], t[1], t[2], t[3]);
+    assert_eq!(base.baseline_tags(false), vec![tag(b"hang"), tag(b"ideo"), tag(b"romn")]);
+    assert_eq!(base.baseline_tags(true), vec![]);
+    assert_eq!(base.script_tags(false), vec![tag(b"DFLT"), tag(b"deva")]);
+
+    assert_eq!(base.default_baseline(false, tag(b"deva")), Some(tag(b"hang")));
+    assert_eq!(base.baseline(false, tag(b"deva"), Baseline::Hanging.tag()), Some(1400));
+    assert_eq!(base.baseline(false, tag(b"deva"), Baseline::Ideographic.tag()), Some(-150));
+    // Scripts that aren't listed use 'DFLT'.
+    assert_eq!(base.default_baseline(false, tag(b"latn")), Some(tag(b"romn")));
+    assert_eq!(base.baselines(false, tag(b"latn")),
+               vec![(tag(b"hang"), 1500), (tag(b"ideo"), -120), (tag(b"romn"), 0)]);
+    assert_eq!(base.baseline(false, tag(b"latn"), Baseline::Math.tag()), None);
+
+    let min_max = |language: &[u8; 4], feature: Option<u32>| base.min_max(false, tag(b"deva"), tag(language), feature);
+    assert_eq!(min_max(b"MAR ", None), Some(MinMax { min: Some(-500), max: Some(1800) }));
+    assert_eq!(min_max(b"HIN ", None), Some(MinMax { min: Some(-600), max: None }));
+    assert_eq!(min_max(b"HIN ", Some(tag(b"abvm"))), Some(MinMax { min: None, max: Some(2000) }));
+    assert_eq!(base.min_max(false, tag(b"latn"), tag(b"ENG "), None), None);
+}
+
+#[test]
+fn test_text_layout_dominant_baseline() {
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Arial".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+    // The Devanagari falls back to another font.
+    let text = "\u{928}\u{92E}\u{938}\u{94D}\u{924}\u{947} world";
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, f32::INFINITY);
+    let deva = make_opentype_tag(b'd', b'e', b'v', b'a');
+
+    // The paragraph starts in Devanagari, so its runs hang from the
+    // line's baseline.
+    assert_eq!(layout.dominant_baseline(), None);
+    let line = layout.lines()[0].clone();
+    assert!(line.glyph_runs.len() >= 2);
+    for run in &line.glyph_runs {
+        let base = BaseTable::from_font_face(&run.font_face);
+        let metrics = run.font_face.metrics();
+        let script = if run.text_start == 0 { deva } else { make_opentype_tag(b'l', b'a', b't', b'n') };
+        let hanging = font_baseline(base.as_ref(), metrics, false, script, Baseline::Hanging);
+        let offset = hanging as f32 * run.em_size / metrics.designUnitsPerEm as f32;
+        assert!((run.baseline_origin_y - offset - line.baseline).abs() < 0.01);
+        assert!(line.top + 0.01 < line.baseline - offset);
+    }
+
+    // Or they can all sit on the alphabetic one.
+    layout.set_dominant_baseline(Some(Baseline::Roman));
+    let line = layout.lines()[0].clone();
+    assert!(line.glyph_runs.iter().all(|r| r.baseline_origin_y == line.baseline));
+}
+
+#[test]
+fn test_math_table_glyph_info() {
+    // Glyph 5 has an italics correction of 50, its top accent at 300,
+    // top right kerning of -10 below 100, -20 below 400 and -30 above,
+    // and bottom left kerning of 15; glyph 7 is an extended shape.
+    let math_data: Vec<u8> = vec![
+        0, 1, 0, 0, 0, 0, 0, 10, 0, 0,
+        0, 8, 0, 22, 0, 36, 0, 42,
+        0, 8, 0, 1, 0, 50, 0, 0,
+        0, 1, 0, 1, 0, 5,
+        0, 8, 0, 1, 1, 44, 0, 0,
+        0, 1, 0, 1, 0, 5,
+        0, 1, 0, 1, 0, 7,
+        0, 12, 0, 1, 0, 18, 0, 0, 0, 0, 0, 40,
+        0, 1, 0, 1, 0, 5,
+        0, 2, 0, 100, 0, 0, 1, 144, 0, 0, 255, 246, 0, 0, 255, 236, 0, 0, 255, 226, 0, 0,
+        0, 0, 0, 15, 0, 0,
+    ];
+    let math = MathTable::new(math_data).unwrap();
+    assert_eq!(math.italics_correction(5), Some(50));
+    assert_eq!(math.italics_correction(6), None);
+    assert_eq!(math.top_accent_attachment(5), Some(300));
+    assert!(math.is_extended_shape(7));
+    assert!(!math.is_extended_shape(5));
+
+    assert_eq!(math.kerning(5, MathKernCorner::TopRight, 50), -10);
+    assert_eq!(math.kerning(5, MathKernCorner::TopRight, 100), -20);
+    assert_eq!(math.kerning(5, MathKernCorner::TopRight, 1000), -30);
+    assert_eq!(math.kerning(5, MathKernCorner::BottomLeft, -200), 15);
+    assert_eq!(math.kerning(5, MathKernCorner::TopLeft, 50), 0);
+    assert_eq!(math.kerning(6, MathKernCorner::TopRight, 50), 0);
+
+    // The table has no constants or variants.
+    assert_eq!(math.constant(MathConstant::AxisHeight), 0);
+    assert_eq!(math.glyph_variants(5, true), vec![]);
+    assert_eq!(math.glyph_assembly(5, true), None);
+}
+
+#[test]
+fn test_math_table_stretch_glyph() {
+    let system_fc = FontCollection::system();
+    let family = system_fc.get_font_family_by_name("Cambria Math").unwrap();
+    let font = family.get_first_matching_font(FontWeight::Regular, FontStretch::Normal, FontStyle::Normal);
+    let face = font.create_font_face();
+    let math = face.get_math_table().unwrap();
+
+    let scale_down = math.constant(MathConstant::ScriptPercentScaleDown);
+    assert!(0 < scale_down && scale_down < 100);
+    assert!(math.constant(MathConstant::ScriptScriptPercentScaleDown) < scale_down);
+    assert!(math.constant(MathConstant::AxisHeight) > 0);
+    assert!(math.constant(MathConstant::FractionRuleThickness) > 0);
+    assert!(math.constant(MathConstant::RadicalDegreeBottomRaisePercent) > 0);
+
+    // Parentheses come in bigger and bigger sizes, and beyond those are
+    // built from a top, a bottom and an extender.
+    let paren = face.get_glyph_indices(&['(' as u32])[0];
+    let variants = math.glyph_variants(paren, true);
+    assert!(variants.len() > 1);
+    assert_eq!(variants[0].glyph, paren);
+    assert!(variants.windows(2).all(|pair| pair[0].advance < pair[1].advance));
+    let assembly = math.glyph_assembly(paren, true).unwrap();
+    assert!(assembly.parts.iter().any(|p| p.is_extender));
+    assert!(math.glyph_variants(paren, false).is_empty());
+
+    let scale = 16. / face.metrics().designUnitsPerEm as f32;
+    let small = math.stretch_glyph(&face, paren, true, variants[1].advance as f32 * scale, 16.);
+    assert_eq!(small.glyph_indices, vec![variants[1].glyph]);
+
+    // The parts are stacked up from the baseline, overlapping.
+    let big = math.stretch_glyph(&face, paren, true, 200., 16.);
+    assert!(big.glyph_indices.len() > assembly.parts.len());
+    assert!(big.size >= 200. && big.size < 210.);
+    assert!(big.glyph_offsets.windows(2).all(|pair| pair[0].ascenderOffset < pair[1].ascenderOffset));
+    assert!(big.glyph_advances[..big.glyph_advances.len() - 1].iter().all(|&a| a == 0.));
+    assert!(*big.glyph_advances.last().unwrap() > 0.);
+
+    // Horizontal assemblies advance along the baseline.
+    let arrow = face.get_glyph_indices(&[0x2192])[0];
+    let long = math.stretch_glyph(&face, arrow, false, 200., 16.);
+    assert!(long.glyph_indices.len() > 1);
+    let width = long.glyph_advances.iter().fold(0., |a, &b| a + b);
+    assert!((width - long.size).abs() < 0.01);
+    assert!(long.size >= 200.);
+}
+
+#[test]
+fn test_text_layout_inline_objects() {
+    let system_fc = FontCollection::system();
+    let descriptor = FontDescriptor {
+        family_name: "Arial".to_owned(),
+        weight: FontWeight::Regular,
+        stretch: FontStretch::Normal,
+        style: FontStyle::Normal,
+    };
+    let object = InlineObject {
+        width: 30.,
+        height: 40.,
+        baseline: 30.,
+        break_before: BreakCondition::Neutral,
+        break_after: BreakCondition::Neutral,
+    };
+    let text = "ab \u{FFFC} cd";
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, f32::INFINITY);
+    let plain = layout.lines()[0].clone();
+    assert!(plain.inline_objects.is_empty());
+
+    // Only a U+FFFC can be replaced.
+    layout.set_inline_object(Some(object), 1);
+    assert_eq!(layout.format(1).inline_object, None);
+    assert!(layout.lines()[0].inline_objects.is_empty());
+
+    // The object takes its character's place, on the baseline, and
+    // makes the line taller.
+    layout.set_inline_object(Some(object), 3);
+    assert_eq!(layout.format(3).inline_object, Some(object));
+    assert_eq!(layout.lines().len(), 1);
+    let line = layout.lines()[0].clone();
+    assert_eq!(line.inline_objects.len(), 1);
+    let placed = line.inline_objects[0];
+    assert_eq!(placed.text_position, 3);
+    assert_eq!(placed.object, object);
+    assert_eq!(placed.bidi_level, 0);
+    assert_eq!(placed.top, line.baseline - 30.);
+    assert!(line.height >= 40.);
+    assert!(line.baseline - line.top >= 30.);
+    assert!(line.glyph_runs.iter().all(|r| r.text_start > 3 || r.text_start + r.cluster_map.len() <= 3));
+    let before = line.glyph_runs.iter().filter(|r| r.text_start < 3).fold(0., |a, r| a + r.width());
+    assert!((placed.left - before).abs() < 0.01);
+    assert_eq!(layout.hit_test_point(placed.left + 10., placed.top + 10.).0, 3);
+
+    // Lines can break on either side of it, unless it says otherwise.
+    let mut layout = TextLayout::new("ab\u{FFFC}cd", &system_fc, &descriptor, 16.0, 60.0);
+    layout.set_inline_object(Some(object), 2);
+    assert_eq!(layout.lines().iter().map(|l| l.start).collect::<Vec<_>>(), vec![0, 3]);
+    layout.set_inline_object(Some(InlineObject { break_after: BreakCondition::MayNotBreak, ..object }), 2);
+    assert_eq!(layout.lines().iter().map(|l| l.start).collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(layout.lines()[1].inline_objects.len(), 1);
+    layout.set_max_width(f32::INFINITY);
+    layout.set_inline_object(Some(InlineObject { break_before: BreakCondition::MustBreak, ..object }), 2);
+    assert_eq!(layout.lines().iter().map(|l| l.start).collect::<Vec<_>>(), vec![0, 2]);
+
+    // In right-to-left text it comes left of the text before it.
+    let text = "\u{5D0}\u{5D1} \u{FFFC} \u{5D2}";
+    let mut layout = TextLayout::new(text, &system_fc, &descriptor, 16.0, f32::INFINITY);
+    layout.set_inline_object(Some(object), 3);
+    let line = layout.lines()[0].clone();
+    let placed = line.inline_objects[0];
+    assert_eq!(placed.bidi_level, 1);
+    let first = line.glyph_runs.iter().find(|r| r.text_start == 0).unwrap();
+    assert!(first.is_right_to_left());
+    assert!(placed.left + 30. <= first.baseline_origin_x - first.width() + 0.01);
+}
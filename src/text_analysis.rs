@@ -0,0 +1,114 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Splits text into runs of one script, like DirectWrite's
+// IDWriteTextAnalyzer::AnalyzeScript, so each run can be shaped with
+// its script's rules and given a font that supports it.
+//
+// Characters shared between scripts (Common) and combining marks
+// (Inherited) take the script of the run they're in, as described in
+// UAX #24 (https://www.unicode.org/reports/tr24/), so only changes
+// between real scripts start new runs.  A closing bracket goes with
+// its opening bracket's script, so "(text)" keeps its brackets
+// together even when the text is in another script.
+
+use ot_layout::DEFAULT_SCRIPT;
+use shaping;
+use unicode_data;
+
+const COMMON: u32 = ot_tag!(b"Zyyy");
+const INHERITED: u32 = ot_tag!(b"Zinh");
+const UNKNOWN: u32 = ot_tag!(b"Zzzz");
+
+// How many open brackets are remembered, as in the bidi algorithm.
+const MAX_BRACKET_DEPTH: usize = 63;
+
+// A range of text (in UTF-16 code units) in one script.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct ScriptRun {
+    pub start: usize,
+    pub end: usize,
+    // The ISO 15924 code packed like an OpenType tag, e.g.
+    // make_opentype_tag(b'L', b'a', b't', b'n'); Zyyy if the run only
+    // has characters common to all scripts.
+    pub script: u32,
+}
+
+impl ScriptRun {
+    // The OpenType script tag to shape the run with.
+    pub fn opentype_script(&self) -> u32 {
+        opentype_script_tag(self.script)
+    }
+
+    // The script tags to look for in a font, best first; e.g. 'dev3',
+    // 'dev2' and 'deva' for Devanagari.
+    pub fn opentype_script_tags(&self) -> Vec<u32> {
+        shaping::script_tag_candidates(self.opentype_script())
+    }
+}
+
+// Maps an ISO 15924 code to the OpenType script tag for it.  Text
+// that isn't in a particular script uses the default script.
+pub fn opentype_script_tag(script: u32) -> u32 {
+    match script {
+        COMMON | INHERITED | UNKNOWN => DEFAULT_SCRIPT,
+        s if s == ot_tag!(b"Hira") || s == ot_tag!(b"Kana") || s == ot_tag!(b"Hrkt") => ot_tag!(b"kana"),
+        s if s == ot_tag!(b"Laoo") => ot_tag!(b"lao "),
+        s if s == ot_tag!(b"Yiii") => ot_tag!(b"yi  "),
+        s if s == ot_tag!(b"Nkoo") => ot_tag!(b"nko "),
+        s if s == ot_tag!(b"Vaii") => ot_tag!(b"vai "),
+        // Otherwise it's the code in lower case.
+        s => s | 0x20,
+    }
+}
+
+fn is_real_script(script: u32) -> bool {
+    script != COMMON && script != INHERITED && script != UNKNOWN
+}
+
+pub fn analyze_scripts(text: &str) -> Vec<ScriptRun> {
+    let mut runs: Vec<ScriptRun> = vec![];
+    // The opening brackets not closed yet, with the run they're in.
+    let mut open_brackets: Vec<(u32, usize)> = vec![];
+    let mut offset = 0;
+    for c in text.chars() {
+        let end = offset + c.len_utf16();
+        let mut script = unicode_data::script(c as u32);
+        let bracket = if script == COMMON { unicode_data::bidi_bracket(c as u32) } else { None };
+        if let Some((pair, false)) = bracket {
+            if let Some(i) = open_brackets.iter().rposition(|b| b.0 == pair) {
+                script = runs[open_brackets[i].1].script;
+                open_brackets.truncate(i);
+            }
+        }
+
+        let continues_run = match runs.last() {
+            Some(run) => !is_real_script(script) || !is_real_script(run.script) ||
+                         opentype_script_tag(run.script) == opentype_script_tag(script),
+            None => false,
+        };
+        if continues_run {
+            let run = runs.last_mut().unwrap();
+            run.end = end;
+            // Leading common characters take the first real script.
+            if !is_real_script(run.script) && is_real_script(script) {
+                run.script = script;
+            }
+        } else {
+            runs.push(ScriptRun {
+                start: offset,
+                end: end,
+                script: if is_real_script(script) { script } else { COMMON },
+            });
+        }
+
+        if let Some((pair, true)) = bracket {
+            if open_brackets.len() < MAX_BRACKET_DEPTH {
+                open_brackets.push((pair, runs.len() - 1));
+            }
+        }
+        offset = end;
+    }
+    runs
+}
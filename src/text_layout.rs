@@ -0,0 +1,1822 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Lays out a paragraph (or several) of text in a box of a given width,
+// like IDWriteTextLayout: the text is split into runs of one bidi level,
+// script and font, each run is shaped, and lines are broken at the
+// UAX #14 break opportunities, or between clusters when a word doesn't
+// fit (or at a hyphenation point, with a hyphen added, if hyphenation
+// is on).  Each line's runs are put in visual order and positioned, ready
+// to be drawn with BitmapRenderTarget::draw_glyph_run_at_level.
+//
+// Positions in the text are in UTF-16 code units, and distances are in
+// DIPs, with y going down from the top of the layout.
+//
+// Vertical layouts are laid out the same way, in a frame turned 90
+// degrees right: x goes down the page and y to the left, so lines run
+// top to bottom and follow each other from right to left.  Glyphs set
+// upright (UAX #50) are sideways in that frame, and drawn turned back
+// with BitmapRenderTarget::draw_glyph_run_sideways; the rest, like
+// Latin text, turn with the lines.  vertical_transform maps the frame
+// onto the page.
+
+use std::cmp::Ordering;
+use std::f32;
+use std::mem;
+use std::rc::Rc;
+
+use base_table::{self, Baseline};
+use bidi::BidiAnalysis;
+use font_fallback::FontFallback;
+use glyph_buffer::{GlyphBuffer, TextDirection};
+use hyphenation::Hyphenator;
+use line_metrics::LineMetrics;
+use line_break::{self, LineBreak};
+use ot_layout::{DEFAULT_LANGUAGE, DEFAULT_SCRIPT};
+use segmentation;
+use shaping::{FeatureSetting, ShapedGlyphs, GLOBAL_MASK};
+use text_analysis;
+use unicode_data::{self, JoiningType, LineBreakClass, VerticalOrientation};
+use super::{FontCollection, FontDescriptor, FontFace, FontStretch, FontStyle, FontWeight, GlyphOffset, DWRITE_MATRIX};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum TextAlignment {
+    // Lines start at the edge their paragraph's direction starts from:
+    // the left for left-to-right paragraphs.
+    Leading,
+    Trailing,
+    Center,
+    // Like Leading, with the spaces of every line but the last of each
+    // paragraph stretched to fill the width.
+    Justified,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum LineSpacingMethod {
+    // Lines are as high as the largest ascent, descent and line gap of
+    // their fonts' FontMetrics, with the gap below the text.
+    Default,
+    // Every line is `height` high with its baseline `baseline` below
+    // its top.
+    Uniform,
+    // The default height and baseline, times `height` and `baseline`.
+    Proportional,
+    // Like CSS `line-height: normal` in browsers: from the fonts'
+    // 'hhea' metrics, or their typo metrics if they set
+    // USE_TYPO_METRICS, with half of each font's line gap above its
+    // text and half below.
+    CssNormal,
+}
+
+// mirrors DWRITE_LINE_SPACING: `height` and `baseline` are in DIPs for
+// uniform spacing and factors for proportional spacing, and otherwise
+// unused.  In vertical layouts the baseline is always centered.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct LineSpacing {
+    pub method: LineSpacingMethod,
+    pub height: f32,
+    pub baseline: f32,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum TrimmingGranularity {
+    None,
+    Character,
+    Word,
+}
+
+// mirrors DWRITE_TRIMMING: how to shorten lines that don't fit with an
+// ellipsis.  Lines are trimmed when they are wider than the maximum
+// width without word wrapping, and the last line shown is when there
+// are more lines than fit in the maximum height.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Trimming {
+    pub granularity: TrimmingGranularity,
+    // The text from the delimiter_count-th delimiter from the end of
+    // the line is kept after the ellipsis, so trimming a path keeps
+    // the file name: "C:\Users\...\file.txt".
+    pub delimiter: Option<char>,
+    pub delimiter_count: u32,
+}
+
+// A shaped run of glyphs in one line, positioned in the layout.
+#[derive(Debug, Clone)]
+pub struct LayoutGlyphRun {
+    pub font_face: Rc<FontFace>,
+    pub em_size: f32,
+    pub glyph_indices: Vec<u16>,
+    pub glyph_advances: Vec<f32>,
+    pub glyph_offsets: Vec<GlyphOffset>,
+    pub bidi_level: u32,
+    // Whether the glyphs are set upright in a vertical layout, so are
+    // drawn sideways (see DWRITE_GLYPH_RUN::isSideways).
+    pub is_sideways: bool,
+    // The color to draw the run with, or None for the default.
+    pub color: Option<(f32, f32, f32)>,
+    // The origin to draw the run at: the left end of the baseline for
+    // left-to-right runs, and the right end for right-to-left ones.
+    pub baseline_origin_x: f32,
+    pub baseline_origin_y: f32,
+    // The text the run shows, and for each code unit of it the index
+    // of the first glyph of its cluster.
+    pub text_start: usize,
+    pub cluster_map: Vec<u16>,
+}
+
+impl LayoutGlyphRun {
+    pub fn text_end(&self) -> usize {
+        self.text_start + self.cluster_map.len()
+    }
+
+    pub fn is_right_to_left(&self) -> bool {
+        self.bidi_level & 1 == 1
+    }
+
+    pub fn width(&self) -> f32 {
+        self.glyph_advances.iter().fold(0., |a, &b| a + b)
+    }
+
+    // The left edge of the run.
+    pub fn left(&self) -> f32 {
+        if self.is_right_to_left() {
+            self.baseline_origin_x - self.width()
+        } else {
+            self.baseline_origin_x
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LayoutLine {
+    pub start: usize,
+    // Where the line ends, after any trailing whitespace and line
+    // separator.
+    pub end: usize,
+    // How much of the end of the line is whitespace, including the
+    // line separator, which hangs past the line's width.
+    pub trailing_whitespace_length: usize,
+    pub newline_length: usize,
+    pub top: f32,
+    pub height: f32,
+    // The dominant baseline, from the top of the layout.
+    pub baseline: f32,
+    // The left edge and width of the line without its trailing
+    // whitespace.
+    pub left: f32,
+    pub width: f32,
+    pub width_including_trailing_whitespace: f32,
+    // The runs, in visual order from left to right.
+    pub glyph_runs: Vec<LayoutGlyphRun>,
+    // The inline objects, in visual order too.
+    pub inline_objects: Vec<LayoutInlineObject>,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct TextMetrics {
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub width_including_trailing_whitespace: f32,
+    pub height: f32,
+    // The width lines are aligned in: the maximum width, or the widest
+    // line's if there is none.
+    pub layout_width: f32,
+    pub line_count: usize,
+}
+
+// mirrors DWRITE_HIT_TEST_METRICS: the box a range of text takes up
+// in a line.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct HitTestMetrics {
+    pub text_position: usize,
+    pub length: usize,
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32,
+    pub bidi_level: u32,
+}
+
+// A grapheme cluster in a line, or the part of a ligature a grapheme
+// takes up, which is what hit-testing works in.
+struct HitBox {
+    start: usize,
+    end: usize,
+    left: f32,
+    width: f32,
+    level: u32,
+}
+
+// mirrors DWRITE_BREAK_CONDITION: whether lines can break on one side
+// of an inline object.  Neutral leaves it to the text next to it, and
+// the strongest condition wins between two objects.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum BreakCondition {
+    Neutral,
+    CanBreak,
+    MayNotBreak,
+    MustBreak,
+}
+
+// An image, widget or the like laid out in place of a U+FFFC object
+// replacement character, like IDWriteInlineObject.  It takes part in bidi
+// reordering and line breaking like the character would.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct InlineObject {
+    pub width: f32,
+    pub height: f32,
+    // How far its baseline is below its top.
+    pub baseline: f32,
+    pub break_before: BreakCondition,
+    pub break_after: BreakCondition,
+}
+
+// An inline object in a line, positioned in the layout.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct LayoutInlineObject {
+    pub text_position: usize,
+    pub object: InlineObject,
+    // Its top left corner; its baseline is on the line's.
+    pub left: f32,
+    pub top: f32,
+    pub bidi_level: u32,
+}
+
+// The formatting of a range of the text.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TextFormat {
+    pub font: FontDescriptor,
+    pub font_size: f32,
+    pub features: Vec<FeatureSetting>,
+    // Space added after every cluster, in DIPs.
+    pub letter_spacing: f32,
+    pub color: Option<(f32, f32, f32)>,
+    // The object each character is replaced with.
+    pub inline_object: Option<InlineObject>,
+}
+
+impl TextFormat {
+    // Whether text in the two formats can be shaped together.
+    fn shapes_like(&self, other: &TextFormat) -> bool {
+        self.font == other.font && self.font_size == other.font_size && self.features == other.features &&
+            self.letter_spacing == other.letter_spacing && self.inline_object == other.inline_object
+    }
+}
+
+struct FormatSpan {
+    start: usize,
+    end: usize,
+    format: TextFormat,
+}
+
+// A run of text in one format, font, script and bidi level, and its
+// glyphs.
+struct ShapedRun {
+    start: usize,
+    end: usize,
+    // The font is fallbacks[fallback].fonts[font].
+    fallback: usize,
+    font: usize,
+    // The OpenType script tag it was shaped with.
+    script: u32,
+    level: u8,
+    // Shaped to be set upright in a vertical layout.
+    is_sideways: bool,
+    glyphs: ShapedGlyphs,
+}
+
+pub struct TextLayout<'a> {
+    text: String,
+    collection: &'a FontCollection,
+    // Fonts for each font descriptor used by the text.
+    fallbacks: Vec<FontFallback<'a>>,
+    // Formatting covering the text, in order.
+    spans: Vec<FormatSpan>,
+    max_width: f32,
+    max_height: f32,
+    word_wrapping: bool,
+    trimming: Trimming,
+    alignment: TextAlignment,
+    line_spacing: LineSpacing,
+    dominant_baseline: Option<Baseline>,
+    direction: TextDirection,
+    vertical: bool,
+    hyphenator: Option<&'a Hyphenator>,
+
+    // The UTF-16 offset of each character, then the text's length.
+    offsets: Vec<usize>,
+    // The same in bytes, for slicing the text.
+    byte_offsets: Vec<usize>,
+    chars: Vec<char>,
+    bidi: BidiAnalysis,
+    runs: Vec<ShapedRun>,
+    breaks: Vec<LineBreak>,
+    // For each code unit, the advance of the cluster starting there,
+    // summed so that advance_sums[end] - advance_sums[start] is the
+    // width of a range.
+    advance_sums: Vec<f32>,
+    // Whether each position (up to the text's length) is between
+    // clusters and grapheme clusters, so lines can be broken there.
+    cluster_boundaries: Vec<bool>,
+    graphemes: Vec<usize>,
+    // Where words can be hyphenated, in order, with the hyphen shown
+    // when a line is broken there.
+    hyphens: Vec<(usize, LayoutGlyphRun)>,
+    lines: Vec<LayoutLine>,
+    is_trimmed: bool,
+}
+
+fn is_newline(c: char) -> bool {
+    match unicode_data::line_break_class(c as u32) {
+        LineBreakClass::BK | LineBreakClass::CR | LineBreakClass::LF | LineBreakClass::NL => true,
+        _ => false,
+    }
+}
+
+// Whitespace that hangs at the end of a line; no-break spaces don't.
+fn is_hanging_whitespace(c: char) -> bool {
+    c.is_whitespace() && c != '\u{A0}' && c != '\u{2007}' && c != '\u{202F}'
+}
+
+// Ideographs, kana and Hangul, which justified text spaces out.
+fn is_cjk(c: char) -> bool {
+    match unicode_data::line_break_class(c as u32) {
+        LineBreakClass::ID | LineBreakClass::CJ | LineBreakClass::H2 | LineBreakClass::H3 |
+        LineBreakClass::JL | LineBreakClass::JV | LineBreakClass::JT => true,
+        _ => false,
+    }
+}
+
+// The clusters of a glyph run in text order, as (start, end, first
+// glyph, end glyph).
+fn run_clusters(run: &LayoutGlyphRun) -> Vec<(usize, usize, usize, usize)> {
+    let mut clusters = vec![];
+    let len = run.cluster_map.len();
+    let mut i = 0;
+    while i < len {
+        let first_glyph = run.cluster_map[i] as usize;
+        let mut j = i + 1;
+        while j < len && run.cluster_map[j] as usize == first_glyph {
+            j += 1;
+        }
+        let end_glyph = if j < len { run.cluster_map[j] as usize } else { run.glyph_advances.len() };
+        clusters.push((run.text_start + i, run.text_start + j, first_glyph, end_glyph));
+        i = j;
+    }
+    clusters
+}
+
+impl<'a> TextLayout<'a> {
+    // Lays out `text` in the font matching `descriptor` (or fallback
+    // fonts from `collection` for characters it lacks), at `font_size`
+    // DIPs, in lines up to `max_width` wide; f32::INFINITY for no
+    // wrapping.
+    pub fn new(text: &str,
+               collection: &'a FontCollection,
+               descriptor: &FontDescriptor,
+               font_size: f32,
+               max_width: f32)
+        -> TextLayout<'a>
+    {
+        let mut offsets = vec![];
+        let mut byte_offsets = vec![];
+        let mut offset = 0;
+        for (i, c) in text.char_indices() {
+            offsets.push(offset);
+            byte_offsets.push(i);
+            offset += c.len_utf16();
+        }
+        offsets.push(offset);
+        byte_offsets.push(text.len());
+
+        let format = TextFormat {
+            font: descriptor.clone(),
+            font_size: font_size,
+            features: vec![],
+            letter_spacing: 0.,
+            color: None,
+            inline_object: None,
+        };
+        let mut layout = TextLayout {
+            text: text.to_owned(),
+            collection: collection,
+            fallbacks: vec![FontFallback::new(collection, descriptor)],
+            spans: vec![FormatSpan { start: 0, end: offset, format: format }],
+            max_width: max_width,
+            max_height: f32::INFINITY,
+            word_wrapping: true,
+            trimming: Trimming { granularity: TrimmingGranularity::None, delimiter: None, delimiter_count: 0 },
+            alignment: TextAlignment::Leading,
+            line_spacing: LineSpacing { method: LineSpacingMethod::Default, height: 0., baseline: 0. },
+            dominant_baseline: None,
+            direction: TextDirection::LeftToRight,
+            vertical: false,
+            hyphenator: None,
+            offsets: offsets,
+            byte_offsets: byte_offsets,
+            chars: text.chars().collect(),
+            bidi: BidiAnalysis::new(text, Some(TextDirection::LeftToRight)),
+            runs: vec![],
+            breaks: line_break::line_breaks(text),
+            advance_sums: vec![],
+            cluster_boundaries: vec![],
+            graphemes: vec![],
+            hyphens: vec![],
+            lines: vec![],
+            is_trimmed: false,
+        };
+        layout.shape();
+        layout
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn text_len(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+
+    pub fn max_width(&self) -> f32 {
+        self.max_width
+    }
+
+    pub fn set_max_width(&mut self, max_width: f32) {
+        self.max_width = max_width;
+        self.break_lines();
+    }
+
+    pub fn max_height(&self) -> f32 {
+        self.max_height
+    }
+
+    // The height lines are shown in when trimming; lines below it are
+    // left out.
+    pub fn set_max_height(&mut self, max_height: f32) {
+        self.max_height = max_height;
+        self.break_lines();
+    }
+
+    pub fn word_wrapping(&self) -> bool {
+        self.word_wrapping
+    }
+
+    // Whether lines wrap at the maximum width, which they do by
+    // default; otherwise they only end at line separators.
+    pub fn set_word_wrapping(&mut self, word_wrapping: bool) {
+        self.word_wrapping = word_wrapping;
+        self.break_lines();
+    }
+
+    pub fn trimming(&self) -> Trimming {
+        self.trimming
+    }
+
+    pub fn set_trimming(&mut self, trimming: Trimming) {
+        self.trimming = trimming;
+        self.break_lines();
+    }
+
+    // Whether any text was left out of the lines.
+    pub fn is_trimmed(&self) -> bool {
+        self.is_trimmed
+    }
+
+    pub fn text_alignment(&self) -> TextAlignment {
+        self.alignment
+    }
+
+    pub fn set_text_alignment(&mut self, alignment: TextAlignment) {
+        self.alignment = alignment;
+        self.break_lines();
+    }
+
+    pub fn line_spacing(&self) -> LineSpacing {
+        self.line_spacing
+    }
+
+    pub fn set_line_spacing(&mut self, line_spacing: LineSpacing) {
+        self.line_spacing = line_spacing;
+        self.break_lines();
+    }
+
+    pub fn dominant_baseline(&self) -> Option<Baseline> {
+        self.dominant_baseline
+    }
+
+    // The baseline the runs of a horizontal line are lined up on, in
+    // each run's font; None, the default, for the one the font of each
+    // paragraph's first run uses for its script.
+    pub fn set_dominant_baseline(&mut self, baseline: Option<Baseline>) {
+        self.dominant_baseline = baseline;
+        self.break_lines();
+    }
+
+    pub fn reading_direction(&self) -> TextDirection {
+        self.direction
+    }
+
+    // The direction of every paragraph of the text; left to right by
+    // default.
+    pub fn set_reading_direction(&mut self, direction: TextDirection) {
+        self.direction = direction;
+        self.bidi = BidiAnalysis::new(&self.text, Some(direction));
+        self.shape();
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.vertical
+    }
+
+    // Lays the text out in vertical lines, as for Japanese, or in
+    // horizontal ones.
+    pub fn set_vertical(&mut self, vertical: bool) {
+        self.vertical = vertical;
+        self.shape();
+    }
+
+    // Maps the frame a vertical layout is in onto the page: the first
+    // line's top edge goes to the right of the layout's box and its
+    // start to the top.  Set on the render target to draw the layout.
+    pub fn vertical_transform(&self) -> DWRITE_MATRIX {
+        DWRITE_MATRIX {
+            m11: 0.,
+            m12: 1.,
+            m21: -1.,
+            m22: 0.,
+            dx: self.metrics().height,
+            dy: 0.,
+        }
+    }
+
+    pub fn hyphenator(&self) -> Option<&'a Hyphenator> {
+        self.hyphenator
+    }
+
+    // Lets lines be broken inside words where `hyphenator` allows, or
+    // only between words for None.
+    pub fn set_hyphenator(&mut self, hyphenator: Option<&'a Hyphenator>) {
+        self.hyphenator = hyphenator;
+        self.find_hyphens();
+        self.break_lines();
+    }
+
+    // The formatting of the text at `position`.
+    pub fn format(&self, position: usize) -> &TextFormat {
+        &self.spans[self.span_index(position)].format
+    }
+
+    pub fn set_font_family_name(&mut self, family_name: &str, start: usize, end: usize) {
+        self.set_format(start, end, |format| format.font.family_name = family_name.to_owned());
+        self.shape();
+    }
+
+    pub fn set_font_weight(&mut self, weight: FontWeight, start: usize, end: usize) {
+        self.set_format(start, end, |format| format.font.weight = weight);
+        self.shape();
+    }
+
+    pub fn set_font_stretch(&mut self, stretch: FontStretch, start: usize, end: usize) {
+        self.set_format(start, end, |format| format.font.stretch = stretch);
+        self.shape();
+    }
+
+    pub fn set_font_style(&mut self, style: FontStyle, start: usize, end: usize) {
+        self.set_format(start, end, |format| format.font.style = style);
+        self.shape();
+    }
+
+    pub fn set_font_size(&mut self, font_size: f32, start: usize, end: usize) {
+        self.set_format(start, end, |format| format.font_size = font_size);
+        self.shape();
+    }
+
+    // OpenType features to turn on or off for the range, on top of the
+    // ones the shaper applies by default.
+    pub fn set_font_features(&mut self, features: &[FeatureSetting], start: usize, end: usize) {
+        self.set_format(start, end, |format| format.features = features.to_vec());
+        self.shape();
+    }
+
+    pub fn set_letter_spacing(&mut self, letter_spacing: f32, start: usize, end: usize) {
+        self.set_format(start, end, |format| format.letter_spacing = letter_spacing);
+        self.shape();
+    }
+
+    // The color doesn't change how the text is shaped; runs are only
+    // split where it changes.
+    pub fn set_color(&mut self, color: (f32, f32, f32), start: usize, end: usize) {
+        self.set_format(start, end, |format| format.color = Some(color));
+        self.break_lines();
+    }
+
+    // Puts `object` in place of the U+FFFC at `position`, or the
+    // character back for None.  Other characters are left alone.
+    pub fn set_inline_object(&mut self, object: Option<InlineObject>, position: usize) {
+        if position >= self.text_len() {
+            return;
+        }
+        let end = self.offsets[self.char_index(position) + 1];
+        if self.slice(position, end) != "\u{FFFC}" {
+            return;
+        }
+        self.set_format(position, end, |format| format.inline_object = object);
+        self.shape();
+    }
+
+    // Changes the formatting of the text from `start` to `end`,
+    // splitting the spans it covers part of.
+    fn set_format<F>(&mut self, start: usize, end: usize, change: F) where F: Fn(&mut TextFormat) {
+        let end = end.min(self.text_len());
+        if start >= end {
+            return;
+        }
+        let mut spans: Vec<FormatSpan> = vec![];
+        for span in self.spans.drain(..) {
+            let mut cuts = vec![span.start, start.max(span.start).min(span.end), end.max(span.start).min(span.end),
+                                span.end];
+            cuts.dedup();
+            for pair in cuts.windows(2) {
+                let mut format = span.format.clone();
+                if start <= pair[0] && pair[1] <= end {
+                    change(&mut format);
+                }
+                match spans.last_mut() {
+                    Some(ref mut last) if last.format == format => {
+                        last.end = pair[1];
+                        continue;
+                    }
+                    _ => {}
+                }
+                spans.push(FormatSpan { start: pair[0], end: pair[1], format: format });
+            }
+        }
+        self.spans = spans;
+    }
+
+    fn span_index(&self, position: usize) -> usize {
+        self.spans.iter().position(|s| position < s.end).unwrap_or(self.spans.len() - 1)
+    }
+
+    // The fonts for a descriptor, looked for the first time it's used.
+    fn fallback_index(&mut self, descriptor: &FontDescriptor) -> usize {
+        if let Some(index) = self.fallbacks.iter().position(|f| f.descriptor == *descriptor) {
+            return index;
+        }
+        self.fallbacks.push(FontFallback::new(self.collection, descriptor));
+        self.fallbacks.len() - 1
+    }
+
+    pub fn lines(&self) -> &[LayoutLine] {
+        &self.lines
+    }
+
+    pub fn metrics(&self) -> TextMetrics {
+        let mut metrics = TextMetrics {
+            left: f32::INFINITY,
+            top: 0.,
+            width: 0.,
+            width_including_trailing_whitespace: 0.,
+            height: 0.,
+            layout_width: self.layout_width(),
+            line_count: self.lines.len(),
+        };
+        for line in &self.lines {
+            metrics.left = metrics.left.min(line.left);
+            metrics.width = metrics.width.max(line.width);
+            metrics.width_including_trailing_whitespace =
+                metrics.width_including_trailing_whitespace.max(line.width_including_trailing_whitespace);
+            metrics.height += line.height;
+        }
+        metrics
+    }
+
+    fn char_index(&self, position: usize) -> usize {
+        match self.offsets.binary_search(&position) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        }
+    }
+
+    fn slice(&self, start: usize, end: usize) -> &str {
+        &self.text[self.byte_offsets[self.char_index(start)]..self.byte_offsets[self.char_index(end)]]
+    }
+
+    fn width(&self, start: usize, end: usize) -> f32 {
+        self.advance_sums[end] - self.advance_sums[start]
+    }
+
+    // Splits the text into runs of one format, font, script and level,
+    // and shapes them.
+    fn shape(&mut self) {
+        let text_len = self.text_len();
+        let levels = self.bidi.levels();
+        let scripts = text_analysis::analyze_scripts(&self.text);
+        self.graphemes = segmentation::grapheme_boundaries(&self.text);
+        self.breaks = self.inline_object_breaks(line_break::line_breaks(&self.text));
+
+        // Fonts are picked for each stretch of text shaped alike, which
+        // may have different colors.
+        let mut fonts: Vec<(usize, usize, usize, usize)> = vec![];
+        let mut i = 0;
+        while i < self.spans.len() {
+            let (start, format) = (self.spans[i].start, self.spans[i].format.clone());
+            while i + 1 < self.spans.len() && self.spans[i + 1].format.shapes_like(&format) {
+                i += 1;
+            }
+            let end = self.spans[i].end;
+            i += 1;
+            let fallback = self.fallback_index(&format.font);
+            let text = self.slice(start, end).to_owned();
+            for run in self.fallbacks[fallback].font_runs(&text) {
+                fonts.push((start + run.0, start + run.1, fallback, run.2));
+            }
+        }
+
+        let script_at = |position: usize| scripts.iter()
+                                                 .find(|r| r.start <= position && position < r.end)
+                                                 .map_or(DEFAULT_SCRIPT, |r| r.opentype_script());
+        let font_at = |position: usize| fonts.iter()
+                                             .find(|r| r.0 <= position && position < r.1)
+                                             .map_or((0, 0), |r| (r.2, r.3));
+
+        // Which graphemes are set upright in vertical text, by their
+        // first character.
+        let mut upright = vec![false; text_len];
+        if self.vertical {
+            for pair in self.graphemes.windows(2) {
+                let c = self.chars[self.char_index(pair[0])];
+                let is_upright = match unicode_data::vertical_orientation(c as u32) {
+                    VerticalOrientation::Upright | VerticalOrientation::TransformedOrUpright => true,
+                    VerticalOrientation::Rotated => false,
+                    VerticalOrientation::TransformedOrRotated => {
+                        let (fallback, font) = font_at(pair[0]);
+                        let font_ref = &self.fallbacks[fallback].fonts[font];
+                        let glyph = font_ref.face.get_glyph_indices(&[c as u32])[0];
+                        font_ref.shaper.has_vertical_form(glyph, script_at(pair[0]), DEFAULT_LANGUAGE)
+                    }
+                };
+                for p in pair[0]..pair[1] {
+                    upright[p] = is_upright;
+                }
+            }
+        }
+
+        let mut boundaries: Vec<usize> = vec![0, text_len];
+        for i in 1..levels.len() {
+            if levels[i] != levels[i - 1] || upright[i] != upright[i - 1] {
+                boundaries.push(i);
+            }
+        }
+        boundaries.extend(scripts.iter().map(|r| r.start));
+        boundaries.extend(fonts.iter().map(|r| r.0));
+        boundaries.extend(self.breaks.iter().filter(|b| b.mandatory).map(|b| b.position));
+        boundaries.sort();
+        boundaries.dedup();
+
+        self.runs.clear();
+        for pair in boundaries.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            let script = script_at(start);
+            let (fallback, font) = font_at(start);
+            let level = levels[start];
+            let direction = if level & 1 == 1 { TextDirection::RightToLeft } else { TextDirection::LeftToRight };
+
+            let format = &self.spans[self.span_index(start)].format;
+            let font_ref = &self.fallbacks[fallback].fonts[font];
+            let text = self.slice(start, end);
+            let mut glyphs = if let Some(object) = format.inline_object {
+                // A glyph as wide as the object stands in for each
+                // character until the lines are laid out.
+                let mut cluster_map = vec![];
+                let mut glyph_clusters = vec![];
+                for (i, c) in text.chars().enumerate() {
+                    glyph_clusters.push(cluster_map.len() as u32);
+                    cluster_map.extend((0..c.len_utf16()).map(|_| i as u16));
+                }
+                ShapedGlyphs {
+                    glyph_indices: vec![0; glyph_clusters.len()],
+                    glyph_advances: vec![object.width; glyph_clusters.len()],
+                    glyph_offsets: vec![GlyphOffset { advanceOffset: 0., ascenderOffset: 0. }; glyph_clusters.len()],
+                    cluster_map: cluster_map,
+                    glyph_clusters: glyph_clusters,
+                    is_right_to_left: level & 1 == 1,
+                    em_size: format.font_size,
+                }
+            } else if upright[start] {
+                font_ref.shaper.shape_vertical(&font_ref.face, text, format.font_size, script, DEFAULT_LANGUAGE,
+                                               &format.features)
+            } else {
+                font_ref.shaper.shape(&font_ref.face, text, format.font_size, script, DEFAULT_LANGUAGE, direction,
+                                      &format.features)
+            };
+            if format.letter_spacing != 0. && format.inline_object.is_none() {
+                // The space goes after the last glyph of each cluster.
+                let count = glyphs.glyph_clusters.len();
+                for g in 0..count {
+                    if g + 1 == count || glyphs.glyph_clusters[g + 1] != glyphs.glyph_clusters[g] {
+                        glyphs.glyph_advances[g] += format.letter_spacing;
+                    }
+                }
+            }
+            self.runs.push(ShapedRun {
+                start: start,
+                end: end,
+                fallback: fallback,
+                font: font,
+                script: script,
+                level: level,
+                is_sideways: upright[start],
+                glyphs: glyphs,
+            });
+        }
+
+        let mut advances = vec![0.; text_len];
+        let mut boundaries = vec![false; text_len + 1];
+        for run in &self.runs {
+            for (g, &advance) in run.glyphs.glyph_advances.iter().enumerate() {
+                advances[run.start + run.glyphs.glyph_clusters[g] as usize] += advance;
+            }
+            for p in run.start..run.end {
+                let i = p - run.start;
+                boundaries[p] = i == 0 || run.glyphs.cluster_map[i] != run.glyphs.cluster_map[i - 1];
+            }
+        }
+        boundaries[text_len] = true;
+        for p in 0..text_len {
+            boundaries[p] = boundaries[p] && self.graphemes.binary_search(&p).is_ok();
+        }
+        self.cluster_boundaries = boundaries;
+
+        self.advance_sums = Vec::with_capacity(text_len + 1);
+        let mut sum = 0.;
+        self.advance_sums.push(sum);
+        for advance in advances {
+            sum += advance;
+            self.advance_sums.push(sum);
+        }
+
+        self.find_hyphens();
+        self.break_lines();
+    }
+
+    // Finds where the words of the text can be hyphenated, between
+    // clusters, and shapes the hyphen for each place.
+    fn find_hyphens(&mut self) {
+        self.hyphens.clear();
+        let hyphenator = match self.hyphenator {
+            Some(hyphenator) => hyphenator,
+            None => return,
+        };
+        let words = segmentation::word_boundaries(&self.text);
+        let mut positions = vec![];
+        for pair in words.windows(2) {
+            let (first, last) = (self.char_index(pair[0]), self.char_index(pair[1]));
+            let word = &self.chars[first..last];
+            if !word.iter().all(|&c| c.is_alphabetic() || c == '\'' || c == '\u{2019}') {
+                continue;
+            }
+            positions.extend(hyphenator.hyphenate(word)
+                                       .into_iter()
+                                       .map(|i| self.offsets[first + i])
+                                       .filter(|&p| self.cluster_boundaries[p]));
+        }
+
+        // One hyphen is shaped for each run, in its font.
+        let mut shaped: Vec<(usize, LayoutGlyphRun)> = vec![];
+        for position in positions {
+            let r = self.runs.iter().position(|r| r.start < position && position <= r.end).unwrap();
+            if shaped.last().map_or(true, |s| s.0 != r) {
+                let hyphen = self.hyphen_run(&self.runs[r], position);
+                shaped.push((r, hyphen));
+            }
+            let mut hyphen = shaped.last().unwrap().1.clone();
+            hyphen.text_start = position;
+            self.hyphens.push((position, hyphen));
+        }
+    }
+
+    // A hyphen to end a line broken inside a word of `run`: U+2010, or
+    // U+002D if the run's font doesn't have it.
+    fn hyphen_run(&self, run: &ShapedRun, position: usize) -> LayoutGlyphRun {
+        let font = &self.fallbacks[run.fallback].fonts[run.font];
+        let text = if font.font.has_character(0x2010) { "\u{2010}" } else { "-" };
+        let format = self.format(position - 1);
+        let direction = if run.level & 1 == 1 { TextDirection::RightToLeft } else { TextDirection::LeftToRight };
+        let glyphs = font.shaper.shape(&font.face, text, format.font_size, run.script, DEFAULT_LANGUAGE, direction,
+                                       &format.features);
+        LayoutGlyphRun {
+            font_face: font.face.clone(),
+            em_size: glyphs.em_size,
+            glyph_indices: glyphs.glyph_indices,
+            glyph_advances: glyphs.glyph_advances,
+            glyph_offsets: glyphs.glyph_offsets,
+            bidi_level: run.level as u32,
+            is_sideways: false,
+            // Set for the line it ends, as the color can change without
+            // the text being shaped again.
+            color: None,
+            baseline_origin_x: 0.,
+            baseline_origin_y: 0.,
+            // Like an ellipsis, the hyphen isn't part of the text.
+            text_start: position,
+            cluster_map: vec![],
+        }
+    }
+
+    // Where the trailing whitespace and the line separator of a line
+    // ending at `end` start.
+    fn trailing_whitespace_start(&self, start: usize, end: usize) -> (usize, usize) {
+        let mut i = self.char_index(end);
+        let first = self.char_index(start);
+        while i > first && is_newline(self.chars[i - 1]) {
+            i -= 1;
+        }
+        let newline_start = self.offsets[i];
+        while i > first && is_hanging_whitespace(self.chars[i - 1]) {
+            i -= 1;
+        }
+        (self.offsets[i], newline_start)
+    }
+
+    // The width of a line without its trailing whitespace.
+    fn content_width(&self, start: usize, end: usize) -> f32 {
+        let (whitespace_start, _) = self.trailing_whitespace_start(start, end);
+        self.width(start, whitespace_start)
+    }
+
+    // Where lines can end, in order, as (position, whether the line
+    // has to end there, whether it's a hyphenation point).
+    fn line_end_candidates(&self) -> Vec<(usize, bool, bool)> {
+        let mut candidates: Vec<(usize, bool, bool)> = self.breaks.iter()
+            .filter(|b| self.cluster_boundaries[b.position])
+            .map(|b| (b.position, b.mandatory, false))
+            .collect();
+        candidates.extend(self.hyphens.iter().map(|h| (h.0, false, true)));
+        // Breaks come before hyphenation points at the same place, and
+        // are kept instead of them.
+        candidates.sort_by_key(|c| c.0);
+        candidates.dedup_by_key(|c| c.0);
+        candidates
+    }
+
+    // Where the line starting at `start` ends, whether it ends its
+    // paragraph, and whether it's broken at a hyphenation point.
+    // `candidates` are the line_end_candidates after `start`.
+    fn line_end(&self, start: usize, max_width: f32, candidates: &[(usize, bool, bool)]) -> (usize, bool, bool) {
+        let mut end = None;
+        for &(position, mandatory, hyphenated) in candidates {
+            let hyphen_width = if hyphenated { self.hyphens[self.hyphen_index(position)].1.width() } else { 0. };
+            if self.content_width(start, position) + hyphen_width <= max_width {
+                end = Some((position, mandatory, hyphenated));
+                if mandatory {
+                    break;
+                }
+            } else {
+                if end.is_none() {
+                    // The first word doesn't fit, so break it between
+                    // clusters, keeping at least one on the line.
+                    let mut p = start + 1;
+                    while !self.cluster_boundaries[p] {
+                        p += 1;
+                    }
+                    let mut fits = p;
+                    while p < position {
+                        p += 1;
+                        while !self.cluster_boundaries[p] {
+                            p += 1;
+                        }
+                        if self.content_width(start, p) > max_width {
+                            break;
+                        }
+                        fits = p;
+                    }
+                    end = Some((fits, false, false));
+                }
+                break;
+            }
+        }
+        end.unwrap_or((self.text_len(), true, false))
+    }
+
+    // The index in `hyphens` of the hyphenation point at `position`, or
+    // of the next one.
+    fn hyphen_index(&self, position: usize) -> usize {
+        match self.hyphens.binary_search_by_key(&position, |h| h.0) {
+            Ok(i) | Err(i) => i,
+        }
+    }
+
+    fn layout_width(&self) -> f32 {
+        if self.max_width.is_finite() {
+            return self.max_width;
+        }
+        let mut width: f32 = 0.;
+        for line in &self.lines {
+            width = width.max(line.width);
+        }
+        width
+    }
+
+    fn break_lines(&mut self) {
+        let text_len = self.text_len();
+        let mut lines = vec![];
+        let mut start = 0;
+        let mut top = 0.;
+        let wrap_width = if self.word_wrapping { self.max_width } else { f32::INFINITY };
+        let candidates = self.line_end_candidates();
+        let mut next_candidate = 0;
+        loop {
+            while next_candidate < candidates.len() && candidates[next_candidate].0 <= start {
+                next_candidate += 1;
+            }
+            let (end, ends_paragraph, hyphenated) = self.line_end(start, wrap_width, &candidates[next_candidate..]);
+            let line = self.make_line(start, end, ends_paragraph, hyphenated, top);
+            top += line.height;
+            lines.push(line);
+            start = end;
+            if start >= text_len {
+                break;
+            }
+        }
+        // A line separator at the end of the text starts one more,
+        // empty line.
+        if self.chars.last().map_or(false, |&c| is_newline(c)) {
+            lines.push(self.make_line(text_len, text_len, true, false, top));
+        }
+
+        self.is_trimmed = false;
+        if self.trimming.granularity != TrimmingGranularity::None {
+            // Keep the lines that fit in the height, or at least one;
+            // the last one kept gets an ellipsis if any are left out.
+            let visible = lines.iter().take_while(|l| l.top + l.height <= self.max_height).count().max(1);
+            let cut = visible < lines.len();
+            lines.truncate(visible);
+            for i in 0..lines.len() {
+                let more_text = cut && i + 1 == lines.len();
+                if more_text || lines[i].width > self.max_width {
+                    self.trim_line(&mut lines[i]);
+                    self.is_trimmed = true;
+                }
+            }
+        }
+        self.lines = lines;
+        self.align_lines();
+        let mut lines = mem::replace(&mut self.lines, vec![]);
+        for line in &mut lines {
+            self.take_inline_objects(line);
+        }
+        self.lines = lines;
+    }
+
+    // Whether a run stands in for inline objects.  Ellipses and
+    // hyphens never do.
+    fn is_inline_object(&self, run: &LayoutGlyphRun) -> bool {
+        !run.cluster_map.is_empty() && run.text_start < self.text_len() &&
+            self.format(run.text_start).inline_object.is_some()
+    }
+
+    // Moves the runs standing in for inline objects out of a laid out
+    // line, into its objects where their glyphs were.
+    fn take_inline_objects(&self, line: &mut LayoutLine) {
+        let mut glyph_runs = vec![];
+        for run in line.glyph_runs.drain(..) {
+            if !self.is_inline_object(&run) {
+                glyph_runs.push(run);
+                continue;
+            }
+            let is_rtl = run.is_right_to_left();
+            let mut x = run.baseline_origin_x;
+            let mut objects = vec![];
+            for (start, _, first_glyph, _) in run_clusters(&run) {
+                let object = self.format(start).inline_object.unwrap();
+                let width = run.glyph_advances[first_glyph];
+                if is_rtl {
+                    x -= width;
+                }
+                objects.push(LayoutInlineObject {
+                    text_position: start,
+                    object: object,
+                    left: x,
+                    top: run.baseline_origin_y - object.baseline,
+                    bidi_level: run.bidi_level,
+                });
+                if !is_rtl {
+                    x += width;
+                }
+            }
+            if is_rtl {
+                objects.reverse();
+            }
+            line.inline_objects.extend(objects);
+        }
+        line.glyph_runs = glyph_runs;
+    }
+
+    // The line breaks of the text, with those next to inline objects
+    // changed as the objects ask: the strongest of the conditions after
+    // the object before and before the object after wins.
+    fn inline_object_breaks(&self, mut breaks: Vec<LineBreak>) -> Vec<LineBreak> {
+        fn strength(condition: BreakCondition) -> u8 {
+            match condition {
+                BreakCondition::Neutral => 0,
+                BreakCondition::CanBreak => 1,
+                BreakCondition::MayNotBreak => 2,
+                BreakCondition::MustBreak => 3,
+            }
+        }
+        let mut conditions: Vec<(usize, BreakCondition)> = vec![];
+        for span in &self.spans {
+            if let Some(object) = span.format.inline_object {
+                for i in self.char_index(span.start)..self.char_index(span.end) {
+                    conditions.push((self.offsets[i], object.break_before));
+                    conditions.push((self.offsets[i + 1], object.break_after));
+                }
+            }
+        }
+        conditions.sort_by(|a, b| a.0.cmp(&b.0).then(strength(b.1).cmp(&strength(a.1))));
+        conditions.dedup_by_key(|c| c.0);
+
+        let text_len = self.text_len();
+        for (position, condition) in conditions {
+            if position == 0 || position >= text_len {
+                continue;
+            }
+            let existing = breaks.iter().position(|b| b.position == position);
+            match (condition, existing) {
+                (BreakCondition::CanBreak, None) => {
+                    breaks.push(LineBreak { position: position, mandatory: false });
+                }
+                (BreakCondition::MayNotBreak, Some(i)) if !breaks[i].mandatory => {
+                    breaks.remove(i);
+                }
+                (BreakCondition::MustBreak, Some(i)) => breaks[i].mandatory = true,
+                (BreakCondition::MustBreak, None) => {
+                    breaks.push(LineBreak { position: position, mandatory: true });
+                }
+                _ => {}
+            }
+        }
+        breaks.sort_by_key(|b| b.position);
+        breaks
+    }
+
+    // An ellipsis for trimming text at `position`, in the font of the
+    // text before it if the font has one.
+    fn ellipsis_run(&mut self, position: usize, level: u8) -> LayoutGlyphRun {
+        let (fallback, mut font) = self.runs.iter()
+                                       .rev()
+                                       .find(|r| r.start < position.max(1))
+                                       .map_or((0, 0), |r| (r.fallback, r.font));
+        if !self.fallbacks[fallback].fonts[font].font.has_character(0x2026) {
+            font = self.fallbacks[fallback].font_for_cluster(&['\u{2026}']);
+        }
+        let font_ref = &self.fallbacks[fallback].fonts[font];
+        // Three periods if no font has an ellipsis.
+        let text = if font_ref.font.has_character(0x2026) { "\u{2026}" } else { "..." };
+        let format = &self.spans[self.span_index(position.max(1) - 1)].format;
+        let direction = if level & 1 == 1 { TextDirection::RightToLeft } else { TextDirection::LeftToRight };
+        // An ellipsis is upright in vertical text, and periods aren't.
+        let is_sideways = self.vertical && text == "\u{2026}";
+        let glyphs = if is_sideways {
+            font_ref.shaper.shape_vertical(&font_ref.face, text, format.font_size, DEFAULT_SCRIPT, DEFAULT_LANGUAGE,
+                                           &format.features)
+        } else {
+            font_ref.shaper.shape(&font_ref.face, text, format.font_size, DEFAULT_SCRIPT, DEFAULT_LANGUAGE,
+                                  direction, &format.features)
+        };
+        LayoutGlyphRun {
+            font_face: font_ref.face.clone(),
+            em_size: glyphs.em_size,
+            glyph_indices: glyphs.glyph_indices,
+            glyph_advances: glyphs.glyph_advances,
+            glyph_offsets: glyphs.glyph_offsets,
+            bidi_level: level as u32,
+            is_sideways: is_sideways,
+            color: format.color,
+            baseline_origin_x: 0.,
+            baseline_origin_y: 0.,
+            // The ellipsis stands for text, but shows none.
+            text_start: position,
+            cluster_map: vec![],
+        }
+    }
+
+    // Cuts a line short with an ellipsis so it fits in the maximum
+    // width.
+    fn trim_line(&mut self, line: &mut LayoutLine) {
+        let level = self.bidi.paragraph_level(line.start);
+        let content_end = line.end - line.trailing_whitespace_length;
+        let ellipsis = self.ellipsis_run(content_end, level);
+        let ellipsis_width = ellipsis.width();
+
+        // The text kept after the ellipsis, from the delimiter.
+        let mut suffix_start = content_end;
+        if let Some(delimiter) = self.trimming.delimiter {
+            let first = self.char_index(line.start);
+            let mut count = 0;
+            for i in (first..self.char_index(content_end)).rev() {
+                if self.chars[i] == delimiter {
+                    count += 1;
+                    if count == self.trimming.delimiter_count.max(1) {
+                        suffix_start = self.offsets[i];
+                        break;
+                    }
+                }
+            }
+            if self.width(suffix_start, content_end) + ellipsis_width > self.max_width {
+                suffix_start = content_end;
+            }
+        }
+        let available = self.max_width - ellipsis_width - self.width(suffix_start, content_end);
+
+        // Keep as many characters or words as fit before the ellipsis,
+        // without the whitespace at their end.
+        let words = if self.trimming.granularity == TrimmingGranularity::Word {
+            segmentation::word_boundaries(&self.text)
+        } else {
+            vec![]
+        };
+        let mut kept = line.start;
+        for p in line.start + 1..suffix_start + 1 {
+            let allowed = self.cluster_boundaries[p] &&
+                          (self.trimming.granularity != TrimmingGranularity::Word || words.binary_search(&p).is_ok());
+            if allowed && self.content_width(line.start, p) <= available {
+                kept = p;
+            }
+        }
+        let kept = self.trailing_whitespace_start(line.start, kept).0;
+
+        let prefix = self.visual_runs(line.start, kept);
+        let suffix = self.visual_runs(suffix_start, content_end);
+        let mut glyph_runs = vec![];
+        // The ellipsis goes at the end of the kept text in the
+        // paragraph's direction, with the rest beyond it.
+        if level & 1 == 1 {
+            glyph_runs.extend(suffix);
+            glyph_runs.push(ellipsis);
+            glyph_runs.extend(prefix);
+        } else {
+            glyph_runs.extend(prefix);
+            glyph_runs.push(ellipsis);
+            glyph_runs.extend(suffix);
+        }
+        self.set_baseline(&mut glyph_runs, line.baseline, self.dominant_baseline_at(line.start));
+        line.width = glyph_runs.iter().fold(0., |width, run| width + run.width());
+        line.width_including_trailing_whitespace = line.width;
+        line.glyph_runs = glyph_runs;
+    }
+
+    // Builds a line, with its glyph runs at x = 0 and a hyphen at its
+    // end if it's broken at a hyphenation point.
+    fn make_line(&self, start: usize, end: usize, ends_paragraph: bool, hyphenated: bool, top: f32) -> LayoutLine {
+        let (whitespace_start, newline_start) = self.trailing_whitespace_start(start, end);
+        let mut glyph_runs = self.visual_runs(start, newline_start);
+        let mut hyphen_width = 0.;
+        if hyphenated {
+            let mut hyphen = self.hyphens[self.hyphen_index(end)].1.clone();
+            hyphen.color = self.format(end - 1).color;
+            hyphen_width = hyphen.width();
+            // Next to the run the word ends in, on its end side.
+            let index = match glyph_runs.iter().position(|r| r.text_end() == end) {
+                Some(i) if glyph_runs[i].is_right_to_left() => i,
+                Some(i) => i + 1,
+                None => glyph_runs.len(),
+            };
+            glyph_runs.insert(index, hyphen);
+        }
+
+        // The line's height comes from the fonts of its runs, or of the
+        // text before it if it's empty, and from its inline objects.
+        // Each font is raised by how far the dominant baseline is above
+        // its alphabetic one.
+        let dominant = self.dominant_baseline_at(start);
+        let (objects, runs): (Vec<&LayoutGlyphRun>, Vec<&LayoutGlyphRun>) =
+            glyph_runs.iter().partition(|r| self.is_inline_object(r));
+        let mut faces: Vec<(&FontFace, f32, f32)> = runs.iter()
+            .map(|r| (&*r.font_face, r.em_size, self.baseline_offset(&r.font_face, r.em_size, r.text_start, dominant)))
+            .collect();
+        if faces.is_empty() && objects.is_empty() {
+            let (fallback, font) = self.runs.iter()
+                                       .rev()
+                                       .find(|r| r.start < start.max(1))
+                                       .map_or((0, 0), |r| (r.fallback, r.font));
+            let face = &self.fallbacks[fallback].fonts[font].face;
+            let em_size = self.format(start).font_size;
+            faces.push((face, em_size, self.baseline_offset(face, em_size, start, dominant)));
+        }
+        let (mut ascent, mut descent, mut line_gap): (f32, f32, f32) = (0., 0., 0.);
+        for (face, em_size, offset) in faces {
+            let metrics = face.metrics();
+            let scale = em_size / metrics.designUnitsPerEm as f32;
+            if self.line_spacing.method == LineSpacingMethod::CssNormal {
+                let css = face.get_line_metrics().map_or(LineMetrics {
+                    ascent: metrics.ascent as i32,
+                    descent: metrics.descent as i32,
+                    line_gap: metrics.lineGap as i32,
+                }, |m| m.css_normal());
+                let half_gap = css.line_gap as f32 / 2.;
+                ascent = ascent.max((css.ascent as f32 + half_gap) * scale - offset);
+                descent = descent.max((css.descent as f32 + half_gap) * scale + offset);
+            } else {
+                ascent = ascent.max(metrics.ascent as f32 * scale - offset);
+                descent = descent.max(metrics.descent as f32 * scale + offset);
+                line_gap = line_gap.max(metrics.lineGap as f32 * scale);
+            }
+        }
+        for run in objects {
+            for (start, _, _, _) in run_clusters(run) {
+                let object = self.format(start).inline_object.unwrap();
+                ascent = ascent.max(object.baseline);
+                descent = descent.max(object.height - object.baseline);
+            }
+        }
+        let (height, baseline) = match self.line_spacing.method {
+            LineSpacingMethod::Default | LineSpacingMethod::CssNormal => (ascent + descent + line_gap, ascent),
+            LineSpacingMethod::Uniform => (self.line_spacing.height, self.line_spacing.baseline),
+            LineSpacingMethod::Proportional => ((ascent + descent + line_gap) * self.line_spacing.height,
+                                                ascent * self.line_spacing.baseline),
+        };
+
+        let mut line = LayoutLine {
+            start: start,
+            end: end,
+            trailing_whitespace_length: end - whitespace_start,
+            newline_length: end - newline_start,
+            top: top,
+            height: height,
+            // Vertical lines are centered on theirs.
+            baseline: if self.vertical { top + height / 2. } else { top + baseline },
+            left: 0.,
+            width: self.width(start, whitespace_start) + hyphen_width,
+            width_including_trailing_whitespace: self.width(start, newline_start) + hyphen_width,
+            glyph_runs: glyph_runs,
+            inline_objects: vec![],
+        };
+        self.set_baseline(&mut line.glyph_runs, line.baseline, dominant);
+        if !ends_paragraph && self.alignment == TextAlignment::Justified && self.max_width.is_finite() {
+            self.justify(&mut line);
+        }
+        line
+    }
+
+    // Puts a line's runs on its baseline, each with its own font's
+    // `dominant` baseline on it.  In vertical layouts, glyphs set
+    // upright are centered on it, and sideways text is moved so its em
+    // box is.  Inline objects sit on it as they are.
+    fn set_baseline(&self, glyph_runs: &mut [LayoutGlyphRun], baseline: f32, dominant: Baseline) {
+        for run in glyph_runs {
+            if self.is_inline_object(run) {
+                run.baseline_origin_y = baseline;
+                continue;
+            }
+            run.baseline_origin_y = baseline +
+                                    self.baseline_offset(&run.font_face, run.em_size, run.text_start, dominant);
+            if self.vertical && !run.is_sideways {
+                let metrics = run.font_face.metrics();
+                let scale = run.em_size / metrics.designUnitsPerEm as f32;
+                run.baseline_origin_y += (metrics.ascent as f32 - metrics.descent as f32) / 2. * scale;
+            }
+        }
+    }
+
+    // The dominant baseline of the paragraph `position` is in.
+    fn dominant_baseline_at(&self, position: usize) -> Baseline {
+        if let Some(baseline) = self.dominant_baseline {
+            return baseline;
+        }
+        let mut first = self.char_index(position.min(self.text_len()));
+        while first > 0 && !is_newline(self.chars[first - 1]) {
+            first -= 1;
+        }
+        let paragraph_start = self.offsets[first];
+        match self.runs.iter().find(|r| r.end > paragraph_start) {
+            Some(run) => {
+                let font = &self.fallbacks[run.fallback].fonts[run.font];
+                base_table::font_default_baseline(font.base.as_ref(), false, run.script)
+            }
+            None => Baseline::Roman,
+        }
+    }
+
+    // How far `baseline` is above the alphabetic one in a face at
+    // `em_size`, for the script of the text at `position`; 0 in
+    // vertical layouts, whose lines are centered instead.
+    fn baseline_offset(&self, face: &FontFace, em_size: f32, position: usize, baseline: Baseline) -> f32 {
+        if self.vertical {
+            return 0.;
+        }
+        let script = self.runs.iter().rev().find(|r| r.start <= position).map_or(DEFAULT_SCRIPT, |r| r.script);
+        let base = self.fallbacks.iter()
+                                 .flat_map(|f| f.fonts.iter())
+                                 .find(|f| &*f.face as *const FontFace == face as *const FontFace)
+                                 .and_then(|f| f.base.as_ref());
+        let metrics = face.metrics();
+        let coordinate = base_table::font_baseline(base, metrics, false, script, baseline);
+        coordinate as f32 * em_size / metrics.designUnitsPerEm as f32
+    }
+
+    // The glyph runs showing the text from `start` to `end` of a line,
+    // in visual order, at x = 0.
+    fn visual_runs(&self, start: usize, end: usize) -> Vec<LayoutGlyphRun> {
+        let mut glyph_runs = vec![];
+        for bidi_run in self.bidi.reorder_line(start, end) {
+            let mut pieces: Vec<&ShapedRun> = self.runs.iter()
+                .filter(|r| r.start < bidi_run.end && bidi_run.start < r.end)
+                .collect();
+            if bidi_run.level & 1 == 1 {
+                pieces.reverse();
+            }
+            for run in pieces {
+                let piece_start = run.start.max(bidi_run.start);
+                let piece_end = run.end.min(bidi_run.end);
+                // Split where the color changes, between clusters.
+                let mut cuts = vec![piece_start];
+                cuts.extend(self.spans.iter()
+                                .map(|s| s.start)
+                                .filter(|&p| piece_start < p && p < piece_end && self.cluster_boundaries[p]));
+                cuts.push(piece_end);
+                let mut parts: Vec<LayoutGlyphRun> = cuts.windows(2)
+                                                         .map(|pair| self.glyph_run(run, pair[0], pair[1]))
+                                                         .collect();
+                if run.level & 1 == 1 {
+                    parts.reverse();
+                }
+                glyph_runs.extend(parts);
+            }
+        }
+        glyph_runs
+    }
+
+    fn glyph_run(&self, run: &ShapedRun, start: usize, end: usize) -> LayoutGlyphRun {
+        let clusters = &run.glyphs.glyph_clusters;
+        let first_glyph = clusters.iter().position(|&c| run.start + c as usize >= start).unwrap_or(clusters.len());
+        let end_glyph = clusters.iter().position(|&c| run.start + c as usize >= end).unwrap_or(clusters.len());
+        let (first_glyph, end_glyph) = (first_glyph.min(end_glyph), end_glyph.max(first_glyph));
+        let cluster_map = run.glyphs.cluster_map[start - run.start..end - run.start]
+            .iter()
+            .map(|&g| (g as usize).saturating_sub(first_glyph) as u16)
+            .collect();
+        LayoutGlyphRun {
+            font_face: self.fallbacks[run.fallback].fonts[run.font].face.clone(),
+            em_size: run.glyphs.em_size,
+            glyph_indices: run.glyphs.glyph_indices[first_glyph..end_glyph].to_vec(),
+            glyph_advances: run.glyphs.glyph_advances[first_glyph..end_glyph].to_vec(),
+            glyph_offsets: run.glyphs.glyph_offsets[first_glyph..end_glyph].to_vec(),
+            bidi_level: run.level as u32,
+            is_sideways: run.is_sideways,
+            color: self.format(start).color,
+            baseline_origin_x: 0.,
+            baseline_origin_y: 0.,
+            text_start: start,
+            cluster_map: cluster_map,
+        }
+    }
+
+    // Stretches a line so it fills the maximum width.  Arabic is
+    // stretched first with the font's justification alternates and
+    // then with kashidas, which can only add whole tatweel glyphs; the
+    // rest of the space goes between words and after CJK characters.
+    fn justify(&self, line: &mut LayoutLine) {
+        let content_end = line.end - line.trailing_whitespace_length;
+        let mut extra = self.max_width - line.width;
+        if extra <= 0. {
+            return;
+        }
+
+        // Justification alternates, while they fit.  Runs that show no
+        // text, like hyphens, and inline objects are left alone.
+        for run in line.glyph_runs.iter_mut().filter(|r| !r.cluster_map.is_empty() && !self.is_inline_object(r)) {
+            let shaped = self.runs.iter().find(|r| r.start <= run.text_start && run.text_start < r.end).unwrap();
+            let font = &self.fallbacks[shaped.fallback].fonts[shaped.font];
+            let gsub = match font.shaper.gsub {
+                Some(ref gsub) => gsub,
+                None => continue,
+            };
+            let lookups = gsub.lookups_for_feature(shaped.script, DEFAULT_LANGUAGE, ot_tag!(b"jalt"));
+            if lookups.is_empty() {
+                continue;
+            }
+            let scale = run.em_size / font.face.metrics().designUnitsPerEm as f32;
+            for g in 0..run.glyph_indices.len() {
+                let mut buffer = GlyphBuffer::from_glyph_indices(&run.glyph_indices[g..g + 1]);
+                for &lookup in &lookups {
+                    gsub.apply_lookup(lookup, &mut buffer, GLOBAL_MASK);
+                }
+                let alternate = buffer.glyphs[0].glyph_index;
+                if buffer.len() != 1 || alternate == run.glyph_indices[g] {
+                    continue;
+                }
+                let metrics = font.face.get_design_glyph_metrics(&[run.glyph_indices[g], alternate], false);
+                let gain = (metrics[1].advanceWidth as f32 - metrics[0].advanceWidth as f32) * scale;
+                if 0. < gain && gain <= extra {
+                    run.glyph_indices[g] = alternate;
+                    run.glyph_advances[g] += gain;
+                    extra -= gain;
+                }
+            }
+        }
+
+        // Kashidas go after Arabic letters that join the next one, as
+        // (run, glyph to insert at, tatweel glyph, its advance).
+        let mut kashidas: Vec<(usize, usize, u16, f32)> = vec![];
+        for (r, run) in line.glyph_runs.iter().enumerate().filter(|&(_, r)| !r.cluster_map.is_empty()) {
+            let shaped = self.runs.iter().find(|r| r.start <= run.text_start && run.text_start < r.end);
+            let font = match shaped {
+                Some(shaped) => &self.fallbacks[shaped.fallback].fonts[shaped.font],
+                None => continue,
+            };
+            let tatweel = font.face.get_glyph_indices(&[0x0640])[0];
+            if tatweel == 0 {
+                continue;
+            }
+            let scale = run.em_size / font.face.metrics().designUnitsPerEm as f32;
+            let advance = font.face.get_design_glyph_metrics(&[tatweel], false)[0].advanceWidth as f32 * scale;
+            let clusters = run_clusters(run);
+            for pair in clusters.windows(2) {
+                if self.joins_next(pair[1].0) {
+                    kashidas.push((r, pair[0].3, tatweel, advance));
+                }
+            }
+        }
+        let mut counts = vec![0; kashidas.len()];
+        let mut added = true;
+        while added {
+            added = false;
+            for (k, kashida) in kashidas.iter().enumerate() {
+                if kashida.3 > 0. && kashida.3 <= extra {
+                    counts[k] += 1;
+                    extra -= kashida.3;
+                    added = true;
+                }
+            }
+        }
+        // From the end, so the glyph indices stay right.
+        for (k, &(r, at, tatweel, advance)) in kashidas.iter().enumerate().rev() {
+            let run = &mut line.glyph_runs[r];
+            for _ in 0..counts[k] {
+                run.glyph_indices.insert(at, tatweel);
+                run.glyph_advances.insert(at, advance);
+                run.glyph_offsets.insert(at, GlyphOffset { advanceOffset: 0., ascenderOffset: 0. });
+                for first_glyph in run.cluster_map.iter_mut().filter(|g| **g as usize >= at) {
+                    *first_glyph += 1;
+                }
+            }
+        }
+
+        // Spaces between words, and the space after CJK characters
+        // (but not the last one on the line), as (run, glyph).
+        let mut gaps: Vec<(usize, usize)> = vec![];
+        for (r, run) in line.glyph_runs.iter().enumerate() {
+            for (start, end, first_glyph, end_glyph) in run_clusters(run) {
+                if first_glyph == end_glyph || start >= content_end {
+                    continue;
+                }
+                let first = self.chars[self.char_index(start)];
+                let last = self.chars[self.char_index(end - 1)];
+                if is_hanging_whitespace(first) {
+                    gaps.push((r, first_glyph));
+                } else if end < content_end && is_cjk(last) {
+                    gaps.push((r, end_glyph - 1));
+                }
+            }
+        }
+        if !gaps.is_empty() {
+            let per_gap = extra / gaps.len() as f32;
+            for &(r, g) in &gaps {
+                line.glyph_runs[r].glyph_advances[g] += per_gap;
+            }
+            extra = 0.;
+        }
+
+        let trailing_width = line.width_including_trailing_whitespace - line.width;
+        line.width = self.max_width - extra;
+        line.width_including_trailing_whitespace = line.width + trailing_width;
+    }
+
+    // Whether the letter before `position`, ignoring the marks on it,
+    // joins the one at `position`.
+    fn joins_next(&self, position: usize) -> bool {
+        let mut i = self.char_index(position);
+        if i >= self.chars.len() {
+            return false;
+        }
+        let after = unicode_data::joining_type(self.chars[i] as u32);
+        while i > 0 && unicode_data::joining_type(self.chars[i - 1] as u32) == JoiningType::Transparent {
+            i -= 1;
+        }
+        if i == 0 {
+            return false;
+        }
+        let before = unicode_data::joining_type(self.chars[i - 1] as u32);
+        before == JoiningType::DualJoining && (after == JoiningType::DualJoining || after == JoiningType::RightJoining)
+    }
+
+    // Places each line's runs according to the alignment.
+    fn align_lines(&mut self) {
+        let layout_width = self.layout_width();
+        for i in 0..self.lines.len() {
+            let is_rtl = self.bidi.paragraph_level(self.lines[i].start) & 1 == 1;
+            let line = &mut self.lines[i];
+            let free = layout_width - line.width;
+            let at_start = match self.alignment {
+                TextAlignment::Leading | TextAlignment::Justified => 0.,
+                TextAlignment::Trailing => free,
+                TextAlignment::Center => free / 2.,
+            };
+            line.left = if is_rtl { free - at_start } else { at_start };
+            // Trailing whitespace hangs past the end of the line, which
+            // is the left for right-to-left paragraphs.
+            let mut x = if is_rtl {
+                line.left - (line.width_including_trailing_whitespace - line.width)
+            } else {
+                line.left
+            };
+            for run in &mut line.glyph_runs {
+                let width = run.width();
+                run.baseline_origin_x = if run.is_right_to_left() { x + width } else { x };
+                x += width;
+            }
+        }
+    }
+
+    // The line `position` is in; the last line for the end of the text.
+    fn line_index(&self, position: usize) -> usize {
+        self.lines.iter().position(|l| position < l.end).unwrap_or(self.lines.len() - 1)
+    }
+
+    // The graphemes of a line, in visual order from left to right.
+    // Graphemes sharing a ligature glyph are split at the font's
+    // ligature carets, or evenly if it has none.
+    fn hit_boxes(&self, line: &LayoutLine) -> Vec<HitBox> {
+        let mut boxes = vec![];
+        for run in line.glyph_runs.iter().filter(|r| !r.cluster_map.is_empty()) {
+            let shaped = self.runs.iter().find(|r| r.start <= run.text_start && run.text_start < r.end).unwrap();
+            let font = &self.fallbacks[shaped.fallback].fonts[shaped.font];
+            let is_rtl = run.is_right_to_left();
+            // The edge the next cluster starts at: from the left for
+            // left-to-right runs and the right for right-to-left ones.
+            let mut x = run.baseline_origin_x;
+            let mut run_boxes = vec![];
+            for (start, end, first_glyph, end_glyph) in run_clusters(run) {
+                let width = run.glyph_advances[first_glyph..end_glyph].iter().fold(0., |a, &b| a + b);
+
+                let mut starts = vec![start];
+                starts.extend(self.graphemes.iter().cloned().filter(|&p| start < p && p < end));
+                // How far into the cluster each grapheme after the first
+                // starts, in reading order.
+                let count = starts.len();
+                let mut splits: Vec<f32> = (1..count).map(|k| width * k as f32 / count as f32).collect();
+                // Ligature carets are along the horizontal baseline.
+                if count > 1 && end_glyph == first_glyph + 1 && !run.is_sideways {
+                    if let Some(ref gdef) = font.shaper.gdef {
+                        let scale = run.em_size / font.face.metrics().designUnitsPerEm as f32;
+                        let advance = (width / scale) as i32;
+                        let mut carets = gdef.ligature_caret_positions(run.glyph_indices[first_glyph], count, advance);
+                        if carets.len() == count - 1 {
+                            carets.sort();
+                            splits = carets.iter().map(|&c| c as f32 * scale).collect();
+                            if is_rtl {
+                                splits = splits.iter().rev().map(|&c| width - c).collect();
+                            }
+                        }
+                    }
+                }
+
+                for k in 0..count {
+                    let from = if k == 0 { 0. } else { splits[k - 1] };
+                    let to = if k + 1 == count { width } else { splits[k] };
+                    run_boxes.push(HitBox {
+                        start: starts[k],
+                        end: if k + 1 == count { end } else { starts[k + 1] },
+                        left: if is_rtl { x - to } else { x + from },
+                        width: to - from,
+                        level: run.bidi_level,
+                    });
+                }
+                x = if is_rtl { x - width } else { x + width };
+            }
+            if is_rtl {
+                run_boxes.reverse();
+            }
+            boxes.extend(run_boxes);
+        }
+        // Inline objects are hit as a whole.
+        for object in &line.inline_objects {
+            let position = object.text_position;
+            boxes.push(HitBox {
+                start: position,
+                end: self.offsets[self.char_index(position) + 1],
+                left: object.left,
+                width: object.object.width,
+                level: object.bidi_level,
+            });
+        }
+        boxes.sort_by(|a, b| a.left.partial_cmp(&b.left).unwrap_or(Ordering::Equal));
+        boxes
+    }
+
+    // The text position at a point: the grapheme hit, whether the
+    // point is on its trailing half (so the caret goes after it), and
+    // whether the point is inside the text.  Points outside the text
+    // hit the nearest grapheme.
+    pub fn hit_test_point(&self, x: f32, y: f32) -> (usize, bool, bool) {
+        let index = self.lines.iter()
+                              .position(|l| y < l.top + l.height)
+                              .unwrap_or(self.lines.len() - 1);
+        let line = &self.lines[index];
+        let inside_vertically = 0. <= y && y < line.top + line.height;
+        let boxes = self.hit_boxes(line);
+        let (first, last) = match (boxes.first(), boxes.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return (line.start, false, false),
+        };
+        if x < first.left {
+            return (first.start, first.level & 1 == 1, false);
+        }
+        if x >= last.left + last.width {
+            return (last.start, last.level & 1 == 0, false);
+        }
+        let hit = boxes.iter().find(|b| x < b.left + b.width).unwrap_or(last);
+        let on_right_half = x >= hit.left + hit.width / 2.;
+        (hit.start, on_right_half != (hit.level & 1 == 1), inside_vertically)
+    }
+
+    // Where the caret goes for a text position: before the grapheme
+    // at `position`, or after it if `is_trailing_hit`.  Returns the
+    // top of the caret, which is as tall as the line, and the box of
+    // the grapheme.
+    pub fn hit_test_text_position(&self, position: usize, is_trailing_hit: bool) -> (f32, f32, HitTestMetrics) {
+        let line = &self.lines[self.line_index(position)];
+        let boxes = self.hit_boxes(line);
+        if let Some(hit) = boxes.iter().find(|b| b.start <= position && position < b.end) {
+            let is_rtl = hit.level & 1 == 1;
+            let x = if is_trailing_hit != is_rtl { hit.left + hit.width } else { hit.left };
+            return (x, line.top, HitTestMetrics {
+                text_position: hit.start,
+                length: hit.end - hit.start,
+                left: hit.left,
+                top: line.top,
+                width: hit.width,
+                height: line.height,
+                bidi_level: hit.level,
+            });
+        }
+
+        // A line separator, or the end of the text, goes at the end of
+        // the line in the paragraph's direction.
+        let level = self.bidi.paragraph_level(position) as u32;
+        let x = if level & 1 == 1 {
+            boxes.iter().map(|b| b.left).fold(line.left + line.width, f32::min)
+        } else {
+            boxes.iter().map(|b| b.left + b.width).fold(line.left, f32::max)
+        };
+        (x, line.top, HitTestMetrics {
+            text_position: position,
+            length: if position < line.end { line.end - position } else { 0 },
+            left: x,
+            top: line.top,
+            width: 0.,
+            height: line.height,
+            bidi_level: level,
+        })
+    }
+
+    // The boxes covering the text from `start` to `start + length`,
+    // for drawing a selection: one for each stretch of a line at one
+    // bidi level, left to right and top to bottom.
+    pub fn hit_test_text_range(&self, start: usize, length: usize) -> Vec<HitTestMetrics> {
+        let end = start + length;
+        let mut rects: Vec<HitTestMetrics> = vec![];
+        for line in self.lines.iter().filter(|l| l.start < end && start < l.end) {
+            let mut previous_selected = false;
+            for hit in self.hit_boxes(line) {
+                let selected = hit.start < end && start < hit.end;
+                if selected {
+                    match rects.last_mut() {
+                        Some(ref mut rect) if previous_selected && rect.bidi_level == hit.level => {
+                            rect.text_position = rect.text_position.min(hit.start);
+                            rect.length += hit.end - hit.start;
+                            rect.width = hit.left + hit.width - rect.left;
+                            previous_selected = true;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    rects.push(HitTestMetrics {
+                        text_position: hit.start,
+                        length: hit.end - hit.start,
+                        left: hit.left,
+                        top: line.top,
+                        width: hit.width,
+                        height: line.height,
+                        bidi_level: hit.level,
+                    });
+                }
+                previous_selected = selected;
+            }
+        }
+        rects
+    }
+}
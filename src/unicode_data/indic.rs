@@ -0,0 +1,414 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Indic syllabic categories and positions of the characters handled by
+// the Indic, Khmer and Myanmar shapers, derived from the Unicode
+// IndicSyllabicCategory and IndicPositionalCategory properties with
+// the adjustments the OpenType script specs call for.
+
+// The values are the ones the syllable grammars are written in terms
+// of.
+#[allow(dead_code)]
+pub mod category {
+    pub const X: u8 = 0;
+    pub const C: u8 = 1;
+    pub const V: u8 = 2;
+    pub const N: u8 = 3;
+    pub const H: u8 = 4;
+    pub const ZWNJ: u8 = 5;
+    pub const ZWJ: u8 = 6;
+    pub const M: u8 = 7;
+    pub const SM: u8 = 8;
+    pub const A: u8 = 9;  // also vedic signs
+    pub const PLACEHOLDER: u8 = 10;
+    pub const DOTTED_CIRCLE: u8 = 11;
+    pub const RS: u8 = 12;  // register shifter (Khmer)
+    pub const M_PST: u8 = 13;
+    pub const REPHA: u8 = 14;
+    pub const RA: u8 = 15;
+    pub const CM: u8 = 16;  // consonant medial
+    pub const SYMBOL: u8 = 17;  // avagraha etc., which take marks
+    pub const CS: u8 = 18;
+    pub const V_ABV: u8 = 20;
+    pub const V_BLW: u8 = 21;
+    pub const V_PRE: u8 = 22;
+    pub const V_PST: u8 = 23;
+    pub const ROBATIC: u8 = 25;
+    pub const X_GROUP: u8 = 26;
+    pub const Y_GROUP: u8 = 27;
+    pub const AS: u8 = 32;  // asat (Myanmar)
+    pub const MH: u8 = 35;
+    pub const MR: u8 = 36;
+    pub const MW: u8 = 37;
+    pub const MY: u8 = 38;
+    pub const PT: u8 = 39;  // pwo and other tones
+    pub const VS: u8 = 40;  // variation selectors
+    pub const ML: u8 = 41;  // medial la
+}
+
+#[allow(dead_code)]
+pub mod position {
+    pub const START: u8 = 0;
+    pub const RA_TO_BECOME_REPH: u8 = 1;
+    pub const PRE_M: u8 = 2;
+    pub const PRE_C: u8 = 3;
+    pub const BASE_C: u8 = 4;
+    pub const AFTER_MAIN: u8 = 5;
+    pub const ABOVE_C: u8 = 6;
+    pub const BEFORE_SUB: u8 = 7;
+    pub const BELOW_C: u8 = 8;
+    pub const AFTER_SUB: u8 = 9;
+    pub const BEFORE_POST: u8 = 10;
+    pub const POST_C: u8 = 11;
+    pub const AFTER_POST: u8 = 12;
+    pub const SMVD: u8 = 13;
+    pub const END: u8 = 14;
+}
+
+use self::category::*;
+use self::position::*;
+
+// (first, last, (category, position)); everything else is (X, END).
+pub const INDIC_TABLE: &'static [(u32, u32, (u8, u8))] = &[
+    (0x002D, 0x002D, (PLACEHOLDER, BASE_C)),
+    (0x0030, 0x0039, (PLACEHOLDER, BASE_C)),
+    (0x00A0, 0x00A0, (PLACEHOLDER, BASE_C)),
+    (0x00B2, 0x00B3, (SM, SMVD)),
+    (0x00D7, 0x00D7, (PLACEHOLDER, BASE_C)),
+    (0x0900, 0x0903, (SM, SMVD)),
+    (0x0904, 0x0914, (V, BASE_C)),
+    (0x0915, 0x092F, (C, BASE_C)),
+    (0x0930, 0x0930, (RA, BASE_C)),
+    (0x0931, 0x0939, (C, BASE_C)),
+    (0x093A, 0x093B, (M, AFTER_SUB)),
+    (0x093C, 0x093C, (N, END)),
+    (0x093D, 0x093D, (SYMBOL, SMVD)),
+    (0x093E, 0x093E, (M, AFTER_SUB)),
+    (0x093F, 0x093F, (M, PRE_M)),
+    (0x0940, 0x094C, (M, AFTER_SUB)),
+    (0x094D, 0x094D, (H, BELOW_C)),
+    (0x094E, 0x094E, (M, PRE_M)),
+    (0x094F, 0x094F, (M, AFTER_SUB)),
+    (0x0951, 0x0952, (A, SMVD)),
+    (0x0953, 0x0954, (SM, SMVD)),
+    (0x0955, 0x0957, (M, AFTER_SUB)),
+    (0x0958, 0x095F, (C, BASE_C)),
+    (0x0960, 0x0961, (V, BASE_C)),
+    (0x0962, 0x0963, (M, AFTER_SUB)),
+    (0x0966, 0x096F, (PLACEHOLDER, BASE_C)),
+    (0x0972, 0x0977, (V, BASE_C)),
+    (0x0978, 0x097F, (C, BASE_C)),
+    (0x0980, 0x0980, (PLACEHOLDER, BASE_C)),
+    (0x0981, 0x0983, (SM, SMVD)),
+    (0x0985, 0x098C, (V, BASE_C)),
+    (0x098F, 0x0990, (V, BASE_C)),
+    (0x0993, 0x0994, (V, BASE_C)),
+    (0x0995, 0x09A8, (C, BASE_C)),
+    (0x09AA, 0x09AF, (C, BASE_C)),
+    (0x09B0, 0x09B0, (RA, BASE_C)),
+    (0x09B2, 0x09B2, (C, BASE_C)),
+    (0x09B6, 0x09B9, (C, BASE_C)),
+    (0x09BC, 0x09BC, (N, END)),
+    (0x09BD, 0x09BD, (SYMBOL, SMVD)),
+    (0x09BE, 0x09BE, (M, AFTER_POST)),
+    (0x09BF, 0x09BF, (M, PRE_M)),
+    (0x09C0, 0x09C0, (M, AFTER_POST)),
+    (0x09C1, 0x09C4, (M, AFTER_SUB)),
+    (0x09C7, 0x09C8, (M, PRE_M)),
+    (0x09CB, 0x09CC, (M, AFTER_POST)),
+    (0x09CD, 0x09CD, (H, BELOW_C)),
+    (0x09CE, 0x09CE, (C, BASE_C)),
+    (0x09D7, 0x09D7, (M, AFTER_POST)),
+    (0x09DC, 0x09DD, (C, BASE_C)),
+    (0x09DF, 0x09DF, (C, BASE_C)),
+    (0x09E0, 0x09E1, (V, BASE_C)),
+    (0x09E2, 0x09E3, (M, AFTER_SUB)),
+    (0x09E6, 0x09EF, (PLACEHOLDER, BASE_C)),
+    (0x09F0, 0x09F0, (RA, BASE_C)),
+    (0x09F1, 0x09F1, (C, BASE_C)),
+    (0x09FC, 0x09FC, (PLACEHOLDER, BASE_C)),
+    (0x09FE, 0x09FE, (SM, SMVD)),
+    (0x0A01, 0x0A03, (SM, SMVD)),
+    (0x0A05, 0x0A0A, (V, BASE_C)),
+    (0x0A0F, 0x0A10, (V, BASE_C)),
+    (0x0A13, 0x0A14, (V, BASE_C)),
+    (0x0A15, 0x0A28, (C, BASE_C)),
+    (0x0A2A, 0x0A2F, (C, BASE_C)),
+    (0x0A30, 0x0A30, (RA, BASE_C)),
+    (0x0A32, 0x0A33, (C, BASE_C)),
+    (0x0A35, 0x0A36, (C, BASE_C)),
+    (0x0A38, 0x0A39, (C, BASE_C)),
+    (0x0A3C, 0x0A3C, (N, END)),
+    (0x0A3E, 0x0A3E, (M, AFTER_POST)),
+    (0x0A3F, 0x0A3F, (M, PRE_M)),
+    (0x0A40, 0x0A40, (M_PST, AFTER_POST)),
+    (0x0A41, 0x0A42, (M, AFTER_POST)),
+    (0x0A47, 0x0A48, (M, AFTER_POST)),
+    (0x0A4B, 0x0A4C, (M, AFTER_POST)),
+    (0x0A4D, 0x0A4D, (H, BELOW_C)),
+    (0x0A51, 0x0A51, (M, BELOW_C)),
+    (0x0A59, 0x0A5C, (C, BASE_C)),
+    (0x0A5E, 0x0A5E, (C, BASE_C)),
+    (0x0A66, 0x0A6F, (PLACEHOLDER, BASE_C)),
+    (0x0A70, 0x0A71, (SM, SMVD)),
+    (0x0A72, 0x0A73, (C, BASE_C)),
+    (0x0A75, 0x0A75, (CM, BASE_C)),
+    (0x0A81, 0x0A83, (SM, SMVD)),
+    (0x0A85, 0x0A8D, (V, BASE_C)),
+    (0x0A8F, 0x0A91, (V, BASE_C)),
+    (0x0A93, 0x0A94, (V, BASE_C)),
+    (0x0A95, 0x0AA8, (C, BASE_C)),
+    (0x0AAA, 0x0AAF, (C, BASE_C)),
+    (0x0AB0, 0x0AB0, (RA, BASE_C)),
+    (0x0AB2, 0x0AB3, (C, BASE_C)),
+    (0x0AB5, 0x0AB9, (C, BASE_C)),
+    (0x0ABC, 0x0ABC, (N, END)),
+    (0x0ABD, 0x0ABD, (SYMBOL, SMVD)),
+    (0x0ABE, 0x0ABE, (M, AFTER_POST)),
+    (0x0ABF, 0x0ABF, (M, PRE_M)),
+    (0x0AC0, 0x0AC4, (M, AFTER_POST)),
+    (0x0AC5, 0x0AC5, (M, AFTER_SUB)),
+    (0x0AC7, 0x0AC8, (M, AFTER_SUB)),
+    (0x0AC9, 0x0AC9, (M, AFTER_POST)),
+    (0x0ACB, 0x0ACC, (M, AFTER_POST)),
+    (0x0ACD, 0x0ACD, (H, BELOW_C)),
+    (0x0AE0, 0x0AE1, (V, BASE_C)),
+    (0x0AE2, 0x0AE3, (M, AFTER_POST)),
+    (0x0AE6, 0x0AEF, (PLACEHOLDER, BASE_C)),
+    (0x0AF9, 0x0AF9, (C, BASE_C)),
+    (0x0AFA, 0x0AFA, (A, SMVD)),
+    (0x0AFB, 0x0AFB, (N, END)),
+    (0x0AFC, 0x0AFC, (A, SMVD)),
+    (0x0AFD, 0x0AFF, (N, END)),
+    (0x0B01, 0x0B01, (SM, BEFORE_SUB)),
+    (0x0B02, 0x0B03, (SM, SMVD)),
+    (0x0B05, 0x0B0C, (V, BASE_C)),
+    (0x0B0F, 0x0B10, (V, BASE_C)),
+    (0x0B13, 0x0B14, (V, BASE_C)),
+    (0x0B15, 0x0B28, (C, BASE_C)),
+    (0x0B2A, 0x0B2F, (C, BASE_C)),
+    (0x0B30, 0x0B30, (RA, BASE_C)),
+    (0x0B32, 0x0B33, (C, BASE_C)),
+    (0x0B35, 0x0B39, (C, BASE_C)),
+    (0x0B3C, 0x0B3C, (N, END)),
+    (0x0B3D, 0x0B3D, (SYMBOL, SMVD)),
+    (0x0B3E, 0x0B3E, (M, AFTER_POST)),
+    (0x0B3F, 0x0B3F, (M, AFTER_MAIN)),
+    (0x0B40, 0x0B40, (M, AFTER_POST)),
+    (0x0B41, 0x0B44, (M, AFTER_SUB)),
+    (0x0B47, 0x0B47, (M, PRE_M)),
+    (0x0B48, 0x0B48, (M, AFTER_MAIN)),
+    (0x0B4B, 0x0B4C, (M, AFTER_POST)),
+    (0x0B4D, 0x0B4D, (H, BELOW_C)),
+    (0x0B55, 0x0B55, (N, END)),
+    (0x0B56, 0x0B56, (M, AFTER_MAIN)),
+    (0x0B57, 0x0B57, (M, AFTER_POST)),
+    (0x0B5C, 0x0B5D, (C, BASE_C)),
+    (0x0B5F, 0x0B5F, (C, BASE_C)),
+    (0x0B60, 0x0B61, (V, BASE_C)),
+    (0x0B62, 0x0B63, (M, AFTER_SUB)),
+    (0x0B66, 0x0B6F, (PLACEHOLDER, BASE_C)),
+    (0x0B71, 0x0B71, (C, BASE_C)),
+    (0x0B82, 0x0B82, (SM, SMVD)),
+    (0x0B85, 0x0B8A, (V, BASE_C)),
+    (0x0B8E, 0x0B90, (V, BASE_C)),
+    (0x0B92, 0x0B94, (V, BASE_C)),
+    (0x0B95, 0x0B95, (C, BASE_C)),
+    (0x0B99, 0x0B9A, (C, BASE_C)),
+    (0x0B9C, 0x0B9C, (C, BASE_C)),
+    (0x0B9E, 0x0B9F, (C, BASE_C)),
+    (0x0BA3, 0x0BA4, (C, BASE_C)),
+    (0x0BA8, 0x0BAA, (C, BASE_C)),
+    (0x0BAE, 0x0BAF, (C, BASE_C)),
+    (0x0BB0, 0x0BB0, (RA, BASE_C)),
+    (0x0BB1, 0x0BB9, (C, BASE_C)),
+    (0x0BBE, 0x0BBF, (M, AFTER_POST)),
+    (0x0BC0, 0x0BC0, (M, AFTER_SUB)),
+    (0x0BC1, 0x0BC2, (M, AFTER_POST)),
+    (0x0BC6, 0x0BC8, (M, PRE_M)),
+    (0x0BCA, 0x0BCC, (M, AFTER_POST)),
+    (0x0BCD, 0x0BCD, (H, ABOVE_C)),
+    (0x0BD7, 0x0BD7, (M, AFTER_POST)),
+    (0x0BE6, 0x0BEF, (PLACEHOLDER, BASE_C)),
+    (0x0C00, 0x0C04, (SM, SMVD)),
+    (0x0C05, 0x0C0C, (V, BASE_C)),
+    (0x0C0E, 0x0C10, (V, BASE_C)),
+    (0x0C12, 0x0C14, (V, BASE_C)),
+    (0x0C15, 0x0C28, (C, BASE_C)),
+    (0x0C2A, 0x0C2F, (C, BASE_C)),
+    (0x0C30, 0x0C30, (RA, BASE_C)),
+    (0x0C31, 0x0C39, (C, BASE_C)),
+    (0x0C3C, 0x0C3C, (N, END)),
+    (0x0C3D, 0x0C3D, (SYMBOL, SMVD)),
+    (0x0C3E, 0x0C42, (M, BEFORE_SUB)),
+    (0x0C43, 0x0C44, (M, AFTER_SUB)),
+    (0x0C46, 0x0C48, (M, BEFORE_SUB)),
+    (0x0C4A, 0x0C4C, (M, BEFORE_SUB)),
+    (0x0C4D, 0x0C4D, (H, ABOVE_C)),
+    (0x0C55, 0x0C56, (M, BEFORE_SUB)),
+    (0x0C58, 0x0C5A, (C, BASE_C)),
+    (0x0C5D, 0x0C5D, (C, BASE_C)),
+    (0x0C60, 0x0C61, (V, BASE_C)),
+    (0x0C62, 0x0C63, (M, BEFORE_SUB)),
+    (0x0C66, 0x0C6F, (PLACEHOLDER, BASE_C)),
+    (0x0C80, 0x0C80, (PLACEHOLDER, BASE_C)),
+    (0x0C81, 0x0C83, (SM, SMVD)),
+    (0x0C85, 0x0C8C, (V, BASE_C)),
+    (0x0C8E, 0x0C90, (V, BASE_C)),
+    (0x0C92, 0x0C94, (V, BASE_C)),
+    (0x0C95, 0x0CA8, (C, BASE_C)),
+    (0x0CAA, 0x0CAF, (C, BASE_C)),
+    (0x0CB0, 0x0CB0, (RA, BASE_C)),
+    (0x0CB1, 0x0CB3, (C, BASE_C)),
+    (0x0CB5, 0x0CB9, (C, BASE_C)),
+    (0x0CBC, 0x0CBC, (N, END)),
+    (0x0CBD, 0x0CBD, (SYMBOL, SMVD)),
+    (0x0CBE, 0x0CC2, (M, BEFORE_SUB)),
+    (0x0CC3, 0x0CC4, (M, AFTER_SUB)),
+    (0x0CC6, 0x0CC6, (M, BEFORE_SUB)),
+    (0x0CC7, 0x0CC8, (M, AFTER_SUB)),
+    (0x0CCA, 0x0CCB, (M, AFTER_SUB)),
+    (0x0CCC, 0x0CCC, (M, BEFORE_SUB)),
+    (0x0CCD, 0x0CCD, (H, ABOVE_C)),
+    (0x0CD5, 0x0CD6, (M, AFTER_SUB)),
+    (0x0CDD, 0x0CDE, (C, BASE_C)),
+    (0x0CE0, 0x0CE1, (V, BASE_C)),
+    (0x0CE2, 0x0CE3, (M, BEFORE_SUB)),
+    (0x0CE6, 0x0CEF, (PLACEHOLDER, BASE_C)),
+    (0x0CF1, 0x0CF2, (CS, BASE_C)),
+    (0x0CF3, 0x0CF3, (SM, SMVD)),
+    (0x0D00, 0x0D03, (SM, SMVD)),
+    (0x0D04, 0x0D04, (PLACEHOLDER, BASE_C)),
+    (0x0D05, 0x0D0C, (V, BASE_C)),
+    (0x0D0E, 0x0D10, (V, BASE_C)),
+    (0x0D12, 0x0D14, (V, BASE_C)),
+    (0x0D15, 0x0D2F, (C, BASE_C)),
+    (0x0D30, 0x0D30, (RA, BASE_C)),
+    (0x0D31, 0x0D3A, (C, BASE_C)),
+    (0x0D3B, 0x0D3C, (M, AFTER_SUB)),
+    (0x0D3D, 0x0D3D, (SYMBOL, SMVD)),
+    (0x0D3E, 0x0D44, (M, AFTER_POST)),
+    (0x0D46, 0x0D48, (M, PRE_M)),
+    (0x0D4A, 0x0D4C, (M, AFTER_POST)),
+    (0x0D4D, 0x0D4D, (H, ABOVE_C)),
+    (0x0D4E, 0x0D4E, (REPHA, END)),
+    (0x0D54, 0x0D56, (C, BASE_C)),
+    (0x0D57, 0x0D57, (M, AFTER_POST)),
+    (0x0D5F, 0x0D61, (V, BASE_C)),
+    (0x0D62, 0x0D63, (M, AFTER_POST)),
+    (0x0D66, 0x0D6F, (PLACEHOLDER, BASE_C)),
+    (0x0D7A, 0x0D7F, (C, BASE_C)),
+    (0x1000, 0x1003, (C, BASE_C)),
+    (0x1004, 0x1004, (RA, BASE_C)),
+    (0x1005, 0x101A, (C, BASE_C)),
+    (0x101B, 0x101B, (RA, BASE_C)),
+    (0x101C, 0x1020, (C, BASE_C)),
+    (0x1021, 0x102A, (V, BASE_C)),
+    (0x102B, 0x102C, (V_PST, POST_C)),
+    (0x102D, 0x102E, (V_ABV, ABOVE_C)),
+    (0x102F, 0x1030, (V_BLW, BELOW_C)),
+    (0x1031, 0x1031, (V_PRE, PRE_C)),
+    (0x1032, 0x1032, (A, SMVD)),
+    (0x1033, 0x1035, (V_ABV, ABOVE_C)),
+    (0x1036, 0x1036, (A, SMVD)),
+    (0x1037, 0x1037, (N, END)),
+    (0x1038, 0x1038, (SM, SMVD)),
+    (0x1039, 0x1039, (H, END)),
+    (0x103A, 0x103A, (AS, END)),
+    (0x103B, 0x103B, (MY, END)),
+    (0x103C, 0x103C, (MR, END)),
+    (0x103D, 0x103D, (MW, END)),
+    (0x103E, 0x103E, (MH, END)),
+    (0x103F, 0x103F, (C, BASE_C)),
+    (0x1040, 0x104B, (PLACEHOLDER, BASE_C)),
+    (0x104E, 0x104E, (C, BASE_C)),
+    (0x1050, 0x1051, (C, BASE_C)),
+    (0x1052, 0x1055, (V, BASE_C)),
+    (0x1056, 0x1057, (V_PST, POST_C)),
+    (0x1058, 0x1059, (V_BLW, BELOW_C)),
+    (0x105A, 0x105A, (RA, BASE_C)),
+    (0x105B, 0x105D, (C, BASE_C)),
+    (0x105E, 0x105F, (MY, END)),
+    (0x1060, 0x1060, (ML, END)),
+    (0x1061, 0x1061, (C, BASE_C)),
+    (0x1062, 0x1062, (V_PST, POST_C)),
+    (0x1063, 0x1064, (PT, END)),
+    (0x1065, 0x1066, (C, BASE_C)),
+    (0x1067, 0x1068, (V_PST, POST_C)),
+    (0x1069, 0x106D, (PT, END)),
+    (0x106E, 0x1070, (C, BASE_C)),
+    (0x1071, 0x1074, (V_ABV, ABOVE_C)),
+    (0x1075, 0x1081, (C, BASE_C)),
+    (0x1082, 0x1082, (MW, END)),
+    (0x1083, 0x1083, (V_PST, POST_C)),
+    (0x1084, 0x1084, (V_PRE, PRE_C)),
+    (0x1085, 0x1086, (V_ABV, ABOVE_C)),
+    (0x1087, 0x108D, (SM, SMVD)),
+    (0x108E, 0x108E, (C, BASE_C)),
+    (0x108F, 0x108F, (SM, SMVD)),
+    (0x1090, 0x1099, (PLACEHOLDER, BASE_C)),
+    (0x109A, 0x109C, (SM, SMVD)),
+    (0x109D, 0x109D, (V_ABV, ABOVE_C)),
+    (0x1780, 0x1799, (C, BASE_C)),
+    (0x179A, 0x179A, (RA, BASE_C)),
+    (0x179B, 0x17A2, (C, BASE_C)),
+    (0x17A3, 0x17B3, (V, BASE_C)),
+    (0x17B6, 0x17B6, (V_PST, POST_C)),
+    (0x17B7, 0x17BA, (V_ABV, ABOVE_C)),
+    (0x17BB, 0x17BD, (V_BLW, BELOW_C)),
+    (0x17BE, 0x17BE, (V_ABV, ABOVE_C)),
+    (0x17BF, 0x17C0, (V_PST, POST_C)),
+    (0x17C1, 0x17C3, (V_PRE, PRE_C)),
+    (0x17C4, 0x17C5, (V_PST, POST_C)),
+    (0x17C6, 0x17C6, (X_GROUP, END)),
+    (0x17C7, 0x17C8, (Y_GROUP, END)),
+    (0x17C9, 0x17CA, (ROBATIC, END)),
+    (0x17CB, 0x17CB, (X_GROUP, END)),
+    (0x17CC, 0x17CC, (ROBATIC, END)),
+    (0x17CD, 0x17D1, (X_GROUP, END)),
+    (0x17D2, 0x17D2, (H, END)),
+    (0x17D3, 0x17D3, (Y_GROUP, END)),
+    (0x17D9, 0x17D9, (PLACEHOLDER, BASE_C)),
+    (0x17DC, 0x17DC, (SYMBOL, SMVD)),
+    (0x17DD, 0x17DD, (Y_GROUP, END)),
+    (0x17E0, 0x17E9, (PLACEHOLDER, BASE_C)),
+    (0x1CD0, 0x1CD2, (A, SMVD)),
+    (0x1CD4, 0x1CE8, (A, SMVD)),
+    (0x1CE9, 0x1CEC, (SYMBOL, SMVD)),
+    (0x1CED, 0x1CED, (A, SMVD)),
+    (0x1CEE, 0x1CF1, (SYMBOL, SMVD)),
+    (0x1CF2, 0x1CF3, (C, BASE_C)),
+    (0x1CF4, 0x1CF4, (A, SMVD)),
+    (0x1CF5, 0x1CF6, (C, BASE_C)),
+    (0x1CF7, 0x1CF9, (A, SMVD)),
+    (0x1CFA, 0x1CFA, (PLACEHOLDER, BASE_C)),
+    (0x200C, 0x200C, (ZWNJ, END)),
+    (0x200D, 0x200D, (ZWJ, END)),
+    (0x2010, 0x2015, (PLACEHOLDER, BASE_C)),
+    (0x2022, 0x2022, (PLACEHOLDER, BASE_C)),
+    (0x2074, 0x2074, (SM, SMVD)),
+    (0x2082, 0x2084, (SM, SMVD)),
+    (0x25CC, 0x25CC, (DOTTED_CIRCLE, BASE_C)),
+    (0x25FB, 0x25FE, (PLACEHOLDER, BASE_C)),
+    (0xA8E0, 0xA8F1, (A, SMVD)),
+    (0xA8F2, 0xA8F7, (SYMBOL, SMVD)),
+    (0xA8FE, 0xA8FE, (V, BASE_C)),
+    (0xA8FF, 0xA8FF, (M, AFTER_SUB)),
+    (0xA9E0, 0xA9E4, (C, BASE_C)),
+    (0xA9E5, 0xA9E5, (V_ABV, ABOVE_C)),
+    (0xA9E7, 0xA9EF, (C, BASE_C)),
+    (0xA9F0, 0xA9F9, (PLACEHOLDER, BASE_C)),
+    (0xA9FA, 0xA9FE, (C, BASE_C)),
+    (0xAA60, 0xAA6F, (C, BASE_C)),
+    (0xAA71, 0xAA73, (C, BASE_C)),
+    (0xAA74, 0xAA76, (PLACEHOLDER, BASE_C)),
+    (0xAA7A, 0xAA7A, (C, BASE_C)),
+    (0xAA7B, 0xAA7B, (PT, END)),
+    (0xAA7C, 0xAA7D, (N, END)),
+    (0xAA7E, 0xAA7F, (C, BASE_C)),
+    (0xFE00, 0xFE0F, (VS, END)),
+    (0x11301, 0x11303, (SM, SMVD)),
+    (0x1133B, 0x1133C, (N, END)),
+    (0x116D0, 0x116E3, (PLACEHOLDER, BASE_C)),
+];
@@ -0,0 +1,519 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Generated from the Unicode 15.0 DerivedJoiningType.txt, with the
+// Syriac ALAPH and DALATH RISH joining groups from ArabicShaping.txt
+// split out.  Join_Causing characters are folded into Dual_Joining, and
+// characters not listed are Non_Joining.
+
+use super::JoiningType;
+use super::JoiningType::{LeftJoining as L, RightJoining as R, DualJoining as D, Transparent as T,
+                         Alaph, DalathRish};
+
+pub const JOINING_TYPE_TABLE: &'static [(u32, u32, JoiningType)] = &[
+    (0x00AD, 0x00AD, T),
+    (0x0300, 0x036F, T),
+    (0x0483, 0x0489, T),
+    (0x0591, 0x05BD, T),
+    (0x05BF, 0x05BF, T),
+    (0x05C1, 0x05C2, T),
+    (0x05C4, 0x05C5, T),
+    (0x05C7, 0x05C7, T),
+    (0x0610, 0x061A, T),
+    (0x061C, 0x061C, T),
+    (0x0620, 0x0620, D),
+    (0x0622, 0x0625, R),
+    (0x0626, 0x0626, D),
+    (0x0627, 0x0627, R),
+    (0x0628, 0x0628, D),
+    (0x0629, 0x0629, R),
+    (0x062A, 0x062E, D),
+    (0x062F, 0x0632, R),
+    (0x0633, 0x0647, D),
+    (0x0648, 0x0648, R),
+    (0x0649, 0x064A, D),
+    (0x064B, 0x065F, T),
+    (0x066E, 0x066F, D),
+    (0x0670, 0x0670, T),
+    (0x0671, 0x0673, R),
+    (0x0675, 0x0677, R),
+    (0x0678, 0x0687, D),
+    (0x0688, 0x0699, R),
+    (0x069A, 0x06BF, D),
+    (0x06C0, 0x06C0, R),
+    (0x06C1, 0x06C2, D),
+    (0x06C3, 0x06CB, R),
+    (0x06CC, 0x06CC, D),
+    (0x06CD, 0x06CD, R),
+    (0x06CE, 0x06CE, D),
+    (0x06CF, 0x06CF, R),
+    (0x06D0, 0x06D1, D),
+    (0x06D2, 0x06D3, R),
+    (0x06D5, 0x06D5, R),
+    (0x06D6, 0x06DC, T),
+    (0x06DF, 0x06E4, T),
+    (0x06E7, 0x06E8, T),
+    (0x06EA, 0x06ED, T),
+    (0x06EE, 0x06EF, R),
+    (0x06FA, 0x06FC, D),
+    (0x06FF, 0x06FF, D),
+    (0x070F, 0x070F, T),
+    (0x0710, 0x0710, Alaph),
+    (0x0711, 0x0711, T),
+    (0x0712, 0x0714, D),
+    (0x0715, 0x0716, DalathRish),
+    (0x0717, 0x0719, R),
+    (0x071A, 0x071D, D),
+    (0x071E, 0x071E, R),
+    (0x071F, 0x0727, D),
+    (0x0728, 0x0728, R),
+    (0x0729, 0x0729, D),
+    (0x072A, 0x072A, DalathRish),
+    (0x072B, 0x072B, D),
+    (0x072C, 0x072C, R),
+    (0x072D, 0x072E, D),
+    (0x072F, 0x072F, DalathRish),
+    (0x0730, 0x074A, T),
+    (0x074D, 0x074D, R),
+    (0x074E, 0x0758, D),
+    (0x0759, 0x075B, R),
+    (0x075C, 0x076A, D),
+    (0x076B, 0x076C, R),
+    (0x076D, 0x0770, D),
+    (0x0771, 0x0771, R),
+    (0x0772, 0x0772, D),
+    (0x0773, 0x0774, R),
+    (0x0775, 0x0777, D),
+    (0x0778, 0x0779, R),
+    (0x077A, 0x077F, D),
+    (0x07A6, 0x07B0, T),
+    (0x07CA, 0x07EA, D),
+    (0x07EB, 0x07F3, T),
+    (0x07FA, 0x07FA, D),
+    (0x07FD, 0x07FD, T),
+    (0x0816, 0x0819, T),
+    (0x081B, 0x0823, T),
+    (0x0825, 0x0827, T),
+    (0x0829, 0x082D, T),
+    (0x0840, 0x0840, R),
+    (0x0841, 0x0845, D),
+    (0x0846, 0x0847, R),
+    (0x0848, 0x0848, D),
+    (0x0849, 0x0849, R),
+    (0x084A, 0x0853, D),
+    (0x0854, 0x0854, R),
+    (0x0855, 0x0855, D),
+    (0x0856, 0x0858, R),
+    (0x0859, 0x085B, T),
+    (0x0860, 0x0860, D),
+    (0x0862, 0x0865, D),
+    (0x0867, 0x0867, R),
+    (0x0868, 0x0868, D),
+    (0x0869, 0x086A, R),
+    (0x0870, 0x0882, R),
+    (0x0883, 0x0886, D),
+    (0x0889, 0x088D, D),
+    (0x088E, 0x088E, R),
+    (0x0898, 0x089F, T),
+    (0x08A0, 0x08A9, D),
+    (0x08AA, 0x08AC, R),
+    (0x08AE, 0x08AE, R),
+    (0x08AF, 0x08B0, D),
+    (0x08B1, 0x08B2, R),
+    (0x08B3, 0x08B8, D),
+    (0x08B9, 0x08B9, R),
+    (0x08BA, 0x08C8, D),
+    (0x08CA, 0x08E1, T),
+    (0x08E3, 0x0902, T),
+    (0x093A, 0x093A, T),
+    (0x093C, 0x093C, T),
+    (0x0941, 0x0948, T),
+    (0x094D, 0x094D, T),
+    (0x0951, 0x0957, T),
+    (0x0962, 0x0963, T),
+    (0x0981, 0x0981, T),
+    (0x09BC, 0x09BC, T),
+    (0x09C1, 0x09C4, T),
+    (0x09CD, 0x09CD, T),
+    (0x09E2, 0x09E3, T),
+    (0x09FE, 0x09FE, T),
+    (0x0A01, 0x0A02, T),
+    (0x0A3C, 0x0A3C, T),
+    (0x0A41, 0x0A42, T),
+    (0x0A47, 0x0A48, T),
+    (0x0A4B, 0x0A4D, T),
+    (0x0A51, 0x0A51, T),
+    (0x0A70, 0x0A71, T),
+    (0x0A75, 0x0A75, T),
+    (0x0A81, 0x0A82, T),
+    (0x0ABC, 0x0ABC, T),
+    (0x0AC1, 0x0AC5, T),
+    (0x0AC7, 0x0AC8, T),
+    (0x0ACD, 0x0ACD, T),
+    (0x0AE2, 0x0AE3, T),
+    (0x0AFA, 0x0AFF, T),
+    (0x0B01, 0x0B01, T),
+    (0x0B3C, 0x0B3C, T),
+    (0x0B3F, 0x0B3F, T),
+    (0x0B41, 0x0B44, T),
+    (0x0B4D, 0x0B4D, T),
+    (0x0B55, 0x0B56, T),
+    (0x0B62, 0x0B63, T),
+    (0x0B82, 0x0B82, T),
+    (0x0BC0, 0x0BC0, T),
+    (0x0BCD, 0x0BCD, T),
+    (0x0C00, 0x0C00, T),
+    (0x0C04, 0x0C04, T),
+    (0x0C3C, 0x0C3C, T),
+    (0x0C3E, 0x0C40, T),
+    (0x0C46, 0x0C48, T),
+    (0x0C4A, 0x0C4D, T),
+    (0x0C55, 0x0C56, T),
+    (0x0C62, 0x0C63, T),
+    (0x0C81, 0x0C81, T),
+    (0x0CBC, 0x0CBC, T),
+    (0x0CBF, 0x0CBF, T),
+    (0x0CC6, 0x0CC6, T),
+    (0x0CCC, 0x0CCD, T),
+    (0x0CE2, 0x0CE3, T),
+    (0x0D00, 0x0D01, T),
+    (0x0D3B, 0x0D3C, T),
+    (0x0D41, 0x0D44, T),
+    (0x0D4D, 0x0D4D, T),
+    (0x0D62, 0x0D63, T),
+    (0x0D81, 0x0D81, T),
+    (0x0DCA, 0x0DCA, T),
+    (0x0DD2, 0x0DD4, T),
+    (0x0DD6, 0x0DD6, T),
+    (0x0E31, 0x0E31, T),
+    (0x0E34, 0x0E3A, T),
+    (0x0E47, 0x0E4E, T),
+    (0x0EB1, 0x0EB1, T),
+    (0x0EB4, 0x0EBC, T),
+    (0x0EC8, 0x0ECE, T),
+    (0x0F18, 0x0F19, T),
+    (0x0F35, 0x0F35, T),
+    (0x0F37, 0x0F37, T),
+    (0x0F39, 0x0F39, T),
+    (0x0F71, 0x0F7E, T),
+    (0x0F80, 0x0F84, T),
+    (0x0F86, 0x0F87, T),
+    (0x0F8D, 0x0F97, T),
+    (0x0F99, 0x0FBC, T),
+    (0x0FC6, 0x0FC6, T),
+    (0x102D, 0x1030, T),
+    (0x1032, 0x1037, T),
+    (0x1039, 0x103A, T),
+    (0x103D, 0x103E, T),
+    (0x1058, 0x1059, T),
+    (0x105E, 0x1060, T),
+    (0x1071, 0x1074, T),
+    (0x1082, 0x1082, T),
+    (0x1085, 0x1086, T),
+    (0x108D, 0x108D, T),
+    (0x109D, 0x109D, T),
+    (0x135D, 0x135F, T),
+    (0x1712, 0x1714, T),
+    (0x1732, 0x1733, T),
+    (0x1752, 0x1753, T),
+    (0x1772, 0x1773, T),
+    (0x17B4, 0x17B5, T),
+    (0x17B7, 0x17BD, T),
+    (0x17C6, 0x17C6, T),
+    (0x17C9, 0x17D3, T),
+    (0x17DD, 0x17DD, T),
+    (0x1807, 0x1807, D),
+    (0x180A, 0x180A, D),
+    (0x180B, 0x180D, T),
+    (0x180F, 0x180F, T),
+    (0x1820, 0x1878, D),
+    (0x1885, 0x1886, T),
+    (0x1887, 0x18A8, D),
+    (0x18A9, 0x18A9, T),
+    (0x18AA, 0x18AA, D),
+    (0x1920, 0x1922, T),
+    (0x1927, 0x1928, T),
+    (0x1932, 0x1932, T),
+    (0x1939, 0x193B, T),
+    (0x1A17, 0x1A18, T),
+    (0x1A1B, 0x1A1B, T),
+    (0x1A56, 0x1A56, T),
+    (0x1A58, 0x1A5E, T),
+    (0x1A60, 0x1A60, T),
+    (0x1A62, 0x1A62, T),
+    (0x1A65, 0x1A6C, T),
+    (0x1A73, 0x1A7C, T),
+    (0x1A7F, 0x1A7F, T),
+    (0x1AB0, 0x1ACE, T),
+    (0x1B00, 0x1B03, T),
+    (0x1B34, 0x1B34, T),
+    (0x1B36, 0x1B3A, T),
+    (0x1B3C, 0x1B3C, T),
+    (0x1B42, 0x1B42, T),
+    (0x1B6B, 0x1B73, T),
+    (0x1B80, 0x1B81, T),
+    (0x1BA2, 0x1BA5, T),
+    (0x1BA8, 0x1BA9, T),
+    (0x1BAB, 0x1BAD, T),
+    (0x1BE6, 0x1BE6, T),
+    (0x1BE8, 0x1BE9, T),
+    (0x1BED, 0x1BED, T),
+    (0x1BEF, 0x1BF1, T),
+    (0x1C2C, 0x1C33, T),
+    (0x1C36, 0x1C37, T),
+    (0x1CD0, 0x1CD2, T),
+    (0x1CD4, 0x1CE0, T),
+    (0x1CE2, 0x1CE8, T),
+    (0x1CED, 0x1CED, T),
+    (0x1CF4, 0x1CF4, T),
+    (0x1CF8, 0x1CF9, T),
+    (0x1DC0, 0x1DFF, T),
+    (0x200B, 0x200B, T),
+    (0x200D, 0x200D, D),
+    (0x200E, 0x200F, T),
+    (0x202A, 0x202E, T),
+    (0x2060, 0x2064, T),
+    (0x206A, 0x206F, T),
+    (0x20D0, 0x20F0, T),
+    (0x2CEF, 0x2CF1, T),
+    (0x2D7F, 0x2D7F, T),
+    (0x2DE0, 0x2DFF, T),
+    (0x302A, 0x302D, T),
+    (0x3099, 0x309A, T),
+    (0xA66F, 0xA672, T),
+    (0xA674, 0xA67D, T),
+    (0xA69E, 0xA69F, T),
+    (0xA6F0, 0xA6F1, T),
+    (0xA802, 0xA802, T),
+    (0xA806, 0xA806, T),
+    (0xA80B, 0xA80B, T),
+    (0xA825, 0xA826, T),
+    (0xA82C, 0xA82C, T),
+    (0xA840, 0xA871, D),
+    (0xA872, 0xA872, L),
+    (0xA8C4, 0xA8C5, T),
+    (0xA8E0, 0xA8F1, T),
+    (0xA8FF, 0xA8FF, T),
+    (0xA926, 0xA92D, T),
+    (0xA947, 0xA951, T),
+    (0xA980, 0xA982, T),
+    (0xA9B3, 0xA9B3, T),
+    (0xA9B6, 0xA9B9, T),
+    (0xA9BC, 0xA9BD, T),
+    (0xA9E5, 0xA9E5, T),
+    (0xAA29, 0xAA2E, T),
+    (0xAA31, 0xAA32, T),
+    (0xAA35, 0xAA36, T),
+    (0xAA43, 0xAA43, T),
+    (0xAA4C, 0xAA4C, T),
+    (0xAA7C, 0xAA7C, T),
+    (0xAAB0, 0xAAB0, T),
+    (0xAAB2, 0xAAB4, T),
+    (0xAAB7, 0xAAB8, T),
+    (0xAABE, 0xAABF, T),
+    (0xAAC1, 0xAAC1, T),
+    (0xAAEC, 0xAAED, T),
+    (0xAAF6, 0xAAF6, T),
+    (0xABE5, 0xABE5, T),
+    (0xABE8, 0xABE8, T),
+    (0xABED, 0xABED, T),
+    (0xFB1E, 0xFB1E, T),
+    (0xFE00, 0xFE0F, T),
+    (0xFE20, 0xFE2F, T),
+    (0xFEFF, 0xFEFF, T),
+    (0xFFF9, 0xFFFB, T),
+    (0x101FD, 0x101FD, T),
+    (0x102E0, 0x102E0, T),
+    (0x10376, 0x1037A, T),
+    (0x10A01, 0x10A03, T),
+    (0x10A05, 0x10A06, T),
+    (0x10A0C, 0x10A0F, T),
+    (0x10A38, 0x10A3A, T),
+    (0x10A3F, 0x10A3F, T),
+    (0x10AC0, 0x10AC4, D),
+    (0x10AC5, 0x10AC5, R),
+    (0x10AC7, 0x10AC7, R),
+    (0x10AC9, 0x10ACA, R),
+    (0x10ACD, 0x10ACD, L),
+    (0x10ACE, 0x10AD2, R),
+    (0x10AD3, 0x10AD6, D),
+    (0x10AD7, 0x10AD7, L),
+    (0x10AD8, 0x10ADC, D),
+    (0x10ADD, 0x10ADD, R),
+    (0x10ADE, 0x10AE0, D),
+    (0x10AE1, 0x10AE1, R),
+    (0x10AE4, 0x10AE4, R),
+    (0x10AE5, 0x10AE6, T),
+    (0x10AEB, 0x10AEE, D),
+    (0x10AEF, 0x10AEF, R),
+    (0x10B80, 0x10B80, D),
+    (0x10B81, 0x10B81, R),
+    (0x10B82, 0x10B82, D),
+    (0x10B83, 0x10B85, R),
+    (0x10B86, 0x10B88, D),
+    (0x10B89, 0x10B89, R),
+    (0x10B8A, 0x10B8B, D),
+    (0x10B8C, 0x10B8C, R),
+    (0x10B8D, 0x10B8D, D),
+    (0x10B8E, 0x10B8F, R),
+    (0x10B90, 0x10B90, D),
+    (0x10B91, 0x10B91, R),
+    (0x10BA9, 0x10BAC, R),
+    (0x10BAD, 0x10BAE, D),
+    (0x10D00, 0x10D00, L),
+    (0x10D01, 0x10D21, D),
+    (0x10D22, 0x10D22, R),
+    (0x10D23, 0x10D23, D),
+    (0x10D24, 0x10D27, T),
+    (0x10EAB, 0x10EAC, T),
+    (0x10EFD, 0x10EFF, T),
+    (0x10F30, 0x10F32, D),
+    (0x10F33, 0x10F33, R),
+    (0x10F34, 0x10F44, D),
+    (0x10F46, 0x10F50, T),
+    (0x10F51, 0x10F53, D),
+    (0x10F54, 0x10F54, R),
+    (0x10F70, 0x10F73, D),
+    (0x10F74, 0x10F75, R),
+    (0x10F76, 0x10F81, D),
+    (0x10F82, 0x10F85, T),
+    (0x10FB0, 0x10FB0, D),
+    (0x10FB2, 0x10FB3, D),
+    (0x10FB4, 0x10FB6, R),
+    (0x10FB8, 0x10FB8, D),
+    (0x10FB9, 0x10FBA, R),
+    (0x10FBB, 0x10FBC, D),
+    (0x10FBD, 0x10FBD, R),
+    (0x10FBE, 0x10FBF, D),
+    (0x10FC1, 0x10FC1, D),
+    (0x10FC2, 0x10FC3, R),
+    (0x10FC4, 0x10FC4, D),
+    (0x10FC9, 0x10FC9, R),
+    (0x10FCA, 0x10FCA, D),
+    (0x10FCB, 0x10FCB, L),
+    (0x11001, 0x11001, T),
+    (0x11038, 0x11046, T),
+    (0x11070, 0x11070, T),
+    (0x11073, 0x11074, T),
+    (0x1107F, 0x11081, T),
+    (0x110B3, 0x110B6, T),
+    (0x110B9, 0x110BA, T),
+    (0x110C2, 0x110C2, T),
+    (0x11100, 0x11102, T),
+    (0x11127, 0x1112B, T),
+    (0x1112D, 0x11134, T),
+    (0x11173, 0x11173, T),
+    (0x11180, 0x11181, T),
+    (0x111B6, 0x111BE, T),
+    (0x111C9, 0x111CC, T),
+    (0x111CF, 0x111CF, T),
+    (0x1122F, 0x11231, T),
+    (0x11234, 0x11234, T),
+    (0x11236, 0x11237, T),
+    (0x1123E, 0x1123E, T),
+    (0x11241, 0x11241, T),
+    (0x112DF, 0x112DF, T),
+    (0x112E3, 0x112EA, T),
+    (0x11300, 0x11301, T),
+    (0x1133B, 0x1133C, T),
+    (0x11340, 0x11340, T),
+    (0x11366, 0x1136C, T),
+    (0x11370, 0x11374, T),
+    (0x11438, 0x1143F, T),
+    (0x11442, 0x11444, T),
+    (0x11446, 0x11446, T),
+    (0x1145E, 0x1145E, T),
+    (0x114B3, 0x114B8, T),
+    (0x114BA, 0x114BA, T),
+    (0x114BF, 0x114C0, T),
+    (0x114C2, 0x114C3, T),
+    (0x115B2, 0x115B5, T),
+    (0x115BC, 0x115BD, T),
+    (0x115BF, 0x115C0, T),
+    (0x115DC, 0x115DD, T),
+    (0x11633, 0x1163A, T),
+    (0x1163D, 0x1163D, T),
+    (0x1163F, 0x11640, T),
+    (0x116AB, 0x116AB, T),
+    (0x116AD, 0x116AD, T),
+    (0x116B0, 0x116B5, T),
+    (0x116B7, 0x116B7, T),
+    (0x1171D, 0x1171F, T),
+    (0x11722, 0x11725, T),
+    (0x11727, 0x1172B, T),
+    (0x1182F, 0x11837, T),
+    (0x11839, 0x1183A, T),
+    (0x1193B, 0x1193C, T),
+    (0x1193E, 0x1193E, T),
+    (0x11943, 0x11943, T),
+    (0x119D4, 0x119D7, T),
+    (0x119DA, 0x119DB, T),
+    (0x119E0, 0x119E0, T),
+    (0x11A01, 0x11A0A, T),
+    (0x11A33, 0x11A38, T),
+    (0x11A3B, 0x11A3E, T),
+    (0x11A47, 0x11A47, T),
+    (0x11A51, 0x11A56, T),
+    (0x11A59, 0x11A5B, T),
+    (0x11A8A, 0x11A96, T),
+    (0x11A98, 0x11A99, T),
+    (0x11C30, 0x11C36, T),
+    (0x11C38, 0x11C3D, T),
+    (0x11C3F, 0x11C3F, T),
+    (0x11C92, 0x11CA7, T),
+    (0x11CAA, 0x11CB0, T),
+    (0x11CB2, 0x11CB3, T),
+    (0x11CB5, 0x11CB6, T),
+    (0x11D31, 0x11D36, T),
+    (0x11D3A, 0x11D3A, T),
+    (0x11D3C, 0x11D3D, T),
+    (0x11D3F, 0x11D45, T),
+    (0x11D47, 0x11D47, T),
+    (0x11D90, 0x11D91, T),
+    (0x11D95, 0x11D95, T),
+    (0x11D97, 0x11D97, T),
+    (0x11EF3, 0x11EF4, T),
+    (0x11F00, 0x11F01, T),
+    (0x11F36, 0x11F3A, T),
+    (0x11F40, 0x11F40, T),
+    (0x11F42, 0x11F42, T),
+    (0x13430, 0x13440, T),
+    (0x13447, 0x13455, T),
+    (0x16AF0, 0x16AF4, T),
+    (0x16B30, 0x16B36, T),
+    (0x16F4F, 0x16F4F, T),
+    (0x16F8F, 0x16F92, T),
+    (0x16FE4, 0x16FE4, T),
+    (0x1BC9D, 0x1BC9E, T),
+    (0x1BCA0, 0x1BCA3, T),
+    (0x1CF00, 0x1CF2D, T),
+    (0x1CF30, 0x1CF46, T),
+    (0x1D167, 0x1D169, T),
+    (0x1D173, 0x1D182, T),
+    (0x1D185, 0x1D18B, T),
+    (0x1D1AA, 0x1D1AD, T),
+    (0x1D242, 0x1D244, T),
+    (0x1DA00, 0x1DA36, T),
+    (0x1DA3B, 0x1DA6C, T),
+    (0x1DA75, 0x1DA75, T),
+    (0x1DA84, 0x1DA84, T),
+    (0x1DA9B, 0x1DA9F, T),
+    (0x1DAA1, 0x1DAAF, T),
+    (0x1E000, 0x1E006, T),
+    (0x1E008, 0x1E018, T),
+    (0x1E01B, 0x1E021, T),
+    (0x1E023, 0x1E024, T),
+    (0x1E026, 0x1E02A, T),
+    (0x1E08F, 0x1E08F, T),
+    (0x1E130, 0x1E136, T),
+    (0x1E2AE, 0x1E2AE, T),
+    (0x1E2EC, 0x1E2EF, T),
+    (0x1E4EC, 0x1E4EF, T),
+    (0x1E8D0, 0x1E8D6, T),
+    (0x1E900, 0x1E943, D),
+    (0x1E944, 0x1E94B, T),
+    (0xE0001, 0xE0001, T),
+    (0xE0020, 0xE007F, T),
+    (0xE0100, 0xE01EF, T),
+];
@@ -0,0 +1,312 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Unicode character properties needed by the shapers and text analysis,
+// stored as sorted tables of (first, last, value) code point ranges.
+
+mod bidi;
+mod combining_class;
+mod joining_type;
+mod line_break;
+mod script;
+mod segmentation;
+mod vertical_orientation;
+pub mod indic;
+pub mod use_category;
+
+// Looks up `cp` in a sorted, non-overlapping range table.
+pub fn range_lookup<T: Copy>(table: &[(u32, u32, T)], cp: u32, default: T) -> T {
+    let (mut lo, mut hi) = (0, table.len());
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let (first, last, value) = table[mid];
+        if cp < first {
+            hi = mid;
+        } else if cp > last {
+            lo = mid + 1;
+        } else {
+            return value;
+        }
+    }
+    default
+}
+
+// Unicode Joining_Type, plus the two Syriac joining groups that get
+// their own final forms.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum JoiningType {
+    NonJoining,
+    LeftJoining,
+    RightJoining,
+    DualJoining,
+    Alaph,
+    DalathRish,
+    Transparent,
+}
+
+pub fn joining_type(cp: u32) -> JoiningType {
+    range_lookup(joining_type::JOINING_TYPE_TABLE, cp, JoiningType::NonJoining)
+}
+
+// The Indic syllabic category and position of a character, as
+// (indic::category, indic::position).
+pub fn indic_properties(cp: u32) -> (u8, u8) {
+    range_lookup(indic::INDIC_TABLE, cp, (indic::category::X, indic::position::END))
+}
+
+pub fn use_category(cp: u32) -> u8 {
+    range_lookup(use_category::USE_TABLE, cp, use_category::category::O)
+}
+
+// Unicode Bidi_Class.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum BidiClass {
+    L,
+    R,
+    AL,
+    EN,
+    ES,
+    ET,
+    AN,
+    CS,
+    NSM,
+    BN,
+    B,
+    S,
+    WS,
+    ON,
+    LRE,
+    LRO,
+    RLE,
+    RLO,
+    PDF,
+    LRI,
+    RLI,
+    FSI,
+    PDI,
+}
+
+pub fn bidi_class(cp: u32) -> BidiClass {
+    range_lookup(bidi::BIDI_CLASS_TABLE, cp, BidiClass::L)
+}
+
+// The character drawn in place of `cp` in right-to-left text, such as
+// ')' for '('.
+pub fn bidi_mirror(cp: u32) -> Option<u32> {
+    bidi::BIDI_MIRROR_TABLE.binary_search_by_key(&cp, |m| m.0).ok().map(|i| bidi::BIDI_MIRROR_TABLE[i].1)
+}
+
+// For a paired bracket, the opening bracket of its pair and whether
+// it's the opening one.  Brackets that are canonically equivalent map
+// to the same pair.
+pub fn bidi_bracket(cp: u32) -> Option<(u32, bool)> {
+    let cp = match cp {
+        0x2329 => 0x3008,
+        0x232A => 0x3009,
+        _ => cp,
+    };
+    for &(open, close) in bidi::BIDI_BRACKET_TABLE {
+        if cp == open {
+            return Some((open, true));
+        } else if cp == close {
+            return Some((open, false));
+        }
+    }
+    None
+}
+
+// Unicode Line_Break, with the names UAX #14 uses.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum LineBreakClass {
+    BK,
+    CR,
+    LF,
+    CM,
+    NL,
+    SG,
+    WJ,
+    ZW,
+    GL,
+    SP,
+    ZWJ,
+    B2,
+    BA,
+    BB,
+    HY,
+    CB,
+    CL,
+    CP,
+    EX,
+    IN,
+    NS,
+    OP,
+    QU,
+    IS,
+    NU,
+    PO,
+    PR,
+    SY,
+    AI,
+    AL,
+    CJ,
+    EB,
+    EM,
+    H2,
+    H3,
+    HL,
+    ID,
+    JL,
+    JV,
+    JT,
+    RI,
+    SA,
+    XX,
+}
+
+pub fn line_break_class(cp: u32) -> LineBreakClass {
+    range_lookup(line_break::LINE_BREAK_TABLE, cp, LineBreakClass::XX)
+}
+
+// Whether an SA (complex context) character is a combining mark.
+pub fn is_complex_context_mark(cp: u32) -> bool {
+    range_lookup(line_break::COMPLEX_CONTEXT_MARK_TABLE, cp, false)
+}
+
+// Whether `cp` is an unassigned code point reserved for pictographs.
+pub fn is_unassigned_pictographic(cp: u32) -> bool {
+    range_lookup(line_break::UNASSIGNED_PICTOGRAPHIC_TABLE, cp, false)
+}
+
+// Grapheme_Cluster_Break, plus the other properties UAX #29 uses to
+// find grapheme clusters.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum GraphemeBreak {
+    CR,
+    LF,
+    Control,
+    Extend,
+    ZWJ,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    LV,
+    LVT,
+    ExtendedPictographic,
+    InCBConsonant,
+    Other,
+}
+
+pub fn grapheme_break(cp: u32) -> GraphemeBreak {
+    if 0xAC00 <= cp && cp <= 0xD7A3 {
+        return if (cp - 0xAC00) % 28 == 0 { GraphemeBreak::LV } else { GraphemeBreak::LVT };
+    }
+    range_lookup(segmentation::GRAPHEME_BREAK_TABLE, cp, GraphemeBreak::Other)
+}
+
+// Indic_Conjunct_Break: the viramas that join consonants into one
+// grapheme cluster, and the marks that can come between them.
+pub fn is_incb_linker(cp: u32) -> bool {
+    match cp {
+        0x094D | 0x09CD | 0x0ACD | 0x0B4D | 0x0C4D | 0x0D4D => true,
+        _ => false,
+    }
+}
+
+pub fn is_incb_extend(cp: u32) -> bool {
+    range_lookup(segmentation::INCB_EXTEND_TABLE, cp, false)
+}
+
+// Word_Break.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum WordBreak {
+    CR,
+    LF,
+    Newline,
+    Extend,
+    ZWJ,
+    RegionalIndicator,
+    Format,
+    Katakana,
+    HebrewLetter,
+    ALetter,
+    SingleQuote,
+    DoubleQuote,
+    MidNumLet,
+    MidLetter,
+    MidNum,
+    Numeric,
+    ExtendNumLet,
+    WSegSpace,
+    Other,
+}
+
+pub fn word_break(cp: u32) -> WordBreak {
+    range_lookup(segmentation::WORD_BREAK_TABLE, cp, WordBreak::Other)
+}
+
+// The Unicode Script property, as an ISO 15924 code packed like an
+// OpenType tag, e.g. ot_tag!(b"Latn").  Zyyy is Common, Zinh
+// Inherited and Zzzz Unknown.
+pub fn script(cp: u32) -> u32 {
+    range_lookup(script::SCRIPT_TABLE, cp, ot_tag!(b"Zzzz"))
+}
+
+// Vertical_Orientation (UAX #50): how a character is set in vertical
+// text.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum VerticalOrientation {
+    // Upright, as in the code charts.
+    Upright,
+    // Sideways, turned 90 degrees clockwise.
+    Rotated,
+    // With a vertical form if the font has one, or else upright...
+    TransformedOrUpright,
+    // ... or else sideways.
+    TransformedOrRotated,
+}
+
+pub fn vertical_orientation(cp: u32) -> VerticalOrientation {
+    range_lookup(vertical_orientation::VERTICAL_ORIENTATION_TABLE, cp, VerticalOrientation::Rotated)
+}
+
+// Unicode Canonical_Combining_Class.
+pub fn combining_class(cp: u32) -> u8 {
+    range_lookup(combining_class::COMBINING_CLASS_TABLE, cp, 0)
+}
+
+// Canonical decompositions of the two- and three-part vowel signs of
+// Indic scripts, whose parts are reordered separately, plus the Khmer
+// split vowels which have no Unicode decomposition.  Returns the first
+// part, which may itself decompose, and the last.
+pub fn decompose_split_vowel(cp: u32) -> Option<(u32, u32)> {
+    let parts = match cp {
+        0x09CB => (0x09C7, 0x09BE),
+        0x09CC => (0x09C7, 0x09D7),
+        0x0B48 => (0x0B47, 0x0B56),
+        0x0B4B => (0x0B47, 0x0B3E),
+        0x0B4C => (0x0B47, 0x0B57),
+        0x0BCA => (0x0BC6, 0x0BBE),
+        0x0BCB => (0x0BC7, 0x0BBE),
+        0x0BCC => (0x0BC6, 0x0BD7),
+        0x0C48 => (0x0C46, 0x0C56),
+        0x0CC0 => (0x0CBF, 0x0CD5),
+        0x0CC7 => (0x0CC6, 0x0CD5),
+        0x0CC8 => (0x0CC6, 0x0CD6),
+        0x0CCA => (0x0CC6, 0x0CC2),
+        0x0CCB => (0x0CCA, 0x0CD5),
+        0x0D4A => (0x0D46, 0x0D3E),
+        0x0D4B => (0x0D47, 0x0D3E),
+        0x0D4C => (0x0D46, 0x0D57),
+        0x0DDA => (0x0DD9, 0x0DCA),
+        0x0DDC => (0x0DD9, 0x0DCF),
+        0x0DDD => (0x0DDC, 0x0DCA),
+        0x0DDE => (0x0DD9, 0x0DDF),
+        0x17BE | 0x17BF | 0x17C0 | 0x17C4 | 0x17C5 => (0x17C1, cp),
+        _ => return None,
+    };
+    Some(parts)
+}
@@ -0,0 +1,994 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Generated from the Unicode 17.0 Scripts.txt.  Scripts are ISO 15924
+// codes packed like OpenType tags; characters not listed are Zzzz
+// (Unknown).
+
+pub const SCRIPT_TABLE: &'static [(u32, u32, u32)] = &[
+    (0x0000, 0x0040, ot_tag!(b"Zyyy")),
+    (0x0041, 0x005A, ot_tag!(b"Latn")),
+    (0x005B, 0x0060, ot_tag!(b"Zyyy")),
+    (0x0061, 0x007A, ot_tag!(b"Latn")),
+    (0x007B, 0x00A9, ot_tag!(b"Zyyy")),
+    (0x00AA, 0x00AA, ot_tag!(b"Latn")),
+    (0x00AB, 0x00B9, ot_tag!(b"Zyyy")),
+    (0x00BA, 0x00BA, ot_tag!(b"Latn")),
+    (0x00BB, 0x00BF, ot_tag!(b"Zyyy")),
+    (0x00C0, 0x00D6, ot_tag!(b"Latn")),
+    (0x00D7, 0x00D7, ot_tag!(b"Zyyy")),
+    (0x00D8, 0x00F6, ot_tag!(b"Latn")),
+    (0x00F7, 0x00F7, ot_tag!(b"Zyyy")),
+    (0x00F8, 0x02B8, ot_tag!(b"Latn")),
+    (0x02B9, 0x02DF, ot_tag!(b"Zyyy")),
+    (0x02E0, 0x02E4, ot_tag!(b"Latn")),
+    (0x02E5, 0x02E9, ot_tag!(b"Zyyy")),
+    (0x02EA, 0x02EB, ot_tag!(b"Bopo")),
+    (0x02EC, 0x02FF, ot_tag!(b"Zyyy")),
+    (0x0300, 0x036F, ot_tag!(b"Zinh")),
+    (0x0370, 0x0373, ot_tag!(b"Grek")),
+    (0x0374, 0x0374, ot_tag!(b"Zyyy")),
+    (0x0375, 0x0377, ot_tag!(b"Grek")),
+    (0x037A, 0x037D, ot_tag!(b"Grek")),
+    (0x037E, 0x037E, ot_tag!(b"Zyyy")),
+    (0x037F, 0x037F, ot_tag!(b"Grek")),
+    (0x0384, 0x0384, ot_tag!(b"Grek")),
+    (0x0385, 0x0385, ot_tag!(b"Zyyy")),
+    (0x0386, 0x0386, ot_tag!(b"Grek")),
+    (0x0387, 0x0387, ot_tag!(b"Zyyy")),
+    (0x0388, 0x038A, ot_tag!(b"Grek")),
+    (0x038C, 0x038C, ot_tag!(b"Grek")),
+    (0x038E, 0x03A1, ot_tag!(b"Grek")),
+    (0x03A3, 0x03E1, ot_tag!(b"Grek")),
+    (0x03E2, 0x03EF, ot_tag!(b"Copt")),
+    (0x03F0, 0x03FF, ot_tag!(b"Grek")),
+    (0x0400, 0x0484, ot_tag!(b"Cyrl")),
+    (0x0485, 0x0486, ot_tag!(b"Zinh")),
+    (0x0487, 0x052F, ot_tag!(b"Cyrl")),
+    (0x0531, 0x0556, ot_tag!(b"Armn")),
+    (0x0559, 0x058A, ot_tag!(b"Armn")),
+    (0x058D, 0x058F, ot_tag!(b"Armn")),
+    (0x0591, 0x05C7, ot_tag!(b"Hebr")),
+    (0x05D0, 0x05EA, ot_tag!(b"Hebr")),
+    (0x05EF, 0x05F4, ot_tag!(b"Hebr")),
+    (0x0600, 0x0604, ot_tag!(b"Arab")),
+    (0x0605, 0x0605, ot_tag!(b"Zyyy")),
+    (0x0606, 0x060B, ot_tag!(b"Arab")),
+    (0x060C, 0x060C, ot_tag!(b"Zyyy")),
+    (0x060D, 0x061A, ot_tag!(b"Arab")),
+    (0x061B, 0x061B, ot_tag!(b"Zyyy")),
+    (0x061C, 0x061E, ot_tag!(b"Arab")),
+    (0x061F, 0x061F, ot_tag!(b"Zyyy")),
+    (0x0620, 0x063F, ot_tag!(b"Arab")),
+    (0x0640, 0x0640, ot_tag!(b"Zyyy")),
+    (0x0641, 0x064A, ot_tag!(b"Arab")),
+    (0x064B, 0x0655, ot_tag!(b"Zinh")),
+    (0x0656, 0x066F, ot_tag!(b"Arab")),
+    (0x0670, 0x0670, ot_tag!(b"Zinh")),
+    (0x0671, 0x06DC, ot_tag!(b"Arab")),
+    (0x06DD, 0x06DD, ot_tag!(b"Zyyy")),
+    (0x06DE, 0x06FF, ot_tag!(b"Arab")),
+    (0x0700, 0x070D, ot_tag!(b"Syrc")),
+    (0x070F, 0x074A, ot_tag!(b"Syrc")),
+    (0x074D, 0x074F, ot_tag!(b"Syrc")),
+    (0x0750, 0x077F, ot_tag!(b"Arab")),
+    (0x0780, 0x07B1, ot_tag!(b"Thaa")),
+    (0x07C0, 0x07FA, ot_tag!(b"Nkoo")),
+    (0x07FD, 0x07FF, ot_tag!(b"Nkoo")),
+    (0x0800, 0x082D, ot_tag!(b"Samr")),
+    (0x0830, 0x083E, ot_tag!(b"Samr")),
+    (0x0840, 0x085B, ot_tag!(b"Mand")),
+    (0x085E, 0x085E, ot_tag!(b"Mand")),
+    (0x0860, 0x086A, ot_tag!(b"Syrc")),
+    (0x0870, 0x0891, ot_tag!(b"Arab")),
+    (0x0897, 0x08E1, ot_tag!(b"Arab")),
+    (0x08E2, 0x08E2, ot_tag!(b"Zyyy")),
+    (0x08E3, 0x08FF, ot_tag!(b"Arab")),
+    (0x0900, 0x0950, ot_tag!(b"Deva")),
+    (0x0951, 0x0954, ot_tag!(b"Zinh")),
+    (0x0955, 0x0963, ot_tag!(b"Deva")),
+    (0x0964, 0x0965, ot_tag!(b"Zyyy")),
+    (0x0966, 0x097F, ot_tag!(b"Deva")),
+    (0x0980, 0x0983, ot_tag!(b"Beng")),
+    (0x0985, 0x098C, ot_tag!(b"Beng")),
+    (0x098F, 0x0990, ot_tag!(b"Beng")),
+    (0x0993, 0x09A8, ot_tag!(b"Beng")),
+    (0x09AA, 0x09B0, ot_tag!(b"Beng")),
+    (0x09B2, 0x09B2, ot_tag!(b"Beng")),
+    (0x09B6, 0x09B9, ot_tag!(b"Beng")),
+    (0x09BC, 0x09C4, ot_tag!(b"Beng")),
+    (0x09C7, 0x09C8, ot_tag!(b"Beng")),
+    (0x09CB, 0x09CE, ot_tag!(b"Beng")),
+    (0x09D7, 0x09D7, ot_tag!(b"Beng")),
+    (0x09DC, 0x09DD, ot_tag!(b"Beng")),
+    (0x09DF, 0x09E3, ot_tag!(b"Beng")),
+    (0x09E6, 0x09FE, ot_tag!(b"Beng")),
+    (0x0A01, 0x0A03, ot_tag!(b"Guru")),
+    (0x0A05, 0x0A0A, ot_tag!(b"Guru")),
+    (0x0A0F, 0x0A10, ot_tag!(b"Guru")),
+    (0x0A13, 0x0A28, ot_tag!(b"Guru")),
+    (0x0A2A, 0x0A30, ot_tag!(b"Guru")),
+    (0x0A32, 0x0A33, ot_tag!(b"Guru")),
+    (0x0A35, 0x0A36, ot_tag!(b"Guru")),
+    (0x0A38, 0x0A39, ot_tag!(b"Guru")),
+    (0x0A3C, 0x0A3C, ot_tag!(b"Guru")),
+    (0x0A3E, 0x0A42, ot_tag!(b"Guru")),
+    (0x0A47, 0x0A48, ot_tag!(b"Guru")),
+    (0x0A4B, 0x0A4D, ot_tag!(b"Guru")),
+    (0x0A51, 0x0A51, ot_tag!(b"Guru")),
+    (0x0A59, 0x0A5C, ot_tag!(b"Guru")),
+    (0x0A5E, 0x0A5E, ot_tag!(b"Guru")),
+    (0x0A66, 0x0A76, ot_tag!(b"Guru")),
+    (0x0A81, 0x0A83, ot_tag!(b"Gujr")),
+    (0x0A85, 0x0A8D, ot_tag!(b"Gujr")),
+    (0x0A8F, 0x0A91, ot_tag!(b"Gujr")),
+    (0x0A93, 0x0AA8, ot_tag!(b"Gujr")),
+    (0x0AAA, 0x0AB0, ot_tag!(b"Gujr")),
+    (0x0AB2, 0x0AB3, ot_tag!(b"Gujr")),
+    (0x0AB5, 0x0AB9, ot_tag!(b"Gujr")),
+    (0x0ABC, 0x0AC5, ot_tag!(b"Gujr")),
+    (0x0AC7, 0x0AC9, ot_tag!(b"Gujr")),
+    (0x0ACB, 0x0ACD, ot_tag!(b"Gujr")),
+    (0x0AD0, 0x0AD0, ot_tag!(b"Gujr")),
+    (0x0AE0, 0x0AE3, ot_tag!(b"Gujr")),
+    (0x0AE6, 0x0AF1, ot_tag!(b"Gujr")),
+    (0x0AF9, 0x0AFF, ot_tag!(b"Gujr")),
+    (0x0B01, 0x0B03, ot_tag!(b"Orya")),
+    (0x0B05, 0x0B0C, ot_tag!(b"Orya")),
+    (0x0B0F, 0x0B10, ot_tag!(b"Orya")),
+    (0x0B13, 0x0B28, ot_tag!(b"Orya")),
+    (0x0B2A, 0x0B30, ot_tag!(b"Orya")),
+    (0x0B32, 0x0B33, ot_tag!(b"Orya")),
+    (0x0B35, 0x0B39, ot_tag!(b"Orya")),
+    (0x0B3C, 0x0B44, ot_tag!(b"Orya")),
+    (0x0B47, 0x0B48, ot_tag!(b"Orya")),
+    (0x0B4B, 0x0B4D, ot_tag!(b"Orya")),
+    (0x0B55, 0x0B57, ot_tag!(b"Orya")),
+    (0x0B5C, 0x0B5D, ot_tag!(b"Orya")),
+    (0x0B5F, 0x0B63, ot_tag!(b"Orya")),
+    (0x0B66, 0x0B77, ot_tag!(b"Orya")),
+    (0x0B82, 0x0B83, ot_tag!(b"Taml")),
+    (0x0B85, 0x0B8A, ot_tag!(b"Taml")),
+    (0x0B8E, 0x0B90, ot_tag!(b"Taml")),
+    (0x0B92, 0x0B95, ot_tag!(b"Taml")),
+    (0x0B99, 0x0B9A, ot_tag!(b"Taml")),
+    (0x0B9C, 0x0B9C, ot_tag!(b"Taml")),
+    (0x0B9E, 0x0B9F, ot_tag!(b"Taml")),
+    (0x0BA3, 0x0BA4, ot_tag!(b"Taml")),
+    (0x0BA8, 0x0BAA, ot_tag!(b"Taml")),
+    (0x0BAE, 0x0BB9, ot_tag!(b"Taml")),
+    (0x0BBE, 0x0BC2, ot_tag!(b"Taml")),
+    (0x0BC6, 0x0BC8, ot_tag!(b"Taml")),
+    (0x0BCA, 0x0BCD, ot_tag!(b"Taml")),
+    (0x0BD0, 0x0BD0, ot_tag!(b"Taml")),
+    (0x0BD7, 0x0BD7, ot_tag!(b"Taml")),
+    (0x0BE6, 0x0BFA, ot_tag!(b"Taml")),
+    (0x0C00, 0x0C0C, ot_tag!(b"Telu")),
+    (0x0C0E, 0x0C10, ot_tag!(b"Telu")),
+    (0x0C12, 0x0C28, ot_tag!(b"Telu")),
+    (0x0C2A, 0x0C39, ot_tag!(b"Telu")),
+    (0x0C3C, 0x0C44, ot_tag!(b"Telu")),
+    (0x0C46, 0x0C48, ot_tag!(b"Telu")),
+    (0x0C4A, 0x0C4D, ot_tag!(b"Telu")),
+    (0x0C55, 0x0C56, ot_tag!(b"Telu")),
+    (0x0C58, 0x0C5A, ot_tag!(b"Telu")),
+    (0x0C5C, 0x0C5D, ot_tag!(b"Telu")),
+    (0x0C60, 0x0C63, ot_tag!(b"Telu")),
+    (0x0C66, 0x0C6F, ot_tag!(b"Telu")),
+    (0x0C77, 0x0C7F, ot_tag!(b"Telu")),
+    (0x0C80, 0x0C8C, ot_tag!(b"Knda")),
+    (0x0C8E, 0x0C90, ot_tag!(b"Knda")),
+    (0x0C92, 0x0CA8, ot_tag!(b"Knda")),
+    (0x0CAA, 0x0CB3, ot_tag!(b"Knda")),
+    (0x0CB5, 0x0CB9, ot_tag!(b"Knda")),
+    (0x0CBC, 0x0CC4, ot_tag!(b"Knda")),
+    (0x0CC6, 0x0CC8, ot_tag!(b"Knda")),
+    (0x0CCA, 0x0CCD, ot_tag!(b"Knda")),
+    (0x0CD5, 0x0CD6, ot_tag!(b"Knda")),
+    (0x0CDC, 0x0CDE, ot_tag!(b"Knda")),
+    (0x0CE0, 0x0CE3, ot_tag!(b"Knda")),
+    (0x0CE6, 0x0CEF, ot_tag!(b"Knda")),
+    (0x0CF1, 0x0CF3, ot_tag!(b"Knda")),
+    (0x0D00, 0x0D0C, ot_tag!(b"Mlym")),
+    (0x0D0E, 0x0D10, ot_tag!(b"Mlym")),
+    (0x0D12, 0x0D44, ot_tag!(b"Mlym")),
+    (0x0D46, 0x0D48, ot_tag!(b"Mlym")),
+    (0x0D4A, 0x0D4F, ot_tag!(b"Mlym")),
+    (0x0D54, 0x0D63, ot_tag!(b"Mlym")),
+    (0x0D66, 0x0D7F, ot_tag!(b"Mlym")),
+    (0x0D81, 0x0D83, ot_tag!(b"Sinh")),
+    (0x0D85, 0x0D96, ot_tag!(b"Sinh")),
+    (0x0D9A, 0x0DB1, ot_tag!(b"Sinh")),
+    (0x0DB3, 0x0DBB, ot_tag!(b"Sinh")),
+    (0x0DBD, 0x0DBD, ot_tag!(b"Sinh")),
+    (0x0DC0, 0x0DC6, ot_tag!(b"Sinh")),
+    (0x0DCA, 0x0DCA, ot_tag!(b"Sinh")),
+    (0x0DCF, 0x0DD4, ot_tag!(b"Sinh")),
+    (0x0DD6, 0x0DD6, ot_tag!(b"Sinh")),
+    (0x0DD8, 0x0DDF, ot_tag!(b"Sinh")),
+    (0x0DE6, 0x0DEF, ot_tag!(b"Sinh")),
+    (0x0DF2, 0x0DF4, ot_tag!(b"Sinh")),
+    (0x0E01, 0x0E3A, ot_tag!(b"Thai")),
+    (0x0E3F, 0x0E3F, ot_tag!(b"Zyyy")),
+    (0x0E40, 0x0E5B, ot_tag!(b"Thai")),
+    (0x0E81, 0x0E82, ot_tag!(b"Laoo")),
+    (0x0E84, 0x0E84, ot_tag!(b"Laoo")),
+    (0x0E86, 0x0E8A, ot_tag!(b"Laoo")),
+    (0x0E8C, 0x0EA3, ot_tag!(b"Laoo")),
+    (0x0EA5, 0x0EA5, ot_tag!(b"Laoo")),
+    (0x0EA7, 0x0EBD, ot_tag!(b"Laoo")),
+    (0x0EC0, 0x0EC4, ot_tag!(b"Laoo")),
+    (0x0EC6, 0x0EC6, ot_tag!(b"Laoo")),
+    (0x0EC8, 0x0ECE, ot_tag!(b"Laoo")),
+    (0x0ED0, 0x0ED9, ot_tag!(b"Laoo")),
+    (0x0EDC, 0x0EDF, ot_tag!(b"Laoo")),
+    (0x0F00, 0x0F47, ot_tag!(b"Tibt")),
+    (0x0F49, 0x0F6C, ot_tag!(b"Tibt")),
+    (0x0F71, 0x0F97, ot_tag!(b"Tibt")),
+    (0x0F99, 0x0FBC, ot_tag!(b"Tibt")),
+    (0x0FBE, 0x0FCC, ot_tag!(b"Tibt")),
+    (0x0FCE, 0x0FD4, ot_tag!(b"Tibt")),
+    (0x0FD5, 0x0FD8, ot_tag!(b"Zyyy")),
+    (0x0FD9, 0x0FDA, ot_tag!(b"Tibt")),
+    (0x1000, 0x109F, ot_tag!(b"Mymr")),
+    (0x10A0, 0x10C5, ot_tag!(b"Geor")),
+    (0x10C7, 0x10C7, ot_tag!(b"Geor")),
+    (0x10CD, 0x10CD, ot_tag!(b"Geor")),
+    (0x10D0, 0x10FA, ot_tag!(b"Geor")),
+    (0x10FB, 0x10FB, ot_tag!(b"Zyyy")),
+    (0x10FC, 0x10FF, ot_tag!(b"Geor")),
+    (0x1100, 0x11FF, ot_tag!(b"Hang")),
+    (0x1200, 0x1248, ot_tag!(b"Ethi")),
+    (0x124A, 0x124D, ot_tag!(b"Ethi")),
+    (0x1250, 0x1256, ot_tag!(b"Ethi")),
+    (0x1258, 0x1258, ot_tag!(b"Ethi")),
+    (0x125A, 0x125D, ot_tag!(b"Ethi")),
+    (0x1260, 0x1288, ot_tag!(b"Ethi")),
+    (0x128A, 0x128D, ot_tag!(b"Ethi")),
+    (0x1290, 0x12B0, ot_tag!(b"Ethi")),
+    (0x12B2, 0x12B5, ot_tag!(b"Ethi")),
+    (0x12B8, 0x12BE, ot_tag!(b"Ethi")),
+    (0x12C0, 0x12C0, ot_tag!(b"Ethi")),
+    (0x12C2, 0x12C5, ot_tag!(b"Ethi")),
+    (0x12C8, 0x12D6, ot_tag!(b"Ethi")),
+    (0x12D8, 0x1310, ot_tag!(b"Ethi")),
+    (0x1312, 0x1315, ot_tag!(b"Ethi")),
+    (0x1318, 0x135A, ot_tag!(b"Ethi")),
+    (0x135D, 0x137C, ot_tag!(b"Ethi")),
+    (0x1380, 0x1399, ot_tag!(b"Ethi")),
+    (0x13A0, 0x13F5, ot_tag!(b"Cher")),
+    (0x13F8, 0x13FD, ot_tag!(b"Cher")),
+    (0x1400, 0x167F, ot_tag!(b"Cans")),
+    (0x1680, 0x169C, ot_tag!(b"Ogam")),
+    (0x16A0, 0x16EA, ot_tag!(b"Runr")),
+    (0x16EB, 0x16ED, ot_tag!(b"Zyyy")),
+    (0x16EE, 0x16F8, ot_tag!(b"Runr")),
+    (0x1700, 0x1715, ot_tag!(b"Tglg")),
+    (0x171F, 0x171F, ot_tag!(b"Tglg")),
+    (0x1720, 0x1734, ot_tag!(b"Hano")),
+    (0x1735, 0x1736, ot_tag!(b"Zyyy")),
+    (0x1740, 0x1753, ot_tag!(b"Buhd")),
+    (0x1760, 0x176C, ot_tag!(b"Tagb")),
+    (0x176E, 0x1770, ot_tag!(b"Tagb")),
+    (0x1772, 0x1773, ot_tag!(b"Tagb")),
+    (0x1780, 0x17DD, ot_tag!(b"Khmr")),
+    (0x17E0, 0x17E9, ot_tag!(b"Khmr")),
+    (0x17F0, 0x17F9, ot_tag!(b"Khmr")),
+    (0x1800, 0x1801, ot_tag!(b"Mong")),
+    (0x1802, 0x1803, ot_tag!(b"Zyyy")),
+    (0x1804, 0x1804, ot_tag!(b"Mong")),
+    (0x1805, 0x1805, ot_tag!(b"Zyyy")),
+    (0x1806, 0x1819, ot_tag!(b"Mong")),
+    (0x1820, 0x1878, ot_tag!(b"Mong")),
+    (0x1880, 0x18AA, ot_tag!(b"Mong")),
+    (0x18B0, 0x18F5, ot_tag!(b"Cans")),
+    (0x1900, 0x191E, ot_tag!(b"Limb")),
+    (0x1920, 0x192B, ot_tag!(b"Limb")),
+    (0x1930, 0x193B, ot_tag!(b"Limb")),
+    (0x1940, 0x1940, ot_tag!(b"Limb")),
+    (0x1944, 0x194F, ot_tag!(b"Limb")),
+    (0x1950, 0x196D, ot_tag!(b"Tale")),
+    (0x1970, 0x1974, ot_tag!(b"Tale")),
+    (0x1980, 0x19AB, ot_tag!(b"Talu")),
+    (0x19B0, 0x19C9, ot_tag!(b"Talu")),
+    (0x19D0, 0x19DA, ot_tag!(b"Talu")),
+    (0x19DE, 0x19DF, ot_tag!(b"Talu")),
+    (0x19E0, 0x19FF, ot_tag!(b"Khmr")),
+    (0x1A00, 0x1A1B, ot_tag!(b"Bugi")),
+    (0x1A1E, 0x1A1F, ot_tag!(b"Bugi")),
+    (0x1A20, 0x1A5E, ot_tag!(b"Lana")),
+    (0x1A60, 0x1A7C, ot_tag!(b"Lana")),
+    (0x1A7F, 0x1A89, ot_tag!(b"Lana")),
+    (0x1A90, 0x1A99, ot_tag!(b"Lana")),
+    (0x1AA0, 0x1AAD, ot_tag!(b"Lana")),
+    (0x1AB0, 0x1ADD, ot_tag!(b"Zinh")),
+    (0x1AE0, 0x1AEB, ot_tag!(b"Zinh")),
+    (0x1B00, 0x1B4C, ot_tag!(b"Bali")),
+    (0x1B4E, 0x1B7F, ot_tag!(b"Bali")),
+    (0x1B80, 0x1BBF, ot_tag!(b"Sund")),
+    (0x1BC0, 0x1BF3, ot_tag!(b"Batk")),
+    (0x1BFC, 0x1BFF, ot_tag!(b"Batk")),
+    (0x1C00, 0x1C37, ot_tag!(b"Lepc")),
+    (0x1C3B, 0x1C49, ot_tag!(b"Lepc")),
+    (0x1C4D, 0x1C4F, ot_tag!(b"Lepc")),
+    (0x1C50, 0x1C7F, ot_tag!(b"Olck")),
+    (0x1C80, 0x1C8A, ot_tag!(b"Cyrl")),
+    (0x1C90, 0x1CBA, ot_tag!(b"Geor")),
+    (0x1CBD, 0x1CBF, ot_tag!(b"Geor")),
+    (0x1CC0, 0x1CC7, ot_tag!(b"Sund")),
+    (0x1CD0, 0x1CD2, ot_tag!(b"Zinh")),
+    (0x1CD3, 0x1CD3, ot_tag!(b"Zyyy")),
+    (0x1CD4, 0x1CE0, ot_tag!(b"Zinh")),
+    (0x1CE1, 0x1CE1, ot_tag!(b"Zyyy")),
+    (0x1CE2, 0x1CE8, ot_tag!(b"Zinh")),
+    (0x1CE9, 0x1CEC, ot_tag!(b"Zyyy")),
+    (0x1CED, 0x1CED, ot_tag!(b"Zinh")),
+    (0x1CEE, 0x1CF3, ot_tag!(b"Zyyy")),
+    (0x1CF4, 0x1CF4, ot_tag!(b"Zinh")),
+    (0x1CF5, 0x1CF7, ot_tag!(b"Zyyy")),
+    (0x1CF8, 0x1CF9, ot_tag!(b"Zinh")),
+    (0x1CFA, 0x1CFA, ot_tag!(b"Zyyy")),
+    (0x1D00, 0x1D25, ot_tag!(b"Latn")),
+    (0x1D26, 0x1D2A, ot_tag!(b"Grek")),
+    (0x1D2B, 0x1D2B, ot_tag!(b"Cyrl")),
+    (0x1D2C, 0x1D5C, ot_tag!(b"Latn")),
+    (0x1D5D, 0x1D61, ot_tag!(b"Grek")),
+    (0x1D62, 0x1D65, ot_tag!(b"Latn")),
+    (0x1D66, 0x1D6A, ot_tag!(b"Grek")),
+    (0x1D6B, 0x1D77, ot_tag!(b"Latn")),
+    (0x1D78, 0x1D78, ot_tag!(b"Cyrl")),
+    (0x1D79, 0x1DBE, ot_tag!(b"Latn")),
+    (0x1DBF, 0x1DBF, ot_tag!(b"Grek")),
+    (0x1DC0, 0x1DFF, ot_tag!(b"Zinh")),
+    (0x1E00, 0x1EFF, ot_tag!(b"Latn")),
+    (0x1F00, 0x1F15, ot_tag!(b"Grek")),
+    (0x1F18, 0x1F1D, ot_tag!(b"Grek")),
+    (0x1F20, 0x1F45, ot_tag!(b"Grek")),
+    (0x1F48, 0x1F4D, ot_tag!(b"Grek")),
+    (0x1F50, 0x1F57, ot_tag!(b"Grek")),
+    (0x1F59, 0x1F59, ot_tag!(b"Grek")),
+    (0x1F5B, 0x1F5B, ot_tag!(b"Grek")),
+    (0x1F5D, 0x1F5D, ot_tag!(b"Grek")),
+    (0x1F5F, 0x1F7D, ot_tag!(b"Grek")),
+    (0x1F80, 0x1FB4, ot_tag!(b"Grek")),
+    (0x1FB6, 0x1FC4, ot_tag!(b"Grek")),
+    (0x1FC6, 0x1FD3, ot_tag!(b"Grek")),
+    (0x1FD6, 0x1FDB, ot_tag!(b"Grek")),
+    (0x1FDD, 0x1FEF, ot_tag!(b"Grek")),
+    (0x1FF2, 0x1FF4, ot_tag!(b"Grek")),
+    (0x1FF6, 0x1FFE, ot_tag!(b"Grek")),
+    (0x2000, 0x200B, ot_tag!(b"Zyyy")),
+    (0x200C, 0x200D, ot_tag!(b"Zinh")),
+    (0x200E, 0x2064, ot_tag!(b"Zyyy")),
+    (0x2066, 0x2070, ot_tag!(b"Zyyy")),
+    (0x2071, 0x2071, ot_tag!(b"Latn")),
+    (0x2074, 0x207E, ot_tag!(b"Zyyy")),
+    (0x207F, 0x207F, ot_tag!(b"Latn")),
+    (0x2080, 0x208E, ot_tag!(b"Zyyy")),
+    (0x2090, 0x209C, ot_tag!(b"Latn")),
+    (0x20A0, 0x20C1, ot_tag!(b"Zyyy")),
+    (0x20D0, 0x20F0, ot_tag!(b"Zinh")),
+    (0x2100, 0x2125, ot_tag!(b"Zyyy")),
+    (0x2126, 0x2126, ot_tag!(b"Grek")),
+    (0x2127, 0x2129, ot_tag!(b"Zyyy")),
+    (0x212A, 0x212B, ot_tag!(b"Latn")),
+    (0x212C, 0x2131, ot_tag!(b"Zyyy")),
+    (0x2132, 0x2132, ot_tag!(b"Latn")),
+    (0x2133, 0x214D, ot_tag!(b"Zyyy")),
+    (0x214E, 0x214E, ot_tag!(b"Latn")),
+    (0x214F, 0x215F, ot_tag!(b"Zyyy")),
+    (0x2160, 0x2188, ot_tag!(b"Latn")),
+    (0x2189, 0x218B, ot_tag!(b"Zyyy")),
+    (0x2190, 0x2429, ot_tag!(b"Zyyy")),
+    (0x2440, 0x244A, ot_tag!(b"Zyyy")),
+    (0x2460, 0x27FF, ot_tag!(b"Zyyy")),
+    (0x2800, 0x28FF, ot_tag!(b"Brai")),
+    (0x2900, 0x2B73, ot_tag!(b"Zyyy")),
+    (0x2B76, 0x2BFF, ot_tag!(b"Zyyy")),
+    (0x2C00, 0x2C5F, ot_tag!(b"Glag")),
+    (0x2C60, 0x2C7F, ot_tag!(b"Latn")),
+    (0x2C80, 0x2CF3, ot_tag!(b"Copt")),
+    (0x2CF9, 0x2CFF, ot_tag!(b"Copt")),
+    (0x2D00, 0x2D25, ot_tag!(b"Geor")),
+    (0x2D27, 0x2D27, ot_tag!(b"Geor")),
+    (0x2D2D, 0x2D2D, ot_tag!(b"Geor")),
+    (0x2D30, 0x2D67, ot_tag!(b"Tfng")),
+    (0x2D6F, 0x2D70, ot_tag!(b"Tfng")),
+    (0x2D7F, 0x2D7F, ot_tag!(b"Tfng")),
+    (0x2D80, 0x2D96, ot_tag!(b"Ethi")),
+    (0x2DA0, 0x2DA6, ot_tag!(b"Ethi")),
+    (0x2DA8, 0x2DAE, ot_tag!(b"Ethi")),
+    (0x2DB0, 0x2DB6, ot_tag!(b"Ethi")),
+    (0x2DB8, 0x2DBE, ot_tag!(b"Ethi")),
+    (0x2DC0, 0x2DC6, ot_tag!(b"Ethi")),
+    (0x2DC8, 0x2DCE, ot_tag!(b"Ethi")),
+    (0x2DD0, 0x2DD6, ot_tag!(b"Ethi")),
+    (0x2DD8, 0x2DDE, ot_tag!(b"Ethi")),
+    (0x2DE0, 0x2DFF, ot_tag!(b"Cyrl")),
+    (0x2E00, 0x2E5D, ot_tag!(b"Zyyy")),
+    (0x2E80, 0x2E99, ot_tag!(b"Hani")),
+    (0x2E9B, 0x2EF3, ot_tag!(b"Hani")),
+    (0x2F00, 0x2FD5, ot_tag!(b"Hani")),
+    (0x2FF0, 0x3004, ot_tag!(b"Zyyy")),
+    (0x3005, 0x3005, ot_tag!(b"Hani")),
+    (0x3006, 0x3006, ot_tag!(b"Zyyy")),
+    (0x3007, 0x3007, ot_tag!(b"Hani")),
+    (0x3008, 0x3020, ot_tag!(b"Zyyy")),
+    (0x3021, 0x3029, ot_tag!(b"Hani")),
+    (0x302A, 0x302D, ot_tag!(b"Zinh")),
+    (0x302E, 0x302F, ot_tag!(b"Hang")),
+    (0x3030, 0x3037, ot_tag!(b"Zyyy")),
+    (0x3038, 0x303B, ot_tag!(b"Hani")),
+    (0x303C, 0x303F, ot_tag!(b"Zyyy")),
+    (0x3041, 0x3096, ot_tag!(b"Hira")),
+    (0x3099, 0x309A, ot_tag!(b"Zinh")),
+    (0x309B, 0x309C, ot_tag!(b"Zyyy")),
+    (0x309D, 0x309F, ot_tag!(b"Hira")),
+    (0x30A0, 0x30A0, ot_tag!(b"Zyyy")),
+    (0x30A1, 0x30FA, ot_tag!(b"Kana")),
+    (0x30FB, 0x30FC, ot_tag!(b"Zyyy")),
+    (0x30FD, 0x30FF, ot_tag!(b"Kana")),
+    (0x3105, 0x312F, ot_tag!(b"Bopo")),
+    (0x3131, 0x318E, ot_tag!(b"Hang")),
+    (0x3190, 0x319F, ot_tag!(b"Zyyy")),
+    (0x31A0, 0x31BF, ot_tag!(b"Bopo")),
+    (0x31C0, 0x31E5, ot_tag!(b"Zyyy")),
+    (0x31EF, 0x31EF, ot_tag!(b"Zyyy")),
+    (0x31F0, 0x31FF, ot_tag!(b"Kana")),
+    (0x3200, 0x321E, ot_tag!(b"Hang")),
+    (0x3220, 0x325F, ot_tag!(b"Zyyy")),
+    (0x3260, 0x327E, ot_tag!(b"Hang")),
+    (0x327F, 0x32CF, ot_tag!(b"Zyyy")),
+    (0x32D0, 0x32FE, ot_tag!(b"Kana")),
+    (0x32FF, 0x32FF, ot_tag!(b"Zyyy")),
+    (0x3300, 0x3357, ot_tag!(b"Kana")),
+    (0x3358, 0x33FF, ot_tag!(b"Zyyy")),
+    (0x3400, 0x4DBF, ot_tag!(b"Hani")),
+    (0x4DC0, 0x4DFF, ot_tag!(b"Zyyy")),
+    (0x4E00, 0x9FFF, ot_tag!(b"Hani")),
+    (0xA000, 0xA48C, ot_tag!(b"Yiii")),
+    (0xA490, 0xA4C6, ot_tag!(b"Yiii")),
+    (0xA4D0, 0xA4FF, ot_tag!(b"Lisu")),
+    (0xA500, 0xA62B, ot_tag!(b"Vaii")),
+    (0xA640, 0xA69F, ot_tag!(b"Cyrl")),
+    (0xA6A0, 0xA6F7, ot_tag!(b"Bamu")),
+    (0xA700, 0xA721, ot_tag!(b"Zyyy")),
+    (0xA722, 0xA787, ot_tag!(b"Latn")),
+    (0xA788, 0xA78A, ot_tag!(b"Zyyy")),
+    (0xA78B, 0xA7DC, ot_tag!(b"Latn")),
+    (0xA7F1, 0xA7FF, ot_tag!(b"Latn")),
+    (0xA800, 0xA82C, ot_tag!(b"Sylo")),
+    (0xA830, 0xA839, ot_tag!(b"Zyyy")),
+    (0xA840, 0xA877, ot_tag!(b"Phag")),
+    (0xA880, 0xA8C5, ot_tag!(b"Saur")),
+    (0xA8CE, 0xA8D9, ot_tag!(b"Saur")),
+    (0xA8E0, 0xA8FF, ot_tag!(b"Deva")),
+    (0xA900, 0xA92D, ot_tag!(b"Kali")),
+    (0xA92E, 0xA92E, ot_tag!(b"Zyyy")),
+    (0xA92F, 0xA92F, ot_tag!(b"Kali")),
+    (0xA930, 0xA953, ot_tag!(b"Rjng")),
+    (0xA95F, 0xA95F, ot_tag!(b"Rjng")),
+    (0xA960, 0xA97C, ot_tag!(b"Hang")),
+    (0xA980, 0xA9CD, ot_tag!(b"Java")),
+    (0xA9CF, 0xA9CF, ot_tag!(b"Zyyy")),
+    (0xA9D0, 0xA9D9, ot_tag!(b"Java")),
+    (0xA9DE, 0xA9DF, ot_tag!(b"Java")),
+    (0xA9E0, 0xA9FE, ot_tag!(b"Mymr")),
+    (0xAA00, 0xAA36, ot_tag!(b"Cham")),
+    (0xAA40, 0xAA4D, ot_tag!(b"Cham")),
+    (0xAA50, 0xAA59, ot_tag!(b"Cham")),
+    (0xAA5C, 0xAA5F, ot_tag!(b"Cham")),
+    (0xAA60, 0xAA7F, ot_tag!(b"Mymr")),
+    (0xAA80, 0xAAC2, ot_tag!(b"Tavt")),
+    (0xAADB, 0xAADF, ot_tag!(b"Tavt")),
+    (0xAAE0, 0xAAF6, ot_tag!(b"Mtei")),
+    (0xAB01, 0xAB06, ot_tag!(b"Ethi")),
+    (0xAB09, 0xAB0E, ot_tag!(b"Ethi")),
+    (0xAB11, 0xAB16, ot_tag!(b"Ethi")),
+    (0xAB20, 0xAB26, ot_tag!(b"Ethi")),
+    (0xAB28, 0xAB2E, ot_tag!(b"Ethi")),
+    (0xAB30, 0xAB5A, ot_tag!(b"Latn")),
+    (0xAB5B, 0xAB5B, ot_tag!(b"Zyyy")),
+    (0xAB5C, 0xAB64, ot_tag!(b"Latn")),
+    (0xAB65, 0xAB65, ot_tag!(b"Grek")),
+    (0xAB66, 0xAB69, ot_tag!(b"Latn")),
+    (0xAB6A, 0xAB6B, ot_tag!(b"Zyyy")),
+    (0xAB70, 0xABBF, ot_tag!(b"Cher")),
+    (0xABC0, 0xABED, ot_tag!(b"Mtei")),
+    (0xABF0, 0xABF9, ot_tag!(b"Mtei")),
+    (0xAC00, 0xD7A3, ot_tag!(b"Hang")),
+    (0xD7B0, 0xD7C6, ot_tag!(b"Hang")),
+    (0xD7CB, 0xD7FB, ot_tag!(b"Hang")),
+    (0xF900, 0xFA6D, ot_tag!(b"Hani")),
+    (0xFA70, 0xFAD9, ot_tag!(b"Hani")),
+    (0xFB00, 0xFB06, ot_tag!(b"Latn")),
+    (0xFB13, 0xFB17, ot_tag!(b"Armn")),
+    (0xFB1D, 0xFB36, ot_tag!(b"Hebr")),
+    (0xFB38, 0xFB3C, ot_tag!(b"Hebr")),
+    (0xFB3E, 0xFB3E, ot_tag!(b"Hebr")),
+    (0xFB40, 0xFB41, ot_tag!(b"Hebr")),
+    (0xFB43, 0xFB44, ot_tag!(b"Hebr")),
+    (0xFB46, 0xFB4F, ot_tag!(b"Hebr")),
+    (0xFB50, 0xFD3D, ot_tag!(b"Arab")),
+    (0xFD3E, 0xFD3F, ot_tag!(b"Zyyy")),
+    (0xFD40, 0xFDCF, ot_tag!(b"Arab")),
+    (0xFDF0, 0xFDFF, ot_tag!(b"Arab")),
+    (0xFE00, 0xFE0F, ot_tag!(b"Zinh")),
+    (0xFE10, 0xFE19, ot_tag!(b"Zyyy")),
+    (0xFE20, 0xFE2D, ot_tag!(b"Zinh")),
+    (0xFE2E, 0xFE2F, ot_tag!(b"Cyrl")),
+    (0xFE30, 0xFE52, ot_tag!(b"Zyyy")),
+    (0xFE54, 0xFE66, ot_tag!(b"Zyyy")),
+    (0xFE68, 0xFE6B, ot_tag!(b"Zyyy")),
+    (0xFE70, 0xFE74, ot_tag!(b"Arab")),
+    (0xFE76, 0xFEFC, ot_tag!(b"Arab")),
+    (0xFEFF, 0xFEFF, ot_tag!(b"Zyyy")),
+    (0xFF01, 0xFF20, ot_tag!(b"Zyyy")),
+    (0xFF21, 0xFF3A, ot_tag!(b"Latn")),
+    (0xFF3B, 0xFF40, ot_tag!(b"Zyyy")),
+    (0xFF41, 0xFF5A, ot_tag!(b"Latn")),
+    (0xFF5B, 0xFF65, ot_tag!(b"Zyyy")),
+    (0xFF66, 0xFF6F, ot_tag!(b"Kana")),
+    (0xFF70, 0xFF70, ot_tag!(b"Zyyy")),
+    (0xFF71, 0xFF9D, ot_tag!(b"Kana")),
+    (0xFF9E, 0xFF9F, ot_tag!(b"Zyyy")),
+    (0xFFA0, 0xFFBE, ot_tag!(b"Hang")),
+    (0xFFC2, 0xFFC7, ot_tag!(b"Hang")),
+    (0xFFCA, 0xFFCF, ot_tag!(b"Hang")),
+    (0xFFD2, 0xFFD7, ot_tag!(b"Hang")),
+    (0xFFDA, 0xFFDC, ot_tag!(b"Hang")),
+    (0xFFE0, 0xFFE6, ot_tag!(b"Zyyy")),
+    (0xFFE8, 0xFFEE, ot_tag!(b"Zyyy")),
+    (0xFFF9, 0xFFFD, ot_tag!(b"Zyyy")),
+    (0x10000, 0x1000B, ot_tag!(b"Linb")),
+    (0x1000D, 0x10026, ot_tag!(b"Linb")),
+    (0x10028, 0x1003A, ot_tag!(b"Linb")),
+    (0x1003C, 0x1003D, ot_tag!(b"Linb")),
+    (0x1003F, 0x1004D, ot_tag!(b"Linb")),
+    (0x10050, 0x1005D, ot_tag!(b"Linb")),
+    (0x10080, 0x100FA, ot_tag!(b"Linb")),
+    (0x10100, 0x10102, ot_tag!(b"Zyyy")),
+    (0x10107, 0x10133, ot_tag!(b"Zyyy")),
+    (0x10137, 0x1013F, ot_tag!(b"Zyyy")),
+    (0x10140, 0x1018E, ot_tag!(b"Grek")),
+    (0x10190, 0x1019C, ot_tag!(b"Zyyy")),
+    (0x101A0, 0x101A0, ot_tag!(b"Grek")),
+    (0x101D0, 0x101FC, ot_tag!(b"Zyyy")),
+    (0x101FD, 0x101FD, ot_tag!(b"Zinh")),
+    (0x10280, 0x1029C, ot_tag!(b"Lyci")),
+    (0x102A0, 0x102D0, ot_tag!(b"Cari")),
+    (0x102E0, 0x102E0, ot_tag!(b"Zinh")),
+    (0x102E1, 0x102FB, ot_tag!(b"Zyyy")),
+    (0x10300, 0x10323, ot_tag!(b"Ital")),
+    (0x1032D, 0x1032F, ot_tag!(b"Ital")),
+    (0x10330, 0x1034A, ot_tag!(b"Goth")),
+    (0x10350, 0x1037A, ot_tag!(b"Perm")),
+    (0x10380, 0x1039D, ot_tag!(b"Ugar")),
+    (0x1039F, 0x1039F, ot_tag!(b"Ugar")),
+    (0x103A0, 0x103C3, ot_tag!(b"Xpeo")),
+    (0x103C8, 0x103D5, ot_tag!(b"Xpeo")),
+    (0x10400, 0x1044F, ot_tag!(b"Dsrt")),
+    (0x10450, 0x1047F, ot_tag!(b"Shaw")),
+    (0x10480, 0x1049D, ot_tag!(b"Osma")),
+    (0x104A0, 0x104A9, ot_tag!(b"Osma")),
+    (0x104B0, 0x104D3, ot_tag!(b"Osge")),
+    (0x104D8, 0x104FB, ot_tag!(b"Osge")),
+    (0x10500, 0x10527, ot_tag!(b"Elba")),
+    (0x10530, 0x10563, ot_tag!(b"Aghb")),
+    (0x1056F, 0x1056F, ot_tag!(b"Aghb")),
+    (0x10570, 0x1057A, ot_tag!(b"Vith")),
+    (0x1057C, 0x1058A, ot_tag!(b"Vith")),
+    (0x1058C, 0x10592, ot_tag!(b"Vith")),
+    (0x10594, 0x10595, ot_tag!(b"Vith")),
+    (0x10597, 0x105A1, ot_tag!(b"Vith")),
+    (0x105A3, 0x105B1, ot_tag!(b"Vith")),
+    (0x105B3, 0x105B9, ot_tag!(b"Vith")),
+    (0x105BB, 0x105BC, ot_tag!(b"Vith")),
+    (0x105C0, 0x105F3, ot_tag!(b"Todr")),
+    (0x10600, 0x10736, ot_tag!(b"Lina")),
+    (0x10740, 0x10755, ot_tag!(b"Lina")),
+    (0x10760, 0x10767, ot_tag!(b"Lina")),
+    (0x10780, 0x10785, ot_tag!(b"Latn")),
+    (0x10787, 0x107B0, ot_tag!(b"Latn")),
+    (0x107B2, 0x107BA, ot_tag!(b"Latn")),
+    (0x10800, 0x10805, ot_tag!(b"Cprt")),
+    (0x10808, 0x10808, ot_tag!(b"Cprt")),
+    (0x1080A, 0x10835, ot_tag!(b"Cprt")),
+    (0x10837, 0x10838, ot_tag!(b"Cprt")),
+    (0x1083C, 0x1083C, ot_tag!(b"Cprt")),
+    (0x1083F, 0x1083F, ot_tag!(b"Cprt")),
+    (0x10840, 0x10855, ot_tag!(b"Armi")),
+    (0x10857, 0x1085F, ot_tag!(b"Armi")),
+    (0x10860, 0x1087F, ot_tag!(b"Palm")),
+    (0x10880, 0x1089E, ot_tag!(b"Nbat")),
+    (0x108A7, 0x108AF, ot_tag!(b"Nbat")),
+    (0x108E0, 0x108F2, ot_tag!(b"Hatr")),
+    (0x108F4, 0x108F5, ot_tag!(b"Hatr")),
+    (0x108FB, 0x108FF, ot_tag!(b"Hatr")),
+    (0x10900, 0x1091B, ot_tag!(b"Phnx")),
+    (0x1091F, 0x1091F, ot_tag!(b"Phnx")),
+    (0x10920, 0x10939, ot_tag!(b"Lydi")),
+    (0x1093F, 0x1093F, ot_tag!(b"Lydi")),
+    (0x10940, 0x10959, ot_tag!(b"Sidt")),
+    (0x10980, 0x1099F, ot_tag!(b"Mero")),
+    (0x109A0, 0x109B7, ot_tag!(b"Merc")),
+    (0x109BC, 0x109CF, ot_tag!(b"Merc")),
+    (0x109D2, 0x109FF, ot_tag!(b"Merc")),
+    (0x10A00, 0x10A03, ot_tag!(b"Khar")),
+    (0x10A05, 0x10A06, ot_tag!(b"Khar")),
+    (0x10A0C, 0x10A13, ot_tag!(b"Khar")),
+    (0x10A15, 0x10A17, ot_tag!(b"Khar")),
+    (0x10A19, 0x10A35, ot_tag!(b"Khar")),
+    (0x10A38, 0x10A3A, ot_tag!(b"Khar")),
+    (0x10A3F, 0x10A48, ot_tag!(b"Khar")),
+    (0x10A50, 0x10A58, ot_tag!(b"Khar")),
+    (0x10A60, 0x10A7F, ot_tag!(b"Sarb")),
+    (0x10A80, 0x10A9F, ot_tag!(b"Narb")),
+    (0x10AC0, 0x10AE6, ot_tag!(b"Mani")),
+    (0x10AEB, 0x10AF6, ot_tag!(b"Mani")),
+    (0x10B00, 0x10B35, ot_tag!(b"Avst")),
+    (0x10B39, 0x10B3F, ot_tag!(b"Avst")),
+    (0x10B40, 0x10B55, ot_tag!(b"Prti")),
+    (0x10B58, 0x10B5F, ot_tag!(b"Prti")),
+    (0x10B60, 0x10B72, ot_tag!(b"Phli")),
+    (0x10B78, 0x10B7F, ot_tag!(b"Phli")),
+    (0x10B80, 0x10B91, ot_tag!(b"Phlp")),
+    (0x10B99, 0x10B9C, ot_tag!(b"Phlp")),
+    (0x10BA9, 0x10BAF, ot_tag!(b"Phlp")),
+    (0x10C00, 0x10C48, ot_tag!(b"Orkh")),
+    (0x10C80, 0x10CB2, ot_tag!(b"Hung")),
+    (0x10CC0, 0x10CF2, ot_tag!(b"Hung")),
+    (0x10CFA, 0x10CFF, ot_tag!(b"Hung")),
+    (0x10D00, 0x10D27, ot_tag!(b"Rohg")),
+    (0x10D30, 0x10D39, ot_tag!(b"Rohg")),
+    (0x10D40, 0x10D65, ot_tag!(b"Gara")),
+    (0x10D69, 0x10D85, ot_tag!(b"Gara")),
+    (0x10D8E, 0x10D8F, ot_tag!(b"Gara")),
+    (0x10E60, 0x10E7E, ot_tag!(b"Arab")),
+    (0x10E80, 0x10EA9, ot_tag!(b"Yezi")),
+    (0x10EAB, 0x10EAD, ot_tag!(b"Yezi")),
+    (0x10EB0, 0x10EB1, ot_tag!(b"Yezi")),
+    (0x10EC2, 0x10EC7, ot_tag!(b"Arab")),
+    (0x10ED0, 0x10ED8, ot_tag!(b"Arab")),
+    (0x10EFA, 0x10EFF, ot_tag!(b"Arab")),
+    (0x10F00, 0x10F27, ot_tag!(b"Sogo")),
+    (0x10F30, 0x10F59, ot_tag!(b"Sogd")),
+    (0x10F70, 0x10F89, ot_tag!(b"Ougr")),
+    (0x10FB0, 0x10FCB, ot_tag!(b"Chrs")),
+    (0x10FE0, 0x10FF6, ot_tag!(b"Elym")),
+    (0x11000, 0x1104D, ot_tag!(b"Brah")),
+    (0x11052, 0x11075, ot_tag!(b"Brah")),
+    (0x1107F, 0x1107F, ot_tag!(b"Brah")),
+    (0x11080, 0x110C2, ot_tag!(b"Kthi")),
+    (0x110CD, 0x110CD, ot_tag!(b"Kthi")),
+    (0x110D0, 0x110E8, ot_tag!(b"Sora")),
+    (0x110F0, 0x110F9, ot_tag!(b"Sora")),
+    (0x11100, 0x11134, ot_tag!(b"Cakm")),
+    (0x11136, 0x11147, ot_tag!(b"Cakm")),
+    (0x11150, 0x11176, ot_tag!(b"Mahj")),
+    (0x11180, 0x111DF, ot_tag!(b"Shrd")),
+    (0x111E1, 0x111F4, ot_tag!(b"Sinh")),
+    (0x11200, 0x11211, ot_tag!(b"Khoj")),
+    (0x11213, 0x11241, ot_tag!(b"Khoj")),
+    (0x11280, 0x11286, ot_tag!(b"Mult")),
+    (0x11288, 0x11288, ot_tag!(b"Mult")),
+    (0x1128A, 0x1128D, ot_tag!(b"Mult")),
+    (0x1128F, 0x1129D, ot_tag!(b"Mult")),
+    (0x1129F, 0x112A9, ot_tag!(b"Mult")),
+    (0x112B0, 0x112EA, ot_tag!(b"Sind")),
+    (0x112F0, 0x112F9, ot_tag!(b"Sind")),
+    (0x11300, 0x11303, ot_tag!(b"Gran")),
+    (0x11305, 0x1130C, ot_tag!(b"Gran")),
+    (0x1130F, 0x11310, ot_tag!(b"Gran")),
+    (0x11313, 0x11328, ot_tag!(b"Gran")),
+    (0x1132A, 0x11330, ot_tag!(b"Gran")),
+    (0x11332, 0x11333, ot_tag!(b"Gran")),
+    (0x11335, 0x11339, ot_tag!(b"Gran")),
+    (0x1133B, 0x1133B, ot_tag!(b"Zinh")),
+    (0x1133C, 0x11344, ot_tag!(b"Gran")),
+    (0x11347, 0x11348, ot_tag!(b"Gran")),
+    (0x1134B, 0x1134D, ot_tag!(b"Gran")),
+    (0x11350, 0x11350, ot_tag!(b"Gran")),
+    (0x11357, 0x11357, ot_tag!(b"Gran")),
+    (0x1135D, 0x11363, ot_tag!(b"Gran")),
+    (0x11366, 0x1136C, ot_tag!(b"Gran")),
+    (0x11370, 0x11374, ot_tag!(b"Gran")),
+    (0x11380, 0x11389, ot_tag!(b"Tutg")),
+    (0x1138B, 0x1138B, ot_tag!(b"Tutg")),
+    (0x1138E, 0x1138E, ot_tag!(b"Tutg")),
+    (0x11390, 0x113B5, ot_tag!(b"Tutg")),
+    (0x113B7, 0x113C0, ot_tag!(b"Tutg")),
+    (0x113C2, 0x113C2, ot_tag!(b"Tutg")),
+    (0x113C5, 0x113C5, ot_tag!(b"Tutg")),
+    (0x113C7, 0x113CA, ot_tag!(b"Tutg")),
+    (0x113CC, 0x113D5, ot_tag!(b"Tutg")),
+    (0x113D7, 0x113D8, ot_tag!(b"Tutg")),
+    (0x113E1, 0x113E2, ot_tag!(b"Tutg")),
+    (0x11400, 0x1145B, ot_tag!(b"Newa")),
+    (0x1145D, 0x11461, ot_tag!(b"Newa")),
+    (0x11480, 0x114C7, ot_tag!(b"Tirh")),
+    (0x114D0, 0x114D9, ot_tag!(b"Tirh")),
+    (0x11580, 0x115B5, ot_tag!(b"Sidd")),
+    (0x115B8, 0x115DD, ot_tag!(b"Sidd")),
+    (0x11600, 0x11644, ot_tag!(b"Modi")),
+    (0x11650, 0x11659, ot_tag!(b"Modi")),
+    (0x11660, 0x1166C, ot_tag!(b"Mong")),
+    (0x11680, 0x116B9, ot_tag!(b"Takr")),
+    (0x116C0, 0x116C9, ot_tag!(b"Takr")),
+    (0x116D0, 0x116E3, ot_tag!(b"Mymr")),
+    (0x11700, 0x1171A, ot_tag!(b"Ahom")),
+    (0x1171D, 0x1172B, ot_tag!(b"Ahom")),
+    (0x11730, 0x11746, ot_tag!(b"Ahom")),
+    (0x11800, 0x1183B, ot_tag!(b"Dogr")),
+    (0x118A0, 0x118F2, ot_tag!(b"Wara")),
+    (0x118FF, 0x118FF, ot_tag!(b"Wara")),
+    (0x11900, 0x11906, ot_tag!(b"Diak")),
+    (0x11909, 0x11909, ot_tag!(b"Diak")),
+    (0x1190C, 0x11913, ot_tag!(b"Diak")),
+    (0x11915, 0x11916, ot_tag!(b"Diak")),
+    (0x11918, 0x11935, ot_tag!(b"Diak")),
+    (0x11937, 0x11938, ot_tag!(b"Diak")),
+    (0x1193B, 0x11946, ot_tag!(b"Diak")),
+    (0x11950, 0x11959, ot_tag!(b"Diak")),
+    (0x119A0, 0x119A7, ot_tag!(b"Nand")),
+    (0x119AA, 0x119D7, ot_tag!(b"Nand")),
+    (0x119DA, 0x119E4, ot_tag!(b"Nand")),
+    (0x11A00, 0x11A47, ot_tag!(b"Zanb")),
+    (0x11A50, 0x11AA2, ot_tag!(b"Soyo")),
+    (0x11AB0, 0x11ABF, ot_tag!(b"Cans")),
+    (0x11AC0, 0x11AF8, ot_tag!(b"Pauc")),
+    (0x11B00, 0x11B09, ot_tag!(b"Deva")),
+    (0x11B60, 0x11B67, ot_tag!(b"Shrd")),
+    (0x11BC0, 0x11BE1, ot_tag!(b"Sunu")),
+    (0x11BF0, 0x11BF9, ot_tag!(b"Sunu")),
+    (0x11C00, 0x11C08, ot_tag!(b"Bhks")),
+    (0x11C0A, 0x11C36, ot_tag!(b"Bhks")),
+    (0x11C38, 0x11C45, ot_tag!(b"Bhks")),
+    (0x11C50, 0x11C6C, ot_tag!(b"Bhks")),
+    (0x11C70, 0x11C8F, ot_tag!(b"Marc")),
+    (0x11C92, 0x11CA7, ot_tag!(b"Marc")),
+    (0x11CA9, 0x11CB6, ot_tag!(b"Marc")),
+    (0x11D00, 0x11D06, ot_tag!(b"Gonm")),
+    (0x11D08, 0x11D09, ot_tag!(b"Gonm")),
+    (0x11D0B, 0x11D36, ot_tag!(b"Gonm")),
+    (0x11D3A, 0x11D3A, ot_tag!(b"Gonm")),
+    (0x11D3C, 0x11D3D, ot_tag!(b"Gonm")),
+    (0x11D3F, 0x11D47, ot_tag!(b"Gonm")),
+    (0x11D50, 0x11D59, ot_tag!(b"Gonm")),
+    (0x11D60, 0x11D65, ot_tag!(b"Gong")),
+    (0x11D67, 0x11D68, ot_tag!(b"Gong")),
+    (0x11D6A, 0x11D8E, ot_tag!(b"Gong")),
+    (0x11D90, 0x11D91, ot_tag!(b"Gong")),
+    (0x11D93, 0x11D98, ot_tag!(b"Gong")),
+    (0x11DA0, 0x11DA9, ot_tag!(b"Gong")),
+    (0x11DB0, 0x11DDB, ot_tag!(b"Tols")),
+    (0x11DE0, 0x11DE9, ot_tag!(b"Tols")),
+    (0x11EE0, 0x11EF8, ot_tag!(b"Maka")),
+    (0x11F00, 0x11F10, ot_tag!(b"Kawi")),
+    (0x11F12, 0x11F3A, ot_tag!(b"Kawi")),
+    (0x11F3E, 0x11F5A, ot_tag!(b"Kawi")),
+    (0x11FB0, 0x11FB0, ot_tag!(b"Lisu")),
+    (0x11FC0, 0x11FF1, ot_tag!(b"Taml")),
+    (0x11FFF, 0x11FFF, ot_tag!(b"Taml")),
+    (0x12000, 0x12399, ot_tag!(b"Xsux")),
+    (0x12400, 0x1246E, ot_tag!(b"Xsux")),
+    (0x12470, 0x12474, ot_tag!(b"Xsux")),
+    (0x12480, 0x12543, ot_tag!(b"Xsux")),
+    (0x12F90, 0x12FF2, ot_tag!(b"Cpmn")),
+    (0x13000, 0x13455, ot_tag!(b"Egyp")),
+    (0x13460, 0x143FA, ot_tag!(b"Egyp")),
+    (0x14400, 0x14646, ot_tag!(b"Hluw")),
+    (0x16100, 0x16139, ot_tag!(b"Gukh")),
+    (0x16800, 0x16A38, ot_tag!(b"Bamu")),
+    (0x16A40, 0x16A5E, ot_tag!(b"Mroo")),
+    (0x16A60, 0x16A69, ot_tag!(b"Mroo")),
+    (0x16A6E, 0x16A6F, ot_tag!(b"Mroo")),
+    (0x16A70, 0x16ABE, ot_tag!(b"Tnsa")),
+    (0x16AC0, 0x16AC9, ot_tag!(b"Tnsa")),
+    (0x16AD0, 0x16AED, ot_tag!(b"Bass")),
+    (0x16AF0, 0x16AF5, ot_tag!(b"Bass")),
+    (0x16B00, 0x16B45, ot_tag!(b"Hmng")),
+    (0x16B50, 0x16B59, ot_tag!(b"Hmng")),
+    (0x16B5B, 0x16B61, ot_tag!(b"Hmng")),
+    (0x16B63, 0x16B77, ot_tag!(b"Hmng")),
+    (0x16B7D, 0x16B8F, ot_tag!(b"Hmng")),
+    (0x16D40, 0x16D79, ot_tag!(b"Krai")),
+    (0x16E40, 0x16E9A, ot_tag!(b"Medf")),
+    (0x16EA0, 0x16EB8, ot_tag!(b"Berf")),
+    (0x16EBB, 0x16ED3, ot_tag!(b"Berf")),
+    (0x16F00, 0x16F4A, ot_tag!(b"Plrd")),
+    (0x16F4F, 0x16F87, ot_tag!(b"Plrd")),
+    (0x16F8F, 0x16F9F, ot_tag!(b"Plrd")),
+    (0x16FE0, 0x16FE0, ot_tag!(b"Tang")),
+    (0x16FE1, 0x16FE1, ot_tag!(b"Nshu")),
+    (0x16FE2, 0x16FE3, ot_tag!(b"Hani")),
+    (0x16FE4, 0x16FE4, ot_tag!(b"Kits")),
+    (0x16FF0, 0x16FF6, ot_tag!(b"Hani")),
+    (0x17000, 0x18AFF, ot_tag!(b"Tang")),
+    (0x18B00, 0x18CD5, ot_tag!(b"Kits")),
+    (0x18CFF, 0x18CFF, ot_tag!(b"Kits")),
+    (0x18D00, 0x18D1E, ot_tag!(b"Tang")),
+    (0x18D80, 0x18DF2, ot_tag!(b"Tang")),
+    (0x1AFF0, 0x1AFF3, ot_tag!(b"Kana")),
+    (0x1AFF5, 0x1AFFB, ot_tag!(b"Kana")),
+    (0x1AFFD, 0x1AFFE, ot_tag!(b"Kana")),
+    (0x1B000, 0x1B000, ot_tag!(b"Kana")),
+    (0x1B001, 0x1B11F, ot_tag!(b"Hira")),
+    (0x1B120, 0x1B122, ot_tag!(b"Kana")),
+    (0x1B132, 0x1B132, ot_tag!(b"Hira")),
+    (0x1B150, 0x1B152, ot_tag!(b"Hira")),
+    (0x1B155, 0x1B155, ot_tag!(b"Kana")),
+    (0x1B164, 0x1B167, ot_tag!(b"Kana")),
+    (0x1B170, 0x1B2FB, ot_tag!(b"Nshu")),
+    (0x1BC00, 0x1BC6A, ot_tag!(b"Dupl")),
+    (0x1BC70, 0x1BC7C, ot_tag!(b"Dupl")),
+    (0x1BC80, 0x1BC88, ot_tag!(b"Dupl")),
+    (0x1BC90, 0x1BC99, ot_tag!(b"Dupl")),
+    (0x1BC9C, 0x1BC9F, ot_tag!(b"Dupl")),
+    (0x1BCA0, 0x1BCA3, ot_tag!(b"Zyyy")),
+    (0x1CC00, 0x1CCFC, ot_tag!(b"Zyyy")),
+    (0x1CD00, 0x1CEB3, ot_tag!(b"Zyyy")),
+    (0x1CEBA, 0x1CED0, ot_tag!(b"Zyyy")),
+    (0x1CEE0, 0x1CEF0, ot_tag!(b"Zyyy")),
+    (0x1CF00, 0x1CF2D, ot_tag!(b"Zinh")),
+    (0x1CF30, 0x1CF46, ot_tag!(b"Zinh")),
+    (0x1CF50, 0x1CFC3, ot_tag!(b"Zyyy")),
+    (0x1D000, 0x1D0F5, ot_tag!(b"Zyyy")),
+    (0x1D100, 0x1D126, ot_tag!(b"Zyyy")),
+    (0x1D129, 0x1D166, ot_tag!(b"Zyyy")),
+    (0x1D167, 0x1D169, ot_tag!(b"Zinh")),
+    (0x1D16A, 0x1D17A, ot_tag!(b"Zyyy")),
+    (0x1D17B, 0x1D182, ot_tag!(b"Zinh")),
+    (0x1D183, 0x1D184, ot_tag!(b"Zyyy")),
+    (0x1D185, 0x1D18B, ot_tag!(b"Zinh")),
+    (0x1D18C, 0x1D1A9, ot_tag!(b"Zyyy")),
+    (0x1D1AA, 0x1D1AD, ot_tag!(b"Zinh")),
+    (0x1D1AE, 0x1D1EA, ot_tag!(b"Zyyy")),
+    (0x1D200, 0x1D245, ot_tag!(b"Grek")),
+    (0x1D2C0, 0x1D2D3, ot_tag!(b"Zyyy")),
+    (0x1D2E0, 0x1D2F3, ot_tag!(b"Zyyy")),
+    (0x1D300, 0x1D356, ot_tag!(b"Zyyy")),
+    (0x1D360, 0x1D378, ot_tag!(b"Zyyy")),
+    (0x1D400, 0x1D454, ot_tag!(b"Zyyy")),
+    (0x1D456, 0x1D49C, ot_tag!(b"Zyyy")),
+    (0x1D49E, 0x1D49F, ot_tag!(b"Zyyy")),
+    (0x1D4A2, 0x1D4A2, ot_tag!(b"Zyyy")),
+    (0x1D4A5, 0x1D4A6, ot_tag!(b"Zyyy")),
+    (0x1D4A9, 0x1D4AC, ot_tag!(b"Zyyy")),
+    (0x1D4AE, 0x1D4B9, ot_tag!(b"Zyyy")),
+    (0x1D4BB, 0x1D4BB, ot_tag!(b"Zyyy")),
+    (0x1D4BD, 0x1D4C3, ot_tag!(b"Zyyy")),
+    (0x1D4C5, 0x1D505, ot_tag!(b"Zyyy")),
+    (0x1D507, 0x1D50A, ot_tag!(b"Zyyy")),
+    (0x1D50D, 0x1D514, ot_tag!(b"Zyyy")),
+    (0x1D516, 0x1D51C, ot_tag!(b"Zyyy")),
+    (0x1D51E, 0x1D539, ot_tag!(b"Zyyy")),
+    (0x1D53B, 0x1D53E, ot_tag!(b"Zyyy")),
+    (0x1D540, 0x1D544, ot_tag!(b"Zyyy")),
+    (0x1D546, 0x1D546, ot_tag!(b"Zyyy")),
+    (0x1D54A, 0x1D550, ot_tag!(b"Zyyy")),
+    (0x1D552, 0x1D6A5, ot_tag!(b"Zyyy")),
+    (0x1D6A8, 0x1D7CB, ot_tag!(b"Zyyy")),
+    (0x1D7CE, 0x1D7FF, ot_tag!(b"Zyyy")),
+    (0x1D800, 0x1DA8B, ot_tag!(b"Sgnw")),
+    (0x1DA9B, 0x1DA9F, ot_tag!(b"Sgnw")),
+    (0x1DAA1, 0x1DAAF, ot_tag!(b"Sgnw")),
+    (0x1DF00, 0x1DF1E, ot_tag!(b"Latn")),
+    (0x1DF25, 0x1DF2A, ot_tag!(b"Latn")),
+    (0x1E000, 0x1E006, ot_tag!(b"Glag")),
+    (0x1E008, 0x1E018, ot_tag!(b"Glag")),
+    (0x1E01B, 0x1E021, ot_tag!(b"Glag")),
+    (0x1E023, 0x1E024, ot_tag!(b"Glag")),
+    (0x1E026, 0x1E02A, ot_tag!(b"Glag")),
+    (0x1E030, 0x1E06D, ot_tag!(b"Cyrl")),
+    (0x1E08F, 0x1E08F, ot_tag!(b"Cyrl")),
+    (0x1E100, 0x1E12C, ot_tag!(b"Hmnp")),
+    (0x1E130, 0x1E13D, ot_tag!(b"Hmnp")),
+    (0x1E140, 0x1E149, ot_tag!(b"Hmnp")),
+    (0x1E14E, 0x1E14F, ot_tag!(b"Hmnp")),
+    (0x1E290, 0x1E2AE, ot_tag!(b"Toto")),
+    (0x1E2C0, 0x1E2F9, ot_tag!(b"Wcho")),
+    (0x1E2FF, 0x1E2FF, ot_tag!(b"Wcho")),
+    (0x1E4D0, 0x1E4F9, ot_tag!(b"Nagm")),
+    (0x1E5D0, 0x1E5FA, ot_tag!(b"Onao")),
+    (0x1E5FF, 0x1E5FF, ot_tag!(b"Onao")),
+    (0x1E6C0, 0x1E6DE, ot_tag!(b"Tayo")),
+    (0x1E6E0, 0x1E6F5, ot_tag!(b"Tayo")),
+    (0x1E6FE, 0x1E6FF, ot_tag!(b"Tayo")),
+    (0x1E7E0, 0x1E7E6, ot_tag!(b"Ethi")),
+    (0x1E7E8, 0x1E7EB, ot_tag!(b"Ethi")),
+    (0x1E7ED, 0x1E7EE, ot_tag!(b"Ethi")),
+    (0x1E7F0, 0x1E7FE, ot_tag!(b"Ethi")),
+    (0x1E800, 0x1E8C4, ot_tag!(b"Mend")),
+    (0x1E8C7, 0x1E8D6, ot_tag!(b"Mend")),
+    (0x1E900, 0x1E94B, ot_tag!(b"Adlm")),
+    (0x1E950, 0x1E959, ot_tag!(b"Adlm")),
+    (0x1E95E, 0x1E95F, ot_tag!(b"Adlm")),
+    (0x1EC71, 0x1ECB4, ot_tag!(b"Zyyy")),
+    (0x1ED01, 0x1ED3D, ot_tag!(b"Zyyy")),
+    (0x1EE00, 0x1EE03, ot_tag!(b"Arab")),
+    (0x1EE05, 0x1EE1F, ot_tag!(b"Arab")),
+    (0x1EE21, 0x1EE22, ot_tag!(b"Arab")),
+    (0x1EE24, 0x1EE24, ot_tag!(b"Arab")),
+    (0x1EE27, 0x1EE27, ot_tag!(b"Arab")),
+    (0x1EE29, 0x1EE32, ot_tag!(b"Arab")),
+    (0x1EE34, 0x1EE37, ot_tag!(b"Arab")),
+    (0x1EE39, 0x1EE39, ot_tag!(b"Arab")),
+    (0x1EE3B, 0x1EE3B, ot_tag!(b"Arab")),
+    (0x1EE42, 0x1EE42, ot_tag!(b"Arab")),
+    (0x1EE47, 0x1EE47, ot_tag!(b"Arab")),
+    (0x1EE49, 0x1EE49, ot_tag!(b"Arab")),
+    (0x1EE4B, 0x1EE4B, ot_tag!(b"Arab")),
+    (0x1EE4D, 0x1EE4F, ot_tag!(b"Arab")),
+    (0x1EE51, 0x1EE52, ot_tag!(b"Arab")),
+    (0x1EE54, 0x1EE54, ot_tag!(b"Arab")),
+    (0x1EE57, 0x1EE57, ot_tag!(b"Arab")),
+    (0x1EE59, 0x1EE59, ot_tag!(b"Arab")),
+    (0x1EE5B, 0x1EE5B, ot_tag!(b"Arab")),
+    (0x1EE5D, 0x1EE5D, ot_tag!(b"Arab")),
+    (0x1EE5F, 0x1EE5F, ot_tag!(b"Arab")),
+    (0x1EE61, 0x1EE62, ot_tag!(b"Arab")),
+    (0x1EE64, 0x1EE64, ot_tag!(b"Arab")),
+    (0x1EE67, 0x1EE6A, ot_tag!(b"Arab")),
+    (0x1EE6C, 0x1EE72, ot_tag!(b"Arab")),
+    (0x1EE74, 0x1EE77, ot_tag!(b"Arab")),
+    (0x1EE79, 0x1EE7C, ot_tag!(b"Arab")),
+    (0x1EE7E, 0x1EE7E, ot_tag!(b"Arab")),
+    (0x1EE80, 0x1EE89, ot_tag!(b"Arab")),
+    (0x1EE8B, 0x1EE9B, ot_tag!(b"Arab")),
+    (0x1EEA1, 0x1EEA3, ot_tag!(b"Arab")),
+    (0x1EEA5, 0x1EEA9, ot_tag!(b"Arab")),
+    (0x1EEAB, 0x1EEBB, ot_tag!(b"Arab")),
+    (0x1EEF0, 0x1EEF1, ot_tag!(b"Arab")),
+    (0x1F000, 0x1F02B, ot_tag!(b"Zyyy")),
+    (0x1F030, 0x1F093, ot_tag!(b"Zyyy")),
+    (0x1F0A0, 0x1F0AE, ot_tag!(b"Zyyy")),
+    (0x1F0B1, 0x1F0BF, ot_tag!(b"Zyyy")),
+    (0x1F0C1, 0x1F0CF, ot_tag!(b"Zyyy")),
+    (0x1F0D1, 0x1F0F5, ot_tag!(b"Zyyy")),
+    (0x1F100, 0x1F1AD, ot_tag!(b"Zyyy")),
+    (0x1F1E6, 0x1F1FF, ot_tag!(b"Zyyy")),
+    (0x1F200, 0x1F200, ot_tag!(b"Hira")),
+    (0x1F201, 0x1F202, ot_tag!(b"Zyyy")),
+    (0x1F210, 0x1F23B, ot_tag!(b"Zyyy")),
+    (0x1F240, 0x1F248, ot_tag!(b"Zyyy")),
+    (0x1F250, 0x1F251, ot_tag!(b"Zyyy")),
+    (0x1F260, 0x1F265, ot_tag!(b"Zyyy")),
+    (0x1F300, 0x1F6D8, ot_tag!(b"Zyyy")),
+    (0x1F6DC, 0x1F6EC, ot_tag!(b"Zyyy")),
+    (0x1F6F0, 0x1F6FC, ot_tag!(b"Zyyy")),
+    (0x1F700, 0x1F7D9, ot_tag!(b"Zyyy")),
+    (0x1F7E0, 0x1F7EB, ot_tag!(b"Zyyy")),
+    (0x1F7F0, 0x1F7F0, ot_tag!(b"Zyyy")),
+    (0x1F800, 0x1F80B, ot_tag!(b"Zyyy")),
+    (0x1F810, 0x1F847, ot_tag!(b"Zyyy")),
+    (0x1F850, 0x1F859, ot_tag!(b"Zyyy")),
+    (0x1F860, 0x1F887, ot_tag!(b"Zyyy")),
+    (0x1F890, 0x1F8AD, ot_tag!(b"Zyyy")),
+    (0x1F8B0, 0x1F8BB, ot_tag!(b"Zyyy")),
+    (0x1F8C0, 0x1F8C1, ot_tag!(b"Zyyy")),
+    (0x1F8D0, 0x1F8D8, ot_tag!(b"Zyyy")),
+    (0x1F900, 0x1FA57, ot_tag!(b"Zyyy")),
+    (0x1FA60, 0x1FA6D, ot_tag!(b"Zyyy")),
+    (0x1FA70, 0x1FA7C, ot_tag!(b"Zyyy")),
+    (0x1FA80, 0x1FA8A, ot_tag!(b"Zyyy")),
+    (0x1FA8E, 0x1FAC6, ot_tag!(b"Zyyy")),
+    (0x1FAC8, 0x1FAC8, ot_tag!(b"Zyyy")),
+    (0x1FACD, 0x1FADC, ot_tag!(b"Zyyy")),
+    (0x1FADF, 0x1FAEA, ot_tag!(b"Zyyy")),
+    (0x1FAEF, 0x1FAF8, ot_tag!(b"Zyyy")),
+    (0x1FB00, 0x1FB92, ot_tag!(b"Zyyy")),
+    (0x1FB94, 0x1FBFA, ot_tag!(b"Zyyy")),
+    (0x20000, 0x2A6DF, ot_tag!(b"Hani")),
+    (0x2A700, 0x2B81D, ot_tag!(b"Hani")),
+    (0x2B820, 0x2CEAD, ot_tag!(b"Hani")),
+    (0x2CEB0, 0x2EBE0, ot_tag!(b"Hani")),
+    (0x2EBF0, 0x2EE5D, ot_tag!(b"Hani")),
+    (0x2F800, 0x2FA1D, ot_tag!(b"Hani")),
+    (0x30000, 0x3134A, ot_tag!(b"Hani")),
+    (0x31350, 0x33479, ot_tag!(b"Hani")),
+    (0xE0001, 0xE0001, ot_tag!(b"Zyyy")),
+    (0xE0020, 0xE007F, ot_tag!(b"Zyyy")),
+    (0xE0100, 0xE01EF, ot_tag!(b"Zinh")),
+];
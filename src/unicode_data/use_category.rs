@@ -0,0 +1,1116 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Universal Shaping Engine character categories, derived from the
+// Unicode Indic properties and General_Category as described in
+// https://docs.microsoft.com/en-us/typography/script-development/use
+
+// The values are the ones the USE syllable grammar is written in terms
+// of.  Consonant and vowel categories are split by position: ABV
+// (above), BLW (below), PRE and PST (post).
+#[allow(dead_code)]
+pub mod category {
+    pub const O: u8 = 0;  // other
+    pub const B: u8 = 1;  // base
+    pub const N: u8 = 4;  // base number
+    pub const GB: u8 = 5;  // other base, e.g. placeholders
+    pub const CGJ: u8 = 6;  // combining grapheme joiner
+    pub const SUB: u8 = 11;  // subjoined consonant
+    pub const H: u8 = 12;  // halant
+    pub const HN: u8 = 13;  // number joiner
+    pub const ZWNJ: u8 = 14;  // zero width non-joiner
+    pub const WJ: u8 = 16;  // word joiner
+    pub const RSV: u8 = 17;  // reserved
+    pub const R: u8 = 18;  // repha
+    pub const S: u8 = 19;  // symbol
+    pub const V_PRE: u8 = 22;
+    pub const VM_PRE: u8 = 23;
+    pub const F_ABV: u8 = 24;
+    pub const F_BLW: u8 = 25;
+    pub const F_PST: u8 = 26;
+    pub const M_ABV: u8 = 27;
+    pub const M_BLW: u8 = 28;
+    pub const M_PST: u8 = 29;
+    pub const M_PRE: u8 = 30;
+    pub const CM_ABV: u8 = 31;
+    pub const CM_BLW: u8 = 32;
+    pub const V_ABV: u8 = 33;
+    pub const V_BLW: u8 = 34;
+    pub const V_PST: u8 = 35;
+    pub const VM_ABV: u8 = 37;
+    pub const VM_BLW: u8 = 38;
+    pub const VM_PST: u8 = 39;
+    pub const SM_ABV: u8 = 41;
+    pub const SM_BLW: u8 = 42;
+    pub const CS: u8 = 43;  // consonant with stacker
+    pub const IS: u8 = 44;  // invisible stacker
+    pub const FM_ABV: u8 = 45;
+    pub const FM_BLW: u8 = 46;
+    pub const FM_PST: u8 = 47;
+    pub const SK: u8 = 48;  // sakot
+    pub const G: u8 = 49;  // hieroglyph
+    pub const J: u8 = 50;  // hieroglyph joiner
+    pub const SB: u8 = 51;  // hieroglyph segment begin
+    pub const SE: u8 = 52;  // hieroglyph segment end
+    pub const HVM: u8 = 53;  // halant or vowel modifier
+    pub const HM: u8 = 54;  // hieroglyph modifier
+    pub const HR: u8 = 55;  // hieroglyph mirror
+}
+
+use self::category::*;
+
+// (first, last, category); everything else is O.
+pub const USE_TABLE: &'static [(u32, u32, u8)] = &[
+    (0x002D, 0x002D, GB),
+    (0x0030, 0x0039, B),
+    (0x005B, 0x005B, SB),
+    (0x005D, 0x005D, SE),
+    (0x007B, 0x007B, SB),
+    (0x007D, 0x007D, SE),
+    (0x00A0, 0x00A0, GB),
+    (0x00AD, 0x00AD, WJ),
+    (0x00B2, 0x00B3, FM_PST),
+    (0x00D7, 0x00D7, GB),
+    (0x034F, 0x034F, CGJ),
+    (0x0640, 0x0640, B),
+    (0x07CA, 0x07EA, B),
+    (0x07EB, 0x07F3, VM_ABV),
+    (0x07FA, 0x07FA, B),
+    (0x07FD, 0x07FD, VM_ABV),
+    (0x0840, 0x0858, B),
+    (0x0859, 0x085B, CM_BLW),
+    (0x0900, 0x0902, VM_ABV),
+    (0x0903, 0x0903, VM_PST),
+    (0x0904, 0x0939, B),
+    (0x093A, 0x093A, V_ABV),
+    (0x093B, 0x093B, V_PST),
+    (0x093C, 0x093C, CM_BLW),
+    (0x093D, 0x093D, B),
+    (0x093E, 0x093E, V_PST),
+    (0x093F, 0x093F, V_PRE),
+    (0x0940, 0x0940, V_PST),
+    (0x0941, 0x0944, V_BLW),
+    (0x0945, 0x0948, V_ABV),
+    (0x0949, 0x094C, V_PST),
+    (0x094D, 0x094D, H),
+    (0x094E, 0x094E, V_PRE),
+    (0x094F, 0x094F, V_PST),
+    (0x0951, 0x0951, VM_ABV),
+    (0x0952, 0x0952, VM_BLW),
+    (0x0955, 0x0955, V_ABV),
+    (0x0956, 0x0957, V_BLW),
+    (0x0958, 0x0961, B),
+    (0x0962, 0x0963, V_BLW),
+    (0x0966, 0x096F, B),
+    (0x0972, 0x097F, B),
+    (0x0980, 0x0980, GB),
+    (0x0981, 0x0981, VM_ABV),
+    (0x0982, 0x0983, VM_PST),
+    (0x0985, 0x098C, B),
+    (0x098F, 0x0990, B),
+    (0x0993, 0x09A8, B),
+    (0x09AA, 0x09B0, B),
+    (0x09B2, 0x09B2, B),
+    (0x09B6, 0x09B9, B),
+    (0x09BC, 0x09BC, CM_BLW),
+    (0x09BD, 0x09BD, B),
+    (0x09BE, 0x09BE, V_PST),
+    (0x09BF, 0x09BF, V_PRE),
+    (0x09C0, 0x09C0, V_PST),
+    (0x09C1, 0x09C4, V_BLW),
+    (0x09C7, 0x09C8, V_PRE),
+    (0x09CB, 0x09CC, V_PRE),
+    (0x09CD, 0x09CD, H),
+    (0x09D7, 0x09D7, V_PST),
+    (0x09DC, 0x09DD, B),
+    (0x09DF, 0x09E1, B),
+    (0x09E2, 0x09E3, V_BLW),
+    (0x09E6, 0x09F1, B),
+    (0x09FC, 0x09FC, B),
+    (0x09FE, 0x09FE, FM_ABV),
+    (0x0A01, 0x0A02, VM_ABV),
+    (0x0A03, 0x0A03, VM_PST),
+    (0x0A05, 0x0A0A, B),
+    (0x0A0F, 0x0A10, B),
+    (0x0A13, 0x0A28, B),
+    (0x0A2A, 0x0A30, B),
+    (0x0A32, 0x0A33, B),
+    (0x0A35, 0x0A36, B),
+    (0x0A38, 0x0A39, B),
+    (0x0A3C, 0x0A3C, CM_BLW),
+    (0x0A3E, 0x0A3E, V_PST),
+    (0x0A3F, 0x0A3F, V_PRE),
+    (0x0A40, 0x0A40, V_PST),
+    (0x0A41, 0x0A42, V_BLW),
+    (0x0A47, 0x0A48, V_ABV),
+    (0x0A4B, 0x0A4C, V_ABV),
+    (0x0A4D, 0x0A4D, H),
+    (0x0A51, 0x0A51, VM_BLW),
+    (0x0A59, 0x0A5C, B),
+    (0x0A5E, 0x0A5E, B),
+    (0x0A66, 0x0A6F, B),
+    (0x0A70, 0x0A70, VM_ABV),
+    (0x0A71, 0x0A71, CM_ABV),
+    (0x0A72, 0x0A73, GB),
+    (0x0A75, 0x0A75, M_BLW),
+    (0x0A81, 0x0A82, VM_ABV),
+    (0x0A83, 0x0A83, VM_PST),
+    (0x0A85, 0x0A8D, B),
+    (0x0A8F, 0x0A91, B),
+    (0x0A93, 0x0AA8, B),
+    (0x0AAA, 0x0AB0, B),
+    (0x0AB2, 0x0AB3, B),
+    (0x0AB5, 0x0AB9, B),
+    (0x0ABC, 0x0ABC, CM_BLW),
+    (0x0ABD, 0x0ABD, B),
+    (0x0ABE, 0x0ABE, V_PST),
+    (0x0ABF, 0x0ABF, V_PRE),
+    (0x0AC0, 0x0AC0, V_PST),
+    (0x0AC1, 0x0AC4, V_BLW),
+    (0x0AC5, 0x0AC5, V_ABV),
+    (0x0AC7, 0x0AC9, V_ABV),
+    (0x0ACB, 0x0ACC, V_PST),
+    (0x0ACD, 0x0ACD, H),
+    (0x0AE0, 0x0AE1, B),
+    (0x0AE2, 0x0AE3, V_BLW),
+    (0x0AE6, 0x0AEF, B),
+    (0x0AF9, 0x0AF9, B),
+    (0x0AFA, 0x0AFA, VM_ABV),
+    (0x0AFB, 0x0AFB, CM_ABV),
+    (0x0AFC, 0x0AFC, VM_ABV),
+    (0x0AFD, 0x0AFF, CM_ABV),
+    (0x0B01, 0x0B01, VM_ABV),
+    (0x0B02, 0x0B03, VM_PST),
+    (0x0B05, 0x0B0C, B),
+    (0x0B0F, 0x0B10, B),
+    (0x0B13, 0x0B28, B),
+    (0x0B2A, 0x0B30, B),
+    (0x0B32, 0x0B33, B),
+    (0x0B35, 0x0B39, B),
+    (0x0B3C, 0x0B3C, CM_BLW),
+    (0x0B3D, 0x0B3D, B),
+    (0x0B3E, 0x0B3E, V_PST),
+    (0x0B3F, 0x0B3F, V_ABV),
+    (0x0B40, 0x0B40, V_PST),
+    (0x0B41, 0x0B44, V_BLW),
+    (0x0B47, 0x0B48, V_PRE),
+    (0x0B4B, 0x0B4C, V_PRE),
+    (0x0B4D, 0x0B4D, H),
+    (0x0B55, 0x0B57, V_ABV),
+    (0x0B5C, 0x0B5D, B),
+    (0x0B5F, 0x0B61, B),
+    (0x0B62, 0x0B63, V_BLW),
+    (0x0B66, 0x0B6F, B),
+    (0x0B71, 0x0B71, B),
+    (0x0B82, 0x0B82, VM_ABV),
+    (0x0B85, 0x0B8A, B),
+    (0x0B8E, 0x0B90, B),
+    (0x0B92, 0x0B95, B),
+    (0x0B99, 0x0B9A, B),
+    (0x0B9C, 0x0B9C, B),
+    (0x0B9E, 0x0B9F, B),
+    (0x0BA3, 0x0BA4, B),
+    (0x0BA8, 0x0BAA, B),
+    (0x0BAE, 0x0BB9, B),
+    (0x0BBE, 0x0BBF, V_PST),
+    (0x0BC0, 0x0BC0, V_ABV),
+    (0x0BC1, 0x0BC2, V_PST),
+    (0x0BC6, 0x0BC8, V_PRE),
+    (0x0BCA, 0x0BCC, V_PRE),
+    (0x0BCD, 0x0BCD, H),
+    (0x0BD7, 0x0BD7, V_PST),
+    (0x0BE6, 0x0BEF, B),
+    (0x0C00, 0x0C00, VM_ABV),
+    (0x0C01, 0x0C03, VM_PST),
+    (0x0C04, 0x0C04, VM_ABV),
+    (0x0C05, 0x0C0C, B),
+    (0x0C0E, 0x0C10, B),
+    (0x0C12, 0x0C28, B),
+    (0x0C2A, 0x0C39, B),
+    (0x0C3C, 0x0C3C, CM_BLW),
+    (0x0C3D, 0x0C3D, B),
+    (0x0C3E, 0x0C40, V_ABV),
+    (0x0C41, 0x0C44, V_PST),
+    (0x0C46, 0x0C48, V_ABV),
+    (0x0C4A, 0x0C4C, V_ABV),
+    (0x0C4D, 0x0C4D, H),
+    (0x0C55, 0x0C55, V_ABV),
+    (0x0C56, 0x0C56, V_BLW),
+    (0x0C58, 0x0C5A, B),
+    (0x0C60, 0x0C61, B),
+    (0x0C62, 0x0C63, V_BLW),
+    (0x0C66, 0x0C6F, B),
+    (0x0C80, 0x0C80, B),
+    (0x0C81, 0x0C81, VM_ABV),
+    (0x0C82, 0x0C83, VM_PST),
+    (0x0C85, 0x0C8C, B),
+    (0x0C8E, 0x0C90, B),
+    (0x0C92, 0x0CA8, B),
+    (0x0CAA, 0x0CB3, B),
+    (0x0CB5, 0x0CB9, B),
+    (0x0CBC, 0x0CBC, CM_BLW),
+    (0x0CBD, 0x0CBD, B),
+    (0x0CBE, 0x0CBE, V_PST),
+    (0x0CBF, 0x0CC0, V_ABV),
+    (0x0CC1, 0x0CC4, V_PST),
+    (0x0CC6, 0x0CC8, V_ABV),
+    (0x0CCA, 0x0CCC, V_ABV),
+    (0x0CCD, 0x0CCD, H),
+    (0x0CD5, 0x0CD6, V_PST),
+    (0x0CDE, 0x0CDE, B),
+    (0x0CE0, 0x0CE1, B),
+    (0x0CE2, 0x0CE3, V_BLW),
+    (0x0CE6, 0x0CEF, B),
+    (0x0CF1, 0x0CF2, CS),
+    (0x0CF3, 0x0CF3, VM_PST),
+    (0x0D00, 0x0D01, VM_ABV),
+    (0x0D02, 0x0D03, VM_PST),
+    (0x0D04, 0x0D0C, B),
+    (0x0D0E, 0x0D10, B),
+    (0x0D12, 0x0D3A, B),
+    (0x0D3B, 0x0D3C, V_ABV),
+    (0x0D3D, 0x0D3D, B),
+    (0x0D3E, 0x0D40, V_PST),
+    (0x0D41, 0x0D44, V_BLW),
+    (0x0D46, 0x0D48, V_PRE),
+    (0x0D4A, 0x0D4C, V_PRE),
+    (0x0D4D, 0x0D4D, H),
+    (0x0D4E, 0x0D4E, R),
+    (0x0D57, 0x0D57, V_PST),
+    (0x0D5F, 0x0D61, B),
+    (0x0D62, 0x0D63, V_BLW),
+    (0x0D66, 0x0D6F, B),
+    (0x0D81, 0x0D81, VM_ABV),
+    (0x0D82, 0x0D83, VM_PST),
+    (0x0D85, 0x0D96, B),
+    (0x0D9A, 0x0DB1, B),
+    (0x0DB3, 0x0DBB, B),
+    (0x0DBD, 0x0DBD, B),
+    (0x0DC0, 0x0DC6, B),
+    (0x0DCA, 0x0DCA, HVM),
+    (0x0DCF, 0x0DD1, V_PST),
+    (0x0DD2, 0x0DD3, V_ABV),
+    (0x0DD4, 0x0DD4, V_BLW),
+    (0x0DD6, 0x0DD6, V_BLW),
+    (0x0DD8, 0x0DD8, V_PST),
+    (0x0DD9, 0x0DDE, V_PRE),
+    (0x0DDF, 0x0DDF, V_PST),
+    (0x0DE6, 0x0DEF, B),
+    (0x0DF2, 0x0DF3, V_PST),
+    (0x0F00, 0x0F01, B),
+    (0x0F04, 0x0F06, B),
+    (0x0F18, 0x0F19, V_BLW),
+    (0x0F20, 0x0F33, B),
+    (0x0F35, 0x0F35, FM_BLW),
+    (0x0F37, 0x0F37, FM_BLW),
+    (0x0F39, 0x0F39, CM_ABV),
+    (0x0F3E, 0x0F3E, V_PST),
+    (0x0F3F, 0x0F3F, V_PRE),
+    (0x0F40, 0x0F47, B),
+    (0x0F49, 0x0F6C, B),
+    (0x0F71, 0x0F71, CM_BLW),
+    (0x0F72, 0x0F72, V_BLW),
+    (0x0F73, 0x0F74, V_ABV),
+    (0x0F75, 0x0F75, V_BLW),
+    (0x0F76, 0x0F79, V_ABV),
+    (0x0F7A, 0x0F7D, V_BLW),
+    (0x0F7E, 0x0F7E, VM_ABV),
+    (0x0F80, 0x0F80, V_BLW),
+    (0x0F81, 0x0F81, V_ABV),
+    (0x0F82, 0x0F83, VM_ABV),
+    (0x0F84, 0x0F84, V_BLW),
+    (0x0F86, 0x0F87, VM_ABV),
+    (0x0F88, 0x0F8C, B),
+    (0x0F8D, 0x0F97, SUB),
+    (0x0F99, 0x0FBC, SUB),
+    (0x0FC6, 0x0FC6, FM_BLW),
+    (0x1000, 0x102A, B),
+    (0x102B, 0x102C, V_PST),
+    (0x102D, 0x102E, V_ABV),
+    (0x102F, 0x1030, V_BLW),
+    (0x1031, 0x1031, V_PRE),
+    (0x1032, 0x1035, V_ABV),
+    (0x1036, 0x1036, VM_ABV),
+    (0x1037, 0x1037, VM_BLW),
+    (0x1038, 0x1038, VM_PST),
+    (0x1039, 0x1039, IS),
+    (0x103A, 0x103A, V_ABV),
+    (0x103B, 0x103B, M_PST),
+    (0x103C, 0x103C, M_PRE),
+    (0x103D, 0x103E, M_BLW),
+    (0x103F, 0x1049, B),
+    (0x104B, 0x104B, GB),
+    (0x104E, 0x104E, GB),
+    (0x1050, 0x1055, B),
+    (0x1056, 0x1057, V_PST),
+    (0x1058, 0x1059, V_BLW),
+    (0x105A, 0x105D, B),
+    (0x105E, 0x1060, M_BLW),
+    (0x1061, 0x1061, B),
+    (0x1062, 0x1062, V_PST),
+    (0x1063, 0x1064, VM_PST),
+    (0x1065, 0x1066, B),
+    (0x1067, 0x1068, V_PST),
+    (0x1069, 0x106D, VM_PST),
+    (0x106E, 0x1070, B),
+    (0x1071, 0x1074, V_ABV),
+    (0x1075, 0x1081, B),
+    (0x1082, 0x1082, M_BLW),
+    (0x1083, 0x1083, V_PST),
+    (0x1084, 0x1084, V_PRE),
+    (0x1085, 0x1086, V_ABV),
+    (0x1087, 0x108C, VM_PST),
+    (0x108D, 0x108D, VM_BLW),
+    (0x108E, 0x108E, B),
+    (0x108F, 0x108F, VM_PST),
+    (0x1090, 0x1099, B),
+    (0x109A, 0x109B, VM_PST),
+    (0x109C, 0x109C, V_PST),
+    (0x109D, 0x109D, V_ABV),
+    (0x1700, 0x1711, B),
+    (0x1712, 0x1712, V_ABV),
+    (0x1713, 0x1714, V_BLW),
+    (0x1715, 0x1715, V_PST),
+    (0x171F, 0x1731, B),
+    (0x1732, 0x1732, V_ABV),
+    (0x1733, 0x1733, V_BLW),
+    (0x1734, 0x1734, V_PST),
+    (0x1740, 0x1751, B),
+    (0x1752, 0x1752, V_ABV),
+    (0x1753, 0x1753, V_BLW),
+    (0x1760, 0x176C, B),
+    (0x176E, 0x1770, B),
+    (0x1772, 0x1772, V_ABV),
+    (0x1773, 0x1773, V_BLW),
+    (0x1780, 0x17B3, B),
+    (0x17B4, 0x17B5, CGJ),
+    (0x17B6, 0x17B6, V_PST),
+    (0x17B7, 0x17BA, V_ABV),
+    (0x17BB, 0x17BD, V_BLW),
+    (0x17BE, 0x17C5, V_PRE),
+    (0x17C6, 0x17C6, VM_ABV),
+    (0x17C7, 0x17C7, VM_PST),
+    (0x17C8, 0x17C8, V_PST),
+    (0x17C9, 0x17CA, VM_ABV),
+    (0x17CB, 0x17CB, FM_ABV),
+    (0x17CC, 0x17CC, F_ABV),
+    (0x17CD, 0x17CD, CM_ABV),
+    (0x17CE, 0x17CE, FM_ABV),
+    (0x17CF, 0x17CF, VM_ABV),
+    (0x17D0, 0x17D0, FM_ABV),
+    (0x17D1, 0x17D1, V_ABV),
+    (0x17D2, 0x17D2, IS),
+    (0x17D3, 0x17D3, FM_ABV),
+    (0x17DC, 0x17DC, B),
+    (0x17DD, 0x17DD, FM_ABV),
+    (0x17E0, 0x17E9, B),
+    (0x1800, 0x1800, B),
+    (0x1807, 0x1807, B),
+    (0x180A, 0x180A, B),
+    (0x180B, 0x180D, CGJ),
+    (0x180E, 0x180E, WJ),
+    (0x180F, 0x180F, CGJ),
+    (0x1820, 0x1878, B),
+    (0x1880, 0x1884, GB),
+    (0x1885, 0x1886, CM_ABV),
+    (0x1887, 0x18A8, B),
+    (0x18A9, 0x18A9, CM_BLW),
+    (0x18AA, 0x18AA, B),
+    (0x1900, 0x1900, GB),
+    (0x1901, 0x191E, B),
+    (0x1920, 0x1921, V_ABV),
+    (0x1922, 0x1922, V_BLW),
+    (0x1923, 0x1924, V_PST),
+    (0x1925, 0x1928, V_ABV),
+    (0x1929, 0x192B, SUB),
+    (0x1930, 0x1931, F_PST),
+    (0x1932, 0x1932, VM_BLW),
+    (0x1933, 0x1938, F_PST),
+    (0x1939, 0x1939, F_BLW),
+    (0x193A, 0x193A, VM_ABV),
+    (0x193B, 0x193B, FM_BLW),
+    (0x1946, 0x196D, B),
+    (0x1970, 0x1974, B),
+    (0x1980, 0x19AB, B),
+    (0x19B0, 0x19C7, B),
+    (0x19C8, 0x19C9, VM_PST),
+    (0x19D0, 0x19DA, B),
+    (0x1A00, 0x1A16, B),
+    (0x1A17, 0x1A18, V_ABV),
+    (0x1A19, 0x1A19, V_PRE),
+    (0x1A1A, 0x1A1A, V_PST),
+    (0x1A1B, 0x1A1B, V_ABV),
+    (0x1A20, 0x1A54, B),
+    (0x1A55, 0x1A55, M_PRE),
+    (0x1A56, 0x1A56, M_BLW),
+    (0x1A57, 0x1A57, SUB),
+    (0x1A58, 0x1A59, F_ABV),
+    (0x1A5A, 0x1A5A, M_ABV),
+    (0x1A5B, 0x1A5E, SUB),
+    (0x1A60, 0x1A60, SK),
+    (0x1A61, 0x1A61, V_PST),
+    (0x1A62, 0x1A62, V_ABV),
+    (0x1A63, 0x1A64, V_PST),
+    (0x1A65, 0x1A68, V_ABV),
+    (0x1A69, 0x1A6A, V_BLW),
+    (0x1A6B, 0x1A6B, V_ABV),
+    (0x1A6C, 0x1A6C, V_BLW),
+    (0x1A6D, 0x1A6D, V_PST),
+    (0x1A6E, 0x1A72, V_PRE),
+    (0x1A73, 0x1A73, V_ABV),
+    (0x1A74, 0x1A79, VM_ABV),
+    (0x1A7A, 0x1A7A, V_ABV),
+    (0x1A7B, 0x1A7C, VM_ABV),
+    (0x1A7F, 0x1A7F, VM_BLW),
+    (0x1A80, 0x1A89, B),
+    (0x1A90, 0x1A99, B),
+    (0x1B00, 0x1B02, VM_ABV),
+    (0x1B03, 0x1B03, F_ABV),
+    (0x1B04, 0x1B04, VM_PST),
+    (0x1B05, 0x1B33, B),
+    (0x1B34, 0x1B34, CM_ABV),
+    (0x1B35, 0x1B35, V_PST),
+    (0x1B36, 0x1B37, V_ABV),
+    (0x1B38, 0x1B3B, V_BLW),
+    (0x1B3C, 0x1B3D, V_ABV),
+    (0x1B3E, 0x1B41, V_PRE),
+    (0x1B42, 0x1B43, V_ABV),
+    (0x1B44, 0x1B44, H),
+    (0x1B45, 0x1B4C, B),
+    (0x1B50, 0x1B59, B),
+    (0x1B6B, 0x1B73, SM_ABV),
+    (0x1B80, 0x1B80, VM_ABV),
+    (0x1B81, 0x1B81, F_ABV),
+    (0x1B82, 0x1B82, VM_PST),
+    (0x1B83, 0x1BA0, B),
+    (0x1BA1, 0x1BA3, SUB),
+    (0x1BA4, 0x1BA4, V_ABV),
+    (0x1BA5, 0x1BA5, V_BLW),
+    (0x1BA6, 0x1BA6, V_PRE),
+    (0x1BA7, 0x1BA7, V_PST),
+    (0x1BA8, 0x1BA9, V_ABV),
+    (0x1BAA, 0x1BAA, V_PST),
+    (0x1BAB, 0x1BAB, IS),
+    (0x1BAC, 0x1BAD, SUB),
+    (0x1BAE, 0x1BE5, B),
+    (0x1BE6, 0x1BE6, CM_ABV),
+    (0x1BE7, 0x1BE7, V_PST),
+    (0x1BE8, 0x1BE9, V_ABV),
+    (0x1BEA, 0x1BEC, V_PST),
+    (0x1BED, 0x1BED, V_ABV),
+    (0x1BEE, 0x1BEE, V_PST),
+    (0x1BEF, 0x1BEF, V_ABV),
+    (0x1BF0, 0x1BF1, F_ABV),
+    (0x1BF2, 0x1BF3, CM_BLW),
+    (0x1C00, 0x1C23, B),
+    (0x1C24, 0x1C25, SUB),
+    (0x1C26, 0x1C26, V_PST),
+    (0x1C27, 0x1C29, V_PRE),
+    (0x1C2A, 0x1C2B, V_PST),
+    (0x1C2C, 0x1C2C, V_BLW),
+    (0x1C2D, 0x1C33, F_ABV),
+    (0x1C34, 0x1C35, VM_PRE),
+    (0x1C36, 0x1C36, FM_ABV),
+    (0x1C37, 0x1C37, CM_BLW),
+    (0x1C40, 0x1C49, B),
+    (0x1C4D, 0x1C4F, B),
+    (0x1CD0, 0x1CD2, VM_ABV),
+    (0x1CD4, 0x1CD9, VM_BLW),
+    (0x1CDA, 0x1CDB, VM_ABV),
+    (0x1CDC, 0x1CDF, VM_BLW),
+    (0x1CE0, 0x1CE0, VM_ABV),
+    (0x1CE1, 0x1CE1, VM_PST),
+    (0x1CE2, 0x1CE8, VM_BLW),
+    (0x1CED, 0x1CED, VM_BLW),
+    (0x1CF4, 0x1CF4, VM_ABV),
+    (0x1CF5, 0x1CF6, CS),
+    (0x1CF7, 0x1CF7, VM_PST),
+    (0x1CF8, 0x1CF9, VM_ABV),
+    (0x1CFA, 0x1CFA, GB),
+    (0x1DFB, 0x1DFB, FM_ABV),
+    (0x200B, 0x200B, WJ),
+    (0x200C, 0x200C, ZWNJ),
+    (0x200D, 0x200D, CGJ),
+    (0x200E, 0x200F, WJ),
+    (0x2010, 0x2014, GB),
+    (0x202A, 0x202E, WJ),
+    (0x2060, 0x206F, WJ),
+    (0x2074, 0x2074, FM_PST),
+    (0x2082, 0x2084, FM_PST),
+    (0x20F0, 0x20F0, VM_ABV),
+    (0x25CC, 0x25CC, B),
+    (0x27E6, 0x27E6, SB),
+    (0x27E7, 0x27E7, SE),
+    (0x27E8, 0x27E8, SB),
+    (0x27E9, 0x27E9, SE),
+    (0x2D30, 0x2D67, B),
+    (0x2D6F, 0x2D6F, B),
+    (0x2D7F, 0x2D7F, H),
+    (0x2E22, 0x2E22, SB),
+    (0x2E23, 0x2E23, SE),
+    (0x2E24, 0x2E24, SB),
+    (0x2E25, 0x2E25, SE),
+    (0xA800, 0xA801, B),
+    (0xA802, 0xA802, V_ABV),
+    (0xA803, 0xA805, B),
+    (0xA806, 0xA806, H),
+    (0xA807, 0xA80A, B),
+    (0xA80B, 0xA80B, VM_ABV),
+    (0xA80C, 0xA822, B),
+    (0xA823, 0xA824, V_PST),
+    (0xA825, 0xA825, V_BLW),
+    (0xA826, 0xA826, V_ABV),
+    (0xA827, 0xA827, V_PST),
+    (0xA82C, 0xA82C, V_BLW),
+    (0xA840, 0xA873, B),
+    (0xA880, 0xA881, VM_PST),
+    (0xA882, 0xA8B3, B),
+    (0xA8B4, 0xA8B4, M_PST),
+    (0xA8B5, 0xA8C3, V_PST),
+    (0xA8C4, 0xA8C4, H),
+    (0xA8C5, 0xA8C5, VM_ABV),
+    (0xA8D0, 0xA8D9, B),
+    (0xA8E0, 0xA8F1, VM_ABV),
+    (0xA8F2, 0xA8F3, B),
+    (0xA8FE, 0xA8FE, B),
+    (0xA8FF, 0xA8FF, V_ABV),
+    (0xA900, 0xA925, B),
+    (0xA926, 0xA92A, V_ABV),
+    (0xA92B, 0xA92D, VM_BLW),
+    (0xA930, 0xA946, B),
+    (0xA947, 0xA949, V_BLW),
+    (0xA94A, 0xA94A, V_ABV),
+    (0xA94B, 0xA94E, V_BLW),
+    (0xA94F, 0xA951, F_ABV),
+    (0xA952, 0xA952, F_PST),
+    (0xA953, 0xA953, V_PST),
+    (0xA980, 0xA981, VM_ABV),
+    (0xA982, 0xA982, F_ABV),
+    (0xA983, 0xA983, VM_PST),
+    (0xA984, 0xA9B2, B),
+    (0xA9B3, 0xA9B3, CM_ABV),
+    (0xA9B4, 0xA9B5, V_PST),
+    (0xA9B6, 0xA9B7, V_ABV),
+    (0xA9B8, 0xA9B9, V_BLW),
+    (0xA9BA, 0xA9BB, V_PRE),
+    (0xA9BC, 0xA9BC, V_ABV),
+    (0xA9BD, 0xA9BD, M_BLW),
+    (0xA9BE, 0xA9BE, M_PST),
+    (0xA9BF, 0xA9BF, M_BLW),
+    (0xA9C0, 0xA9C0, H),
+    (0xA9D0, 0xA9D9, B),
+    (0xA9E0, 0xA9E4, B),
+    (0xA9E5, 0xA9E5, V_ABV),
+    (0xA9E7, 0xA9FE, B),
+    (0xAA00, 0xAA28, B),
+    (0xAA29, 0xAA29, VM_ABV),
+    (0xAA2A, 0xAA2C, V_ABV),
+    (0xAA2D, 0xAA2D, V_BLW),
+    (0xAA2E, 0xAA2E, V_ABV),
+    (0xAA2F, 0xAA30, V_PRE),
+    (0xAA31, 0xAA31, V_ABV),
+    (0xAA32, 0xAA32, V_BLW),
+    (0xAA33, 0xAA33, M_PST),
+    (0xAA34, 0xAA34, M_PRE),
+    (0xAA35, 0xAA35, M_ABV),
+    (0xAA36, 0xAA36, M_BLW),
+    (0xAA40, 0xAA42, B),
+    (0xAA43, 0xAA43, F_ABV),
+    (0xAA44, 0xAA4B, B),
+    (0xAA4C, 0xAA4C, F_ABV),
+    (0xAA4D, 0xAA4D, F_PST),
+    (0xAA50, 0xAA59, B),
+    (0xAA60, 0xAA6F, B),
+    (0xAA71, 0xAA73, B),
+    (0xAA74, 0xAA76, GB),
+    (0xAA7A, 0xAA7A, B),
+    (0xAA7B, 0xAA7B, VM_PST),
+    (0xAA7C, 0xAA7C, VM_ABV),
+    (0xAA7D, 0xAA7D, VM_PST),
+    (0xAA7E, 0xAAAF, B),
+    (0xAAB0, 0xAAB0, V_ABV),
+    (0xAAB1, 0xAAB1, B),
+    (0xAAB2, 0xAAB3, V_ABV),
+    (0xAAB4, 0xAAB4, V_BLW),
+    (0xAAB5, 0xAAB6, B),
+    (0xAAB7, 0xAAB8, V_ABV),
+    (0xAAB9, 0xAABD, B),
+    (0xAABE, 0xAABE, V_ABV),
+    (0xAABF, 0xAABF, VM_ABV),
+    (0xAAC0, 0xAAC0, B),
+    (0xAAC1, 0xAAC1, VM_ABV),
+    (0xAAC2, 0xAAC2, B),
+    (0xAAE0, 0xAAEA, B),
+    (0xAAEB, 0xAAEB, V_PRE),
+    (0xAAEC, 0xAAEC, V_BLW),
+    (0xAAED, 0xAAED, V_ABV),
+    (0xAAEE, 0xAAEE, V_PRE),
+    (0xAAEF, 0xAAEF, V_PST),
+    (0xAAF5, 0xAAF5, VM_PST),
+    (0xAAF6, 0xAAF6, IS),
+    (0xABC0, 0xABE2, B),
+    (0xABE3, 0xABE4, V_PST),
+    (0xABE5, 0xABE5, V_ABV),
+    (0xABE6, 0xABE7, V_PST),
+    (0xABE8, 0xABE8, V_BLW),
+    (0xABE9, 0xABEA, V_PST),
+    (0xABEC, 0xABEC, VM_PST),
+    (0xABED, 0xABED, V_BLW),
+    (0xABF0, 0xABF9, B),
+    (0xFE00, 0xFE0F, CGJ),
+    (0xFEFF, 0xFEFF, WJ),
+    (0xFFF0, 0xFFF8, WJ),
+    (0x10570, 0x1057A, B),
+    (0x1057C, 0x1058A, B),
+    (0x1058C, 0x10592, B),
+    (0x10594, 0x10595, B),
+    (0x10597, 0x105A1, B),
+    (0x105A3, 0x105B1, B),
+    (0x105B3, 0x105B9, B),
+    (0x105BB, 0x105BC, B),
+    (0x10A00, 0x10A00, B),
+    (0x10A01, 0x10A03, V_BLW),
+    (0x10A05, 0x10A05, V_ABV),
+    (0x10A06, 0x10A06, V_BLW),
+    (0x10A0C, 0x10A0C, V_PST),
+    (0x10A0D, 0x10A0E, VM_BLW),
+    (0x10A0F, 0x10A0F, VM_ABV),
+    (0x10A10, 0x10A13, B),
+    (0x10A15, 0x10A17, B),
+    (0x10A19, 0x10A35, B),
+    (0x10A38, 0x10A3A, CM_BLW),
+    (0x10A3F, 0x10A3F, IS),
+    (0x10A40, 0x10A48, B),
+    (0x10AC0, 0x10AC7, B),
+    (0x10AC9, 0x10AE4, B),
+    (0x10AE5, 0x10AE6, CM_BLW),
+    (0x10AEB, 0x10AEF, B),
+    (0x10B80, 0x10B91, B),
+    (0x10BA9, 0x10BAE, B),
+    (0x10D00, 0x10D23, B),
+    (0x10D24, 0x10D26, VM_ABV),
+    (0x10D27, 0x10D27, CM_ABV),
+    (0x10D30, 0x10D39, B),
+    (0x10D4A, 0x10D65, B),
+    (0x10D69, 0x10D6D, V_ABV),
+    (0x10D6F, 0x10D85, B),
+    (0x10E80, 0x10EA9, B),
+    (0x10EAB, 0x10EAC, V_ABV),
+    (0x10EB0, 0x10EB1, B),
+    (0x10F30, 0x10F45, B),
+    (0x10F46, 0x10F50, VM_BLW),
+    (0x10F51, 0x10F54, B),
+    (0x10F70, 0x10F81, B),
+    (0x10F82, 0x10F85, CM_BLW),
+    (0x10FB0, 0x10FB0, B),
+    (0x10FB2, 0x10FB6, B),
+    (0x10FB8, 0x10FBF, B),
+    (0x10FC1, 0x10FC4, B),
+    (0x10FC9, 0x10FCB, B),
+    (0x11000, 0x11000, VM_PST),
+    (0x11001, 0x11001, VM_ABV),
+    (0x11002, 0x11002, VM_PST),
+    (0x11003, 0x11004, CS),
+    (0x11005, 0x11037, B),
+    (0x11038, 0x1103B, V_ABV),
+    (0x1103C, 0x11041, V_BLW),
+    (0x11042, 0x11045, V_ABV),
+    (0x11046, 0x11046, H),
+    (0x11052, 0x11065, N),
+    (0x11066, 0x1106F, B),
+    (0x11070, 0x11070, V_ABV),
+    (0x11071, 0x11072, B),
+    (0x11073, 0x11074, V_ABV),
+    (0x11075, 0x11075, B),
+    (0x1107F, 0x1107F, HN),
+    (0x11080, 0x11081, VM_ABV),
+    (0x11082, 0x11082, VM_PST),
+    (0x11083, 0x110AF, B),
+    (0x110B0, 0x110B0, V_PST),
+    (0x110B1, 0x110B1, V_PRE),
+    (0x110B2, 0x110B2, V_PST),
+    (0x110B3, 0x110B4, V_BLW),
+    (0x110B5, 0x110B6, V_ABV),
+    (0x110B7, 0x110B8, V_PST),
+    (0x110B9, 0x110B9, H),
+    (0x110BA, 0x110BA, CM_BLW),
+    (0x110C2, 0x110C2, V_BLW),
+    (0x11100, 0x11102, VM_ABV),
+    (0x11103, 0x11126, B),
+    (0x11127, 0x11129, V_BLW),
+    (0x1112A, 0x1112B, V_ABV),
+    (0x1112C, 0x1112C, V_PRE),
+    (0x1112D, 0x1112D, V_BLW),
+    (0x1112E, 0x1112F, V_ABV),
+    (0x11130, 0x11130, V_BLW),
+    (0x11131, 0x11132, V_ABV),
+    (0x11133, 0x11133, IS),
+    (0x11134, 0x11134, CM_ABV),
+    (0x11136, 0x1113F, B),
+    (0x11144, 0x11144, B),
+    (0x11145, 0x11146, V_PST),
+    (0x11147, 0x11147, B),
+    (0x11150, 0x11172, B),
+    (0x11173, 0x11173, CM_BLW),
+    (0x11180, 0x11181, VM_ABV),
+    (0x11182, 0x11182, VM_PST),
+    (0x11183, 0x111B2, B),
+    (0x111B3, 0x111B3, V_PST),
+    (0x111B4, 0x111B4, V_PRE),
+    (0x111B5, 0x111B5, V_PST),
+    (0x111B6, 0x111BB, V_BLW),
+    (0x111BC, 0x111BF, V_ABV),
+    (0x111C0, 0x111C0, H),
+    (0x111C1, 0x111C1, B),
+    (0x111C2, 0x111C3, R),
+    (0x111C9, 0x111C9, FM_BLW),
+    (0x111CA, 0x111CA, CM_BLW),
+    (0x111CB, 0x111CB, V_ABV),
+    (0x111CC, 0x111CC, V_BLW),
+    (0x111CE, 0x111CE, V_PRE),
+    (0x111CF, 0x111CF, VM_ABV),
+    (0x111D0, 0x111DA, B),
+    (0x111E1, 0x111F4, B),
+    (0x11200, 0x11211, B),
+    (0x11213, 0x1122B, B),
+    (0x1122C, 0x1122E, V_PST),
+    (0x1122F, 0x1122F, V_BLW),
+    (0x11230, 0x11233, V_ABV),
+    (0x11234, 0x11234, VM_ABV),
+    (0x11235, 0x11235, H),
+    (0x11236, 0x11237, CM_ABV),
+    (0x1123E, 0x1123E, VM_ABV),
+    (0x1123F, 0x11240, B),
+    (0x11241, 0x11241, V_BLW),
+    (0x11280, 0x11286, B),
+    (0x11288, 0x11288, B),
+    (0x1128A, 0x1128D, B),
+    (0x1128F, 0x1129D, B),
+    (0x1129F, 0x112A8, B),
+    (0x112B0, 0x112DE, B),
+    (0x112DF, 0x112DF, VM_ABV),
+    (0x112E0, 0x112E0, V_PST),
+    (0x112E1, 0x112E1, V_PRE),
+    (0x112E2, 0x112E2, V_PST),
+    (0x112E3, 0x112E4, V_BLW),
+    (0x112E5, 0x112E8, V_ABV),
+    (0x112E9, 0x112E9, CM_BLW),
+    (0x112EA, 0x112EA, V_BLW),
+    (0x112F0, 0x112F9, B),
+    (0x11300, 0x11303, VM_ABV),
+    (0x11305, 0x1130C, B),
+    (0x1130F, 0x11310, B),
+    (0x11313, 0x11328, B),
+    (0x1132A, 0x11330, B),
+    (0x11332, 0x11333, B),
+    (0x11335, 0x11339, B),
+    (0x1133B, 0x1133C, CM_BLW),
+    (0x1133D, 0x1133D, B),
+    (0x1133E, 0x1133F, V_PST),
+    (0x11340, 0x11340, V_ABV),
+    (0x11341, 0x11344, V_PST),
+    (0x11347, 0x11348, V_PRE),
+    (0x1134B, 0x1134C, V_PRE),
+    (0x1134D, 0x1134D, H),
+    (0x11357, 0x11357, V_PST),
+    (0x1135E, 0x11361, B),
+    (0x11362, 0x11363, V_PST),
+    (0x11366, 0x1136C, VM_ABV),
+    (0x11370, 0x11374, VM_ABV),
+    (0x11380, 0x11389, B),
+    (0x1138B, 0x1138B, B),
+    (0x1138E, 0x1138E, B),
+    (0x11390, 0x113B5, B),
+    (0x113B7, 0x113B7, B),
+    (0x113B8, 0x113B8, V_PST),
+    (0x113B9, 0x113BA, V_ABV),
+    (0x113BB, 0x113C0, V_BLW),
+    (0x113C2, 0x113C2, V_PRE),
+    (0x113C5, 0x113C5, V_PRE),
+    (0x113C7, 0x113C8, V_PRE),
+    (0x113C9, 0x113C9, V_PST),
+    (0x113CA, 0x113CA, VM_PST),
+    (0x113CC, 0x113CD, VM_PST),
+    (0x113CE, 0x113CE, VM_ABV),
+    (0x113CF, 0x113CF, CM_BLW),
+    (0x113D0, 0x113D0, IS),
+    (0x113D1, 0x113D1, R),
+    (0x113D2, 0x113D2, CM_BLW),
+    (0x113E1, 0x113E1, VM_ABV),
+    (0x113E2, 0x113E2, VM_BLW),
+    (0x11400, 0x11434, B),
+    (0x11435, 0x11435, V_PST),
+    (0x11436, 0x11436, V_PRE),
+    (0x11437, 0x11437, V_PST),
+    (0x11438, 0x1143D, V_BLW),
+    (0x1143E, 0x1143F, V_ABV),
+    (0x11440, 0x11441, V_PST),
+    (0x11442, 0x11442, H),
+    (0x11443, 0x11444, VM_ABV),
+    (0x11445, 0x11445, VM_PST),
+    (0x11446, 0x11446, CM_BLW),
+    (0x11447, 0x11447, B),
+    (0x11450, 0x11459, B),
+    (0x1145E, 0x1145E, FM_ABV),
+    (0x1145F, 0x1145F, B),
+    (0x11460, 0x11461, CS),
+    (0x11481, 0x114AF, B),
+    (0x114B0, 0x114B0, V_PST),
+    (0x114B1, 0x114B1, V_PRE),
+    (0x114B2, 0x114B2, V_PST),
+    (0x114B3, 0x114B8, V_BLW),
+    (0x114B9, 0x114B9, V_PRE),
+    (0x114BA, 0x114BA, V_ABV),
+    (0x114BB, 0x114BC, V_PRE),
+    (0x114BD, 0x114BD, V_PST),
+    (0x114BE, 0x114BE, V_PRE),
+    (0x114BF, 0x114C1, VM_ABV),
+    (0x114C2, 0x114C2, H),
+    (0x114C3, 0x114C3, CM_BLW),
+    (0x114C4, 0x114C4, B),
+    (0x114D0, 0x114D9, B),
+    (0x11580, 0x115AE, B),
+    (0x115AF, 0x115AF, V_PST),
+    (0x115B0, 0x115B0, V_PRE),
+    (0x115B1, 0x115B1, V_PST),
+    (0x115B2, 0x115B5, V_BLW),
+    (0x115B8, 0x115BB, V_PRE),
+    (0x115BC, 0x115BD, VM_ABV),
+    (0x115BE, 0x115BE, VM_PST),
+    (0x115BF, 0x115BF, H),
+    (0x115C0, 0x115C0, CM_BLW),
+    (0x115D8, 0x115DB, B),
+    (0x115DC, 0x115DD, V_BLW),
+    (0x11600, 0x1162F, B),
+    (0x11630, 0x11632, V_PST),
+    (0x11633, 0x11638, V_BLW),
+    (0x11639, 0x1163A, V_ABV),
+    (0x1163B, 0x1163C, V_PST),
+    (0x1163D, 0x1163D, VM_ABV),
+    (0x1163E, 0x1163E, VM_PST),
+    (0x1163F, 0x1163F, H),
+    (0x11640, 0x11640, V_ABV),
+    (0x11650, 0x11659, B),
+    (0x11680, 0x116AA, B),
+    (0x116AB, 0x116AB, VM_ABV),
+    (0x116AC, 0x116AC, VM_PST),
+    (0x116AD, 0x116AD, V_ABV),
+    (0x116AE, 0x116AE, V_PRE),
+    (0x116AF, 0x116AF, V_PST),
+    (0x116B0, 0x116B1, V_BLW),
+    (0x116B2, 0x116B5, V_ABV),
+    (0x116B6, 0x116B6, H),
+    (0x116B7, 0x116B7, CM_BLW),
+    (0x116B8, 0x116B8, B),
+    (0x116C0, 0x116C9, B),
+    (0x116D0, 0x116E3, B),
+    (0x11700, 0x1171A, B),
+    (0x1171D, 0x1171D, M_BLW),
+    (0x1171E, 0x1171E, M_PRE),
+    (0x1171F, 0x1171F, M_ABV),
+    (0x11720, 0x11721, V_PST),
+    (0x11722, 0x11723, V_ABV),
+    (0x11724, 0x11725, V_BLW),
+    (0x11726, 0x11726, V_PRE),
+    (0x11727, 0x11727, V_ABV),
+    (0x11728, 0x11728, V_BLW),
+    (0x11729, 0x1172B, V_ABV),
+    (0x11730, 0x1173B, B),
+    (0x11740, 0x11746, B),
+    (0x11800, 0x1182B, B),
+    (0x1182C, 0x1182C, V_PST),
+    (0x1182D, 0x1182D, V_PRE),
+    (0x1182E, 0x1182E, V_PST),
+    (0x1182F, 0x11832, V_BLW),
+    (0x11833, 0x11836, V_ABV),
+    (0x11837, 0x11837, VM_ABV),
+    (0x11838, 0x11838, VM_PST),
+    (0x11839, 0x11839, H),
+    (0x1183A, 0x1183A, CM_BLW),
+    (0x11900, 0x11906, B),
+    (0x11909, 0x11909, B),
+    (0x1190C, 0x11913, B),
+    (0x11915, 0x11916, B),
+    (0x11918, 0x1192F, B),
+    (0x11930, 0x11934, V_PST),
+    (0x11935, 0x11935, V_PRE),
+    (0x11937, 0x11938, V_PRE),
+    (0x1193B, 0x1193C, VM_ABV),
+    (0x1193D, 0x1193D, V_PST),
+    (0x1193E, 0x1193E, IS),
+    (0x1193F, 0x1193F, R),
+    (0x11940, 0x11940, M_PST),
+    (0x11941, 0x11941, R),
+    (0x11942, 0x11942, M_PST),
+    (0x11943, 0x11943, CM_BLW),
+    (0x11950, 0x11959, B),
+    (0x119A0, 0x119A7, B),
+    (0x119AA, 0x119D0, B),
+    (0x119D1, 0x119D1, V_PST),
+    (0x119D2, 0x119D2, V_PRE),
+    (0x119D3, 0x119D3, V_PST),
+    (0x119D4, 0x119D7, V_BLW),
+    (0x119DA, 0x119DB, V_ABV),
+    (0x119DC, 0x119DD, V_PST),
+    (0x119DE, 0x119DF, VM_PST),
+    (0x119E0, 0x119E0, H),
+    (0x119E1, 0x119E1, B),
+    (0x119E4, 0x119E4, V_PRE),
+    (0x11A00, 0x11A00, B),
+    (0x11A01, 0x11A01, V_ABV),
+    (0x11A02, 0x11A03, V_BLW),
+    (0x11A04, 0x11A09, V_ABV),
+    (0x11A0A, 0x11A0A, V_BLW),
+    (0x11A0B, 0x11A32, B),
+    (0x11A33, 0x11A33, FM_BLW),
+    (0x11A34, 0x11A34, V_BLW),
+    (0x11A35, 0x11A38, VM_ABV),
+    (0x11A39, 0x11A39, VM_PST),
+    (0x11A3A, 0x11A3A, CS),
+    (0x11A3B, 0x11A3E, SUB),
+    (0x11A3F, 0x11A3F, GB),
+    (0x11A45, 0x11A45, GB),
+    (0x11A47, 0x11A47, IS),
+    (0x11A50, 0x11A50, B),
+    (0x11A51, 0x11A51, V_ABV),
+    (0x11A52, 0x11A53, V_BLW),
+    (0x11A54, 0x11A56, V_ABV),
+    (0x11A57, 0x11A58, V_PST),
+    (0x11A59, 0x11A5B, V_BLW),
+    (0x11A5C, 0x11A83, B),
+    (0x11A84, 0x11A89, R),
+    (0x11A8A, 0x11A95, F_BLW),
+    (0x11A96, 0x11A96, VM_ABV),
+    (0x11A97, 0x11A97, VM_PST),
+    (0x11A98, 0x11A98, CM_ABV),
+    (0x11A99, 0x11A99, IS),
+    (0x11A9D, 0x11A9D, B),
+    (0x11C00, 0x11C08, B),
+    (0x11C0A, 0x11C2E, B),
+    (0x11C2F, 0x11C2F, V_PST),
+    (0x11C30, 0x11C31, V_ABV),
+    (0x11C32, 0x11C36, V_BLW),
+    (0x11C38, 0x11C3B, V_ABV),
+    (0x11C3C, 0x11C3D, VM_ABV),
+    (0x11C3E, 0x11C3E, VM_PST),
+    (0x11C3F, 0x11C3F, H),
+    (0x11C40, 0x11C40, B),
+    (0x11C50, 0x11C6C, B),
+    (0x11C72, 0x11C8F, B),
+    (0x11C92, 0x11CA7, SUB),
+    (0x11CA9, 0x11CAF, SUB),
+    (0x11CB0, 0x11CB0, V_BLW),
+    (0x11CB1, 0x11CB1, V_PRE),
+    (0x11CB2, 0x11CB2, V_BLW),
+    (0x11CB3, 0x11CB3, V_ABV),
+    (0x11CB4, 0x11CB4, V_PST),
+    (0x11CB5, 0x11CB6, VM_ABV),
+    (0x11D00, 0x11D06, B),
+    (0x11D08, 0x11D09, B),
+    (0x11D0B, 0x11D30, B),
+    (0x11D31, 0x11D35, V_ABV),
+    (0x11D36, 0x11D36, V_BLW),
+    (0x11D3A, 0x11D3A, V_ABV),
+    (0x11D3C, 0x11D3D, V_ABV),
+    (0x11D3F, 0x11D3F, V_ABV),
+    (0x11D40, 0x11D41, VM_ABV),
+    (0x11D42, 0x11D42, CM_BLW),
+    (0x11D43, 0x11D43, V_ABV),
+    (0x11D44, 0x11D44, V_BLW),
+    (0x11D45, 0x11D45, IS),
+    (0x11D46, 0x11D46, R),
+    (0x11D47, 0x11D47, M_BLW),
+    (0x11D50, 0x11D59, B),
+    (0x11D60, 0x11D65, B),
+    (0x11D67, 0x11D68, B),
+    (0x11D6A, 0x11D89, B),
+    (0x11D8A, 0x11D8E, V_PST),
+    (0x11D90, 0x11D91, V_ABV),
+    (0x11D93, 0x11D94, V_PST),
+    (0x11D95, 0x11D95, VM_ABV),
+    (0x11D96, 0x11D96, VM_PST),
+    (0x11D97, 0x11D97, IS),
+    (0x11DA0, 0x11DA9, B),
+    (0x11EE0, 0x11EF1, B),
+    (0x11EF2, 0x11EF2, GB),
+    (0x11EF3, 0x11EF3, V_ABV),
+    (0x11EF4, 0x11EF4, V_BLW),
+    (0x11EF5, 0x11EF5, V_PRE),
+    (0x11EF6, 0x11EF6, V_PST),
+    (0x11F00, 0x11F01, VM_ABV),
+    (0x11F02, 0x11F02, R),
+    (0x11F03, 0x11F03, VM_PST),
+    (0x11F04, 0x11F10, B),
+    (0x11F12, 0x11F33, B),
+    (0x11F34, 0x11F35, V_PST),
+    (0x11F36, 0x11F37, V_ABV),
+    (0x11F38, 0x11F3A, V_BLW),
+    (0x11F3E, 0x11F3F, V_PRE),
+    (0x11F40, 0x11F40, V_ABV),
+    (0x11F41, 0x11F41, V_PST),
+    (0x11F42, 0x11F42, IS),
+    (0x11F50, 0x11F59, B),
+    (0x11F5A, 0x11F5A, CM_ABV),
+    (0x13000, 0x1342F, G),
+    (0x13430, 0x13436, J),
+    (0x13437, 0x13437, SB),
+    (0x13438, 0x13438, SE),
+    (0x13439, 0x1343B, J),
+    (0x1343C, 0x1343F, G),
+    (0x13440, 0x13440, HR),
+    (0x13441, 0x13446, G),
+    (0x13447, 0x13455, HM),
+    (0x13460, 0x143FA, G),
+    (0x16100, 0x1611D, B),
+    (0x1611E, 0x16129, V_ABV),
+    (0x1612A, 0x1612B, M_PRE),
+    (0x1612C, 0x1612C, M_PST),
+    (0x1612D, 0x1612D, VM_ABV),
+    (0x1612E, 0x1612E, M_BLW),
+    (0x1612F, 0x1612F, V_BLW),
+    (0x16130, 0x16139, B),
+    (0x16AC0, 0x16AC9, B),
+    (0x16B00, 0x16B2F, B),
+    (0x16B30, 0x16B36, VM_ABV),
+    (0x16D40, 0x16D42, VM_PST),
+    (0x16D43, 0x16D6A, B),
+    (0x16D6B, 0x16D6C, V_PST),
+    (0x16D70, 0x16D79, B),
+    (0x16F00, 0x16F4A, B),
+    (0x16F4F, 0x16F4F, CM_BLW),
+    (0x16F51, 0x16F87, V_BLW),
+    (0x16F8F, 0x16F92, VM_BLW),
+    (0x16FE4, 0x16FE4, B),
+    (0x18B00, 0x18CD5, B),
+    (0x18CFF, 0x18CFF, B),
+    (0x1BC00, 0x1BC6A, B),
+    (0x1BC70, 0x1BC7C, B),
+    (0x1BC80, 0x1BC88, B),
+    (0x1BC90, 0x1BC99, B),
+    (0x1BC9D, 0x1BC9E, CM_BLW),
+    (0x1D173, 0x1D17A, WJ),
+    (0x1E100, 0x1E12C, B),
+    (0x1E130, 0x1E136, VM_ABV),
+    (0x1E137, 0x1E13D, B),
+    (0x1E140, 0x1E149, B),
+    (0x1E14E, 0x1E14F, B),
+    (0x1E290, 0x1E2AD, B),
+    (0x1E2AE, 0x1E2AE, VM_ABV),
+    (0x1E2C0, 0x1E2EB, B),
+    (0x1E2EC, 0x1E2EF, VM_ABV),
+    (0x1E2F0, 0x1E2F9, B),
+    (0x1E4D0, 0x1E4EB, B),
+    (0x1E4EC, 0x1E4EF, V_ABV),
+    (0x1E4F0, 0x1E4F9, B),
+    (0x1E5D0, 0x1E5ED, B),
+    (0x1E5EE, 0x1E5EF, V_BLW),
+    (0x1E5F0, 0x1E5FA, B),
+    (0x1E900, 0x1E943, B),
+    (0x1E944, 0x1E94A, CM_ABV),
+    (0x1E94B, 0x1E94B, B),
+    (0x1E950, 0x1E959, B),
+    (0xE0000, 0xE00FF, WJ),
+    (0xE0100, 0xE01EF, CGJ),
+    (0xE01F0, 0xE0FFF, WJ),
+];
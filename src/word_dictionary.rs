@@ -0,0 +1,242 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Splits Thai, Lao, Khmer and Burmese text into words, since those
+// scripts don't put spaces between words and UAX #14 gives no breaks
+// inside them.  Words are found with a dictionary, choosing the split
+// that leaves the fewest characters not in any word, then uses the
+// fewest words, then has the most even word lengths.  Characters no
+// word covers are kept with the word before them (or after them, at
+// the start of a run), as ICU does, and a Thai or Lao leading vowel is
+// never split from its consonant.
+//
+// The dictionaries are ICU's, as built into ICU4X's icu_segmenter_data
+// 2.3.0, and are under the Unicode License (LICENSE-UNICODE).  They're
+// embedded from src/dictionaries as minimal automata, which
+// tools/make_word_dictionaries.py builds from that crate's data and
+// whose format it describes, and are read in place.
+
+use ot_parse::TableData;
+use unicode_data;
+
+static THAI: WordDictionary = WordDictionary { data: include_bytes!("dictionaries/thai.dict") };
+static LAO: WordDictionary = WordDictionary { data: include_bytes!("dictionaries/lao.dict") };
+static KHMER: WordDictionary = WordDictionary { data: include_bytes!("dictionaries/khmer.dict") };
+static BURMESE: WordDictionary = WordDictionary { data: include_bytes!("dictionaries/burmese.dict") };
+
+// Bits of an edge besides the index of its character (the low byte)
+// and of the first edge of the state it leads to.
+const LAST_EDGE: u32 = 1 << 31;
+const ENDS_WORD: u32 = 1 << 30;
+
+struct WordDictionary {
+    data: &'static [u8],
+}
+
+impl WordDictionary {
+    fn table(&self) -> TableData<'static> {
+        TableData::new(self.data)
+    }
+
+    // The index of `ch` in the sorted list of characters the words use.
+    fn char_index(&self, ch: char) -> Option<u32> {
+        let table = self.table();
+        let (mut lo, mut hi) = (0, table.u16(0).unwrap_or(0) as usize);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let c = table.u16(2 + mid * 2).unwrap_or(0) as u32;
+            if (ch as u32) < c {
+                hi = mid;
+            } else if (ch as u32) > c {
+                lo = mid + 1;
+            } else {
+                return Some(mid as u32);
+            }
+        }
+        None
+    }
+
+    fn edge(&self, index: usize) -> u32 {
+        let table = self.table();
+        let edges_start = 2 + table.u16(0).unwrap_or(0) as usize * 2;
+        table.u32(edges_start + index * 4).unwrap_or(LAST_EDGE)
+    }
+
+    // The edge for character `char_index` out of the state whose edges
+    // start at `state`; they're sorted by character.
+    fn find_edge(&self, state: usize, char_index: u32) -> Option<u32> {
+        let mut index = state;
+        loop {
+            let edge = self.edge(index);
+            if edge & 0xFF == char_index {
+                return Some(edge);
+            }
+            if edge & 0xFF > char_index || edge & LAST_EDGE != 0 {
+                return None;
+            }
+            index += 1;
+        }
+    }
+
+    // The lengths of the words `text` starts with, shortest first.
+    fn prefix_lengths(&self, text: &[char]) -> Vec<usize> {
+        let mut lengths = vec![];
+        // The start state's edges come first.
+        let mut state = 0;
+        for (i, &ch) in text.iter().enumerate() {
+            let edge = match self.char_index(ch).and_then(|c| self.find_edge(state, c)) {
+                Some(edge) => edge,
+                None => break,
+            };
+            if edge & ENDS_WORD != 0 {
+                lengths.push(i + 1);
+            }
+            // 0 for a state without edges.
+            state = ((edge >> 8) & 0x3F_FFFF) as usize;
+            if state == 0 {
+                break;
+            }
+        }
+        lengths
+    }
+
+    // Where `text` can be split into words, as character indices
+    // between 0 and the length.
+    fn word_boundaries(&self, text: &[char]) -> Vec<usize> {
+        let len = text.len();
+        // A split can't leave a mark apart from its base, or a leading
+        // vowel apart from the consonant after it.
+        let is_boundary = |i: usize| {
+            i == len || (!unicode_data::is_complex_context_mark(text[i] as u32) && !is_leading_vowel(text[i - 1]))
+        };
+
+        // For each prefix of the text, the best split found for it as
+        // (characters not in a word, words, sum of the squares of the
+        // parts' lengths), where its last part starts, and whether that
+        // part is a word.
+        let mut best: Vec<Option<((usize, usize, usize), usize, bool)>> = vec![None; len + 1];
+        best[0] = Some(((0, 0, 0), 0, true));
+        for start in 0..len {
+            let cost = match best[start] {
+                Some((cost, _, _)) => cost,
+                None => continue,
+            };
+            let mut steps: Vec<(usize, bool)> = self.prefix_lengths(&text[start..])
+                                                    .into_iter()
+                                                    .map(|n| (start + n, true))
+                                                    .filter(|&(end, _)| is_boundary(end))
+                                                    .collect();
+            // Or skip one character, with its marks.
+            let mut end = start + 1;
+            while !is_boundary(end) {
+                end += 1;
+            }
+            steps.push((end, false));
+
+            for (end, is_word) in steps {
+                let squares = cost.2 + (end - start) * (end - start);
+                let new_cost = if is_word {
+                    (cost.0, cost.1 + 1, squares)
+                } else {
+                    (cost.0 + end - start, cost.1 + 1, squares)
+                };
+                let better = match best[end] {
+                    Some((old_cost, _, _)) => new_cost < old_cost,
+                    None => true,
+                };
+                if better {
+                    best[end] = Some((new_cost, start, is_word));
+                }
+            }
+        }
+
+        // The parts of the best split, as where each starts and whether
+        // it's a word.
+        let mut parts = vec![];
+        let mut end = len;
+        while end > 0 {
+            let (_, start, is_word) = best[end].unwrap();
+            parts.push((start, is_word));
+            end = start;
+        }
+        parts.reverse();
+
+        // Characters that aren't in a word join the word before them,
+        // and those before the first word join it.
+        let mut boundaries = vec![0];
+        let mut seen_word = false;
+        for &(start, is_word) in &parts {
+            if is_word && seen_word {
+                boundaries.push(start);
+            }
+            seen_word |= is_word;
+        }
+        boundaries.push(len);
+        boundaries
+    }
+}
+
+// Thai and Lao vowels written before the consonant they're said after.
+fn is_leading_vowel(c: char) -> bool {
+    ('\u{E40}' <= c && c <= '\u{E44}') || ('\u{EC0}' <= c && c <= '\u{EC4}')
+}
+
+fn dictionary_for(cp: u32) -> Option<&'static WordDictionary> {
+    let script = unicode_data::script(cp);
+    if script == ot_tag!(b"Thai") {
+        Some(&THAI)
+    } else if script == ot_tag!(b"Laoo") {
+        Some(&LAO)
+    } else if script == ot_tag!(b"Khmr") {
+        Some(&KHMER)
+    } else if script == ot_tag!(b"Mymr") {
+        Some(&BURMESE)
+    } else {
+        None
+    }
+}
+
+// Whether `cp` is in one of the scripts split into words with a
+// dictionary.
+pub fn uses_dictionary(cp: u32) -> bool {
+    unicode_data::line_break_class(cp) == unicode_data::LineBreakClass::SA && dictionary_for(cp).is_some()
+}
+
+// The positions (in UTF-16 code units) between words inside runs of
+// Thai, Lao, Khmer and Burmese text.
+pub fn dictionary_breaks(text: &str) -> Vec<usize> {
+    let mut breaks = vec![];
+    let chars: Vec<char> = text.chars().collect();
+    let mut offsets = Vec::with_capacity(chars.len() + 1);
+    let mut offset = 0;
+    for c in &chars {
+        offsets.push(offset);
+        offset += c.len_utf16();
+    }
+    offsets.push(offset);
+
+    let mut start = 0;
+    while start < chars.len() {
+        if !uses_dictionary(chars[start] as u32) {
+            start += 1;
+            continue;
+        }
+        let dictionary = dictionary_for(chars[start] as u32).unwrap();
+        // The run of characters of this script that need a dictionary.
+        let script = unicode_data::script(chars[start] as u32);
+        let mut end = start + 1;
+        while end < chars.len() && uses_dictionary(chars[end] as u32) && unicode_data::script(chars[end] as u32) == script {
+            end += 1;
+        }
+
+        let boundaries = dictionary.word_boundaries(&chars[start..end]);
+        // Only the boundaries inside the run; line breaking deals with
+        // its ends.
+        for &i in &boundaries[1..boundaries.len() - 1] {
+            breaks.push(offsets[start + i]);
+        }
+        start = end;
+    }
+    breaks
+}
@@ -0,0 +1,234 @@
+#!/usr/bin/env python3
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+"""Builds the word dictionaries in src/dictionaries.
+
+The words are ICU's word break dictionaries, read from the
+segmenter_dictionary_extended_v1 data baked into ICU4X's
+icu_segmenter_data crate:
+
+    python3 tools/make_word_dictionaries.py \\
+        icu_segmenter_data-2.3.0/data/segmenter_dictionary_extended_v1.rs.data \\
+        src/dictionaries
+
+Each dictionary is written as a minimal acyclic automaton (a DAWG, so
+words share their prefixes and suffixes), in this format, with all
+numbers big-endian:
+
+    u16             the number of characters used by the words, N
+    u16[N]          those characters, sorted
+    u32[...]        the edges, each state's edges sorted by character
+                    and stored together, the start state's first
+
+Each edge is:
+
+    bit 31          set on the last edge of a state
+    bit 30          set if a word ends after taking this edge
+    bits 8-29       the index of the first edge of the state it leads
+                    to, or 0 if that state has no edges
+    bits 0-7        the index of its character in the character list
+
+src/word_dictionary.rs reads them in place.
+"""
+
+import os
+import re
+import struct
+import sys
+import unicodedata
+
+DICTIONARIES = {
+    'THAI': 'thai.dict',
+    'LAO': 'lao.dict',
+    'KHMER': 'khmer.dict',
+    'MYANMAR': 'burmese.dict',
+}
+
+
+def rust_byte_string(literal):
+    out = bytearray()
+    i = 0
+    while i < len(literal):
+        c = literal[i]
+        if c != '\\':
+            out.extend(c.encode('utf-8'))
+            i += 1
+        elif literal[i + 1] == 'x':
+            out.append(int(literal[i + 2:i + 4], 16))
+            i += 4
+        else:
+            out.append({'0': 0, 'n': 10, 't': 9, 'r': 13, '\\': 92, '"': 34, "'": 39}[literal[i + 1]])
+            i += 2
+    return bytes(out)
+
+
+def uchars_trie_words(t):
+    """The strings in an ICU UCharsTrie (see ICU's ucharstrie.h)."""
+    words = []
+
+    def read_value(pos, lead):
+        if lead < 0x4000:
+            return lead, pos
+        if lead < 0x7fff:
+            return ((lead - 0x4000) << 16) | t[pos], pos + 1
+        return (t[pos] << 16) | t[pos + 1], pos + 2
+
+    def jump_by_delta(pos):
+        delta = t[pos]
+        pos += 1
+        if delta >= 0xfc00:
+            if delta == 0xffff:
+                delta = (t[pos] << 16) | t[pos + 1]
+                pos += 2
+            else:
+                delta = ((delta - 0xfc00) << 16) | t[pos]
+                pos += 1
+        return pos + delta
+
+    def skip_delta(pos):
+        delta = t[pos]
+        pos += 1
+        if delta >= 0xfc00:
+            pos += 2 if delta == 0xffff else 1
+        return pos
+
+    # (position of a node, the string leading to it)
+    pending = [(0, '')]
+    # (position of a branch, its length, the string leading to it)
+    branches = []
+    while pending or branches:
+        if branches:
+            pos, length, prefix = branches.pop()
+            while length > 5:
+                branches.append((jump_by_delta(pos + 1), length >> 1, prefix))
+                length -= length >> 1
+                pos = skip_delta(pos + 1)
+            while length > 1:
+                c = t[pos]
+                lead = t[pos + 1]
+                value, pos = read_value(pos + 2, lead & 0x7fff)
+                if lead & 0x8000:
+                    words.append(prefix + chr(c))
+                else:
+                    pending.append((pos + value, prefix + chr(c)))
+                length -= 1
+            pending.append((pos + 1, prefix + chr(t[pos])))
+            continue
+
+        pos, prefix = pending.pop()
+        while True:
+            node = t[pos]
+            pos += 1
+            if node >= 0x40:
+                words.append(prefix)
+                if node & 0x8000:
+                    break
+                if node >= 0x4040:
+                    pos += 1 if node < 0x7fc0 else 2
+                node &= 0x3f
+            if node < 0x30:
+                length = node
+                if length == 0:
+                    length = t[pos]
+                    pos += 1
+                branches.append((pos, length + 1, prefix))
+                break
+            length = node - 0x30 + 1
+            prefix += ''.join(chr(c) for c in t[pos:pos + length])
+            pos += length
+    return words
+
+
+def build_dawg(words):
+    """A minimal automaton for the sorted words, built incrementally as
+    in Daciuk et al., "Incremental Construction of Minimal Acyclic
+    Finite-State Automata".  States are (edges, is_final) with edges a
+    dict from character to state."""
+    states = [({}, False)]
+    register = {}
+
+    def signature(state):
+        edges, is_final = states[state]
+        return is_final, tuple(sorted(edges.items()))
+
+    def minimize(state):
+        # Replaces the last child of `state` (and, first, its own last
+        # child, and so on) with an equivalent registered state.
+        path = [state]
+        while states[path[-1]][0]:
+            edges = states[path[-1]][0]
+            path.append(edges[max(edges)])
+        for parent, child in reversed(list(zip(path, path[1:]))):
+            key = signature(child)
+            edges = states[parent][0]
+            edges[max(edges)] = register.setdefault(key, child)
+
+    previous = ''
+    for word in words:
+        common = 0
+        state = 0
+        while common < min(len(word), len(previous)) and word[common] == previous[common]:
+            state = states[state][0][word[common]]
+            common += 1
+        if states[state][0]:
+            minimize(state)
+        for c in word[common:]:
+            states.append(({}, False))
+            states[state][0][c] = len(states) - 1
+            state = len(states) - 1
+        states[state] = (states[state][0], True)
+        previous = word
+    minimize(0)
+    return states
+
+
+def serialize(words):
+    alphabet = sorted(set(''.join(words)))
+    assert len(alphabet) <= 256 and all(ord(c) <= 0xffff for c in alphabet)
+    states = build_dawg(words)
+
+    # Number the edge lists breadth first, the start state's first.
+    first_edge = {0: 0}
+    order = [0]
+    edge_count = len(states[0][0])
+    for state in order:
+        for c, child in sorted(states[state][0].items()):
+            if child not in first_edge and states[child][0]:
+                first_edge[child] = edge_count
+                edge_count += len(states[child][0])
+                order.append(child)
+    assert edge_count < 1 << 22
+
+    out = bytearray(struct.pack('>H', len(alphabet)))
+    out.extend(struct.pack('>%dH' % len(alphabet), *[ord(c) for c in alphabet]))
+    for state in order:
+        edges = sorted(states[state][0].items())
+        for i, (c, child) in enumerate(edges):
+            edge = alphabet.index(c) | first_edge.get(child, 0) << 8
+            if states[child][1]:
+                edge |= 1 << 30
+            if i == len(edges) - 1:
+                edge |= 1 << 31
+            out.extend(struct.pack('>I', edge))
+    return bytes(out)
+
+
+def main():
+    if len(sys.argv) != 3:
+        sys.exit(__doc__)
+    source = open(sys.argv[1], encoding='utf-8').read()
+    tries = re.findall(r'trie_data: unsafe \{ zerovec::ZeroVec::from_bytes_unchecked\(b"((?:[^"\\]|\\.)*)"\)', source)
+    for trie in tries:
+        data = rust_byte_string(trie)
+        words = sorted(set(w for w in uchars_trie_words(struct.unpack('<%dH' % (len(data) // 2), data)) if w))
+        script = unicodedata.name(words[len(words) // 2][0]).split()[0]
+        path = os.path.join(sys.argv[2], DICTIONARIES[script])
+        with open(path, 'wb') as f:
+            f.write(serialize(words))
+        print('%s: %d words, %d bytes' % (path, len(words), os.path.getsize(path)))
+
+
+if __name__ == '__main__':
+    main()